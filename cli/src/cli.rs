@@ -10,9 +10,20 @@ fn parse_uri(uri: &str) -> Result<String> {
 
 #[derive(Debug, StructOpt)]
 pub struct EthClientOpts {
-	/// Ethereum JSON-RPC client endpoint
-	#[structopt(parse(try_from_str = parse_uri), long = "eth-http", about = "Ethereum client JSON-RPC endpoint")]
-	pub eth_http: Option<String>,
+	/// Ethereum JSON-RPC client endpoints
+	/// May be passed multiple times to query several endpoints
+	#[structopt(
+		parse(try_from_str = parse_uri),
+		long = "eth-http",
+		about = "Ethereum client JSON-RPC endpoint, may be passed multiple times"
+	)]
+	pub eth_http: Vec<String>,
+	/// Number of Ethereum JSON-RPC endpoints which must agree on a response
+	#[structopt(
+		long = "eth-http-quorum",
+		about = "Number of Ethereum client JSON-RPC endpoints which must agree (default: simple majority)"
+	)]
+	pub eth_http_quorum: Option<u32>,
 }
 
 #[derive(Debug, StructOpt)]
@@ -21,7 +21,10 @@
 
 use std::sync::Arc;
 
-use benson_primitives::types::{AccountId, AssetId, Balance, Block, BlockNumber, Hash, Index};
+use benson_primitives::{
+	eth::crypto::AuthorityId as EthBridgeId,
+	types::{AccountId, AssetId, Balance, Block, BlockNumber, Hash, Index},
+};
 use benson_runtime::Runtime;
 use ethy_gadget::notification::EthyEventProofStream;
 use sc_client_api::AuxStore;
@@ -111,8 +114,10 @@ where
 	C::Api: BabeApi<Block>,
 	C::Api: BlockBuilder<Block>,
 	C::Api: crml_cennzx_rpc::CennzxRuntimeApi<Block, AssetId, Balance, AccountId>,
+	C::Api: crml_eth_bridge_rpc::EthBridgeRuntimeApi<Block, EthBridgeId>,
 	C::Api: crml_eth_wallet_rpc::EthWalletRuntimeApi<Block>,
 	C::Api: crml_nft_rpc::NftRuntimeApi<Block, AccountId, Runtime>,
+	C::Api: crml_nomination_pools_rpc::NominationPoolsRuntimeApi<Block, AccountId>,
 	C::Api: crml_staking_rpc::StakingRuntimeApi<Block, AccountId>,
 	C::Api: crml_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: crml_generic_asset_rpc::GenericAssetRuntimeApi<Block, AssetId, Balance, AccountId>,
@@ -123,10 +128,12 @@ where
 	B::State: sc_client_api::backend::StateBackend<sp_runtime::traits::HashFor<Block>>,
 {
 	use crml_cennzx_rpc::{Cennzx, CennzxApi};
+	use crml_eth_bridge_rpc::{EthBridge, EthBridgeApi};
 	use crml_eth_wallet_rpc::{EthWallet, EthWalletApi};
 	use crml_generic_asset_rpc::{GenericAsset, GenericAssetApi};
 	use crml_governance_rpc::{Governance, GovernanceApi};
 	use crml_nft_rpc::{Nft, NftApi};
+	use crml_nomination_pools_rpc::{NominationPools, NominationPoolsApi};
 	use crml_staking_rpc::{Staking, StakingApi};
 	use crml_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
@@ -170,7 +177,11 @@ where
 		deny_unsafe,
 	)));
 	io.extend_with(ethy_gadget_rpc::EthyApi::to_delegate(
-		ethy_gadget_rpc::EthyRpcHandler::new(ethy.event_proof_stream, ethy.subscription_executor, client.clone()),
+		ethy_gadget_rpc::EthyRpcHandler::<_, Block>::new(
+			ethy.event_proof_stream,
+			ethy.subscription_executor,
+			client.clone(),
+		),
 	));
 	io.extend_with(sc_finality_grandpa_rpc::GrandpaApi::to_delegate(
 		GrandpaRpcHandler::new(
@@ -198,6 +209,8 @@ where
 	io.extend_with(StakingApi::to_delegate(Staking::new(client.clone())));
 	io.extend_with(GenericAssetApi::to_delegate(GenericAsset::new(client.clone())));
 	io.extend_with(GovernanceApi::to_delegate(Governance::new(client.clone())));
+	io.extend_with(NominationPoolsApi::to_delegate(NominationPools::new(client.clone())));
+	io.extend_with(EthBridgeApi::to_delegate(EthBridge::new(client.clone())));
 	io.extend_with(EthWalletApi::to_delegate(EthWallet::new(client)));
 
 	Ok(io)
@@ -31,11 +31,14 @@ use sc_service::{config::Configuration, error::Error as ServiceError, TaskManage
 use sc_telemetry::{Telemetry, TelemetryWorker};
 use sp_core::offchain::OffchainStorage;
 use sp_runtime::traits::Block as BlockT;
-use std::sync::Arc;
+use std::{convert::TryInto, sync::Arc};
 
-use crate::rpc as node_rpc;
+use crate::{cli::EthClientOpts, rpc as node_rpc};
 use benson_primitives::types::Block;
-use benson_runtime::{constants::config::ETH_HTTP_URI, RuntimeApi};
+use benson_runtime::{
+	constants::config::{ETH_HTTP_FAILURES, ETH_HTTP_QUORUM, ETH_HTTP_URI},
+	RuntimeApi,
+};
 
 // Declare an instance of the native executor named `ExecutorDispatch`. Include the wasm binary as
 // the equivalent wasm code.
@@ -259,7 +262,7 @@ pub struct NewFullBase {
 /// Creates a full service from the configuration.
 pub fn new_full_base(
 	mut config: Configuration,
-	eth_http_config: Option<String>,
+	eth_client_opts: EthClientOpts,
 	with_startup_data: impl FnOnce(
 		&sc_consensus_babe::BabeBlockImport<Block, FullClient, FullGrandpaBlockImport>,
 		&sc_consensus_babe::BabeLink<Block>,
@@ -279,12 +282,22 @@ pub fn new_full_base(
 	// Set eth http bridge config
 	// the config is stored into the offchain context where it can
 	// be accessed later by the crml-eth-bridge offchain worker.
-	if let Some(eth_http_uri) = eth_http_config {
-		backend.offchain_storage().unwrap().set(
+	if !eth_client_opts.eth_http.is_empty() {
+		let mut offchain_storage = backend.offchain_storage().unwrap();
+		offchain_storage.set(
 			sp_core::offchain::STORAGE_PREFIX,
 			&ETH_HTTP_URI,
-			eth_http_uri.as_bytes(),
+			eth_client_opts.eth_http.join(",").as_bytes(),
 		);
+		match eth_client_opts.eth_http_quorum {
+			// SCALE encoded u32
+			Some(quorum) => offchain_storage.set(
+				sp_core::offchain::STORAGE_PREFIX,
+				&ETH_HTTP_QUORUM,
+				&quorum.to_le_bytes(),
+			),
+			None => offchain_storage.remove(sp_core::offchain::STORAGE_PREFIX, &ETH_HTTP_QUORUM),
+		}
 	}
 
 	let shared_voter_state = rpc_setup;
@@ -296,6 +309,12 @@ pub fn new_full_base(
 		.push(sc_finality_grandpa::grandpa_peers_set_config());
 
 	config.network.extra_sets.push(ethy_gadget::ethy_peers_set_config());
+	let (ethy_proof_request_handler, ethy_proof_request_config) =
+		ethy_gadget::EthyProofRequestHandler::<Block, _>::new(backend.clone());
+	config
+		.network
+		.request_response_protocols
+		.push(ethy_proof_request_config);
 	let warp_sync = Arc::new(sc_finality_grandpa::warp_proof::NetworkProvider::new(
 		backend.clone(),
 		import_setup.1.shared_authority_set().clone(),
@@ -323,6 +342,19 @@ pub fn new_full_base(
 	let enable_grandpa = !config.disable_grandpa;
 	let prometheus_registry = config.prometheus_registry().cloned();
 
+	if let (Some(registry), Some(offchain_storage)) = (prometheus_registry.as_ref(), backend.offchain_storage()) {
+		if !eth_client_opts.eth_http.is_empty() {
+			match EthHttpMetrics::register(registry) {
+				Ok(metrics) => task_manager.spawn_handle().spawn(
+					"eth-http-metrics",
+					None,
+					metrics.run(offchain_storage, eth_client_opts.eth_http.clone()),
+				),
+				Err(err) => log::warn!("failed to register eth http metrics: {:?}", err),
+			}
+		}
+	}
+
 	let _rpc_handlers = sc_service::spawn_tasks(sc_service::SpawnTasksParams {
 		config,
 		backend: backend.clone(),
@@ -440,6 +472,11 @@ pub fn new_full_base(
 		_phantom: std::marker::PhantomData,
 	};
 
+	// Serve ETHY proofs to peers catching up
+	task_manager
+		.spawn_handle()
+		.spawn("ethy-proof-request-handler", None, ethy_proof_request_handler.run());
+
 	// Start the ETHY bridge gadget.
 	task_manager.spawn_essential_handle().spawn_blocking(
 		"ethy-gadget",
@@ -494,6 +531,49 @@ pub fn new_full_base(
 }
 
 /// Builds a new service for a full client.
-pub fn new_full(config: Configuration, eth_http_config: Option<String>) -> Result<TaskManager, ServiceError> {
-	new_full_base(config, eth_http_config, |_, _| ()).map(|NewFullBase { task_manager, .. }| task_manager)
+pub fn new_full(config: Configuration, eth_client_opts: EthClientOpts) -> Result<TaskManager, ServiceError> {
+	new_full_base(config, eth_client_opts, |_, _| ()).map(|NewFullBase { task_manager, .. }| task_manager)
+}
+
+/// Exports Ethereum JSON-RPC request failures observed by the crml-eth-bridge offchain worker
+struct EthHttpMetrics {
+	/// Total request failures by endpoint
+	eth_http_failures: prometheus_endpoint::CounterVec<prometheus_endpoint::U64>,
+}
+
+impl EthHttpMetrics {
+	/// How often offchain failure counters are exported
+	const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(12);
+
+	fn register(registry: &prometheus_endpoint::Registry) -> Result<Self, prometheus_endpoint::PrometheusError> {
+		Ok(Self {
+			eth_http_failures: prometheus_endpoint::register(
+				prometheus_endpoint::CounterVec::new(
+					prometheus_endpoint::Opts::new(
+						"eth_bridge_http_failures",
+						"Number of failed Ethereum JSON-RPC requests by endpoint",
+					),
+					&["endpoint"],
+				)?,
+				registry,
+			)?,
+		})
+	}
+
+	/// Periodically sync counters with the failure counts in offchain storage
+	async fn run<S: OffchainStorage>(self, offchain_storage: S, eth_http_uris: Vec<String>) {
+		loop {
+			futures_timer::Delay::new(Self::POLL_INTERVAL).await;
+			for uri in eth_http_uris.iter() {
+				let key = [&ETH_HTTP_FAILURES[..], uri.as_bytes()].concat();
+				// SCALE encoded u64
+				let failures = offchain_storage
+					.get(sp_core::offchain::STORAGE_PREFIX, &key)
+					.and_then(|value| value.try_into().ok())
+					.map_or(0, u64::from_le_bytes);
+				let counter = self.eth_http_failures.with_label_values(&[uri]);
+				counter.inc_by(failures.saturating_sub(counter.get()));
+			}
+		}
+	}
 }
@@ -43,7 +43,7 @@ mod weights;
 
 pub use impls::{ExchangeAddressFor, ExchangeAddressGenerator};
 pub use types::{FeeRate, HighPrecisionUnsigned, LowPrecisionUnsigned, PerMillion, PerThousand};
-use weights::WeightInfo;
+pub use weights::WeightInfo;
 
 // (core_asset_id, asset_id)
 pub type ExchangeKey<T> = (<T as Config>::AssetId, <T as Config>::AssetId);
@@ -579,6 +579,62 @@ impl<T: Config> Module<T> {
 		Ok(payout_asset_value)
 	}
 
+	/// Get the value of some asset in another at the current exchange rate(s)
+	/// i.e. the sell price excluding fees and price impact, useful as a reference for slippage limits
+	/// `asset_to_sell` is the asset to be sold
+	/// `amount_to_sell` is the amount of `asset_to_sell` to be sold
+	/// `asset_to_payout` is the asset the value is given in
+	pub fn get_spot_price(
+		asset_to_sell: T::AssetId,
+		amount_to_sell: T::Balance,
+		asset_to_payout: T::AssetId,
+	) -> Result<T::Balance, DispatchError> {
+		ensure!(asset_to_sell != asset_to_payout, Error::<T>::AssetCannotSwapForItself);
+		ensure!(amount_to_sell > Zero::zero(), Error::<T>::CannotTradeZero);
+
+		let core_asset_amount = if asset_to_sell == Self::core_asset_id() {
+			amount_to_sell
+		} else {
+			let (core_reserve, asset_reserve) = Self::get_exchange_reserves(asset_to_sell);
+			Self::calculate_spot_price(amount_to_sell, asset_reserve, core_reserve)?
+		};
+
+		if asset_to_payout == Self::core_asset_id() {
+			Ok(core_asset_amount)
+		} else {
+			let (core_reserve, asset_reserve) = Self::get_exchange_reserves(asset_to_payout);
+			Self::calculate_spot_price(core_asset_amount, core_reserve, asset_reserve)
+		}
+	}
+
+	/// `sell_amount` - Amount to sell
+	/// `sell_reserve`- How much of the asset to sell is in the exchange
+	/// `buy_reserve` - How much of the asset to buy is in the exchange
+	/// Returns the value of `sell_amount` in the buy asset at the exchange rate `buy_reserve / sell_reserve`
+	fn calculate_spot_price(
+		sell_amount: T::Balance,
+		sell_reserve: T::Balance,
+		buy_reserve: T::Balance,
+	) -> sp_std::result::Result<T::Balance, DispatchError> {
+		ensure!(
+			!sell_reserve.is_zero() && !buy_reserve.is_zero(),
+			Error::<T>::EmptyExchangePool
+		);
+
+		let sell_amount_hp = HighPrecisionUnsigned::from(sell_amount.saturated_into::<LowPrecisionUnsigned>());
+		let sell_reserve_hp = HighPrecisionUnsigned::from(sell_reserve.saturated_into::<LowPrecisionUnsigned>());
+		let buy_reserve_hp = HighPrecisionUnsigned::from(buy_reserve.saturated_into::<LowPrecisionUnsigned>());
+		let price_hp = buy_reserve_hp
+			.saturating_mul(sell_amount_hp)
+			.checked_div(sell_reserve_hp)
+			.ok_or::<Error<T>>(Error::<T>::DivideByZero)?;
+
+		let price_lp_result: Result<LowPrecisionUnsigned, &'static str> = LowPrecisionUnsigned::try_from(price_hp);
+		ensure!(price_lp_result.is_ok(), Error::<T>::Overflow);
+
+		Ok(price_lp_result.unwrap().saturated_into())
+	}
+
 	/// `asset_id` - Trade asset
 	/// `amount_sold` - Amount of the trade asset to sell
 	/// Returns amount of core that can be bought with input assets.
@@ -1703,6 +1703,40 @@ fn get_sell_price_same_asset_id_ignored() {
 	});
 }
 
+#[test]
+fn get_spot_price_excludes_fees_and_price_impact() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 2000);
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_B_ID => 500);
+
+		assert_eq!(Cennzx::get_spot_price(CORE_ASSET_ID, 100, TRADE_ASSET_A_ID), Ok(200));
+		assert_eq!(Cennzx::get_spot_price(TRADE_ASSET_A_ID, 100, CORE_ASSET_ID), Ok(50));
+		assert_eq!(Cennzx::get_spot_price(TRADE_ASSET_A_ID, 100, TRADE_ASSET_B_ID), Ok(25));
+		// the sell price is always worse
+		assert!(Cennzx::get_sell_price(CORE_ASSET_ID, 100, TRADE_ASSET_A_ID).unwrap() < 200);
+	});
+}
+
+#[test]
+fn get_spot_price_errors() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+
+		assert_err!(
+			Cennzx::get_spot_price(TRADE_ASSET_A_ID, 100, TRADE_ASSET_A_ID),
+			Error::<Test>::AssetCannotSwapForItself
+		);
+		assert_err!(
+			Cennzx::get_spot_price(CORE_ASSET_ID, 0, TRADE_ASSET_A_ID),
+			Error::<Test>::CannotTradeZero
+		);
+		assert_err!(
+			Cennzx::get_spot_price(CORE_ASSET_ID, 100, TRADE_ASSET_B_ID),
+			Error::<Test>::EmptyExchangePool
+		);
+	});
+}
+
 #[test]
 fn get_sell_price_low_sell_asset_liquidity() {
 	ExtBuilder::default().build().execute_with(|| {
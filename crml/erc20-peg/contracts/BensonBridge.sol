@@ -1,30 +1,28 @@
 // SPDX-License-Identifier: Apache-2.0
 pragma solidity ^0.8.0;
 
-import "@openzeppelin/contracts/access/Ownable.sol";
 import "@openzeppelin/contracts/token/ERC20/IERC20.sol";
+import "@openzeppelin/contracts/utils/cryptography/MerkleProof.sol";
 import "@openzeppelin/contracts/utils/math/SafeMath.sol";
+import "./BensonValidatorSet.sol";
 
-contract BensonBridge is Ownable {
+contract BensonBridge is BensonValidatorSet {
     using SafeMath for uint256;
 
     // whether the bridge is accepting deposits
     bool public depositsActive;
     // whether the bridge is accepting withdrawals
     bool public withdrawalsActive;
-    // map from integer index to validator ECDSA address
-    // this is the Benson validator ECDSA session keys
-    address[] public validators;
-    // Nonce for validator set changes
-    uint32 validatorSetNonce;
-    // Global withdrawal nonces
+    // Global withdrawal nonces i.e. Benson event proof Ids which have been withdrawn
     mapping(uint => bool) public withdrawawlNonce;
     // withdrawal fee, offsets bridge upkeep costs
     uint withdrawalFee = 1e14;
+    // domain separator of withdraw messages (crml-support `ERC20_WITHDRAW_TAG`)
+    // signatures of other messages e.g. ERC721 withdrawals can't be replayed here
+    bytes32 constant WITHDRAW_TAG = keccak256("ERC20_WITHDRAW");
 
     event Deposit(address indexed, address tokenType, uint256 amount, bytes32 bensonAddress);
     event Withdraw(address indexed, address tokenType, uint256 amount);
-    event SetValidators(address[], uint reward);
 
     // Deposit amount of tokenType
     // the pegged version of the token will be claim-able on Benson
@@ -39,72 +37,50 @@ contract BensonBridge is Ownable {
     // Requires signatures from a threshold of current Benson validators
     // v,r,s are sparse arrays expected to align w public key in 'validators'
     // i.e. v[i], r[i], s[i] matches the i-th validator[i]
+    // nonce is the Benson event proof Id, the sender must be the withdrawal beneficiary
     function withdraw(address tokenType, uint256 amount, uint nonce, uint8[] memory v, bytes32[] memory r, bytes32[] memory s) payable external {
-        require(withdrawalsActive, "withdrawals paused");
-        require(withdrawawlNonce[nonce] == false, "nonce replayed");
-        require(msg.value >= withdrawalFee, "must supply withdraw fee");
-
-        // 7769746864726177 = "withdraw"
-        bytes32 digest = keccak256(abi.encodePacked(uint(0x7769746864726177), tokenType, amount, nonce));
-        uint acceptanceTreshold = ((validators.length * 1000) * 51) / 1000000; // 51%
-        uint32 notarizations;
-
-        for (uint i; i < validators.length; i++) {
-            // signature omitted
-            if(s[i] == bytes32(0)) continue;
-            // check signature
-            require(validators[i] == ecrecover(digest, v[i], r[i], s[i]), "signature invalid");
-            notarizations += 1;
-            // have we got proven consensus?
-            if(notarizations >= acceptanceTreshold) {
-                break;
-            }
-        }
-
-        require(notarizations >= acceptanceTreshold, "not enough signatures");
-        withdrawawlNonce[nonce] = true;
-        require(IERC20(tokenType).transfer(msg.sender, amount), "withdraw failed");
-
-        emit Withdraw(msg.sender, tokenType, amount);
+        bytes32 digest = withdrawMessageHash(tokenType, amount, msg.sender, nonce);
+        require(hasConsensus(digest, v, r, s), "not enough signatures");
+        transferWithdrawal(tokenType, amount, msg.sender, nonce);
     }
 
-    // Update the validator set
-    // Requires signatures from a threshold of current Benson validators
-    // v,r,s are sparse arrays expected to align w addresses / public key in 'validators'
-    // i.e. v[i], r[i], s[i] matches the i-th validator[i]
-    // 6,737,588 gas
-    function setValidators(
-        address[] memory newValidators,
-        uint32 nonce,
+    // Withdraw amount of tokenType to beneficiary
+    // The withdrawal was signed in a batch, Benson validators signed the batch Merkle root
+    // branch is the Merkle proof of the withdrawal message hash in the batch (sorted pair hashing)
+    // the Ethy `ethy_getEventProofCalldata` RPC returns calldata for this call
+    function withdrawBatched(
+        address tokenType,
+        uint256 amount,
+        address beneficiary,
+        uint eventId,
+        bytes32[] memory branch,
         uint8[] memory v,
         bytes32[] memory r,
         bytes32[] memory s
-    ) external {
-        require(nonce > validatorSetNonce, "nonce replayed");
-        // 0x73657456616c696461746f7273 = "setValidators"
-        bytes32 digest = keccak256(abi.encodePacked(uint(0x73657456616c696461746f7273), newValidators, nonce));
-
-        uint acceptanceTreshold = ((validators.length * 1000) * 51) / 1000000; // 51%
-        uint32 notarizations;
-
-        for (uint i; i < validators.length; i++) {
-            // signature omitted
-            if(s[i] == bytes32(0)) continue;
-            // check signature
-            require(validators[i] == ecrecover(digest, v[i], r[i], s[i]), "signature invalid");
-            notarizations += 1;
-            // have we got proven consensus?
-            if(notarizations >= acceptanceTreshold) {
-                validators = newValidators;
-                validatorSetNonce = nonce;
-                break;
-            }
-        }
-
-        // return any accumlated fees to the sender as a reward
-        uint reward = address(this).balance;
-        payable(msg.sender).transfer(reward);
-        emit SetValidators(newValidators, reward);
+    ) payable external {
+        bytes32 leaf = withdrawMessageHash(tokenType, amount, beneficiary, eventId);
+        bytes32 root = MerkleProof.processProof(branch, leaf);
+        require(hasConsensus(root, v, r, s), "not enough signatures");
+        transferWithdrawal(tokenType, amount, beneficiary, eventId);
+    }
+
+    // The hash of the message signed by Benson validators (crml-erc20-peg `WithdrawMessage`)
+    // appended with the validator set Id and event Id
+    function withdrawMessageHash(address tokenType, uint256 amount, address beneficiary, uint eventId) internal view returns (bytes32) {
+        return keccak256(abi.encode(WITHDRAW_TAG, tokenType, amount, beneficiary, validatorSetId, eventId));
+    }
+
+    // Transfer a proven withdrawal to beneficiary
+    function transferWithdrawal(address tokenType, uint256 amount, address beneficiary, uint eventId) internal {
+        require(withdrawalsActive, "withdrawals paused");
+        require(withdrawawlNonce[eventId] == false, "nonce replayed");
+        require(msg.value >= withdrawalFee, "must supply withdraw fee");
+        require(validators.length > 0, "validators not set");
+
+        withdrawawlNonce[eventId] = true;
+        require(IERC20(tokenType).transfer(beneficiary, amount), "withdraw failed");
+
+        emit Withdraw(beneficiary, tokenType, amount);
     }
 
     function activateDeposits() external onlyOwner {
@@ -0,0 +1,111 @@
+// SPDX-License-Identifier: Apache-2.0
+pragma solidity ^0.8.0;
+
+import "@openzeppelin/contracts/token/ERC721/IERC721.sol";
+import "./BensonERC721.sol";
+import "./BensonValidatorSet.sol";
+
+// Bridges ERC721 tokens to and from Benson crml-nft tokens
+// ERC721s native to Ethereum are held by this contract while they are on Benson
+// Tokens of Benson collections are minted as `BensonERC721`s on withdrawal and burnt on deposit
+contract BensonNFTBridge is BensonValidatorSet {
+    // whether the bridge is accepting deposits
+    bool public depositsActive;
+    // whether the bridge is accepting withdrawals
+    bool public withdrawalsActive;
+    // Benson event proof Ids which have been withdrawn
+    mapping(uint => bool) public eventIds;
+    // ERC721 contracts deployed by this bridge for Benson collections
+    mapping(address => bool) public bensonCollections;
+    // withdrawal fee, offsets bridge upkeep costs
+    uint withdrawalFee = 1e14;
+    // domain separator of withdraw messages (crml-support `ERC721_WITHDRAW_TAG`)
+    // signatures of other messages e.g. ERC20 withdrawals can't be replayed here
+    bytes32 constant WITHDRAW_TAG = keccak256("ERC721_WITHDRAW");
+
+    event Erc721Deposit(address indexed, address tokenType, uint256 tokenId, bytes32 bensonAddress);
+    event Erc721Withdraw(address indexed, address tokenType, uint256 tokenId);
+    event BensonCollection(address tokenType);
+
+    // Deposit token `tokenId` of tokenType
+    // the token will be claim-able on Benson by bensonAddress
+    function deposit(address tokenType, uint256 tokenId, bytes32 bensonAddress) external {
+        require(depositsActive, "deposits paused");
+
+        if (bensonCollections[tokenType]) {
+            require(IERC721(tokenType).ownerOf(tokenId) == msg.sender, "deposit failed");
+            BensonERC721(tokenType).burn(tokenId);
+        } else {
+            IERC721(tokenType).transferFrom(msg.sender, address(this), tokenId);
+        }
+
+        emit Erc721Deposit(msg.sender, tokenType, tokenId, bensonAddress);
+    }
+
+    // Withdraw token `tokenId` of tokenType to beneficiary
+    // Requires signatures from a threshold of current Benson validators over the withdraw message
+    // v,r,s are sparse arrays expected to align w public key in 'validators'
+    // i.e. v[i], r[i], s[i] matches the i-th validator[i]
+    function withdraw(
+        address tokenType,
+        uint256 tokenId,
+        address beneficiary,
+        uint eventId,
+        uint8[] memory v,
+        bytes32[] memory r,
+        bytes32[] memory s
+    ) payable external {
+        require(withdrawalsActive, "withdrawals paused");
+        require(eventIds[eventId] == false, "eventId replayed");
+        require(msg.value >= withdrawalFee, "must supply withdraw fee");
+        require(validators.length > 0, "validators not set");
+
+        // the message signed by Benson validators (crml-nft-peg `Erc721WithdrawMessage`)
+        // appended with the validator set Id and event Id
+        bytes32 digest = keccak256(abi.encode(WITHDRAW_TAG, tokenType, tokenId, beneficiary, validatorSetId, eventId));
+        require(hasConsensus(digest, v, r, s), "not enough signatures");
+        eventIds[eventId] = true;
+
+        if (bensonCollections[tokenType]) {
+            BensonERC721(tokenType).mint(beneficiary, tokenId);
+        } else {
+            IERC721(tokenType).transferFrom(address(this), beneficiary, tokenId);
+        }
+
+        emit Erc721Withdraw(beneficiary, tokenType, tokenId);
+    }
+
+    // Deploy the ERC721 counterpart of a Benson collection
+    // The address should be mapped to the collection on Benson via crml-nft-peg `set_collection_mapping`
+    function createBensonCollection(string memory name, string memory symbol) external onlyOwner returns (address) {
+        BensonERC721 collection = new BensonERC721(name, symbol);
+        bensonCollections[address(collection)] = true;
+        emit BensonCollection(address(collection));
+        return address(collection);
+    }
+
+    function setBensonCollectionBaseURI(address tokenType, string memory uri) external onlyOwner {
+        require(bensonCollections[tokenType], "not a benson collection");
+        BensonERC721(tokenType).setBaseURI(uri);
+    }
+
+    function activateDeposits() external onlyOwner {
+        depositsActive = true;
+    }
+
+    function pauseDeposits() external onlyOwner {
+        depositsActive = false;
+    }
+
+    function activateWithdrawals() external onlyOwner {
+        withdrawalsActive = true;
+    }
+
+    function pauseWithdrawals() external onlyOwner {
+        withdrawalsActive = false;
+    }
+
+    function setWithdrawalFee(uint amount) external onlyOwner {
+        withdrawalFee = amount;
+    }
+}
@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: Apache-2.0
+pragma solidity ^0.8.0;
+
+import "./BensonValidatorSet.sol";
+import "./IBensonMessageReceiver.sol";
+
+// Passes messages between Ethereum contracts and Benson (crml-eth-messaging)
+// Messages to Benson are emitted as `MessageSent` events and claimed on Benson
+// Messages from Benson are relayed with signatures from Benson validators and delivered to the destination contract
+contract BensonRouter is BensonValidatorSet {
+    // whether the router is sending messages to Benson
+    bool public sendActive;
+    // whether the router is delivering messages from Benson
+    bool public receiveActive;
+    // Benson event proof Ids which have been delivered
+    mapping(uint => bool) public eventIds;
+    // fee for sending a message to Benson, offsets bridge upkeep costs
+    uint public messageFee;
+
+    event MessageSent(address source, bytes payload);
+    event MessageReceived(bytes32 indexed sender, address indexed destination, uint eventId);
+
+    // Send payload to Benson
+    // the message is handled by the handler registered for the sender (msg.sender) on Benson
+    function sendMessage(bytes calldata payload) payable external {
+        require(sendActive, "send paused");
+        require(msg.value >= messageFee, "must supply message fee");
+
+        emit MessageSent(msg.sender, payload);
+    }
+
+    // Deliver a message from the Benson account sender to the destination contract
+    // Requires signatures from a threshold of current Benson validators over the message
+    // v,r,s are sparse arrays expected to align w public key in 'validators'
+    // i.e. v[i], r[i], s[i] matches the i-th validator[i]
+    function receiveMessage(
+        bytes32 sender,
+        address destination,
+        bytes calldata payload,
+        uint eventId,
+        uint8[] memory v,
+        bytes32[] memory r,
+        bytes32[] memory s
+    ) external {
+        require(receiveActive, "receive paused");
+        require(eventIds[eventId] == false, "eventId replayed");
+        require(validators.length > 0, "validators not set");
+
+        // the message signed by Benson validators (crml-eth-messaging `OutboundMessage`)
+        // appended with the validator set Id and event Id
+        bytes32 digest = keccak256(abi.encodePacked(abi.encode(sender, destination, payload), validatorSetId, eventId));
+        require(hasConsensus(digest, v, r, s), "not enough signatures");
+        eventIds[eventId] = true;
+
+        IBensonMessageReceiver(destination).onMessageReceived(sender, payload);
+
+        emit MessageReceived(sender, destination, eventId);
+    }
+
+    function activateSend() external onlyOwner {
+        sendActive = true;
+    }
+
+    function pauseSend() external onlyOwner {
+        sendActive = false;
+    }
+
+    function activateReceive() external onlyOwner {
+        receiveActive = true;
+    }
+
+    function pauseReceive() external onlyOwner {
+        receiveActive = false;
+    }
+
+    function setMessageFee(uint amount) external onlyOwner {
+        messageFee = amount;
+    }
+}
@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: Apache-2.0
+pragma solidity ^0.8.0;
+
+import "@openzeppelin/contracts/access/Ownable.sol";
+
+// The Benson validator set trusted by a bridge contract
+// Tracks validator set changes proven by Ethy and checks messages are signed by a threshold of the current set
+abstract contract BensonValidatorSet is Ownable {
+    // map from integer index to validator ECDSA address
+    // this is the Benson validator ECDSA session keys
+    address[] public validators;
+    // Id of the current validator set on Benson
+    uint public validatorSetId;
+
+    event SetValidators(address[], uint validatorSetId, uint reward);
+
+    // Set the first validator set
+    // later changes must be signed by the current validators with `setValidators`
+    function initValidators(address[] memory newValidators, uint newValidatorSetId) external onlyOwner {
+        require(validators.length == 0, "validators already set");
+        require(newValidators.length > 0, "validators empty");
+
+        validators = newValidators;
+        validatorSetId = newValidatorSetId;
+        emit SetValidators(newValidators, newValidatorSetId, 0);
+    }
+
+    // Update the validator set
+    // Requires signatures from a threshold of current Benson validators
+    // v,r,s are sparse arrays expected to align w addresses / public key in 'validators'
+    // i.e. v[i], r[i], s[i] matches the i-th validator[i]
+    // 6,737,588 gas
+    function setValidators(
+        address[] memory newValidators,
+        uint newValidatorSetId,
+        uint eventId,
+        uint8[] memory v,
+        bytes32[] memory r,
+        bytes32[] memory s
+    ) external {
+        require(newValidatorSetId > validatorSetId, "validator set id replayed");
+        require(newValidators.length > 0, "validators empty");
+        // the message signed by Benson validators for the validator set change (ethy-gadget `abi_encode_validator_set_change`)
+        // eventId is the Ethy event proof Id of the change
+        bytes32 digest = keccak256(abi.encode(newValidators, newValidatorSetId, validatorSetId, eventId));
+        require(hasConsensus(digest, v, r, s), "not enough signatures");
+
+        validators = newValidators;
+        validatorSetId = newValidatorSetId;
+
+        // return any accumlated fees to the sender as a reward
+        uint reward = address(this).balance;
+        payable(msg.sender).transfer(reward);
+        emit SetValidators(newValidators, newValidatorSetId, reward);
+    }
+
+    // Whether a threshold (51%) of current validators signed digest
+    // v,r,s must have a (possibly omitted) signature for each validator
+    function hasConsensus(bytes32 digest, uint8[] memory v, bytes32[] memory r, bytes32[] memory s) internal view returns (bool) {
+        require(validators.length > 0, "validators not set");
+        require(
+            v.length == validators.length && r.length == validators.length && s.length == validators.length,
+            "signatures misaligned"
+        );
+        // rounded up, at least 1 signature
+        uint acceptanceTreshold = (validators.length * 51 + 99) / 100;
+        uint notarizations;
+
+        for (uint i; i < validators.length; i++) {
+            // signature omitted
+            if(s[i] == bytes32(0)) continue;
+            // check signature
+            require(validators[i] == ecrecover(digest, v[i], r[i], s[i]), "signature invalid");
+            notarizations += 1;
+            // have we got proven consensus?
+            if(notarizations >= acceptanceTreshold) {
+                return true;
+            }
+        }
+
+        return notarizations > 0 && notarizations >= acceptanceTreshold;
+    }
+}
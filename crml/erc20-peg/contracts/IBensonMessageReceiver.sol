@@ -0,0 +1,9 @@
+// SPDX-License-Identifier: Apache-2.0
+pragma solidity ^0.8.0;
+
+// A contract receiving messages from Benson via the `BensonRouter`
+interface IBensonMessageReceiver {
+    // Handle `payload` sent by the Benson account `sender`
+    // Only the router should be trusted to call this
+    function onMessageReceived(bytes32 sender, bytes calldata payload) external;
+}
@@ -1,10 +1,25 @@
 // deploy contracts for test
 async function main() {
+    // the first Benson validator set, later changes are relayed with `setValidators`
+    // defaults to the deployer (dev account #0) as the only validator of set 0
+    const [deployer] = await ethers.getSigners();
+    const validators = process.env.VALIDATORS ? process.env.VALIDATORS.split(',') : [deployer.address];
+    const validatorSetId = process.env.VALIDATOR_SET_ID || 0;
+
     const Bridge = await ethers.getContractFactory('BensonBridge');
     console.log('Deploying Benson bridge contract...');
     const bridge = await Bridge.deploy();
     await bridge.deployed();
     console.log('Benson bridge deployed to:', bridge.address);
+    await (await bridge.initValidators(validators, validatorSetId)).wait();
+    console.log('Benson bridge validators:', validators, 'set id:', validatorSetId);
+
+    const NFTBridge = await ethers.getContractFactory('BensonNFTBridge');
+    console.log('Deploying Benson NFT bridge contract...');
+    const nftBridge = await NFTBridge.deploy();
+    await nftBridge.deployed();
+    console.log('Benson NFT bridge deployed to:', nftBridge.address);
+    await (await nftBridge.initValidators(validators, validatorSetId)).wait();
 
     const TestToken = await ethers.getContractFactory('TestToken');
     console.log('Deploying TestToken contract...');
@@ -17,21 +17,30 @@
 
 use benson_primitives::types::{AssetId, Balance};
 use codec::Decode;
-use crml_support::{EventClaimSubscriber, EventClaimVerifier, MultiCurrency};
+use crml_support::{EthReceiptProof, EventClaimSubscriber, EventClaimVerifier, MultiCurrency};
 use frame_support::{
 	decl_error, decl_event, decl_module, decl_storage, ensure, log,
 	traits::{ExistenceRequirement, Get, IsType, WithdrawReasons},
-	transactional, PalletId,
+	transactional,
+	weights::Weight,
+	PalletId,
 };
 use frame_system::{ensure_root, ensure_signed};
 use sp_runtime::{
-	traits::{AccountIdConversion, Zero},
+	traits::{AccountIdConversion, One, Saturating, Zero},
 	DispatchError,
 };
 use sp_std::prelude::*;
 
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod test;
 mod types;
-use types::*;
+pub use types::*;
+
+/// The number of buckets withdrawals are tracked in over an asset's rolling window
+pub const WITHDRAWAL_WINDOW_BUCKETS: u32 = 24;
 
 pub trait Config: frame_system::Config {
 	/// An onchain address for this pallet
@@ -42,6 +51,8 @@ pub trait Config: frame_system::Config {
 	type EthBridge: EventClaimVerifier;
 	/// Currency functions
 	type MultiCurrency: MultiCurrency<AccountId = Self::AccountId, Balance = Balance, CurrencyId = AssetId>;
+	/// The number of blocks withdrawals over an asset's delay threshold are held before release
+	type WithdrawalDelay: Get<Self::BlockNumber>;
 	/// The overarching event type.
 	type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 }
@@ -62,6 +73,16 @@ decl_storage! {
 		ContractAddress get(fn contract_address): EthAddress;
 		/// Whether CENNZ deposits are active
 		CENNZDepositsActive get(fn cennz_deposit_active): bool;
+		/// Withdrawal limits per asset
+		WithdrawalLimits get(fn withdrawal_limits): map hasher(twox_64_concat) AssetId => Option<WithdrawalLimit<T::BlockNumber>>;
+		/// Amounts withdrawn per asset within its rolling window, bucketed by block (bucket start, amount)
+		WithdrawalHistory get(fn withdrawal_history): map hasher(twox_64_concat) AssetId => Vec<(T::BlockNumber, Balance)>;
+		/// The next available delayed withdrawal Id
+		NextWithdrawalId get(fn next_withdrawal_id): u64;
+		/// Withdrawals waiting in the delay queue
+		PendingWithdrawals get(fn pending_withdrawals): map hasher(twox_64_concat) u64 => Option<PendingWithdrawal<T::AccountId, T::BlockNumber>>;
+		/// Block numbers where delayed withdrawals will be released. Value is `true` if withdrawal `u64` is scheduled at the block.
+		WithdrawalSchedule get(fn withdrawal_schedule): double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) u64 => bool;
 	}
 	add_extra_genesis {
 		config(erc20s): Vec<(EthAddress, Vec<u8>, u8)>;
@@ -77,7 +98,10 @@ decl_storage! {
 }
 
 decl_event! {
-	pub enum Event<T> where AccountId = <T as frame_system::Config>::AccountId {
+	pub enum Event<T> where
+		AccountId = <T as frame_system::Config>::AccountId,
+		BlockNumber = <T as frame_system::Config>::BlockNumber,
+	{
 		/// An erc20 deposit claim has started. (deposit Id, sender)
 		Erc20Claim(u64, AccountId),
 		/// A bridged erc20 deposit succeeded.(deposit Id, asset, amount, beneficiary)
@@ -90,6 +114,14 @@ decl_event! {
 		SetContractAddress(EthAddress),
 		/// ERC20 CENNZ deposits activated
 		CENNZDepositsActive,
+		/// A withdrawal over the delay threshold was queued (withdrawal Id, asset, amount, beneficiary, release block)
+		Erc20WithdrawalDelayed(u64, AssetId, Balance, EthAddress, BlockNumber),
+		/// A delayed withdrawal was released (withdrawal Id, withdrawal proof Id)
+		Erc20WithdrawalReleased(u64, u64),
+		/// A delayed withdrawal was cancelled and refunded (withdrawal Id)
+		Erc20WithdrawalCancelled(u64),
+		/// The withdrawal limit of an asset has been set
+		SetWithdrawalLimit(AssetId, Option<WithdrawalLimit<BlockNumber>>),
 	}
 }
 
@@ -106,7 +138,13 @@ decl_error! {
 		/// Withdrawals are inactive
 		WithdrawalsPaused,
 		/// Withdrawals of this asset are not supported
-		UnsupportedAsset
+		UnsupportedAsset,
+		/// The withdrawal would exceed the asset's withdrawal cap
+		WithdrawalCapExceeded,
+		/// The withdrawal limit is invalid
+		InvalidWithdrawalLimit,
+		/// No delayed withdrawal exists with the Id
+		NoPendingWithdrawal,
 	}
 }
 
@@ -115,6 +153,12 @@ decl_module! {
 
 		fn deposit_event() = default;
 
+		/// Release delayed withdrawals scheduled for this block
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let released_count = Self::release_withdrawals_at(now);
+			T::DbWeight::get().reads_writes(2, 2).saturating_mul(released_count as Weight)
+		}
+
 		/// Activate/deactivate deposits (root only)
 		#[weight = 10_000_000]
 		pub fn activate_deposits(origin, activate: bool) {
@@ -154,9 +198,34 @@ decl_module! {
 			Self::deposit_event(<Event<T>>::Erc20Claim(event_claim_id, origin));
 		}
 
+		#[weight = 100_000_000]
+		/// Submit deposit claim with a proof of the deposit tx receipt
+		/// The claim is verified against the proven Ethereum block header, notaries need only agree on the header
+		/// Any caller may initiate a claim while only the intended beneficiary will be paid.
+		#[transactional]
+		pub fn deposit_claim_with_proof(origin, claim: Erc20DepositEvent, proof: EthReceiptProof) {
+			let origin = ensure_signed(origin)?;
+			ensure!(Self::deposits_active(), Error::<T>::DepositsPaused);
+			// fail a claim early for an amount that is too large
+			ensure!(claim.amount < U256::from(u128::max_value()), Error::<T>::InvalidAmount);
+			// fail a claim if beneficiary is not a valid Benson address
+			ensure!(T::AccountId::decode(&mut &claim.beneficiary.0[..]).is_ok(), Error::<T>::InvalidAddress);
+
+			let event_claim_id = T::EthBridge::submit_event_claim_with_proof(
+					&Self::contract_address().into(),
+					&T::DepositEventSignature::get().into(),
+					&EthAbiCodec::encode(&claim),
+					&proof,
+			)?;
+
+			Self::deposit_event(<Event<T>>::Erc20Claim(event_claim_id, origin));
+		}
+
 		#[weight = 50_000_000]
 		/// Withdraw generic assets from Benson in exchange for ERC20s
 		/// Tokens will be burnt and a proof generated to allow redemption of tokens on Ethereum
+		/// Withdrawals are subject to the asset's withdrawal limit, if any.
+		/// Amounts over the limit's delay threshold are queued and released after `WithdrawalDelay` blocks
 		#[transactional]
 		pub fn withdraw(origin, asset_id: AssetId, amount: Balance, beneficiary: EthAddress) {
 			let origin = ensure_signed(origin)?;
@@ -167,6 +236,15 @@ decl_module! {
 			let token_address = Self::asset_to_erc20(asset_id);
 			ensure!(token_address.is_some(), Error::<T>::UnsupportedAsset);
 
+			let now = <frame_system::Pallet<T>>::block_number();
+			let is_delayed = match Self::withdrawal_limits(asset_id) {
+				Some(limit) => {
+					Self::note_withdrawal(asset_id, amount, &limit, now)?;
+					amount >= limit.delay_threshold
+				}
+				None => false,
+			};
+
 			let _imbalance = T::MultiCurrency::withdraw(&origin, asset_id, amount, WithdrawReasons::empty(), frame_support::traits::ExistenceRequirement::KeepAlive)?;
 
 			let message = WithdrawMessage {
@@ -174,9 +252,57 @@ decl_module! {
 				amount: amount.into(),
 				beneficiary
 			};
-			let event_proof_id = T::EthBridge::generate_event_proof(&message)?;
 
-			Self::deposit_event(<Event<T>>::Erc20Withdraw(event_proof_id, asset_id, amount, beneficiary));
+			if is_delayed {
+				let withdrawal_id = Self::next_withdrawal_id();
+				let release_block = now.saturating_add(T::WithdrawalDelay::get());
+				<PendingWithdrawals<T>>::insert(withdrawal_id, PendingWithdrawal {
+					account: origin,
+					asset_id,
+					amount,
+					message,
+					release_block,
+					requested_block: now,
+				});
+				<WithdrawalSchedule<T>>::insert(release_block, withdrawal_id, true);
+				NextWithdrawalId::put(withdrawal_id.wrapping_add(1));
+
+				Self::deposit_event(<Event<T>>::Erc20WithdrawalDelayed(withdrawal_id, asset_id, amount, beneficiary, release_block));
+			} else {
+				let event_proof_id = T::EthBridge::generate_event_proof(&message, true)?;
+
+				Self::deposit_event(<Event<T>>::Erc20Withdraw(event_proof_id, asset_id, amount, beneficiary));
+			}
+		}
+
+		#[weight = 10_000_000]
+		#[transactional]
+		/// Cancel a delayed withdrawal, refunding the withdrawn amount (requires governance)
+		pub fn cancel_withdrawal(origin, withdrawal_id: u64) {
+			ensure_root(origin)?;
+			let pending = <PendingWithdrawals<T>>::take(withdrawal_id).ok_or(Error::<T>::NoPendingWithdrawal)?;
+			<WithdrawalSchedule<T>>::remove(pending.release_block, withdrawal_id);
+			Self::forget_withdrawal(pending.asset_id, pending.amount, pending.requested_block);
+			let _imbalance = T::MultiCurrency::deposit_creating(&pending.account, pending.asset_id, pending.amount);
+
+			Self::deposit_event(<Event<T>>::Erc20WithdrawalCancelled(withdrawal_id));
+		}
+
+		#[weight = 1_000_000]
+		/// Set or clear the withdrawal limit of an asset (requires governance)
+		pub fn set_withdrawal_limit(origin, asset_id: AssetId, limit: Option<WithdrawalLimit<T::BlockNumber>>) {
+			ensure_root(origin)?;
+			match limit.clone() {
+				Some(limit) => {
+					ensure!(!limit.window.is_zero(), Error::<T>::InvalidWithdrawalLimit);
+					<WithdrawalLimits<T>>::insert(asset_id, limit);
+				}
+				None => {
+					<WithdrawalLimits<T>>::remove(asset_id);
+					<WithdrawalHistory<T>>::remove(asset_id);
+				}
+			}
+			Self::deposit_event(<Event<T>>::SetWithdrawalLimit(asset_id, limit));
 		}
 
 		#[weight = 1_000_000]
@@ -212,6 +338,94 @@ decl_module! {
 }
 
 impl<T: Config> Module<T> {
+	/// Record a withdrawal of `amount` of `asset_id` at block `now`
+	/// Fails if the withdrawal would exceed the cap of `limit` within its rolling window
+	fn note_withdrawal(
+		asset_id: AssetId,
+		amount: Balance,
+		limit: &WithdrawalLimit<T::BlockNumber>,
+		now: T::BlockNumber,
+	) -> Result<(), DispatchError> {
+		// withdrawals are tracked in buckets, a bucket counts toward the window until its last block leaves the window.
+		// so a withdrawal counts for up to `bucket_length - 1` blocks longer than `window`, the cap is never exceeded
+		let bucket_length = (limit.window / WITHDRAWAL_WINDOW_BUCKETS.into()).max(One::one());
+		let bucket_start = now - now % bucket_length;
+
+		let mut history = Self::withdrawal_history(asset_id);
+		history.retain(|(start, _)| {
+			start.saturating_add(bucket_length).saturating_add(limit.window) > now.saturating_add(One::one())
+		});
+		let withdrawn = history
+			.iter()
+			.fold(Balance::zero(), |total, (_, amount)| total.saturating_add(*amount));
+		ensure!(
+			withdrawn.saturating_add(amount) <= limit.cap,
+			Error::<T>::WithdrawalCapExceeded
+		);
+
+		match history.last_mut() {
+			Some((start, bucket_amount)) if *start == bucket_start => {
+				*bucket_amount = bucket_amount.saturating_add(amount)
+			}
+			_ => history.push((bucket_start, amount)),
+		}
+		<WithdrawalHistory<T>>::insert(asset_id, history);
+
+		Ok(())
+	}
+
+	/// Remove a cancelled withdrawal of `amount` requested at `requested_block` from the withdrawal history
+	fn forget_withdrawal(asset_id: AssetId, amount: Balance, requested_block: T::BlockNumber) {
+		let mut history = Self::withdrawal_history(asset_id);
+		// the withdrawal was added to the latest bucket at the time, if it hasn't left the window yet
+		if let Some((_, bucket_amount)) = history.iter_mut().rev().find(|(start, _)| *start <= requested_block) {
+			*bucket_amount = bucket_amount.saturating_sub(amount);
+		}
+		history.retain(|(_, bucket_amount)| !bucket_amount.is_zero());
+		if history.is_empty() {
+			<WithdrawalHistory<T>>::remove(asset_id);
+		} else {
+			<WithdrawalHistory<T>>::insert(asset_id, history);
+		}
+	}
+
+	/// Generate proofs for all delayed withdrawals scheduled for release at block `now`
+	/// Withdrawals are retried next block if the bridge can't generate proofs
+	/// Returns the number of withdrawals processed
+	fn release_withdrawals_at(now: T::BlockNumber) -> u32 {
+		let mut count = 0_u32;
+		for (withdrawal_id, _) in <WithdrawalSchedule<T>>::drain_prefix(now) {
+			count += 1;
+			let mut pending = match <PendingWithdrawals<T>>::get(withdrawal_id) {
+				Some(pending) => pending,
+				None => continue,
+			};
+			match T::EthBridge::generate_event_proof(&pending.message, true) {
+				Ok(event_proof_id) => {
+					<PendingWithdrawals<T>>::remove(withdrawal_id);
+					Self::deposit_event(<Event<T>>::Erc20WithdrawalReleased(withdrawal_id, event_proof_id));
+					Self::deposit_event(<Event<T>>::Erc20Withdraw(
+						event_proof_id,
+						pending.asset_id,
+						pending.amount,
+						pending.message.beneficiary,
+					));
+				}
+				Err(err) => {
+					log::warn!(
+						"📌 delayed ERC20 withdrawal {:?} not released: {:?}",
+						withdrawal_id,
+						err
+					);
+					pending.release_block = now.saturating_add(One::one());
+					<WithdrawalSchedule<T>>::insert(pending.release_block, withdrawal_id, true);
+					<PendingWithdrawals<T>>::insert(withdrawal_id, pending);
+				}
+			}
+		}
+		count
+	}
+
 	/// fulfil a deposit claim for the given event
 	pub fn do_deposit(verified_event: Erc20DepositEvent) -> Result<(AssetId, Balance, T::AccountId), DispatchError> {
 		let asset_id = match Self::erc20_to_asset(verified_event.token_address) {
@@ -13,8 +13,249 @@
 *     https://annie lai.ai/licenses/lgplv3.txt
 */
 
+use crate::{
+	mock::{AccountId, Erc20Peg, Event as MockEvent, ExtBuilder, GenericAsset, MockEthBridge, System, Test},
+	AssetIdToErc20, Error, Event, PendingWithdrawal, WithdrawMessage, WithdrawalLimit, WithdrawalsActive,
+};
+use benson_primitives::types::{AssetId, Balance};
+use crml_support::{EthAbiCodec, MultiCurrency, H160};
+use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
+use frame_system::RawOrigin;
+
+const ALICE: AccountId = 1;
+const TOKEN_ADDRESS: H160 = H160([0x11; 20]);
+const BENEFICIARY: H160 = H160([0x22; 20]);
+
+/// Returns the asset Id of the mapped ERC20, after funding Alice and activating withdrawals
+fn setup() -> AssetId {
+	let asset_id = <GenericAsset as MultiCurrency>::fee_currency();
+	AssetIdToErc20::insert(asset_id, TOKEN_ADDRESS);
+	WithdrawalsActive::put(true);
+	let _ = <GenericAsset as MultiCurrency>::deposit_creating(&ALICE, asset_id, 1_000_000);
+	asset_id
+}
+
+fn set_limit(asset_id: AssetId, cap: Balance, window: u64, delay_threshold: Balance) {
+	assert_ok!(Erc20Peg::set_withdrawal_limit(
+		RawOrigin::Root.into(),
+		asset_id,
+		Some(WithdrawalLimit {
+			cap,
+			window,
+			delay_threshold,
+		}),
+	));
+}
+
+fn withdraw_message(amount: Balance) -> WithdrawMessage {
+	WithdrawMessage {
+		token_address: TOKEN_ADDRESS,
+		amount: amount.into(),
+		beneficiary: BENEFICIARY,
+	}
+}
+
+fn has_event(event: Event<Test>) -> bool {
+	System::events()
+		.iter()
+		.any(|record| record.event == MockEvent::Erc20Peg(event.clone()))
+}
+
 #[test]
 fn erc20_deposit_new_asset() {}
 
 #[test]
 fn erc20_deposit_existing_asset() {}
+
+#[test]
+fn withdraw_without_limit_is_proven_immediately() {
+	ExtBuilder::default().build().execute_with(|| {
+		let asset_id = setup();
+		assert_ok!(Erc20Peg::withdraw(Some(ALICE).into(), asset_id, 500_000, BENEFICIARY));
+
+		assert_eq!(GenericAsset::free_balance(asset_id, &ALICE), 500_000);
+		assert_eq!(MockEthBridge::event_proofs(), vec![withdraw_message(500_000).encode()]);
+		assert!(has_event(Event::Erc20Withdraw(0, asset_id, 500_000, BENEFICIARY)));
+	});
+}
+
+#[test]
+fn set_withdrawal_limit_requires_root_and_window() {
+	ExtBuilder::default().build().execute_with(|| {
+		let asset_id = setup();
+		let limit = WithdrawalLimit {
+			cap: 100,
+			window: 0,
+			delay_threshold: 100,
+		};
+		assert_noop!(
+			Erc20Peg::set_withdrawal_limit(Some(ALICE).into(), asset_id, Some(limit.clone())),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Erc20Peg::set_withdrawal_limit(RawOrigin::Root.into(), asset_id, Some(limit)),
+			Error::<Test>::InvalidWithdrawalLimit
+		);
+
+		set_limit(asset_id, 100, 10, 100);
+		assert_eq!(Erc20Peg::withdrawal_limits(asset_id).unwrap().cap, 100);
+
+		assert_ok!(Erc20Peg::set_withdrawal_limit(RawOrigin::Root.into(), asset_id, None));
+		assert!(Erc20Peg::withdrawal_limits(asset_id).is_none());
+		assert!(has_event(Event::SetWithdrawalLimit(asset_id, None)));
+	});
+}
+
+#[test]
+fn withdrawals_over_cap_fail_until_window_passes() {
+	ExtBuilder::default().build().execute_with(|| {
+		let asset_id = setup();
+		set_limit(asset_id, 1_000, 24, 1_000);
+
+		assert_ok!(Erc20Peg::withdraw(Some(ALICE).into(), asset_id, 600, BENEFICIARY));
+		System::set_block_number(10);
+		assert_ok!(Erc20Peg::withdraw(Some(ALICE).into(), asset_id, 400, BENEFICIARY));
+		assert_noop!(
+			Erc20Peg::withdraw(Some(ALICE).into(), asset_id, 1, BENEFICIARY),
+			Error::<Test>::WithdrawalCapExceeded
+		);
+
+		// the first withdrawal leaves the window
+		System::set_block_number(25);
+		assert_noop!(
+			Erc20Peg::withdraw(Some(ALICE).into(), asset_id, 601, BENEFICIARY),
+			Error::<Test>::WithdrawalCapExceeded
+		);
+		assert_ok!(Erc20Peg::withdraw(Some(ALICE).into(), asset_id, 600, BENEFICIARY));
+		assert_eq!(Erc20Peg::withdrawal_history(asset_id), vec![(10, 400), (25, 600)]);
+	});
+}
+
+#[test]
+fn withdrawals_count_until_their_bucket_leaves_the_window() {
+	ExtBuilder::default().build().execute_with(|| {
+		let asset_id = setup();
+		// 2 block buckets
+		set_limit(asset_id, 1_000, 48, 1_000);
+
+		System::set_block_number(0);
+		assert_ok!(Erc20Peg::withdraw(Some(ALICE).into(), asset_id, 1_000, BENEFICIARY));
+		assert_eq!(Erc20Peg::withdrawal_history(asset_id), vec![(0, 1_000)]);
+
+		// slack: the withdrawal at block 0 shares a bucket with block 1, it counts until block 1 leaves the window
+		System::set_block_number(48);
+		assert_noop!(
+			Erc20Peg::withdraw(Some(ALICE).into(), asset_id, 1, BENEFICIARY),
+			Error::<Test>::WithdrawalCapExceeded
+		);
+		System::set_block_number(49);
+		assert_ok!(Erc20Peg::withdraw(Some(ALICE).into(), asset_id, 1_000, BENEFICIARY));
+		assert_eq!(Erc20Peg::withdrawal_history(asset_id), vec![(48, 1_000)]);
+	});
+}
+
+#[test]
+fn withdrawal_over_threshold_is_delayed_and_released() {
+	ExtBuilder::default().build().execute_with(|| {
+		let asset_id = setup();
+		set_limit(asset_id, 1_000_000, 100, 1_000);
+
+		assert_ok!(Erc20Peg::withdraw(Some(ALICE).into(), asset_id, 1_000, BENEFICIARY));
+		assert_eq!(GenericAsset::free_balance(asset_id, &ALICE), 999_000);
+		assert!(MockEthBridge::event_proofs().is_empty());
+		assert_eq!(
+			Erc20Peg::pending_withdrawals(0),
+			Some(PendingWithdrawal {
+				account: ALICE,
+				asset_id,
+				amount: 1_000,
+				message: withdraw_message(1_000),
+				release_block: 11,
+				requested_block: 1,
+			})
+		);
+		assert!(Erc20Peg::withdrawal_schedule(11, 0));
+		assert!(has_event(Event::Erc20WithdrawalDelayed(
+			0,
+			asset_id,
+			1_000,
+			BENEFICIARY,
+			11
+		)));
+
+		Erc20Peg::on_initialize(10);
+		assert!(MockEthBridge::event_proofs().is_empty());
+
+		Erc20Peg::on_initialize(11);
+		assert_eq!(MockEthBridge::event_proofs(), vec![withdraw_message(1_000).encode()]);
+		assert!(Erc20Peg::pending_withdrawals(0).is_none());
+		assert!(!Erc20Peg::withdrawal_schedule(11, 0));
+		assert!(has_event(Event::Erc20WithdrawalReleased(0, 0)));
+		assert!(has_event(Event::Erc20Withdraw(0, asset_id, 1_000, BENEFICIARY)));
+	});
+}
+
+#[test]
+fn delayed_withdrawal_is_retried_when_proof_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		let asset_id = setup();
+		set_limit(asset_id, 1_000_000, 100, 1_000);
+		assert_ok!(Erc20Peg::withdraw(Some(ALICE).into(), asset_id, 1_000, BENEFICIARY));
+
+		MockEthBridge::pause_proofs(true);
+		Erc20Peg::on_initialize(11);
+		assert_eq!(Erc20Peg::pending_withdrawals(0).unwrap().release_block, 12);
+		assert!(Erc20Peg::withdrawal_schedule(12, 0));
+
+		MockEthBridge::pause_proofs(false);
+		Erc20Peg::on_initialize(12);
+		assert!(Erc20Peg::pending_withdrawals(0).is_none());
+		assert_eq!(MockEthBridge::event_proofs().len(), 1);
+	});
+}
+
+#[test]
+fn cancel_withdrawal_refunds_account() {
+	ExtBuilder::default().build().execute_with(|| {
+		let asset_id = setup();
+		set_limit(asset_id, 1_000_000, 100, 1_000);
+		assert_ok!(Erc20Peg::withdraw(Some(ALICE).into(), asset_id, 5_000, BENEFICIARY));
+		assert_eq!(GenericAsset::free_balance(asset_id, &ALICE), 995_000);
+
+		assert_noop!(
+			Erc20Peg::cancel_withdrawal(Some(ALICE).into(), 0),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(Erc20Peg::cancel_withdrawal(RawOrigin::Root.into(), 0));
+		assert_eq!(GenericAsset::free_balance(asset_id, &ALICE), 1_000_000);
+		assert!(Erc20Peg::pending_withdrawals(0).is_none());
+		assert!(!Erc20Peg::withdrawal_schedule(11, 0));
+		assert!(has_event(Event::Erc20WithdrawalCancelled(0)));
+
+		Erc20Peg::on_initialize(11);
+		assert!(MockEthBridge::event_proofs().is_empty());
+		assert_noop!(
+			Erc20Peg::cancel_withdrawal(RawOrigin::Root.into(), 0),
+			Error::<Test>::NoPendingWithdrawal
+		);
+	});
+}
+
+#[test]
+fn cancel_withdrawal_frees_withdrawal_limit() {
+	ExtBuilder::default().build().execute_with(|| {
+		let asset_id = setup();
+		set_limit(asset_id, 10_000, 100, 5_000);
+		assert_ok!(Erc20Peg::withdraw(Some(ALICE).into(), asset_id, 2_000, BENEFICIARY));
+		assert_ok!(Erc20Peg::withdraw(Some(ALICE).into(), asset_id, 8_000, BENEFICIARY));
+		assert_noop!(
+			Erc20Peg::withdraw(Some(ALICE).into(), asset_id, 1, BENEFICIARY),
+			Error::<Test>::WithdrawalCapExceeded
+		);
+
+		// the cancelled amount no longer counts toward the cap
+		assert_ok!(Erc20Peg::cancel_withdrawal(RawOrigin::Root.into(), 0));
+		assert_eq!(Erc20Peg::withdrawal_history(asset_id), vec![(0, 2_000)]);
+		assert_ok!(Erc20Peg::withdraw(Some(ALICE).into(), asset_id, 8_000, BENEFICIARY));
+	});
+}
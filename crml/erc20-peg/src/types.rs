@@ -12,8 +12,9 @@
 *     https://annie lai.ai/licenses/gplv3.txt
 *     https://annie lai.ai/licenses/lgplv3.txt
 */
+use benson_primitives::types::{AssetId, Balance};
 use codec::{Decode, Encode};
-pub use crml_support::{EthAbiCodec, H160, H256, U256};
+pub use crml_support::{EthAbiCodec, ERC20_WITHDRAW_TAG, H160, H256, U256};
 use scale_info::TypeInfo;
 use sp_std::convert::TryInto;
 use sp_std::prelude::*;
@@ -35,7 +36,7 @@ pub struct Erc20DepositEvent {
 
 /// A withdraw message to prove and submit to Ethereum
 /// Allowing redemption of ERC20s
-#[derive(Debug, Default, Clone, PartialEq, Decode, Encode)]
+#[derive(Debug, Default, Clone, PartialEq, Decode, Encode, TypeInfo)]
 pub struct WithdrawMessage {
 	/// The ERC20 token address / type deposited
 	pub token_address: EthAddress,
@@ -45,14 +46,43 @@ pub struct WithdrawMessage {
 	pub beneficiary: EthAddress,
 }
 
+/// Limits on the withdrawals of an asset
+#[derive(Debug, Default, Clone, PartialEq, Decode, Encode, TypeInfo)]
+pub struct WithdrawalLimit<BlockNumber> {
+	/// The max. total amount withdrawn within any `window` blocks
+	pub cap: Balance,
+	/// The length of the rolling window in blocks
+	pub window: BlockNumber,
+	/// Withdrawals of at least this amount are delayed
+	pub delay_threshold: Balance,
+}
+
+/// A withdrawal waiting in the delay queue
+#[derive(Debug, Default, Clone, PartialEq, Decode, Encode, TypeInfo)]
+pub struct PendingWithdrawal<AccountId, BlockNumber> {
+	/// The account which made the withdrawal
+	pub account: AccountId,
+	/// The asset withdrawn
+	pub asset_id: AssetId,
+	/// The amount withdrawn
+	pub amount: Balance,
+	/// The withdraw message to prove on release
+	pub message: WithdrawMessage,
+	/// The block at which the withdrawal is released
+	pub release_block: BlockNumber,
+	/// The block at which the withdrawal was requested and counted toward the withdrawal limit
+	pub requested_block: BlockNumber,
+}
+
 impl EthAbiCodec for WithdrawMessage {
-	/// Encode `ERC20DepositEvent` into 32-byte words
+	/// Encode `WithdrawMessage` into 32-byte words, prefixed by `ERC20_WITHDRAW_TAG`
 	/// https://docs.soliditylang.org/en/v0.5.3/abi-spec.html#formal-specification-of-the-encoding
 	fn encode(&self) -> Vec<u8> {
-		let mut buf = [0_u8; 32 * 3];
-		buf[12..32].copy_from_slice(&self.token_address.to_fixed_bytes());
-		buf[32..64].copy_from_slice(&Into::<[u8; 32]>::into(self.amount));
-		buf[76..96].copy_from_slice(&self.beneficiary.to_fixed_bytes());
+		let mut buf = [0_u8; 32 * 4];
+		buf[..32].copy_from_slice(&ERC20_WITHDRAW_TAG);
+		buf[44..64].copy_from_slice(&self.token_address.to_fixed_bytes());
+		buf[64..96].copy_from_slice(&Into::<[u8; 32]>::into(self.amount));
+		buf[108..128].copy_from_slice(&self.beneficiary.to_fixed_bytes());
 		buf.to_vec()
 	}
 
@@ -91,9 +121,35 @@ impl EthAbiCodec for Erc20DepositEvent {
 
 #[cfg(test)]
 mod test {
-	use super::{Erc20DepositEvent, EthAbiCodec};
+	use super::{Erc20DepositEvent, EthAbiCodec, WithdrawMessage, ERC20_WITHDRAW_TAG};
 	use crml_support::{H160, H256, U256};
 
+	#[test]
+	fn withdraw_message_encode() {
+		let message = WithdrawMessage {
+			token_address: H160::from_low_u64_be(55),
+			amount: U256::from(123),
+			beneficiary: H160::from_low_u64_be(77),
+		};
+		let raw = [
+			&ERC20_WITHDRAW_TAG[..],
+			H256::from_low_u64_be(55).as_bytes(),
+			H256::from_low_u64_be(123).as_bytes(),
+			H256::from_low_u64_be(77).as_bytes(),
+		]
+		.concat();
+		assert_eq!(EthAbiCodec::encode(&message), raw);
+	}
+
+	#[test]
+	fn withdraw_tags() {
+		assert_eq!(ERC20_WITHDRAW_TAG, sp_core::keccak_256(b"ERC20_WITHDRAW"));
+		assert_eq!(
+			crml_support::ERC721_WITHDRAW_TAG,
+			sp_core::keccak_256(b"ERC721_WITHDRAW")
+		);
+	}
+
 	#[test]
 	fn deposit_event_encode() {
 		let event = Erc20DepositEvent {
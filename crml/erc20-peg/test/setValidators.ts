@@ -0,0 +1,93 @@
+import { expect, use } from 'chai';
+import { utils, Wallet } from 'ethers';
+import { deployContract, MockProvider, solidity } from 'ethereum-waffle';
+// compiled by `yarn build`
+import BensonBridge from '../artifacts/contracts/BensonBridge.sol/BensonBridge.json';
+import BensonNFTBridge from '../artifacts/contracts/BensonNFTBridge.sol/BensonNFTBridge.json';
+import BensonRouter from '../artifacts/contracts/BensonRouter.sol/BensonRouter.json';
+
+use(solidity);
+
+// hardhat's first development account, signing as a Benson validator
+// ECDSA public key: 0x038318535b54105d4a7aae60c08fc45f9687181b4fdfc625bd1a753fa7397fed75
+const VALIDATOR_KEY = '0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80';
+// hardhat's second development account
+const OTHER_VALIDATOR_KEY = '0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d';
+// The message ethy-gadget signs for a change to validator set 2 (the validator above), witnessed by validator set 1
+// with event id 7. Asserted in ethy-gadget `worker::test::encode_validator_set_change_matches_bridge_contracts`
+const SET_VALIDATORS_MESSAGE = '0x' + [
+  '0000000000000000000000000000000000000000000000000000000000000080',
+  '0000000000000000000000000000000000000000000000000000000000000002',
+  '0000000000000000000000000000000000000000000000000000000000000001',
+  '0000000000000000000000000000000000000000000000000000000000000007',
+  '0000000000000000000000000000000000000000000000000000000000000001',
+  '000000000000000000000000f39fd6e51aad88f6f4ce6ab8827279cfffb92266',
+].join('');
+const SET_VALIDATORS_TYPES = ['address[]', 'uint256', 'uint256', 'uint256'];
+
+describe('setValidators', () => {
+  const [deployer, other] = new MockProvider().getWallets();
+  const validator = new Wallet(VALIDATOR_KEY);
+  const otherValidator = new Wallet(OTHER_VALIDATOR_KEY);
+  // ethy-gadget signs the keccak hash of the message, without a prefix
+  const sign = (message: string, signer: Wallet = validator) => signer._signingKey().signDigest(utils.keccak256(message));
+
+  for (const artifact of [BensonBridge, BensonNFTBridge, BensonRouter]) {
+    it(`${artifact.contractName} only lets the owner set the first validators`, async () => {
+      const bridge = await deployContract(deployer, artifact);
+      await expect(bridge.setValidators([validator.address], 1, 0, [], [], []))
+        .to.be.revertedWith('validators not set');
+      await expect(bridge.connect(other).initValidators([validator.address], 1))
+        .to.be.revertedWith('Ownable: caller is not the owner');
+
+      await bridge.initValidators([validator.address], 1);
+      expect(await bridge.validatorSetId()).to.equal(1);
+      await expect(bridge.initValidators([other.address], 2))
+        .to.be.revertedWith('validators already set');
+    });
+
+    it(`${artifact.contractName} requires signatures from 51% of validators`, async () => {
+      const bridge = await deployContract(deployer, artifact);
+      await bridge.initValidators([validator.address, otherValidator.address], 1);
+      const message = utils.defaultAbiCoder.encode(SET_VALIDATORS_TYPES, [[validator.address], 2, 1, 7]);
+      const signature = sign(message);
+      const otherSignature = sign(message, otherValidator);
+      const zero = utils.hexZeroPad('0x', 32);
+
+      await expect(bridge.setValidators([validator.address], 2, 7, [], [], []))
+        .to.be.revertedWith('signatures misaligned');
+      await expect(bridge.setValidators([validator.address], 2, 7, [0, 0], [zero, zero], [zero, zero]))
+        .to.be.revertedWith('not enough signatures');
+      // 1 of 2 is below the threshold
+      await expect(bridge.setValidators([validator.address], 2, 7, [signature.v, 0], [signature.r, zero], [signature.s, zero]))
+        .to.be.revertedWith('not enough signatures');
+
+      await bridge.setValidators(
+        [validator.address],
+        2,
+        7,
+        [signature.v, otherSignature.v],
+        [signature.r, otherSignature.r],
+        [signature.s, otherSignature.s],
+      );
+      expect(await bridge.validatorSetId()).to.equal(2);
+    });
+
+    it(`${artifact.contractName} verifies validator set changes signed by ethy-gadget`, async () => {
+      const bridge = await deployContract(deployer, artifact);
+      await bridge.initValidators([validator.address], 1);
+
+      expect(utils.defaultAbiCoder.encode(SET_VALIDATORS_TYPES, [[validator.address], 2, 1, 7]))
+        .to.equal(SET_VALIDATORS_MESSAGE);
+
+      // signed for another event
+      const otherEvent = sign(utils.defaultAbiCoder.encode(SET_VALIDATORS_TYPES, [[validator.address], 2, 1, 8]));
+      await expect(bridge.setValidators([validator.address], 2, 7, [otherEvent.v], [otherEvent.r], [otherEvent.s]))
+        .to.be.revertedWith('signature invalid');
+
+      const signature = sign(SET_VALIDATORS_MESSAGE);
+      await bridge.setValidators([validator.address], 2, 7, [signature.v], [signature.r], [signature.s]);
+      expect(await bridge.validatorSetId()).to.equal(2);
+    });
+  }
+});
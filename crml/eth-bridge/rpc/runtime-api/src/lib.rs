@@ -0,0 +1,44 @@
+// Copyright 2019-2021
+//     by  Annie Lai Investments Ltd.
+//     and Parity Technologies (UK) Ltd.
+// This file is part of Plug-blockchain.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition required by Eth Bridge RPC extensions.
+//!
+//! This API should be imported and implemented by the runtime,
+//! of a node that wants to use the custom RPC extension
+//! adding eth bridge claim queries.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use crml_eth_bridge::{EthHash, EventClaimId, EventClaimStatus, NotaryParticipation};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait EthBridgeApi<EthyId> where
+		EthyId: Codec,
+	{
+		/// Get the notarization status of a queued event claim
+		fn event_claim_status(event_claim_id: EventClaimId) -> Option<EventClaimStatus<EthyId>>;
+		/// Get the Ids of queued event claims for an Ethereum tx hash
+		fn event_claim_ids_by_tx_hash(tx_hash: EthHash) -> Vec<EventClaimId>;
+		/// Get whether an event from an Ethereum tx hash has been processed
+		fn is_tx_hash_processed(tx_hash: EthHash) -> bool;
+		/// Get the number of claims notarized this session and each active notary's participation in them
+		fn notary_participation() -> (u32, Vec<(EthyId, NotaryParticipation)>);
+	}
+}
@@ -0,0 +1,222 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC interface for the eth bridge module.
+
+use codec::{Codec, Encode};
+use crml_eth_bridge::{EthAddress, EthHash, EventClaimId, EventClaimStatus as RuntimeEventClaimStatus};
+pub use crml_eth_bridge_rpc_runtime_api::EthBridgeApi as EthBridgeRuntimeApi;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+#[rpc]
+pub trait EthBridgeApi<BlockHash> {
+	/// Get the notarization status of a queued event claim with a yay/nay breakdown
+	/// Returns `null` if the claim doesn't exist or has already been processed
+	#[rpc(name = "ethBridge_getEventClaim")]
+	fn event_claim(&self, event_claim_id: EventClaimId, at: Option<BlockHash>) -> Result<Option<EventClaimStatus>>;
+
+	/// Get the queued event claims for an Ethereum tx hash and whether the tx has been processed
+	#[rpc(name = "ethBridge_getTxStatus")]
+	fn tx_status(&self, tx_hash: EthHash, at: Option<BlockHash>) -> Result<TxStatus>;
+
+	/// Get each active notary's participation in the claims notarized this session
+	#[rpc(name = "ethBridge_getNotaryParticipation")]
+	fn notary_participation(&self, at: Option<BlockHash>) -> Result<SessionParticipation>;
+}
+
+/// A struct that implements the [`EthBridgeApi`].
+pub struct EthBridge<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> EthBridge<C, P> {
+	/// Create new `EthBridge` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+/// A notary's vote on an event claim
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Notarization {
+	/// The SCALE encoded notary public key
+	notary: Bytes,
+	/// The `EventClaimResult` reported by the notary e.g. "Valid", "NotEnoughConfirmations"
+	result: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EventClaimStatus {
+	event_claim_id: EventClaimId,
+	tx_hash: EthHash,
+	contract_address: EthAddress,
+	event_signature: EthHash,
+	event_data: Option<Bytes>,
+	notarizations: Vec<Notarization>,
+	yay_count: u32,
+	nay_count: u32,
+	/// Notarizations with a transient result e.g. "DataProviderErr", these don't count toward invalidation
+	transient_count: u32,
+	notary_count: u32,
+}
+
+impl<EthyId: Codec> From<RuntimeEventClaimStatus<EthyId>> for EventClaimStatus {
+	fn from(status: RuntimeEventClaimStatus<EthyId>) -> Self {
+		let (contract_address, event_signature) = status.event_type;
+		EventClaimStatus {
+			event_claim_id: status.event_claim_id,
+			tx_hash: status.tx_hash,
+			contract_address,
+			event_signature,
+			event_data: status.event_data.map(Into::into),
+			notarizations: status
+				.notarizations
+				.into_iter()
+				.map(|(notary, result)| Notarization {
+					notary: notary.encode().into(),
+					result: format!("{:?}", result),
+				})
+				.collect(),
+			yay_count: status.yay_count,
+			nay_count: status.nay_count,
+			transient_count: status.transient_count,
+			notary_count: status.notary_count,
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TxStatus {
+	/// Ids of queued event claims for the tx
+	event_claim_ids: Vec<EventClaimId>,
+	/// Whether an event from the tx has been processed (`ProcessedTxHashes`)
+	processed: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NotaryParticipation {
+	/// The SCALE encoded notary public key
+	notary: Bytes,
+	/// Claims the notary voted on
+	votes: u32,
+	/// Claims the notary voted against the outcome of
+	disagreements: u32,
+	/// Claims the notary did not vote on
+	missed: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionParticipation {
+	/// The number of claims notarized this session
+	claim_count: u32,
+	notaries: Vec<NotaryParticipation>,
+}
+
+impl<C, Block, EthyId> EthBridgeApi<<Block as BlockT>::Hash> for EthBridge<C, (Block, EthyId)>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: EthBridgeRuntimeApi<Block, EthyId>,
+	EthyId: Codec + Send + Sync + 'static,
+{
+	fn event_claim(
+		&self,
+		event_claim_id: EventClaimId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<EventClaimStatus>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		self.client
+			.runtime_api()
+			.event_claim_status(&at, event_claim_id)
+			.map(|status| status.map(Into::into))
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError as i64),
+				message: "Unable to query event claim.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+
+	fn tx_status(&self, tx_hash: EthHash, at: Option<<Block as BlockT>::Hash>) -> Result<TxStatus> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let api = self.client.runtime_api();
+
+		let event_claim_ids = api.event_claim_ids_by_tx_hash(&at, tx_hash).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError as i64),
+			message: "Unable to query event claims.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+		let processed = api.is_tx_hash_processed(&at, tx_hash).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError as i64),
+			message: "Unable to query processed tx hashes.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+
+		Ok(TxStatus {
+			event_claim_ids,
+			processed,
+		})
+	}
+
+	fn notary_participation(&self, at: Option<<Block as BlockT>::Hash>) -> Result<SessionParticipation> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let (claim_count, participation) =
+			self.client
+				.runtime_api()
+				.notary_participation(&at)
+				.map_err(|e| RpcError {
+					code: ErrorCode::ServerError(Error::RuntimeError as i64),
+					message: "Unable to query notary participation.".into(),
+					data: Some(format!("{:?}", e).into()),
+				})?;
+
+		Ok(SessionParticipation {
+			claim_count,
+			notaries: participation
+				.into_iter()
+				.map(|(notary, participation)| NotaryParticipation {
+					notary: notary.encode().into(),
+					votes: participation.votes,
+					disagreements: participation.disagreements,
+					missed: claim_count.saturating_sub(participation.votes),
+				})
+				.collect(),
+		})
+	}
+}
@@ -0,0 +1,126 @@
+/* Copyright 2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+//! Ethereum block, transaction and receipt fixtures for proof tests
+//!
+//! - `MAINNET_*` fixtures are reproducible from any archive node
+//! - The puppy trie is the `trietest` example from ethereum/tests, it includes extension & inline nodes
+//! - The deposit block is synthetic, not captured chain data: its transactions and receipts are consensus encoded
+//! and committed to by a London header the same way as a mainnet block. It covers each transaction/receipt type
+//!
+//! Mainnet receipt proofs can be captured from an archive node with the `capture-receipt-proof` tool in `ethy-relayer`,
+//! which checks the header hash and the rebuilt trie roots before printing the fixture consts
+
+use hex_literal::hex;
+
+/// Ethereum mainnet genesis block header (hash: `0xd4e5…8fa3`)
+pub const MAINNET_GENESIS_HEADER: [u8; 535] = hex!("f90214a00000000000000000000000000000000000000000000000000000000000000000a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347940000000000000000000000000000000000000000a0d7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000850400000000808213888080a011bbe8db4e347b4e8c937c1c8370e4b5ed33adb3db69cbdb7a38e1e50b1b82faa00000000000000000000000000000000000000000000000000000000000000000880000000000000042");
+
+/// The first Ethereum mainnet transaction, the only tx in block 46147 (hash: `0x5c50…2060`)
+pub const MAINNET_TX_46147: [u8; 105] = hex!("f86780862d79883d2000825208945df9b87991262f6ba471f09758cde1c0fc1de734827a69801ca088ff6cf0fefd94db46111149ae4bfc179e9b94721fffd821d38d16464b3f71d0a045e0aff800961cfce805daef7016b9b675c137a6a41a548f7b60a3484c06a33a");
+
+/// Transactions root of mainnet block 46147
+pub const MAINNET_TX_ROOT_46147: [u8; 32] = hex!("4513310fcb9f6f616972a3b948dc5d547f280849a87ebb5af0191f98b87be598");
+
+/// Proof of tx 0 in the transactions trie of mainnet block 46147
+pub const MAINNET_TX_PROOF_46147: [&[u8]; 1] = [
+	&hex!("f86e822080b869f86780862d79883d2000825208945df9b87991262f6ba471f09758cde1c0fc1de734827a69801ca088ff6cf0fefd94db46111149ae4bfc179e9b94721fffd821d38d16464b3f71d0a045e0aff800961cfce805daef7016b9b675c137a6a41a548f7b60a3484c06a33a"),
+];
+
+/// Root of the trie {do: verb, dog: puppy, doge: coin, horse: stallion}
+pub const PUPPY_TRIE_ROOT: [u8; 32] = hex!("5991bb8c6514148a29db676a14ac506cd2cd5775ace63c30a4fe457715e9ac84");
+
+/// Proof of `doge` in the puppy trie
+pub const PUPPY_TRIE_PROOF_DOGE: [&[u8]; 4] = [
+	&hex!("e216a0bd3ee507e6c67cfefca98f84be47c1bbc009315fabc4405db4ba32190374572a"),
+	&hex!("f84080808080a094a9f95bd89698e4da1812e0518053813b4d5b87caaf6b3c6fa57e9e50c0ff68808080cf85206f727365887374616c6c696f6e8080808080808080"),
+	&hex!("e482006fa0d43b87fdcd4217013ccc92d04662e12d36e4cc25dc690077cd821a1956fc3e36"),
+	&hex!("f3808080808080de17dc808080808080c63584636f696e8080808080808080808570757070798080808080808080808476657262"),
+];
+
+/// Proof of `horse` in the puppy trie
+pub const PUPPY_TRIE_PROOF_HORSE: [&[u8]; 2] = [
+	&hex!("e216a0bd3ee507e6c67cfefca98f84be47c1bbc009315fabc4405db4ba32190374572a"),
+	&hex!("f84080808080a094a9f95bd89698e4da1812e0518053813b4d5b87caaf6b3c6fa57e9e50c0ff68808080cf85206f727365887374616c6c696f6e8080808080808080"),
+];
+
+/// A London block header (number 13,000,000 at timestamp 1,630,000,000) committing to `DEPOSIT_BLOCK_TXS` and their receipts
+pub const DEPOSIT_BLOCK_HEADER: [u8; 528] = hex!("f9020da01111111111111111111111111111111111111111111111111111111111111111a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942222222222222222222222222222222222222222a03333333333333333333333333333333333333333333333333333333333333333a0eaa2540ca7886c0b0b2dd4c06b5d958d7b22918fbdd9583876a89cc8ce4fcebca069b0b80afbcd48b1d0e4034912617995ca3a6bb9b3ab37cc8422cf5b44c26a5bb9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000871d1f1a1b1c1d1e83c65d408401c9c3808302bf20846127d3808662656e736f6ea04444444444444444444444444444444444444444444444444444444444444444880000000000000000843b9aca00");
+
+/// Hash of `DEPOSIT_BLOCK_HEADER`
+pub const DEPOSIT_BLOCK_HASH: [u8; 32] = hex!("b12e9141d6154963d788ea22694eda987dfcb8b9824ed0e637d1e677b667f1ef");
+
+/// Transactions of the deposit block
+/// 0: legacy transfer, 1: EIP-1559 deposit, 2: legacy deposit via another contract, 3: failed EIP-2930 call
+pub const DEPOSIT_BLOCK_TXS: [&[u8]; 4] = [
+	&hex!("f86780862d79883d2000825208945df9b87991262f6ba471f09758cde1c0fc1de734827a69801ca088ff6cf0fefd94db46111149ae4bfc179e9b94721fffd821d38d16464b3f71d0a045e0aff800961cfce805daef7016b9b675c137a6a41a548f7b60a3484c06a33a"),
+	&hex!("02f40107843b9aca0085174876e800830138809404040404040404040404040404040404040404048084deadbeefc001821111822222"),
+	&hex!("eb01850ba43b740083015f909409090909090909090909090909090909090909098082cafe25823333824444"),
+	&hex!("01ec0103850ba43b740082ea609404040404040404040404040404040404040404048082beefc080825555826666"),
+];
+
+/// Proof of tx 0 in the deposit block transactions trie
+pub const DEPOSIT_BLOCK_TX_PROOF_0: [&[u8]; 2] = [
+	&hex!("f851a0080a44826ba85a7df2de6fe139a6d51d37a483abc0c4083448450130b56d2f1180808080808080a053c4205913a8c0f08fcde981d7542d7a134805c6f448a128035b8233d30356108080808080808080"),
+	&hex!("f86c30b869f86780862d79883d2000825208945df9b87991262f6ba471f09758cde1c0fc1de734827a69801ca088ff6cf0fefd94db46111149ae4bfc179e9b94721fffd821d38d16464b3f71d0a045e0aff800961cfce805daef7016b9b675c137a6a41a548f7b60a3484c06a33a"),
+];
+
+/// Proof of receipt 0 in the deposit block receipts trie
+pub const DEPOSIT_BLOCK_RECEIPT_PROOF_0: [&[u8]; 2] = [
+	&hex!("f851a04c9ac82c3dac8c468dada16650cd9daba1d4425e7868b60a9d2d4295a62916c380808080808080a0e58215be848c1293dd381210359d84485553000a82b67410406d183b42adbbdd8080808080808080"),
+	&hex!("f9010f30b9010bf9010801825208b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c0"),
+];
+
+/// Proof of tx 1 in the deposit block transactions trie
+pub const DEPOSIT_BLOCK_TX_PROOF_1: [&[u8]; 3] = [
+	&hex!("f851a0080a44826ba85a7df2de6fe139a6d51d37a483abc0c4083448450130b56d2f1180808080808080a053c4205913a8c0f08fcde981d7542d7a134805c6f448a128035b8233d30356108080808080808080"),
+	&hex!("f87180a01b8d7ca14f6d3d491667798d205ec884965633bfa7b108f2a0dd1f80c42d3d9da0cb5c0730d5ba7aff0818ba2728cfd78cbc785ab2b156ee59c605808d83d831b2a054ac4d4c6bfdf2066e2769553c5f480c5a8c70e69009448d3ecc6a8637ec130e80808080808080808080808080"),
+	&hex!("f83820b602f40107843b9aca0085174876e800830138809404040404040404040404040404040404040404048084deadbeefc001821111822222"),
+];
+
+/// Proof of receipt 1 in the deposit block receipts trie
+pub const DEPOSIT_BLOCK_RECEIPT_PROOF_1: [&[u8]; 3] = [
+	&hex!("f851a04c9ac82c3dac8c468dada16650cd9daba1d4425e7868b60a9d2d4295a62916c380808080808080a0e58215be848c1293dd381210359d84485553000a82b67410406d183b42adbbdd8080808080808080"),
+	&hex!("f87180a0aa8a09edaa73e5fd853b4564eb39865172925ebb81ceb38c294c6e6b8d365ad1a0afa10c4e92b438dfbfc5ad80838f9e9d5015bbad2a974a1d0428726b9f8072daa0ae03865754c0cb45cb24d868aeb77497f9108e0c3db44c56f00638717330014d80808080808080808080808080"),
+	&hex!("f901cf20b901cb02f901c70183011558b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f8bdf8bb940404040404040404040404040404040404040404f842a00505050505050505050505050505050505050505050505050505050505050505a00000000000000000000000000707070707070707070707070707070707070707b860010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101"),
+];
+
+/// Proof of tx 2 in the deposit block transactions trie
+pub const DEPOSIT_BLOCK_TX_PROOF_2: [&[u8]; 3] = [
+	&hex!("f851a0080a44826ba85a7df2de6fe139a6d51d37a483abc0c4083448450130b56d2f1180808080808080a053c4205913a8c0f08fcde981d7542d7a134805c6f448a128035b8233d30356108080808080808080"),
+	&hex!("f87180a01b8d7ca14f6d3d491667798d205ec884965633bfa7b108f2a0dd1f80c42d3d9da0cb5c0730d5ba7aff0818ba2728cfd78cbc785ab2b156ee59c605808d83d831b2a054ac4d4c6bfdf2066e2769553c5f480c5a8c70e69009448d3ecc6a8637ec130e80808080808080808080808080"),
+	&hex!("ee20aceb01850ba43b740083015f909409090909090909090909090909090909090909098082cafe25823333824444"),
+];
+
+/// Proof of receipt 2 in the deposit block receipts trie
+pub const DEPOSIT_BLOCK_RECEIPT_PROOF_2: [&[u8]; 3] = [
+	&hex!("f851a04c9ac82c3dac8c468dada16650cd9daba1d4425e7868b60a9d2d4295a62916c380808080808080a0e58215be848c1293dd381210359d84485553000a82b67410406d183b42adbbdd8080808080808080"),
+	&hex!("f87180a0aa8a09edaa73e5fd853b4564eb39865172925ebb81ceb38c294c6e6b8d365ad1a0afa10c4e92b438dfbfc5ad80838f9e9d5015bbad2a974a1d0428726b9f8072daa0ae03865754c0cb45cb24d868aeb77497f9108e0c3db44c56f00638717330014d80808080808080808080808080"),
+	&hex!("f901ce20b901caf901c701830222e0b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f8bdf8bb940404040404040404040404040404040404040404f842a00505050505050505050505050505050505050505050505050505050505050505a00000000000000000000000000707070707070707070707070707070707070707b860010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101"),
+];
+
+/// Proof of tx 3 in the deposit block transactions trie
+pub const DEPOSIT_BLOCK_TX_PROOF_3: [&[u8]; 3] = [
+	&hex!("f851a0080a44826ba85a7df2de6fe139a6d51d37a483abc0c4083448450130b56d2f1180808080808080a053c4205913a8c0f08fcde981d7542d7a134805c6f448a128035b8233d30356108080808080808080"),
+	&hex!("f87180a01b8d7ca14f6d3d491667798d205ec884965633bfa7b108f2a0dd1f80c42d3d9da0cb5c0730d5ba7aff0818ba2728cfd78cbc785ab2b156ee59c605808d83d831b2a054ac4d4c6bfdf2066e2769553c5f480c5a8c70e69009448d3ecc6a8637ec130e80808080808080808080808080"),
+	&hex!("f020ae01ec0103850ba43b740082ea609404040404040404040404040404040404040404048082beefc080825555826666"),
+];
+
+/// Proof of receipt 3 in the deposit block receipts trie
+pub const DEPOSIT_BLOCK_RECEIPT_PROOF_3: [&[u8]; 3] = [
+	&hex!("f851a04c9ac82c3dac8c468dada16650cd9daba1d4425e7868b60a9d2d4295a62916c380808080808080a0e58215be848c1293dd381210359d84485553000a82b67410406d183b42adbbdd8080808080808080"),
+	&hex!("f87180a0aa8a09edaa73e5fd853b4564eb39865172925ebb81ceb38c294c6e6b8d365ad1a0afa10c4e92b438dfbfc5ad80838f9e9d5015bbad2a974a1d0428726b9f8072daa0ae03865754c0cb45cb24d868aeb77497f9108e0c3db44c56f00638717330014d80808080808080808080808080"),
+	&hex!("f9011120b9010d01f90109808302bf20b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c0"),
+];
@@ -22,42 +22,57 @@
 //! Once a threshold of validators sign a notarization having witnessed the event it is considered verified.
 //!
 //! Events are opaque to this module, other modules handle submitting "event claims" and "callbacks" to handle success
+//!
+//! Event claims may optionally carry an Ethereum block header with Merkle-Patricia proofs of the tx and its receipt.
+//! The claimed event is then verified on-chain and notaries need only agree on the block header.
+//! Headers agreed on by notaries are tracked so subsequent claims proven against them are verified immediately.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(test)]
+mod fixtures;
 #[cfg(test)]
 mod tests;
 
+mod proof;
+use proof::{EthHeader, ProofError, Receipt};
 mod types;
 use types::*;
+pub use types::{
+	EthAddress, EthHash, EthyEquivocationOffence, EventClaimId, EventClaimResult, EventClaimStatus,
+	NotaryMisconductOffence, NotaryParticipation,
+};
 
 use benson_primitives::{
-	eth::{ConsensusLog, ValidatorSet, ETHY_ENGINE_ID},
+	eth::{ConsensusLog, EquivocationProof, ValidatorSet, ETHY_ENGINE_ID},
 	types::BlockNumber,
 };
-use codec::Encode;
+use codec::{Decode, Encode};
 use crml_support::{
-	EthAbiCodec, EventClaimSubscriber, EventClaimVerifier, FinalSessionTracker as FinalSessionTrackerT,
-	NotarizationRewardHandler,
+	EthAbiCodec, EthReceiptProof, EventClaimSubscriber, EventClaimVerifier,
+	FinalSessionTracker as FinalSessionTrackerT, NotarizationRewardHandler,
 };
 use frame_support::{
 	decl_error, decl_event, decl_module, decl_storage, log,
 	pallet_prelude::*,
-	traits::{OneSessionHandler, UnixTime, ValidatorSet as ValidatorSetT},
+	traits::{OneSessionHandler, UnixTime, ValidatorSet as ValidatorSetT, ValidatorSetWithIdentification},
 	transactional, Parameter,
 };
 use frame_system::{
 	offchain::{CreateSignedTransaction, SubmitTransaction},
 	pallet_prelude::*,
 };
+use serde::de::DeserializeOwned;
+use sp_core::ecdsa;
 use sp_runtime::{
 	generic::DigestItem,
 	offchain as rt_offchain,
-	offchain::StorageKind,
-	traits::{MaybeSerializeDeserialize, Member, SaturatedConversion, Zero},
+	offchain::{storage::StorageValueRef, StorageKind},
+	traits::{Convert, MaybeSerializeDeserialize, Member, SaturatedConversion, Zero},
 	transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidity, ValidTransaction},
 	DispatchError, Percent, RuntimeAppPublic,
 };
+use sp_staking::offence::ReportOffence;
 #[cfg(not(feature = "std"))]
 use sp_std::alloc::string::ToString;
 use sp_std::{convert::TryInto, prelude::*};
@@ -74,6 +89,29 @@ const REQUEST_TTL_MS: u64 = 2_500;
 const BUCKET_FACTOR_S: u64 = 3_600; // 1 hour
 /// Number of blocks between claim pruning
 const CLAIM_PRUNING_INTERVAL: BlockNumber = BUCKET_FACTOR_S as u32 / 5_u32;
+/// Offchain storage key of the Eth JSON-RPC endpoints (comma separated)
+const ETH_HTTP_URI: [u8; 8] = *b"ETH_HTTP";
+/// Offchain storage key of the number of Eth JSON-RPC endpoints which must agree on a response
+const ETH_HTTP_QUORUM: [u8; 15] = *b"ETH_HTTP_QUORUM";
+/// Offchain storage key prefix of per endpoint request failure counters (suffixed by the endpoint URI)
+const ETH_HTTP_FAILURES: [u8; 17] = *b"ETH_HTTP_FAILURES";
+/// Offchain storage key prefix of transient notarization retry state (suffixed by the event claim Id)
+const ETH_CLAIM_RETRY: [u8; 15] = *b"ETH_CLAIM_RETRY";
+/// Blocks to wait before retrying a claim with a transient notarization result, doubled on each attempt
+const CLAIM_RETRY_BASE_BLOCKS: u64 = 5;
+/// Max. doublings of the claim retry backoff (5 * 2^6 blocks / ~27 minutes)
+const CLAIM_RETRY_MAX_DOUBLINGS: u32 = 6;
+/// Min. number of claims notarized in a session before notaries may be reported for misconduct
+const MIN_CLAIMS_FOR_MISCONDUCT: u32 = 10;
+/// Max. number of notarized claims tracked per session for misconduct, bounds the work at session end
+const MAX_SESSION_CLAIMS: u32 = 256;
+/// Claims notarized within this many blocks of the session end don't count as misses (~1 minute)
+/// notaries may not have had a chance to vote on them
+const MISS_GRACE_BLOCKS: u32 = 12;
+/// Max. number of messages signed together in a batch, the rest are left for the next block
+const MAX_BATCH_SIZE: usize = 256;
+/// Weight of adding one message to the batch signing request log `on_finalize` (encoding + digest size)
+const BATCH_MESSAGE_WEIGHT: Weight = 200_000;
 
 pub(crate) const LOG_TARGET: &str = "eth-bridge";
 
@@ -88,13 +126,19 @@ macro_rules! log {
 	};
 }
 
+/// A tuple of (validator stash, full identification) of a notary
+pub type IdentificationTuple<T> = (
+	<T as frame_system::Config>::AccountId,
+	<<T as Config>::AuthoritySet as ValidatorSetWithIdentification<<T as frame_system::Config>::AccountId>>::Identification,
+);
+
 /// This is the pallet's configuration trait
 pub trait Config: frame_system::Config + CreateSignedTransaction<Call<Self>> {
 	/// The identifier type for an authority in this module (i.e. active validator session key)
 	/// 33 byte ECDSA public key
 	type EthyId: Member + Parameter + AsRef<[u8]> + RuntimeAppPublic + Default + Ord + MaybeSerializeDeserialize;
 	/// Knows the active authority set (validator stash addresses)
-	type AuthoritySet: ValidatorSetT<Self::AccountId, ValidatorId = Self::AccountId>;
+	type AuthoritySet: ValidatorSetWithIdentification<Self::AccountId, ValidatorId = Self::AccountId>;
 	/// The threshold of notarizations required to approve an Ethereum
 	type NotarizationThreshold: Get<Percent>;
 	/// Rewards notaries for participating in claims
@@ -109,6 +153,18 @@ pub trait Config: frame_system::Config + CreateSignedTransaction<Call<Self>> {
 	type Event: From<Event> + Into<<Self as frame_system::Config>::Event>;
 	/// Tracks the status of sessions/eras
 	type FinalSessionTracker: FinalSessionTrackerT;
+	/// Reports notaries who missed or disagreed with too many claims in a session
+	type ReportMisconduct: ReportOffence<
+		Self::AccountId,
+		IdentificationTuple<Self>,
+		NotaryMisconductOffence<IdentificationTuple<Self>>,
+	>;
+	/// Reports Ethy authorities who signed conflicting witnesses for an event
+	type ReportEquivocation: ReportOffence<
+		Self::AccountId,
+		IdentificationTuple<Self>,
+		EthyEquivocationOffence<IdentificationTuple<Self>>,
+	>;
 }
 
 decl_storage! {
@@ -155,6 +211,39 @@ decl_storage! {
 		EventConfirmations get(fn event_confirmations): u64 = 3;
 		/// Events cannot be claimed after this time (seconds)
 		EventDeadlineSeconds get(fn event_deadline_seconds): u64 = 604_800; // 1 week
+		/// Claims which don't reach a notarization quorum within this many blocks fail (default: ~20 hours)
+		ClaimExpiryBlocks get(fn claim_expiry_blocks): u32 = 14_400;
+		/// Outcomes (valid or not) of claims notarized in the current session
+		/// Their notarizations are kept until the session ends to track late votes
+		/// (outcome, block notarized at)
+		SessionClaimOutcomes get(fn session_claim_outcomes): map hasher(twox_64_concat) EventClaimId => Option<(bool, T::BlockNumber)>;
+		/// The number of claims in `SessionClaimOutcomes`
+		SessionClaimCount get(fn session_claim_count): u32;
+		/// Claims notarized in the current session and their header (if proven by receipt)
+		/// Kept until the session ends so notaries who hadn't voted before quorum can still vote
+		SessionEventClaims get(fn session_event_claims): map hasher(twox_64_concat) EventClaimId => Option<(EventClaim, Option<(u64, EthHash)>)>;
+		/// Notaries' participation in claims notarized in the current session
+		NotaryParticipations get(fn notary_participations): map hasher(twox_64_concat) T::EthyId => NotaryParticipation;
+		/// Notaries missing more than this share of a session's claims are reported for misconduct (default: 50%)
+		MissThreshold get(fn miss_threshold): Percent = Percent::from_percent(50);
+		/// Notaries voting against the outcome of more than this share of a session's claims are reported for misconduct (default: 20%)
+		DisagreeThreshold get(fn disagree_threshold): Percent = Percent::from_percent(20);
+		/// Queued event claims by the block they expire at
+		EventClaimExpiries get(fn event_claim_expiries): double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) EventClaimId => ();
+		/// Storage version of the pallet, see `Releases`
+		StorageVersion: u32;
+		/// The proven Ethereum block header (number, hash) of queued claims submitted with a receipt proof
+		EventClaimHeaders get(fn event_claim_headers): map hasher(twox_64_concat) EventClaimId => Option<(u64, EthHash)>;
+		/// Ethereum block hashes agreed on by notaries, by block number
+		/// Claims proven against these headers need no further notarization
+		EthHeaders get(fn eth_headers): map hasher(twox_64_concat) u64 => Option<EthHash>;
+		/// Agreed Ethereum block numbers bucketed by unix timestamp (`BUCKET_FACTOR_S`)
+		/// Headers are pruned along with processed tx hashes after `EventDeadlineSeconds`
+		EthHeaderBuckets get(fn eth_header_buckets): double_map hasher(twox_64_concat) u64, hasher(twox_64_concat) u64 => ();
+		/// Whether event proofs are batched, validators sign the Merkle root of each block's messages
+		BatchSigning get(fn batch_signing): bool;
+		/// Messages (event_id, message) awaiting signing in this block's batch
+		PendingBatchMessages get(fn pending_batch_messages): Vec<(EventProofId, Vec<u8>)>;
 	}
 }
 
@@ -164,6 +253,12 @@ decl_event! {
 		Verified(EventClaimId),
 		/// Verifying an event failed
 		Invalid(EventClaimId),
+		/// An event claim expired before notaries reached a quorum e.g. due to persistent data provider errors
+		ClaimExpired(EventClaimId),
+		/// An Ethy authority was reported for signing conflicting witnesses (event_id, authority index)
+		EquivocationReported(EventProofId, u32),
+		/// A batch of event proofs was requested (batch event_id, number of messages)
+		BatchSigningRequested(EventProofId, u32),
 		/// A notary (validator) set change is in motion (event_id, new_validator_set_id)
 		/// A proof for the change will be generated with the given `event_id`
 		AuthoritySetChange(EventProofId, u64),
@@ -182,6 +277,10 @@ decl_error! {
 		HttpFetch,
 		/// Claim was invalid
 		InvalidClaim,
+		/// The equivocation proof is invalid
+		InvalidEquivocationProof,
+		/// The equivocation has been reported already
+		DuplicateEquivocationReport,
 		/// offchain worker not configured properly
 		OcwConfig,
 		/// This message has already been notarized
@@ -189,6 +288,14 @@ decl_error! {
 		/// The bridge is paused pending validator set changes (once every era / 24 hours)
 		/// It will reactive after ~10 minutes
 		BridgePaused,
+		/// Eth JSON-RPC endpoints responded but did not reach quorum on the result
+		EthRpcDisagreement,
+		/// The Ethereum block header, tx or receipt proof is invalid
+		InvalidProof,
+		/// The proven tx receipt does not contain the claimed event
+		UnexpectedEvent,
+		/// The proven event is past the claim deadline
+		EventExpired,
 	}
 }
 
@@ -196,24 +303,55 @@ decl_module! {
 	pub struct Module<T: Config> for enum Call where origin: T::Origin {
 		fn deposit_event() = default;
 
+		fn on_runtime_upgrade() -> Weight {
+			let mut weight = T::DbWeight::get().reads(1);
+			if StorageVersion::get() == Releases::V0 as u32 {
+				weight = weight.saturating_add(Self::migrate_claim_expiries());
+				StorageVersion::put(Releases::V1 as u32);
+			}
+			if StorageVersion::get() == Releases::V1 as u32 {
+				weight = weight.saturating_add(Self::migrate_processed_tx_buckets());
+				StorageVersion::put(Releases::V2 as u32);
+			}
+			weight
+		}
+
 		fn on_initialize(block_number: T::BlockNumber) -> Weight {
+			// reserve the weight of signing this block's batch `on_finalize`
+			let mut weight: Weight = Self::batch_signing_weight();
+			for (event_claim_id, _empty_value) in <EventClaimExpiries<T>>::drain_prefix(block_number) {
+				// the claim may have been processed already
+				if EventClaims::contains_key(event_claim_id) {
+					let _ = Self::do_claim_expiry(event_claim_id);
+				}
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(2, 6));
+			}
+
 			// Prune claim storage every hour on Benson (BUCKET_FACTOR_S / 5 seconds = 720 blocks)
 			if (block_number % T::BlockNumber::from(CLAIM_PRUNING_INTERVAL)).is_zero() {
 				// Find the bucket to expire
 				let now = T::UnixTime::now().as_secs().saturated_into::<u64>();
-				let expired_bucket_index = (now - Self::event_deadline_seconds()) % BUCKET_FACTOR_S;
+				let expired_bucket_index = now.saturating_sub(Self::event_deadline_seconds()) / BUCKET_FACTOR_S;
 				for (expired_tx_hash, _empty_value) in ProcessedTxBuckets::iter_prefix(expired_bucket_index) {
 					ProcessedTxHashes::remove(expired_tx_hash);
 				}
 				ProcessedTxBuckets::remove_prefix(expired_bucket_index, None);
+				for (expired_block_number, _empty_value) in EthHeaderBuckets::iter_prefix(expired_bucket_index) {
+					EthHeaders::remove(expired_block_number);
+				}
+				EthHeaderBuckets::remove_prefix(expired_bucket_index, None);
 
 				// TODO: better estimate
-				50_000_000_u64
+				weight.saturating_add(50_000_000_u64)
 			} else {
-				Zero::zero()
+				weight
 			}
 		}
 
+		fn on_finalize() {
+			Self::request_batch_signing();
+		}
+
 		#[weight = 100_000]
 		/// Set event confirmations (blocks). Required block confirmations for an Ethereum event to be notarized by Benson
 		pub fn set_event_confirmations(origin, confirmations: u64) {
@@ -228,6 +366,50 @@ decl_module! {
 			EventDeadlineSeconds::put(seconds);
 		}
 
+		#[weight = 100_000]
+		/// Set the misconduct thresholds. Notaries missing or disagreeing with a greater share of a session's claims are reported
+		pub fn set_misconduct_thresholds(origin, miss_threshold: Percent, disagree_threshold: Percent) {
+			ensure_root(origin)?;
+			MissThreshold::put(miss_threshold);
+			DisagreeThreshold::put(disagree_threshold);
+		}
+
+		#[weight = 100_000]
+		/// Enable or disable batched event proofs
+		/// When enabled validators sign the Merkle root of each block's messages rather than each message
+		pub fn set_batch_signing(origin, enabled: bool) {
+			ensure_root(origin)?;
+			BatchSigning::put(enabled);
+		}
+
+		#[weight = 100_000]
+		/// Set claim expiry (blocks). Claims submitted hereafter fail if notaries don't reach a quorum within this time
+		pub fn set_claim_expiry(origin, blocks: u32) {
+			ensure_root(origin)?;
+			ClaimExpiryBlocks::put(blocks);
+		}
+
+		#[weight = 50_000_000]
+		/// Internal only
+		/// Report an Ethy authority for signing conflicting witnesses for the same event
+		/// Submitted by the ethy-gadget of the node observing the equivocation
+		pub fn report_equivocation(origin, equivocation_proof: EquivocationProof<T::EthyId>) {
+			let _ = ensure_none(origin)?;
+
+			let (authority_index, offender) = Self::equivocation_offender(&equivocation_proof)
+				.ok_or(Error::<T>::InvalidEquivocationProof)?;
+			let offence = EthyEquivocationOffence {
+				session_index: T::AuthoritySet::session_index(),
+				validator_set_count: T::AuthoritySet::validators().len() as u32,
+				event_id: equivocation_proof.event_id,
+				offender,
+			};
+			T::ReportEquivocation::report_offence(vec![], offence)
+				.map_err(|_| Error::<T>::DuplicateEquivocationReport)?;
+
+			Self::deposit_event(Event::EquivocationReported(equivocation_proof.event_id, authority_index));
+		}
+
 		#[weight = 1_000_000]
 		#[transactional]
 		/// Internal only
@@ -242,10 +424,28 @@ decl_module! {
 				Some(id) => id,
 				None => return Err(Error::<T>::InvalidNotarization.into()),
 			};
+			let previous_result = <EventNotarizations<T>>::get(payload.event_claim_id, notary_public_key);
+
+			// the claim was notarized already this session, note the notary's participation
+			if let Some((is_valid, _)) = Self::session_claim_outcomes(payload.event_claim_id) {
+				ensure!(previous_result.is_none(), Error::<T>::InvalidNotarization);
+				Self::note_participation(notary_public_key, &payload.result, is_valid);
+				<EventNotarizations<T>>::insert::<EventClaimId, T::EthyId, EventClaimResult>(payload.event_claim_id, notary_public_key.clone(), payload.result);
+				return Ok(());
+			}
+			ensure!(EventClaims::contains_key(payload.event_claim_id), Error::<T>::InvalidNotarization);
+
+			// only transient results may be replaced
+			if let Some(previous_result) = previous_result {
+				ensure!(previous_result.is_transient(), Error::<T>::InvalidNotarization);
+			}
 			<EventNotarizations<T>>::insert::<EventClaimId, T::EthyId, EventClaimResult>(payload.event_claim_id, notary_public_key.clone(), payload.result);
 
-			T::AuthoritySet::validators().get(payload.authority_index as usize)
-				.map(|v| T::RewardHandler::reward_notary(v));
+			// a notary is rewarded once per claim, replacing a transient result isn't rewarded again
+			if previous_result.is_none() {
+				T::AuthoritySet::validators().get(payload.authority_index as usize)
+					.map(|v| T::RewardHandler::reward_notary(v));
+			}
 
 			// Count notarization votes
 			let notary_count = T::AuthoritySet::validators().len() as u32;
@@ -254,6 +454,8 @@ decl_module! {
 			for (_id, result) in <EventNotarizations<T>>::iter_prefix(payload.event_claim_id) {
 				match result {
 					EventClaimResult::Valid => yay_count += 1,
+					// transient results don't count toward invalidation, the notary will retry
+					result if result.is_transient() => (),
 					_ => nay_count += 1,
 				}
 			}
@@ -261,43 +463,19 @@ decl_module! {
 			// Claim is invalid (nays > (100% - NotarizationThreshold))
 			if Percent::from_rational(nay_count, notary_count) > (Percent::from_parts(100_u8 - T::NotarizationThreshold::get().deconstruct())) {
 				// event did not notarize / failed, clean up
-				let event_data = EventData::take(payload.event_claim_id);
-				if event_data.is_none() {
-					// this should never happen
-					log!(error, "💎 unexpected empty claim");
-					return Err(Error::<T>::InvalidClaim.into())
-				}
-				<EventNotarizations<T>>::remove_prefix(payload.event_claim_id, None);
-				let (_eth_tx_hash, event_type_id) = EventClaims::take(payload.event_claim_id);
-				let (contract_address, event_signature) = TypeIdToEventType::get(event_type_id);
-				let event_data = event_data.unwrap();
-				Self::deposit_event(Event::Invalid(payload.event_claim_id));
-
-				T::Subscribers::on_failure(payload.event_claim_id, &contract_address, &event_signature, &event_data);
+				Self::note_claim_outcome(payload.event_claim_id, false);
+				Self::do_claim_failure(payload.event_claim_id)?;
 				return Ok(());
 			}
 
 			// Claim is valid
 			if Percent::from_rational(yay_count, notary_count) >= T::NotarizationThreshold::get() {
-				let event_data = EventData::take(payload.event_claim_id);
-				if event_data.is_none() {
-					// this should never happen
-					log!(error, "💎 unexpected empty claim");
-					return Err(Error::<T>::InvalidClaim.into())
+				Self::note_claim_outcome(payload.event_claim_id, true);
+				// the notaries agree on the proven header
+				if let Some((block_number, block_hash)) = EventClaimHeaders::take(payload.event_claim_id) {
+					Self::note_eth_header(block_number, block_hash);
 				}
-				// no need to track info on this claim any more since it's approved
-				<EventNotarizations<T>>::remove_prefix(payload.event_claim_id, None);
-				let (eth_tx_hash, event_type_id) = EventClaims::take(payload.event_claim_id);
-				let (contract_address, event_signature) = TypeIdToEventType::get(event_type_id);
-				let event_data = event_data.unwrap();
-
-				// note this tx as completed
-				let bucket_index = T::UnixTime::now().as_secs().saturated_into::<u64>() % BUCKET_FACTOR_S;
-				ProcessedTxBuckets::insert(bucket_index, eth_tx_hash, ());
-				ProcessedTxHashes::insert(eth_tx_hash, ());
-				Self::deposit_event(Event::Verified(payload.event_claim_id));
-
-				T::Subscribers::on_success(payload.event_claim_id, &contract_address, &event_signature, &event_data);
+				Self::do_claim_success(payload.event_claim_id)?;
 			}
 		}
 
@@ -353,39 +531,57 @@ decl_module! {
 			// this will be invoked once every block
 			// we limit the total claims per invocation using `CLAIMS_PER_BLOCK` so we don't stall block production
 			let mut budget = CLAIMS_PER_BLOCK;
-			for (event_claim_id, (tx_hash, event_type_id)) in EventClaims::iter() {
+			for (event_claim_id, event_claim, header) in Self::offchain_claims_to_notarize(active_key.clone()) {
 				if budget.is_zero() {
 					log!(info, "💎 claims budget exceeded, exiting...");
 					return
 				}
 
 				// check we haven't notarized this already
-				if <EventNotarizations<T>>::contains_key::<EventClaimId, T::EthyId>(event_claim_id, active_key.clone()) {
-					log!(trace, "💎 already cast notarization for claim: {:?}, ignoring...", event_claim_id);
+				// transient results are retried with backoff
+				let previous_result = <EventNotarizations<T>>::get(event_claim_id, active_key.clone());
+				match previous_result {
+					Some(ref result) if !result.is_transient() => {
+						log!(trace, "💎 already cast notarization for claim: {:?}, ignoring...", event_claim_id);
+						continue
+					}
+					_ if !Self::offchain_is_retry_due(event_claim_id, block_number) => {
+						log!(trace, "💎 retry of claim: {:?} not yet due, ignoring...", event_claim_id);
+						continue
+					}
+					_ => (),
 				}
 
-				if let Some(event_data) = Self::event_data(event_claim_id) {
-					let (contract_address, event_signature) = TypeIdToEventType::get(event_type_id);
-					let event_claim = EventClaim { tx_hash, data: event_data, contract_address, event_signature };
-					let result = Self::offchain_try_notarize_event(event_claim);
-					log!(trace, "💎 claim verification status: {:?}", &result);
-					let payload = NotarizationPayload {
-						event_claim_id,
-						authority_index,
-						result: result.clone(),
-					};
-					let _ = Self::offchain_send_notarization(&active_key, payload)
-						.map_err(|err| {
-							log!(error, "💎 sending notarization failed 🙈, {:?}", err);
-						})
-						.map(|_| {
-							log!(info, "💎 sent notarization: '{:?}' for claim: {:?}", result, event_claim_id);
-						});
-					budget = budget.saturating_sub(1);
+				// claims with a receipt proof were verified on submission, only the header needs checking
+				let result = if let Some((block_number, block_hash)) = header {
+					Self::offchain_try_notarize_header(block_number, block_hash)
+				} else {
+					Self::offchain_try_notarize_event(event_claim)
+				};
+				log!(trace, "💎 claim verification status: {:?}", &result);
+				budget = budget.saturating_sub(1);
+				if result.is_transient() {
+					Self::offchain_note_retry(event_claim_id, block_number);
 				} else {
-					// should not happen, defensive only
-					log!(error, "💎 empty claim data for: {:?}", event_claim_id);
+					Self::offchain_clear_retry(event_claim_id);
 				}
+				// the same result is already recorded on-chain
+				if previous_result.as_ref() == Some(&result) {
+					log!(trace, "💎 unchanged notarization: '{:?}' for claim: {:?}", result, event_claim_id);
+					continue
+				}
+				let payload = NotarizationPayload {
+					event_claim_id,
+					authority_index,
+					result: result.clone(),
+				};
+				let _ = Self::offchain_send_notarization(&active_key, payload)
+					.map_err(|err| {
+						log!(error, "💎 sending notarization failed 🙈, {:?}", err);
+					})
+					.map(|_| {
+						log!(info, "💎 sent notarization: '{:?}' for claim: {:?}", result, event_claim_id);
+					});
 			}
 
 			log!(trace, "💎 exiting off-chain worker");
@@ -405,6 +601,188 @@ impl<T: Config> EventClaimVerifier for Module<T> {
 	) -> Result<EventClaimId, DispatchError> {
 		ensure!(!ProcessedTxHashes::contains_key(tx_hash), Error::<T>::AlreadyNotarized);
 
+		Ok(Self::queue_event_claim(
+			contract_address,
+			event_signature,
+			tx_hash,
+			event_data,
+		))
+	}
+
+	/// Submit an event claim with a proof of its tx receipt
+	// The claimed event is verified against the proven block header immediately.
+	// If notaries have already agreed on the header the claim succeeds, otherwise it is queued for
+	// notaries to check the header
+	fn submit_event_claim_with_proof(
+		contract_address: &H160,
+		event_signature: &H256,
+		event_data: &[u8],
+		proof: &EthReceiptProof,
+	) -> Result<EventClaimId, DispatchError> {
+		let (header, tx_hash) = Self::verify_event_proof(contract_address, event_signature, event_data, proof)?;
+		ensure!(!ProcessedTxHashes::contains_key(tx_hash), Error::<T>::AlreadyNotarized);
+		let now = T::UnixTime::now().as_secs().saturated_into::<u64>();
+		ensure!(
+			now.saturating_sub(header.timestamp) <= Self::event_deadline_seconds(),
+			Error::<T>::EventExpired
+		);
+
+		let event_claim_id = Self::queue_event_claim(contract_address, event_signature, &tx_hash, event_data);
+		if Self::eth_headers(header.number) == Some(header.hash) {
+			Self::do_claim_success(event_claim_id)?;
+		} else {
+			EventClaimHeaders::insert(event_claim_id, (header.number, header.hash));
+		}
+
+		Ok(event_claim_id)
+	}
+
+	fn generate_event_proof<E: EthAbiCodec>(event: &E, batchable: bool) -> Result<u64, DispatchError> {
+		ensure!(!Self::bridge_paused(), Error::<T>::BridgePaused);
+		let event_proof_id = Self::next_proof_id();
+
+		let packed_event_with_id = [
+			&event.encode()[..],
+			&EthAbiCodec::encode(&Self::validator_set().id)[..],
+			&EthAbiCodec::encode(&event_proof_id)[..],
+		]
+		.concat();
+		if batchable && Self::batch_signing() {
+			// signed with the block's batch `on_finalize`
+			PendingBatchMessages::append((event_proof_id, packed_event_with_id));
+		} else {
+			let log: DigestItem = DigestItem::Consensus(
+				ETHY_ENGINE_ID,
+				ConsensusLog::<T::AccountId>::OpaqueSigningRequest((packed_event_with_id, event_proof_id)).encode(),
+			);
+			<frame_system::Pallet<T>>::deposit_log(log);
+		}
+
+		NextProofId::put(event_proof_id.wrapping_add(1));
+
+		Ok(event_proof_id)
+	}
+}
+
+impl<T: Config> Module<T> {
+	/// Migrate to `Releases::V1`
+	/// claims queued before `EventClaimExpiries` existed would never expire, their expiry period starts now
+	fn migrate_claim_expiries() -> Weight {
+		let expiry_block =
+			<frame_system::Pallet<T>>::block_number().saturating_add(T::BlockNumber::from(Self::claim_expiry_blocks()));
+		let mut count = 0_u64;
+		for (event_claim_id, _) in EventClaims::iter() {
+			<EventClaimExpiries<T>>::insert(expiry_block, event_claim_id, ());
+			count += 1;
+		}
+		T::DbWeight::get().reads_writes(count + 1, count + 1)
+	}
+
+	/// Migrate to `Releases::V2`
+	/// processed tx hashes were bucketed by the second of the hour (`now % BUCKET_FACTOR_S`), they move to the
+	/// current bucket so they stay replay protected until it's pruned
+	fn migrate_processed_tx_buckets() -> Weight {
+		let bucket_index = T::UnixTime::now().as_secs().saturated_into::<u64>() / BUCKET_FACTOR_S;
+		let mut count = 0_u64;
+		let stale: Vec<(u64, EthHash)> = ProcessedTxBuckets::iter()
+			.filter(|(old_bucket_index, _, _)| *old_bucket_index < BUCKET_FACTOR_S && *old_bucket_index != bucket_index)
+			.map(|(old_bucket_index, tx_hash, _)| (old_bucket_index, tx_hash))
+			.collect();
+		for (old_bucket_index, tx_hash) in stale {
+			ProcessedTxBuckets::remove(old_bucket_index, tx_hash);
+			ProcessedTxBuckets::insert(bucket_index, tx_hash, ());
+			count += 1;
+		}
+		T::DbWeight::get().reads_writes(count + 1, count * 2 + 1)
+	}
+
+	/// The max. weight of `request_batch_signing`, reserved `on_initialize`
+	/// Messages may only be pending if batch signing is (or was) enabled
+	fn batch_signing_weight() -> Weight {
+		if Self::batch_signing() || PendingBatchMessages::exists() {
+			T::DbWeight::get()
+				.reads_writes(4, 3)
+				.saturating_add(BATCH_MESSAGE_WEIGHT.saturating_mul(MAX_BATCH_SIZE as Weight))
+		} else {
+			T::DbWeight::get().reads(2)
+		}
+	}
+
+	/// Request validators sign the Merkle root of messages pending in this block's batch
+	/// At most `MAX_BATCH_SIZE` messages are batched, the rest are left for the next block
+	fn request_batch_signing() {
+		let mut messages = PendingBatchMessages::take();
+		if messages.is_empty() {
+			return;
+		}
+		if messages.len() > MAX_BATCH_SIZE {
+			PendingBatchMessages::put(messages.split_off(MAX_BATCH_SIZE));
+		}
+
+		let batch_id = Self::next_proof_id();
+		let message_count = messages.len() as u32;
+		let log: DigestItem = DigestItem::Consensus(
+			ETHY_ENGINE_ID,
+			ConsensusLog::<T::AccountId>::BatchSigningRequest((messages, batch_id)).encode(),
+		);
+		<frame_system::Pallet<T>>::deposit_log(log);
+		NextProofId::put(batch_id.wrapping_add(1));
+
+		Self::deposit_event(Event::BatchSigningRequested(batch_id, message_count));
+	}
+
+	/// Returns the notarization status of a queued event claim
+	/// `None` if the claim doesn't exist or has already been processed
+	pub fn event_claim_status(event_claim_id: EventClaimId) -> Option<EventClaimStatus<T::EthyId>> {
+		if !EventClaims::contains_key(event_claim_id) {
+			return None;
+		}
+		let (tx_hash, event_type_id) = Self::event_claims(event_claim_id);
+		let notarizations: Vec<(T::EthyId, EventClaimResult)> =
+			<EventNotarizations<T>>::iter_prefix(event_claim_id).collect();
+		let count = |predicate: fn(&EventClaimResult) -> bool| {
+			notarizations.iter().filter(|(_id, result)| predicate(result)).count() as u32
+		};
+		let yay_count = count(|result| *result == EventClaimResult::Valid);
+		let transient_count = count(EventClaimResult::is_transient);
+
+		Some(EventClaimStatus {
+			event_claim_id,
+			tx_hash,
+			event_type: Self::type_id_to_event_type(event_type_id),
+			event_data: Self::event_data(event_claim_id),
+			nay_count: notarizations.len() as u32 - yay_count - transient_count,
+			yay_count,
+			transient_count,
+			notarizations,
+			notary_count: T::AuthoritySet::validators().len() as u32,
+		})
+	}
+
+	/// Returns the Ids of queued event claims for the given Ethereum tx hash
+	pub fn event_claim_ids_by_tx_hash(tx_hash: EthHash) -> Vec<EventClaimId> {
+		let mut event_claim_ids: Vec<EventClaimId> = EventClaims::iter()
+			.filter(|(_id, (claim_tx_hash, _event_type_id))| *claim_tx_hash == tx_hash)
+			.map(|(event_claim_id, _)| event_claim_id)
+			.collect();
+		event_claim_ids.sort();
+		event_claim_ids
+	}
+
+	/// Returns whether an event from the given Ethereum tx hash has been processed
+	/// i.e. a claim on it was verified (and not yet pruned after `EventDeadlineSeconds`)
+	pub fn is_tx_hash_processed(tx_hash: EthHash) -> bool {
+		ProcessedTxHashes::contains_key(tx_hash)
+	}
+
+	/// Queue an event claim for notarization
+	/// Returns the claim Id
+	fn queue_event_claim(
+		contract_address: &H160,
+		event_signature: &H256,
+		tx_hash: &H256,
+		event_data: &[u8],
+	) -> EventClaimId {
 		// check if we've seen this event type before
 		// if not we assign it a type Id (saves us storing the (contract address, event signature) each time)
 		let event_type_id = if !EventTypeToTypeId::contains_key((contract_address, event_signature)) {
@@ -421,35 +799,279 @@ impl<T: Config> EventClaimVerifier for Module<T> {
 		EventData::insert(event_claim_id, event_data);
 		EventClaims::insert(event_claim_id, (tx_hash, event_type_id));
 		NextEventClaimId::put(event_claim_id.wrapping_add(1));
+		let expiry_block =
+			<frame_system::Pallet<T>>::block_number().saturating_add(T::BlockNumber::from(Self::claim_expiry_blocks()));
+		<EventClaimExpiries<T>>::insert(expiry_block, event_claim_id, ());
 
-		Ok(event_claim_id)
+		event_claim_id
 	}
 
-	fn generate_event_proof<E: EthAbiCodec>(event: &E) -> Result<u64, DispatchError> {
-		ensure!(!Self::bridge_paused(), Error::<T>::BridgePaused);
-		let event_proof_id = Self::next_proof_id();
+	/// Handle a verified claim
+	/// Notes the tx as processed and notifies subscribers
+	fn do_claim_success(event_claim_id: EventClaimId) -> Result<(), DispatchError> {
+		let event_data = match EventData::take(event_claim_id) {
+			Some(event_data) => event_data,
+			None => {
+				// this should never happen
+				log!(error, "💎 unexpected empty claim");
+				return Err(Error::<T>::InvalidClaim.into());
+			}
+		};
+		let (eth_tx_hash, event_type_id) = EventClaims::take(event_claim_id);
+		let (contract_address, event_signature) = TypeIdToEventType::get(event_type_id);
 
-		// TODO: does this support multiple consensus logs in a block?
-		// save this for `on_finalize` and insert many
-		let packed_event_with_id = [
-			&event.encode()[..],
-			&EthAbiCodec::encode(&Self::validator_set().id)[..],
-			&EthAbiCodec::encode(&event_proof_id)[..],
-		]
-		.concat();
-		let log: DigestItem = DigestItem::Consensus(
-			ETHY_ENGINE_ID,
-			ConsensusLog::<T::AccountId>::OpaqueSigningRequest((packed_event_with_id, event_proof_id)).encode(),
-		);
-		<frame_system::Pallet<T>>::deposit_log(log);
+		// note this tx as completed
+		let bucket_index = T::UnixTime::now().as_secs().saturated_into::<u64>() / BUCKET_FACTOR_S;
+		ProcessedTxBuckets::insert(bucket_index, eth_tx_hash, ());
+		ProcessedTxHashes::insert(eth_tx_hash, ());
+		Self::deposit_event(Event::Verified(event_claim_id));
 
-		NextProofId::put(event_proof_id.wrapping_add(1));
+		T::Subscribers::on_success(event_claim_id, &contract_address, &event_signature, &event_data);
+		Ok(())
+	}
 
-		Ok(event_proof_id)
+	/// Handle a claim which failed notarization
+	/// Cleans up the claim and notifies subscribers
+	fn do_claim_failure(event_claim_id: EventClaimId) -> Result<(), DispatchError> {
+		Self::remove_failed_claim(event_claim_id, Event::Invalid(event_claim_id))
+	}
+
+	/// Handle a claim which did not reach a notarization quorum before expiring
+	/// Cleans up the claim and notifies subscribers
+	fn do_claim_expiry(event_claim_id: EventClaimId) -> Result<(), DispatchError> {
+		log!(info, "💎 claim: {:?} expired before notarization", event_claim_id);
+		<EventNotarizations<T>>::remove_prefix(event_claim_id, None);
+		Self::remove_failed_claim(event_claim_id, Event::ClaimExpired(event_claim_id))
+	}
+
+	/// Clean up a failed claim, deposit the failure `event` and notify subscribers
+	fn remove_failed_claim(event_claim_id: EventClaimId, event: Event) -> Result<(), DispatchError> {
+		let event_data = match EventData::take(event_claim_id) {
+			Some(event_data) => event_data,
+			None => {
+				// this should never happen
+				log!(error, "💎 unexpected empty claim");
+				return Err(Error::<T>::InvalidClaim.into());
+			}
+		};
+		EventClaimHeaders::remove(event_claim_id);
+		let (_eth_tx_hash, event_type_id) = EventClaims::take(event_claim_id);
+		let (contract_address, event_signature) = TypeIdToEventType::get(event_type_id);
+		Self::deposit_event(event);
+
+		T::Subscribers::on_failure(event_claim_id, &contract_address, &event_signature, &event_data);
+		Ok(())
+	}
+
+	/// Note the outcome of a notarized claim and the participation of the notaries who voted on it
+	/// Once `MAX_SESSION_CLAIMS` claims are tracked this session, the claim's notarizations are removed instead
+	fn note_claim_outcome(event_claim_id: EventClaimId, is_valid: bool) {
+		if Self::session_claim_count() >= MAX_SESSION_CLAIMS {
+			<EventNotarizations<T>>::remove_prefix(event_claim_id, None);
+			return;
+		}
+		SessionClaimCount::mutate(|count| *count = count.saturating_add(1));
+		<SessionClaimOutcomes<T>>::insert(event_claim_id, (is_valid, <frame_system::Pallet<T>>::block_number()));
+		// keep the claim so notaries who haven't voted yet may still do so this session
+		let (tx_hash, event_type_id) = EventClaims::get(event_claim_id);
+		let (contract_address, event_signature) = TypeIdToEventType::get(event_type_id);
+		let event_claim = EventClaim {
+			tx_hash,
+			data: Self::event_data(event_claim_id).unwrap_or_default(),
+			contract_address,
+			event_signature,
+		};
+		SessionEventClaims::insert(event_claim_id, (event_claim, Self::event_claim_headers(event_claim_id)));
+		for (notary, result) in <EventNotarizations<T>>::iter_prefix(event_claim_id) {
+			Self::note_participation(&notary, &result, is_valid);
+		}
+	}
+
+	/// Note a notary voted `result` on a claim notarized as `is_valid`
+	/// Transient results count as participation but not disagreement
+	fn note_participation(notary: &T::EthyId, result: &EventClaimResult, is_valid: bool) {
+		<NotaryParticipations<T>>::mutate(notary, |participation| {
+			participation.votes = participation.votes.saturating_add(1);
+			if !result.is_transient() && (*result == EventClaimResult::Valid) != is_valid {
+				participation.disagreements = participation.disagreements.saturating_add(1);
+			}
+		});
+	}
+
+	/// Returns the number of claims notarized this session and each active notary's participation
+	pub fn session_notary_participation() -> (u32, Vec<(T::EthyId, NotaryParticipation)>) {
+		let claim_count = Self::session_claim_count();
+		let participation = Self::notary_keys()
+			.into_iter()
+			.map(|notary| {
+				let participation = Self::notary_participations(&notary);
+				(notary, participation)
+			})
+			.collect();
+		(claim_count, participation)
+	}
+
+	/// Report notaries who missed or disagreed with too many of the session's claims for misconduct
+	/// Claims notarized within `MISS_GRACE_BLOCKS` of the session end don't count as misses
+	/// Then reset participation tracking for the next session
+	/// Returns the weight consumed, at most `MAX_SESSION_CLAIMS` claims are processed
+	fn end_session_participation() -> Weight {
+		let notaries = Self::notary_keys();
+		let mut participation: Vec<NotaryParticipation> = notaries.iter().map(Self::notary_participations).collect();
+		let claim_count = SessionClaimCount::take();
+		let now = <frame_system::Pallet<T>>::block_number();
+
+		// the number of claims notaries had a chance to vote on
+		let mut judged_count = 0_u32;
+		let mut notarization_count = 0_u64;
+		for (event_claim_id, (_is_valid, notarized_at)) in <SessionClaimOutcomes<T>>::drain() {
+			SessionEventClaims::remove(event_claim_id);
+			let is_judged = notarized_at.saturating_add(MISS_GRACE_BLOCKS.into()) <= now;
+			if is_judged {
+				judged_count += 1;
+			}
+			for (notary, _result) in <EventNotarizations<T>>::drain_prefix(event_claim_id) {
+				notarization_count += 1;
+				if is_judged {
+					continue;
+				}
+				// only votes on judged claims make up for misses
+				if let Some(index) = notaries.iter().position(|n| *n == notary) {
+					participation[index].votes = participation[index].votes.saturating_sub(1);
+				}
+			}
+		}
+
+		if claim_count >= MIN_CLAIMS_FOR_MISCONDUCT {
+			let validators = T::AuthoritySet::validators();
+			let offenders: Vec<IdentificationTuple<T>> = participation
+				.iter()
+				.zip(validators.iter())
+				.filter(|(participation, _validator)| {
+					let missed = judged_count.saturating_sub(participation.votes);
+					(judged_count > 0 && Percent::from_rational(missed, judged_count) > Self::miss_threshold())
+						|| Percent::from_rational(participation.disagreements, claim_count) > Self::disagree_threshold()
+				})
+				.filter_map(|(_, validator)| {
+					<T::AuthoritySet as ValidatorSetWithIdentification<T::AccountId>>::IdentificationOf::convert(
+						validator.clone(),
+					)
+					.map(|identification| (validator.clone(), identification))
+				})
+				.collect();
+
+			if !offenders.is_empty() {
+				log!(info, "💎 reporting {:?} notaries for misconduct", offenders.len());
+				let offence = NotaryMisconductOffence {
+					session_index: T::AuthoritySet::session_index(),
+					validator_set_count: validators.len() as u32,
+					offenders,
+				};
+				if let Err(err) = T::ReportMisconduct::report_offence(vec![], offence) {
+					log!(error, "💎 failed to report notary misconduct: {:?}", err);
+				}
+			}
+		}
+
+		<NotaryParticipations<T>>::drain().for_each(drop);
+
+		let notary_count = notaries.len() as u64;
+		T::DbWeight::get().reads_writes(
+			3 + notary_count + claim_count as u64 + notarization_count,
+			2 + notary_count + 2 * claim_count as u64 + notarization_count,
+		)
+	}
+
+	/// Verify `equivocation_proof` and return the offender's (authority index, identification)
+	/// Only equivocations by the active notary set may be reported
+	/// Both messages must be signing requests for the proof's event and validator set, so witnesses of other
+	/// events can't be passed off as an equivocation
+	fn equivocation_offender(
+		equivocation_proof: &EquivocationProof<T::EthyId>,
+	) -> Option<(u32, IdentificationTuple<T>)> {
+		let EquivocationProof {
+			authority_id,
+			validator_set_id,
+			first,
+			second,
+			..
+		} = equivocation_proof;
+		if *validator_set_id != Self::notary_set_id() || !equivocation_proof.is_conflicting() {
+			return None;
+		}
+
+		// both messages are signed by the authority
+		let signed_by_authority = |(message, signature): &(Vec<u8>, ecdsa::Signature)| {
+			let digest = sp_io::hashing::keccak_256(message);
+			sp_io::crypto::secp256k1_ecdsa_recover_compressed(&signature.0, &digest)
+				.map(|public_key| &public_key[..] == authority_id.as_ref())
+				.unwrap_or(false)
+		};
+		if !signed_by_authority(first) || !signed_by_authority(second) {
+			return None;
+		}
+
+		let authority_index = Self::notary_keys().iter().position(|notary| notary == authority_id)?;
+		let validator = T::AuthoritySet::validators().get(authority_index)?.clone();
+		let identification =
+			<T::AuthoritySet as ValidatorSetWithIdentification<T::AccountId>>::IdentificationOf::convert(
+				validator.clone(),
+			)?;
+
+		Some((authority_index as u32, (validator, identification)))
+	}
+
+	/// Submit an unsigned extrinsic reporting an Ethy signing equivocation
+	/// Called by the ethy-gadget via runtime API
+	pub fn submit_report_equivocation_unsigned_extrinsic(
+		equivocation_proof: EquivocationProof<T::EthyId>,
+	) -> Option<()> {
+		let call = Call::report_equivocation { equivocation_proof };
+		SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into())
+			.map_err(|_| log!(error, "💎 failed to submit equivocation report"))
+			.ok()
+	}
+
+	/// Note an Ethereum block header agreed on by notaries
+	fn note_eth_header(block_number: u64, block_hash: EthHash) {
+		let bucket_index = T::UnixTime::now().as_secs().saturated_into::<u64>() / BUCKET_FACTOR_S;
+		EthHeaders::insert(block_number, block_hash);
+		EthHeaderBuckets::insert(bucket_index, block_number, ());
+	}
+
+	/// Verify `proof` shows a successful tx to `contract_address` which logged the claimed event
+	/// Returns the proven block header and tx hash
+	fn verify_event_proof(
+		contract_address: &H160,
+		event_signature: &H256,
+		event_data: &[u8],
+		proof: &EthReceiptProof,
+	) -> Result<(EthHeader, EthHash), Error<T>> {
+		let invalid_proof = |err: ProofError| {
+			log!(trace, "💎 invalid event proof: {:?}", err);
+			Error::<T>::InvalidProof
+		};
+		let header = EthHeader::decode(&proof.header).map_err(invalid_proof)?;
+		let key = proof::trie_index_key(proof.tx_index);
+		let tx = proof::verify_trie_proof(&header.transactions_root, &key, &proof.tx_proof).map_err(invalid_proof)?;
+		let receipt = proof::verify_trie_proof(&header.receipts_root, &key, &proof.receipt_proof)
+			.and_then(Receipt::decode)
+			.map_err(invalid_proof)?;
+
+		ensure!(receipt.success, Error::<T>::UnexpectedEvent);
+		ensure!(
+			proof::transaction_recipient(tx).map_err(invalid_proof)? == Some(*contract_address),
+			Error::<T>::UnexpectedEvent
+		);
+		let has_event = receipt.logs.iter().any(|log| {
+			&log.address == contract_address && log.topics.first() == Some(event_signature) && log.data == event_data
+		});
+		ensure!(has_event, Error::<T>::UnexpectedEvent);
+
+		// the tx hash is the hash of its consensus encoding
+		Ok((header, EthHash::from(sp_io::hashing::keccak_256(tx))))
 	}
-}
 
-impl<T: Config> Module<T> {
 	/// Verify a message
 	/// `tx_hash` - The ethereum tx hash
 	/// `event_data` - The claimed message data
@@ -471,7 +1093,7 @@ impl<T: Config> Module<T> {
 		let result = Self::get_transaction_receipt(tx_hash);
 		if let Err(err) = result {
 			log!(error, "💎 eth_getTransactionReceipt({:?}) failed: {:?}", tx_hash, err);
-			return EventClaimResult::DataProviderErr;
+			return Self::data_provider_err(err);
 		}
 
 		let maybe_tx_receipt = result.unwrap(); // error handled above qed.
@@ -519,7 +1141,7 @@ impl<T: Config> Module<T> {
 			Ok(Some(block)) => block,
 			Err(err) => {
 				log!(error, "💎 eth_getBlockByNumber latest failed: {:?}", err);
-				return EventClaimResult::DataProviderErr;
+				return Self::data_provider_err(err);
 			}
 		};
 
@@ -542,7 +1164,7 @@ impl<T: Config> Module<T> {
 			Ok(Some(block)) => block,
 			Err(err) => {
 				log!(error, "💎 eth_getBlockByNumber observed failed: {:?}", err);
-				return EventClaimResult::DataProviderErr;
+				return Self::data_provider_err(err);
 			}
 		};
 
@@ -558,72 +1180,203 @@ impl<T: Config> Module<T> {
 		EventClaimResult::Valid
 	}
 
+	/// Verify an Ethereum block header
+	/// `block_number` - The ethereum block number
+	/// `block_hash` - The claimed hash of the block
+	/// Checks:
+	/// - check Eth full node for the block's hash
+	/// - confirmations `>= T::EventConfirmations`
+	/// - block has not expired older than `T::EventDeadline`
+	fn offchain_try_notarize_header(block_number: u64, block_hash: EthHash) -> EventClaimResult {
+		let latest_block: EthBlock = match Self::get_block(LatestOrNumber::Latest) {
+			Ok(None) => return EventClaimResult::DataProviderErr,
+			Ok(Some(block)) => block,
+			Err(err) => {
+				log!(error, "💎 eth_getBlockByNumber latest failed: {:?}", err);
+				return Self::data_provider_err(err);
+			}
+		};
+		let latest_block_number = latest_block.number.unwrap_or_default().as_u64();
+		if latest_block_number.saturating_sub(block_number) < Self::event_confirmations() {
+			return EventClaimResult::NotEnoughConfirmations;
+		}
+
+		let observed_block: EthBlock = match Self::get_block(LatestOrNumber::Number(block_number as u32)) {
+			Ok(None) => return EventClaimResult::DataProviderErr,
+			Ok(Some(block)) => block,
+			Err(err) => {
+				log!(error, "💎 eth_getBlockByNumber observed failed: {:?}", err);
+				return Self::data_provider_err(err);
+			}
+		};
+		if observed_block.hash != Some(block_hash) {
+			log!(
+				trace,
+				"💎 mismatch in provided header vs. observed header. provided: {:?} observed: {:?}",
+				block_hash,
+				observed_block.hash,
+			);
+			return EventClaimResult::UnexpectedBlockHash;
+		}
+
+		if T::UnixTime::now()
+			.as_secs()
+			.saturated_into::<u64>()
+			.saturating_sub(observed_block.timestamp.saturated_into::<u64>())
+			> Self::event_deadline_seconds()
+		{
+			return EventClaimResult::Expired;
+		}
+
+		EventClaimResult::Valid
+	}
+
+	/// Map a failed Eth JSON-RPC query to a claim result
+	fn data_provider_err(err: Error<T>) -> EventClaimResult {
+		match err {
+			Error::<T>::EthRpcDisagreement => EventClaimResult::DataProviderDisagreement,
+			_ => EventClaimResult::DataProviderErr,
+		}
+	}
+
 	/// Get transaction receipt from eth client
+	/// The receipt must be agreed on by a quorum of endpoints
 	fn get_transaction_receipt(tx_hash: EthHash) -> Result<Option<TransactionReceipt>, Error<T>> {
 		let random_request_id = u32::from_be_bytes(sp_io::offchain::random_seed()[..4].try_into().unwrap());
 		let request = GetTxReceiptRequest::new(tx_hash, random_request_id as usize);
-		let resp_bytes = Self::query_eth_client(Some(request)).map_err(|e| {
-			log!(error, "💎 read eth-rpc API error: {:?}", e);
-			<Error<T>>::HttpFetch
-		})?;
-
-		let resp_str = core::str::from_utf8(&resp_bytes).map_err(|_| {
-			log!(error, "💎 response invalid utf8: {:?}", resp_bytes);
-			<Error<T>>::HttpFetch
-		})?;
-
-		// Deserialize JSON to struct
-		serde_json::from_str::<EthResponse<TransactionReceipt>>(resp_str)
-			.map(|resp| resp.result)
-			.map_err(|err| {
-				log!(error, "💎 deserialize json response error: {:?}", err);
-				<Error<T>>::HttpFetch
-			})
+		let (responses, quorum) = Self::query_eth_clients::<_, TransactionReceipt>(&Some(request))?;
+		Self::quorum_response(responses, quorum)
 	}
 
 	/// Get latest block number from eth client
+	/// A block by number must be agreed on by a quorum of endpoints.
+	/// Endpoints may be at slightly different heights, for the latest block the highest block observed by a
+	/// quorum of endpoints is used.
 	fn get_block(req: LatestOrNumber) -> Result<Option<EthBlock>, Error<T>> {
-		let request = match req {
-			LatestOrNumber::Latest => GetBlockRequest::latest(1_usize),
-			LatestOrNumber::Number(n) => GetBlockRequest::for_number(1_usize, n),
+		let (request, is_latest) = match req {
+			LatestOrNumber::Latest => (GetBlockRequest::latest(1_usize), true),
+			LatestOrNumber::Number(n) => (GetBlockRequest::for_number(1_usize, n), false),
 		};
-		let resp_bytes = Self::query_eth_client(request).map_err(|e| {
-			log!(error, "💎 read eth-rpc API error: {:?}", e);
-			<Error<T>>::HttpFetch
-		})?;
-
-		let resp_str = core::str::from_utf8(&resp_bytes).map_err(|_| {
-			log!(error, "💎 response invalid utf8: {:?}", resp_bytes);
-			<Error<T>>::HttpFetch
-		})?;
-
-		// Deserialize JSON to struct
-		serde_json::from_str::<EthResponse<EthBlock>>(resp_str)
-			.map(|resp| resp.result)
-			.map_err(|err| {
-				log!(error, "💎 deserialize json response error: {:?}", err);
-				<Error<T>>::HttpFetch
-			})
+		let (mut responses, quorum) = Self::query_eth_clients::<_, EthBlock>(&request)?;
+		if !is_latest {
+			return Self::quorum_response(responses, quorum);
+		}
+
+		if responses.len() < quorum {
+			log!(error, "💎 {}/{} eth-rpc endpoints responded", responses.len(), quorum);
+			return Err(<Error<T>>::HttpFetch);
+		}
+		// sort highest first, the `quorum`th block is known to at least `quorum` endpoints
+		responses.sort_by(|a, b| {
+			let number_of = |block: &Option<EthBlock>| block.as_ref().and_then(|b| b.number).unwrap_or_default();
+			number_of(b).cmp(&number_of(a))
+		});
+		Ok(responses.swap_remove(quorum - 1))
 	}
 
-	/// This function uses the `offchain::http` API to query the remote ethereum information,
-	/// and returns the JSON response as vector of bytes.
-	fn query_eth_client<R: serde::Serialize>(request_body: R) -> Result<Vec<u8>, Error<T>> {
-		// Load eth http URI from offchain storage
+	/// Returns the response agreed on by at least `quorum` endpoints
+	fn quorum_response<D: PartialEq>(responses: Vec<Option<D>>, quorum: usize) -> Result<Option<D>, Error<T>> {
+		if responses.len() < quorum {
+			log!(error, "💎 {}/{} eth-rpc endpoints responded", responses.len(), quorum);
+			return Err(<Error<T>>::HttpFetch);
+		}
+		let agreed_index = responses
+			.iter()
+			.position(|response| responses.iter().filter(|r| *r == response).count() >= quorum);
+		match agreed_index {
+			Some(index) => Ok(responses.into_iter().nth(index).flatten()),
+			None => {
+				log!(error, "💎 eth-rpc endpoints did not reach quorum: {}", quorum);
+				Err(<Error<T>>::EthRpcDisagreement)
+			}
+		}
+	}
+
+	/// Returns the configured Eth JSON-RPC endpoints and the number which must agree on a response
+	fn eth_http_config() -> Result<(Vec<Vec<u8>>, usize), Error<T>> {
+		// Load eth http URIs from offchain storage
 		// this should have been configured on start up by passing e.g. `--eth-http`
-		// e.g. `--eth-http=http://localhost:8545`
-		let eth_http_uri = if let Some(value) = sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, b"ETH_HTTP")
-		{
-			value
-		} else {
+		// e.g. `--eth-http=http://localhost:8545 --eth-http=https://mainnet.infura.io/v3/<key>`
+		let eth_http_uris =
+			if let Some(value) = sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &ETH_HTTP_URI) {
+				value
+			} else {
+				log!(
+					error,
+					"💎 Eth http uri is not configured! set --eth-http=<value> on start up"
+				);
+				return Err(Error::<T>::OcwConfig);
+			};
+		let eth_http_uris: Vec<Vec<u8>> = eth_http_uris
+			.split(|b| *b == b',')
+			.filter(|uri| !uri.is_empty())
+			.map(|uri| uri.to_vec())
+			.collect();
+		if eth_http_uris.is_empty() {
+			return Err(Error::<T>::OcwConfig);
+		}
+
+		// default to a simple majority of endpoints
+		let majority = eth_http_uris.len() / 2 + 1;
+		let quorum = sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &ETH_HTTP_QUORUM)
+			.and_then(|value| u32::decode(&mut &value[..]).ok())
+			.map_or(majority, |quorum| quorum as usize);
+		if quorum.is_zero() || quorum > eth_http_uris.len() {
 			log!(
 				error,
-				"💎 Eth http uri is not configured! set --eth-http=<value> on start up"
+				"💎 Eth http quorum: {} is invalid for {} endpoints",
+				quorum,
+				eth_http_uris.len()
 			);
 			return Err(Error::<T>::OcwConfig);
-		};
-		let eth_http_uri = core::str::from_utf8(&eth_http_uri).map_err(|_| Error::<T>::OcwConfig)?;
+		}
+
+		Ok((eth_http_uris, quorum))
+	}
+
+	/// Send `request_body` to all configured Eth JSON-RPC endpoints
+	/// Returns the deserialized result of each successful response and the quorum required
+	fn query_eth_clients<R: serde::Serialize, D: DeserializeOwned>(
+		request_body: &R,
+	) -> Result<(Vec<Option<D>>, usize), Error<T>> {
+		let (eth_http_uris, quorum) = Self::eth_http_config()?;
+		let mut responses = Vec::<Option<D>>::with_capacity(eth_http_uris.len());
+		for eth_http_uri in eth_http_uris {
+			let response = core::str::from_utf8(&eth_http_uri)
+				.map_err(|_| Error::<T>::OcwConfig)
+				.and_then(|uri| Self::query_eth_client(uri, request_body))
+				.and_then(|resp_bytes| {
+					serde_json::from_slice::<EthResponse<D>>(&resp_bytes)
+						.map(|resp| resp.result)
+						.map_err(|err| {
+							log!(error, "💎 deserialize json response error: {:?}", err);
+							<Error<T>>::HttpFetch
+						})
+				});
+			match response {
+				Ok(response) => responses.push(response),
+				Err(err) => {
+					log!(error, "💎 read eth-rpc API error: {:?}", err);
+					Self::note_eth_http_failure(&eth_http_uri);
+				}
+			}
+		}
 
+		Ok((responses, quorum))
+	}
+
+	/// Increment the request failure counter for `eth_http_uri`
+	/// Counters are exported by the node as prometheus metrics
+	fn note_eth_http_failure(eth_http_uri: &[u8]) {
+		let key = [&ETH_HTTP_FAILURES[..], eth_http_uri].concat();
+		let _ = StorageValueRef::persistent(&key).mutate(|failures: Result<Option<u64>, _>| {
+			Ok::<u64, ()>(failures.ok().flatten().unwrap_or_default().saturating_add(1))
+		});
+	}
+
+	/// This function uses the `offchain::http` API to query the remote ethereum information,
+	/// and returns the JSON response as vector of bytes.
+	fn query_eth_client<R: serde::Serialize>(eth_http_uri: &str, request_body: R) -> Result<Vec<u8>, Error<T>> {
 		const HEADER_CONTENT_TYPE: &str = "application/json";
 		log!(info, "💎 sending request to: {}", eth_http_uri);
 		let body = serde_json::to_string::<R>(&request_body).unwrap();
@@ -669,6 +1422,64 @@ impl<T: Config> Module<T> {
 		Ok(response.body().collect::<Vec<u8>>())
 	}
 
+	/// Returns the claims `notary` may notarize: pending claims, then claims notarized this session before it voted
+	/// A late vote still counts as participation in the claim
+	fn offchain_claims_to_notarize(
+		notary: T::EthyId,
+	) -> impl Iterator<Item = (EventClaimId, EventClaim, Option<(u64, EthHash)>)> {
+		let pending_claims = EventClaims::iter().filter_map(|(event_claim_id, (tx_hash, event_type_id))| {
+			let event_data = Self::event_data(event_claim_id);
+			if event_data.is_none() {
+				// should not happen, defensive only
+				log!(error, "💎 empty claim data for: {:?}", event_claim_id);
+			}
+			event_data.map(|data| {
+				let (contract_address, event_signature) = TypeIdToEventType::get(event_type_id);
+				let event_claim = EventClaim {
+					tx_hash,
+					data,
+					contract_address,
+					event_signature,
+				};
+				(event_claim_id, event_claim, Self::event_claim_headers(event_claim_id))
+			})
+		});
+		let late_claims = SessionEventClaims::iter()
+			.filter(move |(event_claim_id, _)| !<EventNotarizations<T>>::contains_key(event_claim_id, &notary))
+			.map(|(event_claim_id, (event_claim, header))| (event_claim_id, event_claim, header));
+		pending_claims.chain(late_claims)
+	}
+
+	/// Whether a claim notarized with a transient result is due for retry at `block_number`
+	fn offchain_is_retry_due(event_claim_id: EventClaimId, block_number: T::BlockNumber) -> bool {
+		let key = [&ETH_CLAIM_RETRY[..], &event_claim_id.to_be_bytes()].concat();
+		match StorageValueRef::persistent(&key).get::<(u32, u64)>() {
+			Ok(Some((_attempts, next_retry_block))) => block_number.saturated_into::<u64>() >= next_retry_block,
+			// no retry state e.g. after a restart
+			_ => true,
+		}
+	}
+
+	/// Note a transient notarization result for a claim at `block_number`
+	/// The next retry is scheduled with exponential backoff
+	fn offchain_note_retry(event_claim_id: EventClaimId, block_number: T::BlockNumber) {
+		let key = [&ETH_CLAIM_RETRY[..], &event_claim_id.to_be_bytes()].concat();
+		let _ = StorageValueRef::persistent(&key).mutate(|retry: Result<Option<(u32, u64)>, _>| {
+			let attempts = retry.ok().flatten().map(|(attempts, _)| attempts).unwrap_or_default();
+			let backoff = CLAIM_RETRY_BASE_BLOCKS << attempts.min(CLAIM_RETRY_MAX_DOUBLINGS);
+			Ok::<(u32, u64), ()>((
+				attempts.saturating_add(1),
+				block_number.saturated_into::<u64>().saturating_add(backoff),
+			))
+		});
+	}
+
+	/// Clear the retry state of a claim
+	fn offchain_clear_retry(event_claim_id: EventClaimId) {
+		let key = [&ETH_CLAIM_RETRY[..], &event_claim_id.to_be_bytes()].concat();
+		StorageValueRef::persistent(&key).clear();
+	}
+
 	/// Send a notarization for the given claim
 	fn offchain_send_notarization(key: &T::EthyId, payload: NotarizationPayload) -> Result<(), Error<T>> {
 		let signature = key
@@ -759,7 +1570,32 @@ impl<T: Config> Module<T> {
 impl<T: Config> frame_support::unsigned::ValidateUnsigned for Module<T> {
 	type Call = Call<T>;
 
-	fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+	fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+		if let Call::report_equivocation { ref equivocation_proof } = call {
+			// equivocation reports are submitted by the local ethy-gadget
+			match source {
+				TransactionSource::Local | TransactionSource::InBlock => (),
+				_ => return InvalidTransaction::Call.into(),
+			}
+			let offender = match Self::equivocation_offender(equivocation_proof) {
+				Some((_authority_index, offender)) => offender,
+				None => return InvalidTransaction::BadProof.into(),
+			};
+			if T::ReportEquivocation::is_known_offence(&[offender], &equivocation_proof.event_id) {
+				return InvalidTransaction::Stale.into();
+			}
+			return ValidTransaction::with_tag_prefix("eth-bridge")
+				.priority(UNSIGNED_TXS_PRIORITY)
+				.and_provides((
+					b"equivocation",
+					equivocation_proof.authority_id.clone(),
+					equivocation_proof.event_id,
+				))
+				.longevity(64)
+				.propagate(false)
+				.build();
+		}
+
 		if let Call::submit_notarization {
 			ref payload,
 			_signature: ref signature,
@@ -772,7 +1608,9 @@ impl<T: Config> frame_support::unsigned::ValidateUnsigned for Module<T> {
 				None => return InvalidTransaction::BadProof.into(),
 			};
 			// notarization must not be a duplicate/equivocation
-			if <EventNotarizations<T>>::contains_key(payload.event_claim_id, &notary_public_key) {
+			// transient results may be replaced by a different result
+			let is_replaceable = |previous: EventClaimResult| previous.is_transient() && previous != payload.result;
+			if !<EventNotarizations<T>>::get(payload.event_claim_id, &notary_public_key).map_or(true, is_replaceable) {
 				log!(
 					error,
 					"💎 received equivocation from: {:?} on {:?}",
@@ -842,6 +1680,10 @@ impl<T: Config> OneSessionHandler<T::AccountId> for Module<T> {
 	/// Note it is triggered before any [`SessionManager::end_session`] handlers,
 	/// so we can still affect the validator set.
 	fn on_before_session_ending() {
+		// Penalise notaries for the session ending, before the notary keys may change
+		let weight = Self::end_session_participation();
+		<frame_system::Pallet<T>>::register_extra_weight_unchecked(weight, DispatchClass::Mandatory);
+
 		// Re-activate the bridge, allowing claims & proofs again
 		if T::FinalSessionTracker::is_active_session_final() {
 			log!(trace, "💎 session & era ending, set new validator keys");
@@ -0,0 +1,512 @@
+/* Copyright 2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+//! Ethereum block header and Merkle-Patricia trie proof verification
+//!
+//! Allows event claims to be checked against an Ethereum block header rather than trusting an Eth JSON-RPC
+//! endpoint's view of a transaction receipt.
+//! https://ethereum.github.io/yellowpaper/paper.pdf (Appendix B, D)
+
+use crate::types::{EthAddress, EthHash};
+use rlp::Rlp;
+use sp_io::hashing::keccak_256;
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// Length of a keccak256 hash reference to a trie node
+const HASH_LENGTH: usize = 32;
+
+/// Reasons an Ethereum proof may fail verification
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum ProofError {
+	/// Some bytes were not valid RLP
+	InvalidRlp,
+	/// The block header is malformed
+	InvalidHeader,
+	/// A trie node is malformed
+	InvalidNode,
+	/// A trie node referenced by the proof was not included
+	MissingNode,
+	/// A trie node did not match the hash referencing it
+	UnexpectedNode,
+	/// The trie does not contain the key
+	KeyNotFound,
+	/// The transaction is malformed or of an unknown type
+	InvalidTransaction,
+	/// The receipt is malformed or of an unknown type
+	InvalidReceipt,
+}
+
+impl From<rlp::DecoderError> for ProofError {
+	fn from(_: rlp::DecoderError) -> Self {
+		ProofError::InvalidRlp
+	}
+}
+
+/// The fields of an Ethereum block header needed by the bridge
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct EthHeader {
+	/// keccak256 of the RLP encoded header
+	pub hash: EthHash,
+	/// Root of the block's transactions trie
+	pub transactions_root: EthHash,
+	/// Root of the block's receipts trie
+	pub receipts_root: EthHash,
+	/// The block number
+	pub number: u64,
+	/// The block timestamp (seconds)
+	pub timestamp: u64,
+}
+
+impl EthHeader {
+	/// Decode an RLP encoded Ethereum block header
+	/// Fields added by later forks (e.g. London `base_fee`) are ignored
+	pub fn decode(header_rlp: &[u8]) -> Result<Self, ProofError> {
+		let header = Rlp::new(header_rlp);
+		// parent, uncles, coinbase, state, txs, receipts, bloom, difficulty, number, gas limit, gas used, timestamp
+		if !header.is_list() || header.item_count()? < 12 {
+			return Err(ProofError::InvalidHeader);
+		}
+		let hash_at = |index: usize| -> Result<EthHash, ProofError> {
+			let data = header.at(index)?.data()?;
+			if data.len() != HASH_LENGTH {
+				return Err(ProofError::InvalidHeader);
+			}
+			Ok(EthHash::from_slice(data))
+		};
+
+		Ok(Self {
+			hash: EthHash::from(keccak_256(header_rlp)),
+			transactions_root: hash_at(4)?,
+			receipts_root: hash_at(5)?,
+			number: header.val_at(8)?,
+			timestamp: header.val_at(11)?,
+		})
+	}
+}
+
+/// A log entry in a transaction receipt
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ReceiptLog {
+	/// The contract which emitted the log
+	pub address: EthAddress,
+	/// Indexed log topics, the first is the event signature
+	pub topics: Vec<EthHash>,
+	/// Non-indexed log data
+	pub data: Vec<u8>,
+}
+
+/// The fields of an Ethereum transaction receipt needed by the bridge
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Receipt {
+	/// Whether the transaction succeeded
+	pub success: bool,
+	/// Logs emitted by the transaction
+	pub logs: Vec<ReceiptLog>,
+}
+
+impl Receipt {
+	/// Decode a consensus encoded receipt i.e. a value of the receipts trie
+	/// Supports legacy and typed (EIP-2718) receipts from Byzantium onwards
+	pub fn decode(receipt: &[u8]) -> Result<Self, ProofError> {
+		let receipt = Rlp::new(strip_envelope(receipt, ProofError::InvalidReceipt)?);
+		// status, cumulative gas used, bloom, logs
+		if !receipt.is_list() || receipt.item_count()? != 4 {
+			return Err(ProofError::InvalidReceipt);
+		}
+		// pre-Byzantium receipts have an intermediate state root in place of a status code
+		let status = receipt.at(0)?.data()?;
+		if status.len() > 1 {
+			return Err(ProofError::InvalidReceipt);
+		}
+
+		let logs = receipt
+			.at(3)?
+			.iter()
+			.map(|log| {
+				if log.item_count()? != 3 {
+					return Err(ProofError::InvalidReceipt);
+				}
+				let address = log.at(0)?.data()?;
+				if address.len() != 20 {
+					return Err(ProofError::InvalidReceipt);
+				}
+				let topics = log
+					.at(1)?
+					.iter()
+					.map(|topic| match topic.data()? {
+						topic if topic.len() == HASH_LENGTH => Ok(EthHash::from_slice(topic)),
+						_ => Err(ProofError::InvalidReceipt),
+					})
+					.collect::<Result<Vec<EthHash>, ProofError>>()?;
+				Ok(ReceiptLog {
+					address: EthAddress::from_slice(address),
+					topics,
+					data: log.at(2)?.data()?.to_vec(),
+				})
+			})
+			.collect::<Result<Vec<ReceiptLog>, ProofError>>()?;
+
+		Ok(Self {
+			success: status == [1_u8],
+			logs,
+		})
+	}
+}
+
+/// Returns the recipient of a consensus encoded transaction i.e. a value of the transactions trie
+/// `None` if the transaction deployed a contract
+pub fn transaction_recipient(transaction: &[u8]) -> Result<Option<EthAddress>, ProofError> {
+	let to_index = match transaction.first() {
+		// legacy: nonce, gas price, gas limit, to, ..
+		Some(prefix) if *prefix >= 0xc0 => 3,
+		// EIP-2930: chain id, nonce, gas price, gas limit, to, ..
+		Some(0x01) => 4,
+		// EIP-1559: chain id, nonce, max priority fee, max fee, gas limit, to, ..
+		Some(0x02) => 5,
+		_ => return Err(ProofError::InvalidTransaction),
+	};
+	let transaction = Rlp::new(strip_envelope(transaction, ProofError::InvalidTransaction)?);
+	match transaction.at(to_index)?.data()? {
+		to if to.is_empty() => Ok(None),
+		to if to.len() == 20 => Ok(Some(EthAddress::from_slice(to))),
+		_ => Err(ProofError::InvalidTransaction),
+	}
+}
+
+/// Strip the type byte from a typed (EIP-2718) transaction or receipt
+fn strip_envelope(payload: &[u8], err: ProofError) -> Result<&[u8], ProofError> {
+	match payload.first() {
+		// legacy, untyped RLP list
+		Some(prefix) if *prefix >= 0xc0 => Ok(payload),
+		// EIP-2930 & EIP-1559
+		Some(0x01) | Some(0x02) => Ok(&payload[1..]),
+		_ => Err(err),
+	}
+}
+
+/// The key of the `index`th transaction/receipt in a block's transactions/receipts trie
+pub fn trie_index_key(index: u64) -> Vec<u8> {
+	rlp::encode(&index).to_vec()
+}
+
+/// A reference from a trie node to its child
+enum NodeRef<'a> {
+	/// keccak256 of the child node, the node must be supplied by the proof
+	Hash(EthHash),
+	/// The child node is embedded (it's encoding is shorter than a hash)
+	Inline(&'a [u8]),
+}
+
+/// Verify `proof` contains the value at `key` in the Merkle-Patricia trie with `root`
+/// `proof` is the list of trie nodes on the path from the root to the value, as returned by `eth_getProof`
+/// Returns the value on success
+pub fn verify_trie_proof<'a>(root: &EthHash, key: &[u8], proof: &'a [Vec<u8>]) -> Result<&'a [u8], ProofError> {
+	let key_nibbles: Vec<u8> = key.iter().flat_map(|b| [b >> 4, b & 0x0f]).collect();
+	let mut path = &key_nibbles[..];
+	let mut proof_nodes = proof.iter();
+	let mut next_node = NodeRef::Hash(*root);
+
+	loop {
+		let node = match next_node {
+			NodeRef::Hash(hash) => {
+				let node = proof_nodes.next().ok_or(ProofError::MissingNode)?;
+				if keccak_256(node) != hash.0 {
+					return Err(ProofError::UnexpectedNode);
+				}
+				Rlp::new(node)
+			}
+			NodeRef::Inline(node) => Rlp::new(node),
+		};
+
+		match node.item_count()? {
+			// branch node: 16 children & a value
+			17 => {
+				if path.is_empty() {
+					return match node.at(16)?.data()? {
+						value if value.is_empty() => Err(ProofError::KeyNotFound),
+						value => Ok(value),
+					};
+				}
+				next_node = child_ref(node.at(path[0] as usize)?)?;
+				path = &path[1..];
+			}
+			// extension or leaf node: (hex prefix encoded path, child or value)
+			2 => {
+				let (node_path, is_leaf) = decode_hex_prefix(node.at(0)?.data()?)?;
+				if !path.starts_with(&node_path) {
+					return Err(ProofError::KeyNotFound);
+				}
+				path = &path[node_path.len()..];
+				if is_leaf {
+					if !path.is_empty() {
+						return Err(ProofError::KeyNotFound);
+					}
+					return Ok(node.at(1)?.data()?);
+				}
+				next_node = child_ref(node.at(1)?)?;
+			}
+			_ => return Err(ProofError::InvalidNode),
+		}
+	}
+}
+
+/// Decode a reference to a child node from its parent
+fn child_ref<'a>(child: Rlp<'a>) -> Result<NodeRef<'a>, ProofError> {
+	if child.is_list() {
+		return Ok(NodeRef::Inline(child.as_raw()));
+	}
+	match child.data()? {
+		hash if hash.len() == HASH_LENGTH => Ok(NodeRef::Hash(EthHash::from_slice(hash))),
+		empty if empty.is_empty() => Err(ProofError::KeyNotFound),
+		_ => Err(ProofError::InvalidNode),
+	}
+}
+
+/// Decode a hex prefix encoded path into (nibbles, is leaf)
+fn decode_hex_prefix(encoded: &[u8]) -> Result<(Vec<u8>, bool), ProofError> {
+	let flags = encoded.first().ok_or(ProofError::InvalidNode)? >> 4;
+	if flags > 3 {
+		return Err(ProofError::InvalidNode);
+	}
+	let is_leaf = flags & 0b10 != 0;
+	let is_odd = flags & 0b01 != 0;
+
+	let mut nibbles: Vec<u8> = encoded.iter().flat_map(|b| [b >> 4, b & 0x0f]).collect();
+	// drop the flag nibble and the padding nibble of even length paths
+	nibbles.drain(..if is_odd { 1 } else { 2 });
+
+	Ok((nibbles, is_leaf))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::fixtures::*;
+	use hex_literal::hex;
+
+	fn to_proof(nodes: &[&[u8]]) -> Vec<Vec<u8>> {
+		nodes.iter().map(|node| node.to_vec()).collect()
+	}
+
+	#[test]
+	fn decode_mainnet_genesis_header() {
+		let header = EthHeader::decode(&MAINNET_GENESIS_HEADER).unwrap();
+		assert_eq!(
+			header,
+			EthHeader {
+				hash: hex!("d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3").into(),
+				// the empty trie
+				transactions_root: hex!("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421").into(),
+				receipts_root: hex!("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421").into(),
+				number: 0,
+				timestamp: 0,
+			}
+		);
+	}
+
+	#[test]
+	fn decode_london_header() {
+		let header = EthHeader::decode(&DEPOSIT_BLOCK_HEADER).unwrap();
+		assert_eq!(header.hash, EthHash::from(DEPOSIT_BLOCK_HASH));
+		assert_eq!(header.number, 13_000_000);
+		assert_eq!(header.timestamp, 1_630_000_000);
+	}
+
+	#[test]
+	fn decode_invalid_header() {
+		assert_eq!(EthHeader::decode(&[]), Err(ProofError::InvalidRlp));
+		// truncated
+		assert_eq!(
+			EthHeader::decode(&MAINNET_GENESIS_HEADER[..100]),
+			Err(ProofError::InvalidRlp)
+		);
+		// too few fields
+		assert_eq!(
+			EthHeader::decode(&rlp::encode_list::<u64, _>(&[1, 2, 3])),
+			Err(ProofError::InvalidHeader)
+		);
+	}
+
+	#[test]
+	fn verify_mainnet_transaction_proof() {
+		let proof = to_proof(&MAINNET_TX_PROOF_46147);
+		let transaction = verify_trie_proof(&MAINNET_TX_ROOT_46147.into(), &trie_index_key(0), &proof).unwrap();
+		assert_eq!(transaction, &MAINNET_TX_46147[..]);
+		assert_eq!(
+			keccak_256(transaction),
+			hex!("5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060")
+		);
+		assert_eq!(
+			transaction_recipient(transaction),
+			Ok(Some(hex!("5df9b87991262f6ba471f09758cde1c0fc1de734").into()))
+		);
+		// the block has only 1 tx
+		assert_eq!(
+			verify_trie_proof(&MAINNET_TX_ROOT_46147.into(), &trie_index_key(1), &proof),
+			Err(ProofError::KeyNotFound)
+		);
+	}
+
+	#[test]
+	fn verify_trie_proof_through_extension_and_branch() {
+		let root = PUPPY_TRIE_ROOT.into();
+		assert_eq!(
+			verify_trie_proof(&root, b"doge", &to_proof(&PUPPY_TRIE_PROOF_DOGE)),
+			Ok(&b"coin"[..])
+		);
+		assert_eq!(
+			verify_trie_proof(&root, b"horse", &to_proof(&PUPPY_TRIE_PROOF_HORSE)),
+			Ok(&b"stallion"[..])
+		);
+		// the proof of 'doge' also proves 'dog' and 'do'
+		assert_eq!(
+			verify_trie_proof(&root, b"dog", &to_proof(&PUPPY_TRIE_PROOF_DOGE)),
+			Ok(&b"puppy"[..])
+		);
+		assert_eq!(
+			verify_trie_proof(&root, b"do", &to_proof(&PUPPY_TRIE_PROOF_DOGE)),
+			Ok(&b"verb"[..])
+		);
+		assert_eq!(
+			verify_trie_proof(&root, b"dogs", &to_proof(&PUPPY_TRIE_PROOF_DOGE)),
+			Err(ProofError::KeyNotFound)
+		);
+		assert_eq!(
+			verify_trie_proof(&root, b"cat", &to_proof(&PUPPY_TRIE_PROOF_DOGE)),
+			Err(ProofError::KeyNotFound)
+		);
+	}
+
+	#[test]
+	fn verify_trie_proof_fails_with_bad_nodes() {
+		let root = PUPPY_TRIE_ROOT.into();
+		// a node on the path is missing
+		let mut proof = to_proof(&PUPPY_TRIE_PROOF_DOGE);
+		proof.pop();
+		assert_eq!(verify_trie_proof(&root, b"doge", &proof), Err(ProofError::MissingNode));
+		// a node is tampered
+		let mut proof = to_proof(&PUPPY_TRIE_PROOF_DOGE);
+		let last = proof.last_mut().unwrap();
+		*last.last_mut().unwrap() ^= 1;
+		assert_eq!(
+			verify_trie_proof(&root, b"doge", &proof),
+			Err(ProofError::UnexpectedNode)
+		);
+		// proof for another root
+		assert_eq!(
+			verify_trie_proof(&EthHash::repeat_byte(1), b"doge", &to_proof(&PUPPY_TRIE_PROOF_DOGE)),
+			Err(ProofError::UnexpectedNode)
+		);
+	}
+
+	#[test]
+	fn verify_receipt_proofs() {
+		let header = EthHeader::decode(&DEPOSIT_BLOCK_HEADER).unwrap();
+		let contract = EthAddress::repeat_byte(4);
+		let deposit_log = ReceiptLog {
+			address: contract,
+			topics: vec![
+				EthHash::repeat_byte(5),
+				hex!("0000000000000000000000000707070707070707070707070707070707070707").into(),
+			],
+			data: vec![1_u8; 96],
+		};
+
+		let receipt_of = |index: u64, proof: &[&[u8]]| {
+			let proof = to_proof(proof);
+			let receipt = verify_trie_proof(&header.receipts_root, &trie_index_key(index), &proof).unwrap();
+			Receipt::decode(receipt).unwrap()
+		};
+		// legacy receipt without logs
+		assert_eq!(
+			receipt_of(0, &DEPOSIT_BLOCK_RECEIPT_PROOF_0),
+			Receipt {
+				success: true,
+				logs: vec![]
+			}
+		);
+		// EIP-1559 receipt
+		assert_eq!(
+			receipt_of(1, &DEPOSIT_BLOCK_RECEIPT_PROOF_1),
+			Receipt {
+				success: true,
+				logs: vec![deposit_log.clone()]
+			}
+		);
+		// legacy receipt with logs
+		assert_eq!(
+			receipt_of(2, &DEPOSIT_BLOCK_RECEIPT_PROOF_2),
+			Receipt {
+				success: true,
+				logs: vec![deposit_log]
+			}
+		);
+		// failed EIP-2930 receipt
+		assert_eq!(
+			receipt_of(3, &DEPOSIT_BLOCK_RECEIPT_PROOF_3),
+			Receipt {
+				success: false,
+				logs: vec![]
+			}
+		);
+		// a receipt proof does not prove a transaction
+		assert_eq!(
+			verify_trie_proof(
+				&header.transactions_root,
+				&trie_index_key(1),
+				&to_proof(&DEPOSIT_BLOCK_RECEIPT_PROOF_1)
+			),
+			Err(ProofError::UnexpectedNode)
+		);
+	}
+
+	#[test]
+	fn verify_transaction_proofs() {
+		let header = EthHeader::decode(&DEPOSIT_BLOCK_HEADER).unwrap();
+		let proofs: [&[&[u8]]; 4] = [
+			&DEPOSIT_BLOCK_TX_PROOF_0,
+			&DEPOSIT_BLOCK_TX_PROOF_1,
+			&DEPOSIT_BLOCK_TX_PROOF_2,
+			&DEPOSIT_BLOCK_TX_PROOF_3,
+		];
+		let recipients = [
+			hex!("5df9b87991262f6ba471f09758cde1c0fc1de734").into(),
+			EthAddress::repeat_byte(4),
+			EthAddress::repeat_byte(9),
+			EthAddress::repeat_byte(4),
+		];
+		for (index, (proof, recipient)) in proofs.iter().zip(recipients.iter()).enumerate() {
+			let proof = to_proof(proof);
+			let transaction =
+				verify_trie_proof(&header.transactions_root, &trie_index_key(index as u64), &proof).unwrap();
+			assert_eq!(transaction, DEPOSIT_BLOCK_TXS[index]);
+			assert_eq!(transaction_recipient(transaction), Ok(Some(*recipient)));
+		}
+	}
+
+	#[test]
+	fn decode_invalid_transaction_and_receipt() {
+		// unknown tx type
+		assert_eq!(
+			transaction_recipient(&[0x03, 0xc0]),
+			Err(ProofError::InvalidTransaction)
+		);
+		assert_eq!(Receipt::decode(&[0x03, 0xc0]), Err(ProofError::InvalidReceipt));
+		// pre-Byzantium receipt with a state root
+		let receipt = rlp::encode_list::<Vec<u8>, _>(&[vec![1_u8; 32], vec![1], vec![0_u8; 256], vec![]]);
+		assert_eq!(Receipt::decode(&receipt), Err(ProofError::InvalidReceipt));
+	}
+}
@@ -14,17 +14,29 @@
 */
 
 use crate as crml_eth_bridge;
-use crate::{Config, Module};
-use benson_primitives::eth::crypto::AuthorityId;
-use crml_support::{EventClaimSubscriber, FinalSessionTracker, NotarizationRewardHandler, H160, H256 as H256Crml};
-use frame_support::traits::OneSessionHandler;
+use crate::{
+	fixtures::*,
+	types::{
+		EventClaim, EventClaimResult, EventClaimStatus, GetBlockRequest, GetTxReceiptRequest, LatestOrNumber,
+		NotarizationPayload,
+	},
+	Config, Error, EthyEquivocationOffence, Module, NotaryMisconductOffence, NotaryParticipation, BUCKET_FACTOR_S,
+	CLAIM_PRUNING_INTERVAL,
+};
+use benson_primitives::eth::{crypto::AuthorityId, ConsensusLog, EquivocationProof, ETHY_ENGINE_ID};
+use codec::{Decode, Encode};
+use crml_support::{
+	EthAbiCodec, EthReceiptProof, EventClaimSubscriber, EventClaimVerifier, FinalSessionTracker,
+	NotarizationRewardHandler, H160, H256 as H256Crml,
+};
+use frame_support::traits::{OnFinalize, OnInitialize, OneSessionHandler};
 use frame_support::{
-	parameter_types,
+	assert_noop, assert_ok, parameter_types,
 	storage::StorageValue,
-	traits::{UnixTime, ValidatorSet as ValidatorSetT},
+	traits::{UnixTime, ValidatorSet as ValidatorSetT, ValidatorSetWithIdentification},
 };
 use sp_core::{
-	ecdsa::Signature,
+	ecdsa::{self, Signature},
 	offchain::{testing, OffchainDbExt, OffchainWorkerExt},
 	Public, H256,
 };
@@ -34,6 +46,11 @@ use sp_runtime::{
 	traits::{BlakeTwo256, Convert, Extrinsic as ExtrinsicT, IdentifyAccount, IdentityLookup, Verify},
 	Percent,
 };
+use sp_staking::{
+	offence::{OffenceError, ReportOffence},
+	SessionIndex,
+};
+use std::cell::RefCell;
 
 type SessionIndex = u32;
 type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
@@ -97,6 +114,8 @@ impl Config for TestRuntime {
 	type UnixTime = MockUnixTime;
 	type Call = Call;
 	type Event = Event;
+	type ReportMisconduct = MockOffenceReporter;
+	type ReportEquivocation = MockOffenceReporter;
 }
 
 pub struct NoopConverter<T>(sp_std::marker::PhantomData<T>);
@@ -116,7 +135,73 @@ impl ValidatorSetT<AccountId> for MockValidatorSet {
 	}
 	/// Returns the active set of validators.
 	fn validators() -> Vec<Self::ValidatorId> {
-		Default::default()
+		// one validator per notary key
+		crate::NotaryKeys::<TestRuntime>::get()
+			.iter()
+			.map(|key| AccountId::from_slice(key.as_ref()))
+			.collect()
+	}
+}
+
+impl ValidatorSetWithIdentification<AccountId> for MockValidatorSet {
+	type Identification = AccountId;
+	type IdentificationOf = NoopConverter<TestRuntime>;
+}
+
+/// A reported offence (session index, offenders)
+pub type MockOffence = (SessionIndex, Vec<(AccountId, AccountId)>);
+
+/// A reported equivocation (event id, offender)
+pub type MockEquivocation = (u64, (AccountId, AccountId));
+
+thread_local! {
+	static OFFENCES: RefCell<Vec<MockOffence>> = RefCell::new(Vec::new());
+	static EQUIVOCATIONS: RefCell<Vec<MockEquivocation>> = RefCell::new(Vec::new());
+}
+
+/// Records reported offences
+pub struct MockOffenceReporter;
+impl MockOffenceReporter {
+	/// Offences reported so far
+	pub fn offences() -> Vec<MockOffence> {
+		OFFENCES.with(|offences| offences.borrow().clone())
+	}
+	/// Equivocations reported so far
+	pub fn equivocations() -> Vec<MockEquivocation> {
+		EQUIVOCATIONS.with(|equivocations| equivocations.borrow().clone())
+	}
+}
+impl ReportOffence<AccountId, (AccountId, AccountId), NotaryMisconductOffence<(AccountId, AccountId)>>
+	for MockOffenceReporter
+{
+	fn report_offence(
+		_reporters: Vec<AccountId>,
+		offence: NotaryMisconductOffence<(AccountId, AccountId)>,
+	) -> Result<(), OffenceError> {
+		OFFENCES.with(|offences| offences.borrow_mut().push((offence.session_index, offence.offenders)));
+		Ok(())
+	}
+	fn is_known_offence(_offenders: &[(AccountId, AccountId)], _time_slot: &SessionIndex) -> bool {
+		false
+	}
+}
+impl ReportOffence<AccountId, (AccountId, AccountId), EthyEquivocationOffence<(AccountId, AccountId)>>
+	for MockOffenceReporter
+{
+	fn report_offence(
+		_reporters: Vec<AccountId>,
+		offence: EthyEquivocationOffence<(AccountId, AccountId)>,
+	) -> Result<(), OffenceError> {
+		if Self::is_known_offence(&[offence.offender.clone()], &offence.event_id) {
+			return Err(OffenceError::DuplicateReport);
+		}
+		EQUIVOCATIONS.with(|equivocations| equivocations.borrow_mut().push((offence.event_id, offence.offender)));
+		Ok(())
+	}
+	fn is_known_offence(offenders: &[(AccountId, AccountId)], time_slot: &u64) -> bool {
+		Self::equivocations()
+			.iter()
+			.any(|(event_id, offender)| event_id == time_slot && offenders.contains(offender))
 	}
 }
 
@@ -141,22 +226,46 @@ impl FinalSessionTracker for MockFinalSessionTracker {
 	}
 }
 
-/// Returns the current system time
+thread_local! {
+	static REWARDS: RefCell<Vec<AccountId>> = RefCell::new(Vec::new());
+}
+
+/// Records rewarded notaries
 pub struct MockRewardHandler;
+impl MockRewardHandler {
+	/// Notaries rewarded so far, once per reward
+	pub fn rewards() -> Vec<AccountId> {
+		REWARDS.with(|rewards| rewards.borrow().clone())
+	}
+}
 impl NotarizationRewardHandler for MockRewardHandler {
 	type AccountId = AccountId;
-	fn reward_notary(_notary: &Self::AccountId) {
-		// Do nothing
+	fn reward_notary(notary: &Self::AccountId) {
+		REWARDS.with(|rewards| rewards.borrow_mut().push(notary.clone()));
 	}
 }
 
 /// Returns the current system time
+thread_local! {
+	/// Mock unix time (seconds), the system time is used if unset
+	static MOCK_NOW: RefCell<Option<u64>> = RefCell::new(None);
+}
+
 pub struct MockUnixTime;
+impl MockUnixTime {
+	/// Set the current unix time (seconds)
+	pub fn set_now(seconds: u64) {
+		MOCK_NOW.with(|now| *now.borrow_mut() = Some(seconds));
+	}
+}
 impl UnixTime for MockUnixTime {
 	fn now() -> core::time::Duration {
-		std::time::SystemTime::now()
-			.duration_since(std::time::UNIX_EPOCH)
-			.unwrap()
+		match MOCK_NOW.with(|now| *now.borrow()) {
+			Some(seconds) => core::time::Duration::from_secs(seconds),
+			None => std::time::SystemTime::now()
+				.duration_since(std::time::UNIX_EPOCH)
+				.unwrap(),
+		}
 	}
 }
 
@@ -310,7 +419,9 @@ fn eth_client_http_request() {
 
 	// Test
 	t.execute_with(|| {
-		let response = Module::<TestRuntime>::query_eth_client(request_body).expect("got response");
+		let response =
+			Module::<TestRuntime>::query_eth_client(core::str::from_utf8(&MOCK_ETH_HTTP_URI).unwrap(), request_body)
+				.expect("got response");
 		assert_eq!(
 			serde_json::from_slice::<'_, TestRequest>(response.as_slice()).unwrap(),
 			TestRequest {
@@ -320,11 +431,1532 @@ fn eth_client_http_request() {
 	})
 }
 
+/// Mock eth-http endpoints for quorum tests
+const MOCK_ETH_HTTP_URIS: [&str; 3] = [
+	"http://ethereum-rpc-0.example.com",
+	"http://ethereum-rpc-1.example.com",
+	"http://ethereum-rpc-2.example.com",
+];
+
+/// Setup offchain test externalities with `MOCK_ETH_HTTP_URIS` configured
+fn offchain_ext_with_endpoints(
+	quorum: Option<u32>,
+) -> (
+	sp_io::TestExternalities,
+	std::sync::Arc<parking_lot::RwLock<testing::OffchainState>>,
+) {
+	let (offchain, offchain_state) = testing::TestOffchainExt::new();
+	let mut t = sp_io::TestExternalities::default();
+	t.register_extension(OffchainDbExt::new(offchain.clone()));
+	t.register_extension(OffchainWorkerExt::new(offchain));
+	t.execute_with(|| {
+		sp_io::offchain::local_storage_set(
+			StorageKind::PERSISTENT,
+			b"ETH_HTTP",
+			MOCK_ETH_HTTP_URIS.join(",").as_bytes(),
+		);
+		if let Some(quorum) = quorum {
+			sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, b"ETH_HTTP_QUORUM", &quorum.encode());
+		}
+	});
+	(t, offchain_state)
+}
+
+/// Mock an `eth_getBlockByNumber` response from `uri`, `None` mocks an invalid response
+fn mock_get_block_response(
+	offchain_state: &mut testing::OffchainState,
+	uri: &str,
+	block_number: u32,
+	response: Option<u64>,
+) {
+	let body = serde_json::to_string(&GetBlockRequest::for_number(1, block_number)).unwrap();
+	offchain_state.expect_request(testing::PendingRequest {
+		method: "POST".into(),
+		uri: uri.to_string(),
+		body: body.as_bytes().to_vec(),
+		response: Some(match response {
+			Some(timestamp) => format!(
+				r#"{{"jsonrpc":"2.0","id":1,"result":{{"number":"{:#x}","hash":null,"timestamp":"{:#x}"}}}}"#,
+				block_number, timestamp
+			)
+			.into_bytes(),
+			None => b"internal error".to_vec(),
+		}),
+		headers: vec![
+			("Content-Type".to_string(), "application/json".to_string()),
+			("Content-Length".to_string(), body.len().to_string()),
+		],
+		sent: true,
+		..Default::default()
+	});
+}
+
+#[test]
+fn eth_http_config_defaults_to_majority() {
+	let (mut t, _) = offchain_ext_with_endpoints(None);
+	t.execute_with(|| {
+		let (uris, quorum) = Module::<TestRuntime>::eth_http_config().expect("configured");
+		assert_eq!(
+			uris,
+			MOCK_ETH_HTTP_URIS
+				.iter()
+				.map(|uri| uri.as_bytes().to_vec())
+				.collect::<Vec<Vec<u8>>>()
+		);
+		assert_eq!(quorum, 2);
+	});
+}
+
+#[test]
+fn eth_http_config_invalid_quorum() {
+	let (mut t, _) = offchain_ext_with_endpoints(Some(4));
+	t.execute_with(|| {
+		assert!(matches!(
+			Module::<TestRuntime>::eth_http_config(),
+			Err(crate::Error::<TestRuntime>::OcwConfig)
+		));
+	});
+}
+
+#[test]
+fn quorum_response() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert!(matches!(
+			Module::<TestRuntime>::quorum_response(vec![Some(1), Some(2), Some(1)], 2),
+			Ok(Some(1))
+		));
+		assert!(matches!(
+			Module::<TestRuntime>::quorum_response(vec![None, Some(2), None], 2),
+			Ok(None)
+		));
+		assert!(matches!(
+			Module::<TestRuntime>::quorum_response(vec![Some(1), Some(2), Some(3)], 2),
+			Err(crate::Error::<TestRuntime>::EthRpcDisagreement)
+		));
+		assert!(matches!(
+			Module::<TestRuntime>::quorum_response(vec![Some(1)], 2),
+			Err(crate::Error::<TestRuntime>::HttpFetch)
+		));
+	});
+}
+
+#[test]
+fn get_block_with_endpoint_quorum() {
+	let (mut t, offchain_state) = offchain_ext_with_endpoints(Some(2));
+	{
+		let mut offchain_state = offchain_state.write();
+		mock_get_block_response(&mut offchain_state, MOCK_ETH_HTTP_URIS[0], 5, Some(1_000));
+		mock_get_block_response(&mut offchain_state, MOCK_ETH_HTTP_URIS[1], 5, None);
+		mock_get_block_response(&mut offchain_state, MOCK_ETH_HTTP_URIS[2], 5, Some(1_000));
+	}
+
+	t.execute_with(|| {
+		let block = Module::<TestRuntime>::get_block(LatestOrNumber::Number(5))
+			.expect("quorum reached")
+			.expect("block exists");
+		assert_eq!(block.timestamp.as_u64(), 1_000);
+
+		// the failing endpoint is recorded
+		let failures_key = [&b"ETH_HTTP_FAILURES"[..], MOCK_ETH_HTTP_URIS[1].as_bytes()].concat();
+		assert_eq!(
+			sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &failures_key),
+			Some(1_u64.encode())
+		);
+	});
+}
+
+#[test]
+fn get_block_endpoints_disagree() {
+	let (mut t, offchain_state) = offchain_ext_with_endpoints(Some(2));
+	{
+		let mut offchain_state = offchain_state.write();
+		mock_get_block_response(&mut offchain_state, MOCK_ETH_HTTP_URIS[0], 5, Some(1_000));
+		mock_get_block_response(&mut offchain_state, MOCK_ETH_HTTP_URIS[1], 5, None);
+		mock_get_block_response(&mut offchain_state, MOCK_ETH_HTTP_URIS[2], 5, Some(2_000));
+	}
+
+	t.execute_with(|| {
+		let err = Module::<TestRuntime>::get_block(LatestOrNumber::Number(5)).unwrap_err();
+		assert!(matches!(err, crate::Error::<TestRuntime>::EthRpcDisagreement));
+		assert_eq!(
+			Module::<TestRuntime>::data_provider_err(err),
+			EventClaimResult::DataProviderDisagreement
+		);
+	});
+}
+
+/// Mock unix time (seconds) for notarization tests, a multiple of `BUCKET_FACTOR_S`
+const MOCK_NOW_S: u64 = 1_000 * BUCKET_FACTOR_S;
+
+/// Setup offchain test externalities with `MOCK_ETH_HTTP_URI` configured
+fn offchain_ext() -> (
+	sp_io::TestExternalities,
+	std::sync::Arc<parking_lot::RwLock<testing::OffchainState>>,
+) {
+	let (offchain, offchain_state) = testing::TestOffchainExt::new();
+	let mut t = ExtBuilder::default().build();
+	t.register_extension(OffchainDbExt::new(offchain.clone()));
+	t.register_extension(OffchainWorkerExt::new(offchain));
+	t.execute_with(|| {
+		sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, b"ETH_HTTP", &MOCK_ETH_HTTP_URI);
+		MockUnixTime::set_now(MOCK_NOW_S);
+	});
+	(t, offchain_state)
+}
+
+/// Mock the JSON-RPC `result` returned from `MOCK_ETH_HTTP_URI` for `request`
+fn mock_eth_response<R: serde::Serialize>(offchain_state: &mut testing::OffchainState, request: R, result: String) {
+	let body = serde_json::to_string(&request).unwrap();
+	offchain_state.expect_request(testing::PendingRequest {
+		method: "POST".into(),
+		uri: core::str::from_utf8(&MOCK_ETH_HTTP_URI).unwrap().to_string(),
+		body: body.as_bytes().to_vec(),
+		response: Some(format!(r#"{{"jsonrpc":"2.0","id":1,"result":{}}}"#, result).into_bytes()),
+		headers: vec![
+			("Content-Type".to_string(), "application/json".to_string()),
+			("Content-Length".to_string(), body.len().to_string()),
+		],
+		sent: true,
+		..Default::default()
+	});
+}
+
+/// A mock receipt for `claim` included in `block_number`
+fn mock_tx_receipt(claim: &EventClaim, block_number: u64, status: u64) -> String {
+	format!(
+		r#"{{
+			"blockHash":"{block_hash:?}",
+			"blockNumber":"{block_number:#x}",
+			"contractAddress":null,
+			"cumulativeGasUsed":"0x1685c",
+			"from":"{from:?}",
+			"gasUsed":"0x1685c",
+			"logs":[{{
+				"address":"{to:?}",
+				"topics":["{topic:?}"],
+				"data":"0x{data}",
+				"blockHash":"{block_hash:?}",
+				"blockNumber":"{block_number:#x}",
+				"transactionHash":"{tx_hash:?}",
+				"transactionIndex":"0x0",
+				"logIndex":"0x0",
+				"removed":false
+			}}],
+			"logsBloom":"{logs_bloom:?}",
+			"status":"{status:#x}",
+			"to":"{to:?}",
+			"transactionHash":"{tx_hash:?}",
+			"transactionIndex":"0x0"
+		}}"#,
+		block_hash = H256::repeat_byte(1),
+		block_number = block_number,
+		from = H160::repeat_byte(2),
+		to = claim.contract_address,
+		topic = claim.event_signature,
+		data = sp_core::hexdisplay::HexDisplay::from(&claim.data),
+		tx_hash = claim.tx_hash,
+		logs_bloom = ethereum_types::Bloom::zero(),
+		status = status,
+	)
+}
+
+/// A mock block `block_number` with `timestamp` (seconds)
+fn mock_block(block_number: u64, timestamp: u64) -> String {
+	format!(
+		r#"{{"number":"{:#x}","hash":"{:?}","timestamp":"{:#x}"}}"#,
+		block_number,
+		H256::repeat_byte(1),
+		timestamp
+	)
+}
+
+/// A mock event claim
+fn mock_event_claim() -> EventClaim {
+	EventClaim {
+		tx_hash: H256::repeat_byte(3),
+		data: vec![1_u8; 96],
+		contract_address: H160::repeat_byte(4),
+		event_signature: H256::repeat_byte(5),
+	}
+}
+
+/// Mock the receipt, latest block and observed block responses needed to notarize `claim`
+fn mock_notarization_responses(
+	offchain_state: &mut testing::OffchainState,
+	claim: &EventClaim,
+	receipt: String,
+	(observed_block, latest_block): (u64, u64),
+	observed_timestamp: u64,
+) {
+	// request id is derived from the offchain random seed (zero in tests)
+	mock_eth_response(
+		offchain_state,
+		Some(GetTxReceiptRequest::new(claim.tx_hash, 0)),
+		receipt,
+	);
+	mock_eth_response(
+		offchain_state,
+		GetBlockRequest::latest(1),
+		mock_block(latest_block, MOCK_NOW_S),
+	);
+	mock_eth_response(
+		offchain_state,
+		GetBlockRequest::for_number(1, observed_block as u32),
+		mock_block(observed_block, observed_timestamp),
+	);
+}
+
+#[test]
+fn offchain_notarize_valid_event() {
+	let (mut t, offchain_state) = offchain_ext();
+	let claim = mock_event_claim();
+	mock_notarization_responses(
+		&mut offchain_state.write(),
+		&claim,
+		mock_tx_receipt(&claim, 10, 1),
+		(10, 13),
+		MOCK_NOW_S - 60,
+	);
+
+	t.execute_with(|| {
+		assert_eq!(
+			Module::<TestRuntime>::offchain_try_notarize_event(claim),
+			EventClaimResult::Valid
+		);
+	});
+}
+
+#[test]
+fn offchain_notarize_not_enough_confirmations() {
+	let (mut t, offchain_state) = offchain_ext();
+	let claim = mock_event_claim();
+	{
+		let mut offchain_state = offchain_state.write();
+		mock_eth_response(
+			&mut offchain_state,
+			Some(GetTxReceiptRequest::new(claim.tx_hash, 0)),
+			mock_tx_receipt(&claim, 10, 1),
+		);
+		mock_eth_response(
+			&mut offchain_state,
+			GetBlockRequest::latest(1),
+			mock_block(12, MOCK_NOW_S),
+		);
+	}
+
+	t.execute_with(|| {
+		assert_eq!(
+			Module::<TestRuntime>::offchain_try_notarize_event(claim),
+			EventClaimResult::NotEnoughConfirmations
+		);
+	});
+}
+
+#[test]
+fn offchain_notarize_expired_event() {
+	let (mut t, offchain_state) = offchain_ext();
+	let claim = mock_event_claim();
+	let deadline = t.execute_with(|| Module::<TestRuntime>::event_deadline_seconds());
+	mock_notarization_responses(
+		&mut offchain_state.write(),
+		&claim,
+		mock_tx_receipt(&claim, 10, 1),
+		(10, 13),
+		MOCK_NOW_S - deadline - 1,
+	);
+
+	t.execute_with(|| {
+		assert_eq!(
+			Module::<TestRuntime>::offchain_try_notarize_event(claim),
+			EventClaimResult::Expired
+		);
+	});
+}
+
+#[test]
+fn offchain_notarize_mismatched_receipts() {
+	let claim = mock_event_claim();
+	let mismatches = vec![
+		// claimed data differs from the observed event
+		(
+			mock_tx_receipt(
+				&EventClaim {
+					data: vec![2_u8; 96],
+					..claim.clone()
+				},
+				10,
+				1,
+			),
+			EventClaimResult::UnexpectedData,
+		),
+		// the tx failed
+		(mock_tx_receipt(&claim, 10, 0), EventClaimResult::TxStatusFailed),
+		// the tx called another contract
+		(
+			mock_tx_receipt(
+				&EventClaim {
+					contract_address: H160::repeat_byte(9),
+					..claim.clone()
+				},
+				10,
+				1,
+			),
+			EventClaimResult::UnexpectedContractAddress,
+		),
+		// the tx does not include the claimed event
+		(
+			mock_tx_receipt(
+				&EventClaim {
+					event_signature: H256::repeat_byte(9),
+					..claim.clone()
+				},
+				10,
+				1,
+			),
+			EventClaimResult::NoTxLogs,
+		),
+		// no such tx
+		("null".to_string(), EventClaimResult::NoTxLogs),
+	];
+
+	for (receipt, expected_result) in mismatches {
+		let (mut t, offchain_state) = offchain_ext();
+		mock_eth_response(
+			&mut offchain_state.write(),
+			Some(GetTxReceiptRequest::new(claim.tx_hash, 0)),
+			receipt,
+		);
+		t.execute_with(|| {
+			assert_eq!(
+				Module::<TestRuntime>::offchain_try_notarize_event(claim.clone()),
+				expected_result
+			);
+		});
+	}
+}
+
+/// Submit `claim` and a notarization with `result` from the only notary
+fn submit_claim_and_notarize(claim: &EventClaim, result: EventClaimResult) -> u64 {
+	crate::NotaryKeys::<TestRuntime>::put(vec![AuthorityId::from_slice(&[1_u8; 33])]);
+	let event_claim_id = Module::<TestRuntime>::submit_event_claim(
+		&claim.contract_address,
+		&claim.event_signature,
+		&claim.tx_hash,
+		&claim.data,
+	)
+	.expect("claim submitted");
+	assert!(Module::<TestRuntime>::submit_notarization(
+		frame_system::RawOrigin::None.into(),
+		NotarizationPayload {
+			event_claim_id,
+			authority_index: 0,
+			result,
+		},
+		Default::default(),
+	)
+	.is_ok());
+	event_claim_id
+}
+
+#[test]
+fn prunes_expired_events() {
+	ExtBuilder::default().build().execute_with(|| {
+		MockUnixTime::set_now(MOCK_NOW_S);
+		let claim = mock_event_claim();
+		submit_claim_and_notarize(&claim, EventClaimResult::Valid);
+		assert!(crate::ProcessedTxHashes::contains_key(claim.tx_hash));
+
+		// the bucket has not expired yet
+		let deadline = Module::<TestRuntime>::event_deadline_seconds();
+		MockUnixTime::set_now(MOCK_NOW_S + deadline - BUCKET_FACTOR_S);
+		Module::<TestRuntime>::on_initialize(CLAIM_PRUNING_INTERVAL as u64);
+		assert!(crate::ProcessedTxHashes::contains_key(claim.tx_hash));
+
+		MockUnixTime::set_now(MOCK_NOW_S + deadline);
+		Module::<TestRuntime>::on_initialize(2 * CLAIM_PRUNING_INTERVAL as u64);
+		assert!(!crate::ProcessedTxHashes::contains_key(claim.tx_hash));
+		assert_eq!(
+			crate::ProcessedTxBuckets::iter_prefix(MOCK_NOW_S / BUCKET_FACTOR_S).count(),
+			0
+		);
+	});
+}
+
+#[test]
+fn double_claim_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		let claim = mock_event_claim();
+		let event_claim_id = submit_claim_and_notarize(&claim, EventClaimResult::Valid);
+		assert!(crate::ProcessedTxHashes::contains_key(claim.tx_hash));
+		assert!(!crate::EventClaims::contains_key(event_claim_id));
+
+		assert_eq!(
+			Module::<TestRuntime>::submit_event_claim(
+				&claim.contract_address,
+				&claim.event_signature,
+				&claim.tx_hash,
+				&claim.data,
+			),
+			Err(crate::Error::<TestRuntime>::AlreadyNotarized.into())
+		);
+	});
+}
+
+#[test]
+fn invalid_claim_is_not_processed() {
+	ExtBuilder::default().build().execute_with(|| {
+		let claim = mock_event_claim();
+		let event_claim_id = submit_claim_and_notarize(&claim, EventClaimResult::UnexpectedData);
+
+		assert!(!crate::ProcessedTxHashes::contains_key(claim.tx_hash));
+		assert!(!crate::EventClaims::contains_key(event_claim_id));
+		assert_eq!(Module::<TestRuntime>::event_data(event_claim_id), None);
+	});
+}
+
+/// Submit a notarization of `event_claim_id` with `result` by notary `authority_index`
+fn notarize(event_claim_id: u64, authority_index: u16, result: EventClaimResult) -> sp_runtime::DispatchResult {
+	Module::<TestRuntime>::submit_notarization(
+		frame_system::RawOrigin::None.into(),
+		NotarizationPayload {
+			event_claim_id,
+			authority_index,
+			result,
+		},
+		Default::default(),
+	)
+}
+
+#[test]
+fn transient_notarization_does_not_invalidate_claim() {
+	ExtBuilder::default().build().execute_with(|| {
+		let claim = mock_event_claim();
+		let event_claim_id = submit_claim_and_notarize(&claim, EventClaimResult::DataProviderErr);
+		assert!(crate::EventClaims::contains_key(event_claim_id));
+
+		// the notary retries
+		assert!(notarize(event_claim_id, 0, EventClaimResult::NotEnoughConfirmations).is_ok());
+		assert!(crate::EventClaims::contains_key(event_claim_id));
+		assert_eq!(
+			Module::<TestRuntime>::event_notarizations(event_claim_id, AuthorityId::from_slice(&[1_u8; 33])),
+			Some(EventClaimResult::NotEnoughConfirmations)
+		);
+
+		assert!(notarize(event_claim_id, 0, EventClaimResult::Valid).is_ok());
+		assert!(!crate::EventClaims::contains_key(event_claim_id));
+		assert!(crate::ProcessedTxHashes::contains_key(claim.tx_hash));
+	});
+}
+
+#[test]
+fn notary_is_rewarded_once_per_claim() {
+	ExtBuilder::default().build().execute_with(|| {
+		let notaries: Vec<AuthorityId> = (1_u8..=3).map(|i| AuthorityId::from_slice(&[i; 33])).collect();
+		crate::NotaryKeys::<TestRuntime>::put(notaries);
+		let claim = mock_event_claim();
+		let event_claim_id = Module::<TestRuntime>::submit_event_claim(
+			&claim.contract_address,
+			&claim.event_signature,
+			&claim.tx_hash,
+			&claim.data,
+		)
+		.expect("claim submitted");
+
+		let rewards_before = MockRewardHandler::rewards().len();
+		assert!(notarize(event_claim_id, 0, EventClaimResult::DataProviderErr).is_ok());
+		assert!(notarize(event_claim_id, 0, EventClaimResult::NotEnoughConfirmations).is_ok());
+		assert!(notarize(event_claim_id, 0, EventClaimResult::DataProviderErr).is_ok());
+		assert_eq!(
+			MockRewardHandler::rewards()[rewards_before..].to_vec(),
+			vec![AccountId::from_slice(&[1_u8; 33])]
+		);
+	});
+}
+
+#[test]
+fn on_runtime_upgrade_sets_claim_expiries() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(5);
+		// claims queued before claim expiry was tracked
+		crate::EventClaims::insert(1, (H256::from_low_u64_be(1), 0));
+		crate::EventClaims::insert(2, (H256::from_low_u64_be(2), 0));
+		let expiry_block = 5 + Module::<TestRuntime>::claim_expiry_blocks() as u64;
+
+		let _ = <Module<TestRuntime> as frame_support::traits::OnRuntimeUpgrade>::on_runtime_upgrade();
+
+		assert!(crate::EventClaimExpiries::<TestRuntime>::contains_key(expiry_block, 1));
+		assert!(crate::EventClaimExpiries::<TestRuntime>::contains_key(expiry_block, 2));
+		assert_eq!(crate::StorageVersion::get(), crate::Releases::V2 as u32);
+
+		// the migration only runs once
+		crate::EventClaims::insert(3, (H256::from_low_u64_be(3), 0));
+		let _ = <Module<TestRuntime> as frame_support::traits::OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert!(!crate::EventClaimExpiries::<TestRuntime>::contains_key(expiry_block, 3));
+	});
+}
+
+#[test]
+fn on_runtime_upgrade_rebuckets_processed_tx_hashes() {
+	ExtBuilder::default().build().execute_with(|| {
+		// a realistic time, bucket indices are beyond the old `now % BUCKET_FACTOR_S` range
+		let now = 5_000 * BUCKET_FACTOR_S;
+		MockUnixTime::set_now(now);
+		crate::StorageVersion::put(crate::Releases::V1 as u32);
+		// tx hashes processed under the old bucket index
+		let tx_hashes: Vec<H256> = (1..=2).map(H256::from_low_u64_be).collect();
+		for (old_bucket_index, tx_hash) in vec![0, BUCKET_FACTOR_S - 1].into_iter().zip(tx_hashes.iter()) {
+			crate::ProcessedTxBuckets::insert(old_bucket_index, tx_hash, ());
+			crate::ProcessedTxHashes::insert(tx_hash, ());
+		}
+
+		let _ = <Module<TestRuntime> as frame_support::traits::OnRuntimeUpgrade>::on_runtime_upgrade();
+
+		assert_eq!(crate::StorageVersion::get(), crate::Releases::V2 as u32);
+		assert_eq!(crate::ProcessedTxBuckets::iter_prefix(0).count(), 0);
+		assert_eq!(crate::ProcessedTxBuckets::iter_prefix(BUCKET_FACTOR_S - 1).count(), 0);
+		for tx_hash in tx_hashes.iter() {
+			assert!(crate::ProcessedTxBuckets::contains_key(now / BUCKET_FACTOR_S, tx_hash));
+			assert!(crate::ProcessedTxHashes::contains_key(tx_hash));
+		}
+
+		// pruned with the current bucket
+		MockUnixTime::set_now(now + Module::<TestRuntime>::event_deadline_seconds());
+		Module::<TestRuntime>::on_initialize(CLAIM_PRUNING_INTERVAL as u64);
+		for tx_hash in tx_hashes.iter() {
+			assert!(!crate::ProcessedTxHashes::contains_key(tx_hash));
+		}
+	});
+}
+
+#[test]
+fn final_notarization_cannot_be_replaced() {
+	ExtBuilder::default().build().execute_with(|| {
+		let notaries: Vec<AuthorityId> = (1_u8..=3).map(|i| AuthorityId::from_slice(&[i; 33])).collect();
+		crate::NotaryKeys::<TestRuntime>::put(notaries);
+		let claim = mock_event_claim();
+		let event_claim_id = Module::<TestRuntime>::submit_event_claim(
+			&claim.contract_address,
+			&claim.event_signature,
+			&claim.tx_hash,
+			&claim.data,
+		)
+		.expect("claim submitted");
+
+		assert!(notarize(event_claim_id, 0, EventClaimResult::UnexpectedData).is_ok());
+		assert!(notarize(event_claim_id, 0, EventClaimResult::Valid).is_err());
+		assert_eq!(
+			Module::<TestRuntime>::event_claim_status(event_claim_id).map(|status| status.nay_count),
+			Some(1)
+		);
+	});
+}
+
+#[test]
+fn claim_expires_without_quorum() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert!(Module::<TestRuntime>::set_claim_expiry(frame_system::RawOrigin::Root.into(), 10).is_ok());
+		let claim = mock_event_claim();
+		let event_claim_id = submit_claim_and_notarize(&claim, EventClaimResult::DataProviderErr);
+		assert!(crate::EventClaimExpiries::<TestRuntime>::contains_key(
+			11,
+			event_claim_id
+		));
+
+		Module::<TestRuntime>::on_initialize(10);
+		assert!(crate::EventClaims::contains_key(event_claim_id));
+
+		Module::<TestRuntime>::on_initialize(11);
+		assert!(!crate::EventClaims::contains_key(event_claim_id));
+		assert_eq!(Module::<TestRuntime>::event_data(event_claim_id), None);
+		assert_eq!(
+			crate::EventNotarizations::<TestRuntime>::iter_prefix(event_claim_id).count(),
+			0
+		);
+		assert!(!crate::ProcessedTxHashes::contains_key(claim.tx_hash));
+		assert_eq!(
+			System::events().last().map(|record| record.event.clone()),
+			Some(Event::EthBridge(crate::Event::ClaimExpired(event_claim_id)))
+		);
+	});
+}
+
+#[test]
+fn verified_claim_does_not_expire() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let claim = mock_event_claim();
+		let event_claim_id = submit_claim_and_notarize(&claim, EventClaimResult::Valid);
+		let expiry_block = 1 + Module::<TestRuntime>::claim_expiry_blocks() as u64;
+
+		Module::<TestRuntime>::on_initialize(expiry_block);
+		assert!(!crate::EventClaimExpiries::<TestRuntime>::contains_key(
+			expiry_block,
+			event_claim_id
+		));
+		assert_eq!(
+			System::events().last().map(|record| record.event.clone()),
+			Some(Event::EthBridge(crate::Event::Verified(event_claim_id)))
+		);
+	});
+}
+
+#[test]
+fn offchain_retry_backoff() {
+	let (mut ext, _offchain_state) = offchain_ext();
+	ext.execute_with(|| {
+		let event_claim_id = 5;
+		assert!(Module::<TestRuntime>::offchain_is_retry_due(event_claim_id, 1));
+
+		Module::<TestRuntime>::offchain_note_retry(event_claim_id, 1);
+		assert!(!Module::<TestRuntime>::offchain_is_retry_due(event_claim_id, 5));
+		assert!(Module::<TestRuntime>::offchain_is_retry_due(event_claim_id, 6));
+
+		// the backoff doubles each attempt
+		Module::<TestRuntime>::offchain_note_retry(event_claim_id, 6);
+		assert!(!Module::<TestRuntime>::offchain_is_retry_due(event_claim_id, 15));
+		assert!(Module::<TestRuntime>::offchain_is_retry_due(event_claim_id, 16));
+
+		Module::<TestRuntime>::offchain_clear_retry(event_claim_id);
+		assert!(Module::<TestRuntime>::offchain_is_retry_due(event_claim_id, 7));
+	});
+}
+
+#[test]
+fn invalid_notarization_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		crate::NotaryKeys::<TestRuntime>::put(vec![AuthorityId::from_slice(&[1_u8; 33])]);
+		assert!(Module::<TestRuntime>::submit_notarization(
+			frame_system::RawOrigin::None.into(),
+			NotarizationPayload {
+				event_claim_id: 0,
+				authority_index: 1,
+				result: EventClaimResult::Valid,
+			},
+			Default::default(),
+		)
+		.is_err());
+	});
+}
+
+#[test]
+fn event_claim_status_tallies_notarizations() {
+	ExtBuilder::default().build().execute_with(|| {
+		let notaries: Vec<AuthorityId> = (1_u8..=3).map(|i| AuthorityId::from_slice(&[i; 33])).collect();
+		crate::NotaryKeys::<TestRuntime>::put(notaries.clone());
+		let claim = mock_event_claim();
+		let event_claim_id = Module::<TestRuntime>::submit_event_claim(
+			&claim.contract_address,
+			&claim.event_signature,
+			&claim.tx_hash,
+			&claim.data,
+		)
+		.expect("claim submitted");
+		assert_eq!(
+			Module::<TestRuntime>::event_claim_ids_by_tx_hash(claim.tx_hash),
+			vec![event_claim_id]
+		);
+		assert!(Module::<TestRuntime>::event_claim_ids_by_tx_hash(H256::repeat_byte(9)).is_empty());
+
+		for (authority_index, result) in vec![
+			(0, EventClaimResult::Valid),
+			(1, EventClaimResult::NotEnoughConfirmations),
+		] {
+			assert!(Module::<TestRuntime>::submit_notarization(
+				frame_system::RawOrigin::None.into(),
+				NotarizationPayload {
+					event_claim_id,
+					authority_index,
+					result,
+				},
+				Default::default(),
+			)
+			.is_ok());
+		}
+
+		let mut status = Module::<TestRuntime>::event_claim_status(event_claim_id).expect("claim is pending");
+		status.notarizations.sort_by_key(|(id, _)| id.clone());
+		assert_eq!(
+			status,
+			EventClaimStatus {
+				event_claim_id,
+				tx_hash: claim.tx_hash,
+				event_type: (claim.contract_address, claim.event_signature),
+				event_data: Some(claim.data.clone()),
+				notarizations: vec![
+					(notaries[0].clone(), EventClaimResult::Valid),
+					(notaries[1].clone(), EventClaimResult::NotEnoughConfirmations),
+				],
+				yay_count: 1,
+				nay_count: 0,
+				transient_count: 1,
+				notary_count: 3,
+			}
+		);
+		assert!(!Module::<TestRuntime>::is_tx_hash_processed(claim.tx_hash));
+
+		// the final yay verifies the claim
+		assert!(Module::<TestRuntime>::submit_notarization(
+			frame_system::RawOrigin::None.into(),
+			NotarizationPayload {
+				event_claim_id,
+				authority_index: 2,
+				result: EventClaimResult::Valid,
+			},
+			Default::default(),
+		)
+		.is_ok());
+		assert_eq!(Module::<TestRuntime>::event_claim_status(event_claim_id), None);
+		assert!(Module::<TestRuntime>::event_claim_ids_by_tx_hash(claim.tx_hash).is_empty());
+		assert!(Module::<TestRuntime>::is_tx_hash_processed(claim.tx_hash));
+	});
+}
+
+/// Set 3 notaries and submit a claim on tx `tx_index`, returns the claim Id and notary keys
+fn setup_notaries_and_claim(tx_index: u64) -> (u64, Vec<AuthorityId>) {
+	let notaries: Vec<AuthorityId> = (1_u8..=3).map(|i| AuthorityId::from_slice(&[i; 33])).collect();
+	crate::NotaryKeys::<TestRuntime>::put(notaries.clone());
+	let claim = mock_event_claim();
+	let event_claim_id = Module::<TestRuntime>::submit_event_claim(
+		&claim.contract_address,
+		&claim.event_signature,
+		&H256::from_low_u64_be(tx_index),
+		&claim.data,
+	)
+	.expect("claim submitted");
+	(event_claim_id, notaries)
+}
+
+#[test]
+fn late_notarization_counts_participation() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (event_claim_id, notaries) = setup_notaries_and_claim(1);
+		assert!(notarize(event_claim_id, 0, EventClaimResult::Valid).is_ok());
+		assert!(notarize(event_claim_id, 1, EventClaimResult::Valid).is_ok());
+		assert!(!crate::EventClaims::contains_key(event_claim_id));
+		assert_eq!(
+			Module::<TestRuntime>::session_claim_outcomes(event_claim_id),
+			Some((true, 0))
+		);
+
+		// the last notary votes after the claim was notarized
+		assert!(notarize(event_claim_id, 2, EventClaimResult::UnexpectedData).is_ok());
+		assert!(notarize(event_claim_id, 2, EventClaimResult::Valid).is_err());
+
+		let participation = |votes, disagreements| NotaryParticipation { votes, disagreements };
+		assert_eq!(
+			Module::<TestRuntime>::session_notary_participation(),
+			(
+				1,
+				vec![
+					(notaries[0].clone(), participation(1, 0)),
+					(notaries[1].clone(), participation(1, 0)),
+					(notaries[2].clone(), participation(1, 1)),
+				]
+			)
+		);
+	});
+}
+
+#[test]
+fn transient_notarization_is_not_disagreement() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (event_claim_id, notaries) = setup_notaries_and_claim(1);
+		assert!(notarize(event_claim_id, 2, EventClaimResult::DataProviderErr).is_ok());
+		assert!(notarize(event_claim_id, 0, EventClaimResult::Valid).is_ok());
+		assert!(notarize(event_claim_id, 1, EventClaimResult::Valid).is_ok());
+
+		assert_eq!(
+			Module::<TestRuntime>::notary_participations(&notaries[2]),
+			NotaryParticipation {
+				votes: 1,
+				disagreements: 0,
+			}
+		);
+	});
+}
+
+#[test]
+fn notaries_missing_claims_are_reported() {
+	ExtBuilder::default().build().execute_with(|| {
+		// too few claims to judge misconduct
+		for tx_index in 0..crate::MIN_CLAIMS_FOR_MISCONDUCT as u64 - 1 {
+			let (event_claim_id, _notaries) = setup_notaries_and_claim(tx_index);
+			assert!(notarize(event_claim_id, 0, EventClaimResult::Valid).is_ok());
+			assert!(notarize(event_claim_id, 1, EventClaimResult::Valid).is_ok());
+		}
+		Module::<TestRuntime>::on_before_session_ending();
+		assert!(MockOffenceReporter::offences().is_empty());
+		assert_eq!(Module::<TestRuntime>::session_notary_participation().0, 0);
+
+		let mut notaries = vec![];
+		for tx_index in 100..100 + crate::MIN_CLAIMS_FOR_MISCONDUCT as u64 {
+			let (event_claim_id, keys) = setup_notaries_and_claim(tx_index);
+			assert!(notarize(event_claim_id, 0, EventClaimResult::Valid).is_ok());
+			assert!(notarize(event_claim_id, 1, EventClaimResult::Valid).is_ok());
+			notaries = keys;
+		}
+		System::set_block_number(crate::MISS_GRACE_BLOCKS as u64 + 10);
+		Module::<TestRuntime>::on_before_session_ending();
+
+		let offender = AccountId::from_slice(notaries[2].as_ref());
+		assert_eq!(
+			MockOffenceReporter::offences(),
+			vec![(1, vec![(offender.clone(), offender)])]
+		);
+		// tracking is reset for the next session
+		assert_eq!(Module::<TestRuntime>::session_notary_participation().0, 0);
+		assert_eq!(
+			Module::<TestRuntime>::notary_participations(&notaries[0]),
+			NotaryParticipation::default()
+		);
+		assert_eq!(crate::EventNotarizations::<TestRuntime>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn notaries_voting_after_quorum_are_not_missing() {
+	ExtBuilder::default().build().execute_with(|| {
+		let mut notaries = vec![];
+		for tx_index in 0..crate::MIN_CLAIMS_FOR_MISCONDUCT as u64 {
+			System::set_block_number(1);
+			let (event_claim_id, keys) = setup_notaries_and_claim(tx_index);
+			assert!(notarize(event_claim_id, 0, EventClaimResult::Valid).is_ok());
+			assert!(notarize(event_claim_id, 1, EventClaimResult::Valid).is_ok());
+			assert!(!crate::EventClaims::contains_key(event_claim_id));
+			notaries = keys;
+
+			// the last notary's worker picks up the notarized claim and votes a block later
+			System::set_block_number(2);
+			let late_claims: Vec<u64> = Module::<TestRuntime>::offchain_claims_to_notarize(notaries[2].clone())
+				.map(|(event_claim_id, _claim, _header)| event_claim_id)
+				.collect();
+			assert_eq!(late_claims, vec![event_claim_id]);
+			assert!(notarize(event_claim_id, 2, EventClaimResult::Valid).is_ok());
+			assert_eq!(
+				Module::<TestRuntime>::offchain_claims_to_notarize(notaries[2].clone()).count(),
+				0
+			);
+		}
+		System::set_block_number(crate::MISS_GRACE_BLOCKS as u64 + 10);
+		Module::<TestRuntime>::on_before_session_ending();
+
+		assert!(MockOffenceReporter::offences().is_empty());
+		// notarized claims are kept only for the session
+		assert_eq!(crate::SessionEventClaims::iter().count(), 0);
+		assert_eq!(
+			Module::<TestRuntime>::offchain_claims_to_notarize(notaries[2].clone()).count(),
+			0
+		);
+	});
+}
+
+#[test]
+fn notaries_disagreeing_with_claims_are_reported() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert!(Module::<TestRuntime>::set_misconduct_thresholds(
+			frame_system::RawOrigin::Root.into(),
+			Percent::from_percent(100),
+			Percent::from_percent(50),
+		)
+		.is_ok());
+		let mut notaries = vec![];
+		for tx_index in 0..crate::MIN_CLAIMS_FOR_MISCONDUCT as u64 {
+			let (event_claim_id, keys) = setup_notaries_and_claim(tx_index);
+			assert!(notarize(event_claim_id, 2, EventClaimResult::UnexpectedData).is_ok());
+			assert!(notarize(event_claim_id, 0, EventClaimResult::Valid).is_ok());
+			assert!(notarize(event_claim_id, 1, EventClaimResult::Valid).is_ok());
+			notaries = keys;
+		}
+		System::set_block_number(crate::MISS_GRACE_BLOCKS as u64 + 10);
+		Module::<TestRuntime>::on_before_session_ending();
+
+		let offender = AccountId::from_slice(notaries[2].as_ref());
+		assert_eq!(
+			MockOffenceReporter::offences(),
+			vec![(1, vec![(offender.clone(), offender)])]
+		);
+	});
+}
+
+#[test]
+fn claims_notarized_near_session_end_are_not_missed() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(10);
+		let mut notaries = vec![];
+		for tx_index in 0..crate::MIN_CLAIMS_FOR_MISCONDUCT as u64 {
+			let (event_claim_id, keys) = setup_notaries_and_claim(tx_index);
+			assert!(notarize(event_claim_id, 0, EventClaimResult::Valid).is_ok());
+			assert!(notarize(event_claim_id, 1, EventClaimResult::Valid).is_ok());
+			assert!(notarize(event_claim_id, 2, EventClaimResult::Valid).is_ok());
+			notaries = keys;
+		}
+		// the last notary has no chance to vote on claims notarized right before the session ends
+		System::set_block_number(10 + crate::MISS_GRACE_BLOCKS as u64);
+		for tx_index in 100..100 + crate::MIN_CLAIMS_FOR_MISCONDUCT as u64 {
+			let (event_claim_id, _keys) = setup_notaries_and_claim(tx_index);
+			assert!(notarize(event_claim_id, 0, EventClaimResult::Valid).is_ok());
+			assert!(notarize(event_claim_id, 1, EventClaimResult::Valid).is_ok());
+		}
+		Module::<TestRuntime>::on_before_session_ending();
+		assert!(MockOffenceReporter::offences().is_empty());
+		assert_eq!(crate::EventNotarizations::<TestRuntime>::iter().count(), 0);
+
+		// no votes at all on judged claims are still missed
+		for tx_index in 200..200 + crate::MIN_CLAIMS_FOR_MISCONDUCT as u64 {
+			let (event_claim_id, _keys) = setup_notaries_and_claim(tx_index);
+			assert!(notarize(event_claim_id, 0, EventClaimResult::Valid).is_ok());
+			assert!(notarize(event_claim_id, 1, EventClaimResult::Valid).is_ok());
+		}
+		System::set_block_number(20 + 2 * crate::MISS_GRACE_BLOCKS as u64);
+		Module::<TestRuntime>::on_before_session_ending();
+		let offender = AccountId::from_slice(notaries[2].as_ref());
+		assert_eq!(
+			MockOffenceReporter::offences(),
+			vec![(1, vec![(offender.clone(), offender)])]
+		);
+	});
+}
+
+#[test]
+fn session_claim_tracking_is_bounded() {
+	ExtBuilder::default().build().execute_with(|| {
+		crate::SessionClaimCount::put(crate::MAX_SESSION_CLAIMS);
+		let (event_claim_id, notaries) = setup_notaries_and_claim(1);
+		assert!(notarize(event_claim_id, 0, EventClaimResult::Valid).is_ok());
+		assert!(notarize(event_claim_id, 1, EventClaimResult::Valid).is_ok());
+
+		// the claim is processed but not tracked
+		assert!(!crate::EventClaims::contains_key(event_claim_id));
+		assert_eq!(Module::<TestRuntime>::session_claim_outcomes(event_claim_id), None);
+		assert_eq!(
+			crate::EventNotarizations::<TestRuntime>::iter_prefix(event_claim_id).count(),
+			0
+		);
+		assert_eq!(
+			Module::<TestRuntime>::notary_participations(&notaries[0]),
+			NotaryParticipation::default()
+		);
+		// late votes are rejected
+		assert!(notarize(event_claim_id, 2, EventClaimResult::Valid).is_err());
+	});
+}
+
+/// Unix time (seconds) shortly after the fixture deposit block
+const DEPOSIT_BLOCK_NOW_S: u64 = 1_630_000_000 + 60;
+/// Number of the fixture deposit block
+const DEPOSIT_BLOCK_NUMBER: u64 = 13_000_000;
+
+/// A proof of fixture deposit block tx `tx_index`
+fn mock_receipt_proof(tx_index: u64) -> EthReceiptProof {
+	let to_proof = |nodes: &[&[u8]]| nodes.iter().map(|node| node.to_vec()).collect();
+	let (tx_proof, receipt_proof): (&[&[u8]], &[&[u8]]) = match tx_index {
+		0 => (&DEPOSIT_BLOCK_TX_PROOF_0, &DEPOSIT_BLOCK_RECEIPT_PROOF_0),
+		1 => (&DEPOSIT_BLOCK_TX_PROOF_1, &DEPOSIT_BLOCK_RECEIPT_PROOF_1),
+		2 => (&DEPOSIT_BLOCK_TX_PROOF_2, &DEPOSIT_BLOCK_RECEIPT_PROOF_2),
+		_ => (&DEPOSIT_BLOCK_TX_PROOF_3, &DEPOSIT_BLOCK_RECEIPT_PROOF_3),
+	};
+	EthReceiptProof {
+		header: DEPOSIT_BLOCK_HEADER.to_vec(),
+		tx_index,
+		tx_proof: to_proof(tx_proof),
+		receipt_proof: to_proof(receipt_proof),
+	}
+}
+
+/// Submit a claim for the event logged by fixture deposit block tx `tx_index`
+fn submit_claim_with_proof(tx_index: u64) -> Result<u64, sp_runtime::DispatchError> {
+	let claim = mock_event_claim();
+	Module::<TestRuntime>::submit_event_claim_with_proof(
+		&claim.contract_address,
+		&claim.event_signature,
+		&claim.data,
+		&mock_receipt_proof(tx_index),
+	)
+}
+
+#[test]
+fn claim_with_proof_awaits_header_notarization() {
+	ExtBuilder::default().build().execute_with(|| {
+		MockUnixTime::set_now(DEPOSIT_BLOCK_NOW_S);
+		crate::NotaryKeys::<TestRuntime>::put(vec![AuthorityId::from_slice(&[1_u8; 33])]);
+		let event_claim_id = submit_claim_with_proof(1).expect("claim submitted");
+
+		// the tx hash is derived from the proof
+		let tx_hash = H256::from(sp_io::hashing::keccak_256(DEPOSIT_BLOCK_TXS[1]));
+		assert_eq!(crate::EventClaims::get(event_claim_id).0, tx_hash);
+		assert_eq!(
+			Module::<TestRuntime>::event_claim_headers(event_claim_id),
+			Some((DEPOSIT_BLOCK_NUMBER, DEPOSIT_BLOCK_HASH.into()))
+		);
+
+		assert!(Module::<TestRuntime>::submit_notarization(
+			frame_system::RawOrigin::None.into(),
+			NotarizationPayload {
+				event_claim_id,
+				authority_index: 0,
+				result: EventClaimResult::Valid,
+			},
+			Default::default(),
+		)
+		.is_ok());
+		assert!(crate::ProcessedTxHashes::contains_key(tx_hash));
+		assert!(!crate::EventClaims::contains_key(event_claim_id));
+		assert_eq!(Module::<TestRuntime>::event_claim_headers(event_claim_id), None);
+		// the notarized header is tracked
+		assert_eq!(
+			Module::<TestRuntime>::eth_headers(DEPOSIT_BLOCK_NUMBER),
+			Some(DEPOSIT_BLOCK_HASH.into())
+		);
+	});
+}
+
+#[test]
+fn claim_with_proof_against_known_header_is_verified() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		MockUnixTime::set_now(DEPOSIT_BLOCK_NOW_S);
+		crate::EthHeaders::insert(DEPOSIT_BLOCK_NUMBER, H256::from(DEPOSIT_BLOCK_HASH));
+		let event_claim_id = submit_claim_with_proof(1).expect("claim submitted");
+
+		let tx_hash = H256::from(sp_io::hashing::keccak_256(DEPOSIT_BLOCK_TXS[1]));
+		assert!(crate::ProcessedTxHashes::contains_key(tx_hash));
+		assert!(!crate::EventClaims::contains_key(event_claim_id));
+		assert_eq!(Module::<TestRuntime>::event_claim_headers(event_claim_id), None);
+		assert_eq!(
+			System::events().last().map(|record| record.event.clone()),
+			Some(Event::EthBridge(crate::Event::Verified(event_claim_id)))
+		);
+
+		// the tx may not be claimed again
+		assert_eq!(
+			submit_claim_with_proof(1),
+			Err(crate::Error::<TestRuntime>::AlreadyNotarized.into())
+		);
+	});
+}
+
+#[test]
+fn claim_with_invalid_proof_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		MockUnixTime::set_now(DEPOSIT_BLOCK_NOW_S);
+		let claim = mock_event_claim();
+		let submit = |contract_address: &H160, event_data: &[u8], proof: &EthReceiptProof| {
+			Module::<TestRuntime>::submit_event_claim_with_proof(
+				contract_address,
+				&claim.event_signature,
+				event_data,
+				proof,
+			)
+		};
+		let invalid_proof = Err(crate::Error::<TestRuntime>::InvalidProof.into());
+		let unexpected_event = Err(crate::Error::<TestRuntime>::UnexpectedEvent.into());
+
+		// proofs of another tx
+		let mut proof = mock_receipt_proof(1);
+		proof.tx_index = 2;
+		assert_eq!(submit(&claim.contract_address, &claim.data, &proof), invalid_proof);
+		let mut proof = mock_receipt_proof(1);
+		proof.receipt_proof = mock_receipt_proof(2).receipt_proof;
+		assert_eq!(submit(&claim.contract_address, &claim.data, &proof), invalid_proof);
+		// tampered header transactions root
+		let mut proof = mock_receipt_proof(1);
+		proof.header[130] ^= 1;
+		assert_eq!(submit(&claim.contract_address, &claim.data, &proof), invalid_proof);
+
+		// the tx did not log the claimed event
+		assert_eq!(
+			submit(&claim.contract_address, &[2_u8; 96], &mock_receipt_proof(1)),
+			unexpected_event
+		);
+		assert_eq!(
+			submit(&H160::repeat_byte(9), &claim.data, &mock_receipt_proof(1)),
+			unexpected_event
+		);
+		// the tx was sent to another contract
+		assert_eq!(
+			submit(&claim.contract_address, &claim.data, &mock_receipt_proof(2)),
+			unexpected_event
+		);
+		// the tx failed
+		assert_eq!(
+			submit(&claim.contract_address, &claim.data, &mock_receipt_proof(3)),
+			unexpected_event
+		);
+		assert_eq!(Module::<TestRuntime>::next_event_claim_id(), 0);
+	});
+}
+
+#[test]
+fn expired_claim_with_proof_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		let deadline = Module::<TestRuntime>::event_deadline_seconds();
+		MockUnixTime::set_now(DEPOSIT_BLOCK_NOW_S - 60 + deadline + 1);
+		assert_eq!(
+			submit_claim_with_proof(1),
+			Err(crate::Error::<TestRuntime>::EventExpired.into())
+		);
+	});
+}
+
+#[test]
+fn prunes_expired_eth_headers() {
+	ExtBuilder::default().build().execute_with(|| {
+		MockUnixTime::set_now(MOCK_NOW_S);
+		Module::<TestRuntime>::note_eth_header(DEPOSIT_BLOCK_NUMBER, DEPOSIT_BLOCK_HASH.into());
+
+		let deadline = Module::<TestRuntime>::event_deadline_seconds();
+		MockUnixTime::set_now(MOCK_NOW_S + deadline - BUCKET_FACTOR_S);
+		Module::<TestRuntime>::on_initialize(CLAIM_PRUNING_INTERVAL as u64);
+		assert!(Module::<TestRuntime>::eth_headers(DEPOSIT_BLOCK_NUMBER).is_some());
+
+		MockUnixTime::set_now(MOCK_NOW_S + deadline);
+		Module::<TestRuntime>::on_initialize(2 * CLAIM_PRUNING_INTERVAL as u64);
+		assert!(Module::<TestRuntime>::eth_headers(DEPOSIT_BLOCK_NUMBER).is_none());
+		assert_eq!(
+			crate::EthHeaderBuckets::iter_prefix(MOCK_NOW_S / BUCKET_FACTOR_S).count(),
+			0
+		);
+	});
+}
+
+/// A mock block `block_number` with `hash` and `timestamp` (seconds)
+fn mock_block_with_hash(block_number: u64, hash: H256, timestamp: u64) -> String {
+	format!(
+		r#"{{"number":"{:#x}","hash":"{:?}","timestamp":"{:#x}"}}"#,
+		block_number, hash, timestamp
+	)
+}
+
+#[test]
+fn offchain_notarize_header() {
+	let block_hash = H256::from(DEPOSIT_BLOCK_HASH);
+	let cases = vec![
+		(block_hash, DEPOSIT_BLOCK_NUMBER + 3, EventClaimResult::Valid),
+		(
+			H256::repeat_byte(1),
+			DEPOSIT_BLOCK_NUMBER + 3,
+			EventClaimResult::UnexpectedBlockHash,
+		),
+	];
+	for (observed_hash, latest_block, expected_result) in cases {
+		let (mut t, offchain_state) = offchain_ext();
+		{
+			let mut offchain_state = offchain_state.write();
+			mock_eth_response(
+				&mut offchain_state,
+				GetBlockRequest::latest(1),
+				mock_block(latest_block, DEPOSIT_BLOCK_NOW_S),
+			);
+			mock_eth_response(
+				&mut offchain_state,
+				GetBlockRequest::for_number(1, DEPOSIT_BLOCK_NUMBER as u32),
+				mock_block_with_hash(DEPOSIT_BLOCK_NUMBER, observed_hash, DEPOSIT_BLOCK_NOW_S - 60),
+			);
+		}
+		t.execute_with(|| {
+			MockUnixTime::set_now(DEPOSIT_BLOCK_NOW_S);
+			assert_eq!(
+				Module::<TestRuntime>::offchain_try_notarize_header(DEPOSIT_BLOCK_NUMBER, block_hash),
+				expected_result
+			);
+		});
+	}
+}
+
+#[test]
+fn offchain_notarize_header_not_enough_confirmations() {
+	let (mut t, offchain_state) = offchain_ext();
+	mock_eth_response(
+		&mut offchain_state.write(),
+		GetBlockRequest::latest(1),
+		mock_block(DEPOSIT_BLOCK_NUMBER + 2, DEPOSIT_BLOCK_NOW_S),
+	);
+	t.execute_with(|| {
+		MockUnixTime::set_now(DEPOSIT_BLOCK_NOW_S);
+		assert_eq!(
+			Module::<TestRuntime>::offchain_try_notarize_header(DEPOSIT_BLOCK_NUMBER, DEPOSIT_BLOCK_HASH.into()),
+			EventClaimResult::NotEnoughConfirmations
+		);
+	});
+}
+
+/// Set `signers` as the notary keys, returning the notary key pairs
+fn setup_notary_pairs(signers: &[&str]) -> Vec<ecdsa::Pair> {
+	let pairs: Vec<ecdsa::Pair> = signers
+		.iter()
+		.map(|s| <ecdsa::Pair as sp_core::Pair>::from_string(s, None).unwrap())
+		.collect();
+	crate::NotaryKeys::<TestRuntime>::put(
+		pairs
+			.iter()
+			.map(|p| AuthorityId::from(sp_core::Pair::public(p)))
+			.collect::<Vec<AuthorityId>>(),
+	);
+	pairs
+}
+
+/// A signing request message with `params` for `event_id` of the active notary set
+fn mock_signing_request(params: &[u8], event_id: u64) -> Vec<u8> {
+	[
+		params,
+		&EthAbiCodec::encode(&Module::<TestRuntime>::notary_set_id())[..],
+		&EthAbiCodec::encode(&event_id)[..],
+	]
+	.concat()
+}
+
+/// Return `message` and its signature by `pair`
+fn mock_witness(pair: &ecdsa::Pair, message: Vec<u8>) -> (Vec<u8>, Signature) {
+	let signature = pair.sign_prehashed(&sp_core::keccak_256(&message));
+	(message, signature)
+}
+
+/// An equivocation proof with `pair` signing requests with `first` and `second` params for event 1 of the
+/// active notary set
+fn mock_equivocation_proof(pair: &ecdsa::Pair, first: &[u8], second: &[u8]) -> EquivocationProof<AuthorityId> {
+	EquivocationProof {
+		authority_id: AuthorityId::from(sp_core::Pair::public(pair)),
+		validator_set_id: Module::<TestRuntime>::notary_set_id(),
+		event_id: 1,
+		first: mock_witness(pair, mock_signing_request(first, 1)),
+		second: mock_witness(pair, mock_signing_request(second, 1)),
+	}
+}
+
+#[test]
+fn report_equivocation() {
+	ExtBuilder::default().build().execute_with(|| {
+		let pairs = setup_notary_pairs(&["//Alice", "//Bob", "//Charlie"]);
+		let proof = mock_equivocation_proof(&pairs[1], b"hello", b"world");
+
+		assert_ok!(Module::<TestRuntime>::report_equivocation(
+			frame_system::RawOrigin::None.into(),
+			proof.clone()
+		));
+		let offender = AccountId::from_slice(proof.authority_id.as_ref());
+		assert_eq!(
+			MockOffenceReporter::equivocations(),
+			vec![(1, (offender.clone(), offender))]
+		);
+
+		// the same equivocation can't be reported twice
+		assert_noop!(
+			Module::<TestRuntime>::report_equivocation(frame_system::RawOrigin::None.into(), proof),
+			Error::<TestRuntime>::DuplicateEquivocationReport
+		);
+	});
+}
+
 #[test]
-fn prunes_expired_events() {}
+fn report_equivocation_invalid_proof() {
+	ExtBuilder::default().build().execute_with(|| {
+		let pairs = setup_notary_pairs(&["//Alice", "//Bob", "//Charlie"]);
+		let report = |proof| Module::<TestRuntime>::report_equivocation(frame_system::RawOrigin::None.into(), proof);
+
+		// same digest signed twice is not an equivocation
+		assert_noop!(
+			report(mock_equivocation_proof(&pairs[0], b"hello", b"hello")),
+			Error::<TestRuntime>::InvalidEquivocationProof
+		);
+
+		// a signature not from the reported authority
+		let mut proof = mock_equivocation_proof(&pairs[0], b"hello", b"world");
+		proof.second.1 = pairs[1].sign_prehashed(&sp_core::keccak_256(&proof.second.0));
+		assert_noop!(report(proof), Error::<TestRuntime>::InvalidEquivocationProof);
+
+		// an inactive validator set
+		let mut proof = mock_equivocation_proof(&pairs[0], b"hello", b"world");
+		proof.validator_set_id += 1;
+		assert_noop!(report(proof), Error::<TestRuntime>::InvalidEquivocationProof);
+
+		// not a notary
+		let mallory = <ecdsa::Pair as sp_core::Pair>::from_string("//Mallory", None).unwrap();
+		assert_noop!(
+			report(mock_equivocation_proof(&mallory, b"hello", b"world")),
+			Error::<TestRuntime>::InvalidEquivocationProof
+		);
+		assert!(MockOffenceReporter::equivocations().is_empty());
+	});
+}
 
 #[test]
-fn double_claim_fails() {}
+fn report_equivocation_relabelled_witnesses() {
+	ExtBuilder::default().build().execute_with(|| {
+		let pairs = setup_notary_pairs(&["//Alice", "//Bob", "//Charlie"]);
+		let report = |proof| Module::<TestRuntime>::report_equivocation(frame_system::RawOrigin::None.into(), proof);
+
+		// honest witnesses of events 1 and 2, relabelled as conflicting witnesses of event 1
+		let mut proof = mock_equivocation_proof(&pairs[0], b"hello", b"world");
+		proof.second = mock_witness(&pairs[0], mock_signing_request(b"world", 2));
+		assert_noop!(report(proof), Error::<TestRuntime>::InvalidEquivocationProof);
+
+		// relabelled as witnesses of event 2
+		let mut proof = mock_equivocation_proof(&pairs[0], b"hello", b"world");
+		proof.event_id = 2;
+		assert_noop!(report(proof), Error::<TestRuntime>::InvalidEquivocationProof);
+
+		// witnesses of another validator set
+		let mut proof = mock_equivocation_proof(&pairs[0], b"hello", b"world");
+		proof.first = mock_witness(
+			&pairs[0],
+			[
+				&b"hello"[..],
+				&EthAbiCodec::encode(&5_u64)[..],
+				&EthAbiCodec::encode(&1_u64)[..],
+			]
+			.concat(),
+		);
+		assert_noop!(report(proof), Error::<TestRuntime>::InvalidEquivocationProof);
+
+		// messages which aren't signing requests e.g. a batch root
+		let mut proof = mock_equivocation_proof(&pairs[0], b"hello", b"world");
+		proof.first = mock_witness(&pairs[0], b"hello".to_vec());
+		assert_noop!(report(proof), Error::<TestRuntime>::InvalidEquivocationProof);
+
+		assert!(MockOffenceReporter::equivocations().is_empty());
+	});
+}
+
+/// Return the Ethy consensus logs deposited in the current block
+fn ethy_logs() -> Vec<ConsensusLog<AccountId>> {
+	frame_system::Pallet::<TestRuntime>::digest()
+		.logs
+		.iter()
+		.filter_map(|log| log.as_consensus())
+		.filter(|(engine_id, _)| *engine_id == ETHY_ENGINE_ID)
+		.filter_map(|(_, data)| ConsensusLog::<AccountId>::decode(&mut &data[..]).ok())
+		.collect()
+}
+
+#[test]
+fn event_proofs_are_signed_individually() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(Module::<TestRuntime>::generate_event_proof(&5_u64, true), Ok(0));
+		assert_eq!(Module::<TestRuntime>::generate_event_proof(&6_u64, true), Ok(1));
+		Module::<TestRuntime>::on_finalize(1);
+
+		let logs = ethy_logs();
+		assert_eq!(logs.len(), 2);
+		assert!(matches!(logs[0], ConsensusLog::OpaqueSigningRequest((_, 0))));
+		assert!(matches!(logs[1], ConsensusLog::OpaqueSigningRequest((_, 1))));
+	});
+}
 
 #[test]
-fn invalid_notarization_fails() {}
+fn event_proofs_are_batched() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Module::<TestRuntime>::set_batch_signing(
+			frame_system::RawOrigin::Root.into(),
+			true
+		));
+		assert_eq!(Module::<TestRuntime>::generate_event_proof(&5_u64, true), Ok(0));
+		assert_eq!(Module::<TestRuntime>::generate_event_proof(&6_u64, true), Ok(1));
+		assert!(ethy_logs().is_empty());
+
+		Module::<TestRuntime>::on_finalize(1);
+
+		let validator_set_id = Module::<TestRuntime>::validator_set().id;
+		let message = |value: u64, event_id: u64| {
+			[
+				EthAbiCodec::encode(&value),
+				EthAbiCodec::encode(&validator_set_id),
+				EthAbiCodec::encode(&event_id),
+			]
+			.concat()
+		};
+		match &ethy_logs()[..] {
+			[ConsensusLog::BatchSigningRequest((messages, batch_id))] => {
+				assert_eq!(*batch_id, 2);
+				assert_eq!(*messages, vec![(0, message(5, 0)), (1, message(6, 1))]);
+			}
+			_ => panic!("expected a single batch signing request"),
+		}
+		assert_eq!(Module::<TestRuntime>::next_proof_id(), 3);
+		assert!(Module::<TestRuntime>::pending_batch_messages().is_empty());
+	});
+}
+
+#[test]
+fn unbatchable_event_proofs_are_signed_individually() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Module::<TestRuntime>::set_batch_signing(
+			frame_system::RawOrigin::Root.into(),
+			true
+		));
+		// e.g. an NFT withdrawal, its contract has no batched proof path
+		assert_eq!(Module::<TestRuntime>::generate_event_proof(&5_u64, false), Ok(0));
+		assert_eq!(Module::<TestRuntime>::generate_event_proof(&6_u64, true), Ok(1));
+		assert!(matches!(ethy_logs()[..], [ConsensusLog::OpaqueSigningRequest((_, 0))]));
+		assert_eq!(Module::<TestRuntime>::pending_batch_messages().len(), 1);
+
+		Module::<TestRuntime>::on_finalize(1);
+		match &ethy_logs()[..] {
+			[ConsensusLog::OpaqueSigningRequest((_, 0)), ConsensusLog::BatchSigningRequest((messages, 2))] => {
+				assert_eq!(
+					messages.iter().map(|(event_id, _)| *event_id).collect::<Vec<_>>(),
+					vec![1]
+				);
+			}
+			_ => panic!("expected an individual and a batch signing request"),
+		}
+	});
+}
+
+#[test]
+fn batch_signing_weight_is_reserved() {
+	ExtBuilder::default().build().execute_with(|| {
+		let idle_weight = Module::<TestRuntime>::on_initialize(1);
+		assert_ok!(Module::<TestRuntime>::set_batch_signing(
+			frame_system::RawOrigin::Root.into(),
+			true
+		));
+		assert_eq!(
+			Module::<TestRuntime>::on_initialize(1),
+			Module::<TestRuntime>::batch_signing_weight()
+		);
+		assert!(
+			Module::<TestRuntime>::batch_signing_weight() >= crate::BATCH_MESSAGE_WEIGHT * crate::MAX_BATCH_SIZE as u64
+		);
+		assert!(idle_weight < Module::<TestRuntime>::batch_signing_weight());
+
+		// messages left pending after batching is disabled are still signed `on_finalize`
+		assert_ok!(Module::<TestRuntime>::generate_event_proof(&5_u64, true));
+		assert_ok!(Module::<TestRuntime>::set_batch_signing(
+			frame_system::RawOrigin::Root.into(),
+			false
+		));
+		assert!(idle_weight < Module::<TestRuntime>::on_initialize(1));
+	});
+}
+
+#[test]
+fn event_proof_batches_are_capped() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Module::<TestRuntime>::set_batch_signing(
+			frame_system::RawOrigin::Root.into(),
+			true
+		));
+		let message_count = crate::MAX_BATCH_SIZE as u64 + 1;
+		for value in 0..message_count {
+			assert_ok!(Module::<TestRuntime>::generate_event_proof(&value, true));
+		}
+		Module::<TestRuntime>::on_finalize(1);
+
+		match &ethy_logs()[..] {
+			[ConsensusLog::BatchSigningRequest((messages, batch_id))] => {
+				assert_eq!(messages.len(), crate::MAX_BATCH_SIZE);
+				assert_eq!(*batch_id, message_count);
+			}
+			_ => panic!("expected a single batch signing request"),
+		}
+		// the overflow is batched next block
+		assert_eq!(
+			Module::<TestRuntime>::pending_batch_messages()
+				.iter()
+				.map(|(event_id, _)| *event_id)
+				.collect::<Vec<_>>(),
+			vec![message_count - 1]
+		);
+	});
+}
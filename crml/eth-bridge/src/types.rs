@@ -22,7 +22,11 @@ use ethereum_types::{Bloom as H2048, U64};
 use scale_info::TypeInfo;
 use serde::de::{Error, Visitor};
 use serde::{Deserialize, Deserializer, Serialize};
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{Perbill, RuntimeDebug};
+use sp_staking::{
+	offence::{DisableStrategy, Kind, Offence},
+	SessionIndex,
+};
 use sp_std::{prelude::*, vec::Vec};
 
 /// A bridge message id
@@ -79,6 +83,151 @@ pub enum EventClaimResult {
 	UnexpectedData,
 	/// The deposit tx is past the expiration deadline
 	Expired,
+	/// The Eth clients responded but did not agree on the result
+	DataProviderDisagreement,
+	/// The claimed block header is not part of the canonical chain
+	UnexpectedBlockHash,
+}
+
+impl EventClaimResult {
+	/// Whether the result may change on retry i.e. it reflects the state of the data provider
+	/// rather than the claim. Transient results are not counted toward invalidating a claim.
+	pub fn is_transient(&self) -> bool {
+		matches!(
+			self,
+			Self::DataProviderErr | Self::NotEnoughConfirmations | Self::DataProviderDisagreement
+		)
+	}
+}
+
+/// The notarization status of a queued event claim
+#[derive(Debug, Clone, PartialEq, Decode, Encode, TypeInfo)]
+pub struct EventClaimStatus<EthyId> {
+	/// The event claim Id
+	pub event_claim_id: EventClaimId,
+	/// The ethereum transaction hash of the claimed event
+	pub tx_hash: EthHash,
+	/// The claimed event type (contract address, event signature)
+	pub event_type: (EthAddress, EthHash),
+	/// The claimed event data
+	pub event_data: Option<Vec<u8>>,
+	/// Notarizations submitted so far by notary public key
+	pub notarizations: Vec<(EthyId, EventClaimResult)>,
+	/// The number of `Valid` notarizations
+	pub yay_count: u32,
+	/// The number of notarizations with any other non-transient result
+	pub nay_count: u32,
+	/// The number of notarizations with a transient result, these are retried by the notary
+	pub transient_count: u32,
+	/// The number of notaries eligible to notarize the claim
+	pub notary_count: u32,
+}
+
+// A value placed in storage that represents the current version of the bridge storage.
+// This value is used by the `on_runtime_upgrade` logic to determine whether we run storage migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, TypeInfo)]
+pub enum Releases {
+	/// storage version before claims could expire
+	V0 = 0,
+	/// queued claims have an expiry block
+	V1 = 1,
+	/// processed tx hashes are bucketed by the hour since the unix epoch, not the second of the hour
+	V2 = 2,
+}
+
+/// A notary's participation in claims notarized during the current session
+#[derive(Debug, Default, Clone, PartialEq, Decode, Encode, TypeInfo)]
+pub struct NotaryParticipation {
+	/// The number of notarized claims the notary voted on
+	pub votes: u32,
+	/// The number of notarized claims the notary voted against the outcome of
+	pub disagreements: u32,
+}
+
+/// An offence committed by notaries who missed or disagreed with too many claims in a session
+#[derive(RuntimeDebug, Clone, PartialEq, Eq)]
+pub struct NotaryMisconductOffence<Offender> {
+	/// The session index the offence occurred in
+	pub session_index: SessionIndex,
+	/// The size of the validator set in the session
+	pub validator_set_count: u32,
+	/// The offending notaries' identifications
+	pub offenders: Vec<Offender>,
+}
+
+impl<Offender: Clone> Offence<Offender> for NotaryMisconductOffence<Offender> {
+	const ID: Kind = *b"eth-bridge:notar";
+	type TimeSlot = SessionIndex;
+
+	fn offenders(&self) -> Vec<Offender> {
+		self.offenders.clone()
+	}
+
+	fn session_index(&self) -> SessionIndex {
+		self.session_index
+	}
+
+	fn validator_set_count(&self) -> u32 {
+		self.validator_set_count
+	}
+
+	fn time_slot(&self) -> Self::TimeSlot {
+		self.session_index
+	}
+
+	fn disable_strategy(&self) -> DisableStrategy {
+		DisableStrategy::Never
+	}
+
+	/// Scales with the fraction of offending notaries, like `im-online` unresponsiveness
+	/// i.e. 7% * (3 * offenders / validators), capped at 7%
+	fn slash_fraction(offenders_count: u32, validator_set_count: u32) -> Perbill {
+		Perbill::from_rational(3 * offenders_count, validator_set_count).saturating_mul(Perbill::from_percent(7))
+	}
+}
+
+/// An offence committed by an Ethy authority signing conflicting witnesses for the same event
+#[derive(RuntimeDebug, Clone, PartialEq, Eq)]
+pub struct EthyEquivocationOffence<Offender> {
+	/// The session index the offence was reported in
+	pub session_index: SessionIndex,
+	/// The size of the validator set in the session
+	pub validator_set_count: u32,
+	/// The equivocated event
+	pub event_id: EventProofId,
+	/// The offending authority's identification
+	pub offender: Offender,
+}
+
+impl<Offender: Clone> Offence<Offender> for EthyEquivocationOffence<Offender> {
+	const ID: Kind = *b"eth-bridge:equiv";
+	type TimeSlot = EventProofId;
+
+	fn offenders(&self) -> Vec<Offender> {
+		vec![self.offender.clone()]
+	}
+
+	fn session_index(&self) -> SessionIndex {
+		self.session_index
+	}
+
+	fn validator_set_count(&self) -> u32 {
+		self.validator_set_count
+	}
+
+	fn time_slot(&self) -> Self::TimeSlot {
+		self.event_id
+	}
+
+	fn disable_strategy(&self) -> DisableStrategy {
+		DisableStrategy::WhenSlashed
+	}
+
+	/// Like GRANDPA equivocations i.e. min(3 * offenders / validators, 1)^2
+	fn slash_fraction(offenders_count: u32, validator_set_count: u32) -> Perbill {
+		let x = Perbill::from_rational(3 * offenders_count, validator_set_count);
+		x.square()
+	}
 }
 
 /// An independent notarization vote on a claim
@@ -0,0 +1,267 @@
+/* Copyright 2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+//! # Eth Messaging
+//!
+//! Passes arbitrary messages between Benson and Ethereum contracts via the `BensonRouter` contract.
+//!
+//! Outbound messages `(destination contract, payload)` are sent by signed origins or other pallets
+//! (see `EthMessageSender`), a fee is charged and an Ethy proof is generated for relaying to the router.
+//!
+//! Inbound messages are claimed from the router's `MessageSent` events and, once notarized,
+//! dispatched to the `EthMessageHandler` registered for the source contract.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use benson_primitives::types::{AssetId, Balance};
+use codec::Encode;
+use crml_support::{
+	EthMessageHandler, EthMessageSender, EthReceiptProof, EventClaimSubscriber, EventClaimVerifier, MultiCurrency,
+};
+use frame_support::{
+	decl_error, decl_event, decl_module, decl_storage, ensure, log,
+	traits::{ExistenceRequirement, Get, IsType},
+	transactional, PalletId,
+};
+use frame_system::{ensure_root, ensure_signed};
+use sp_runtime::{
+	traits::{AccountIdConversion, Zero},
+	DispatchError,
+};
+use sp_std::prelude::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+mod types;
+pub use types::*;
+
+pub trait Config: frame_system::Config {
+	/// The EVM event signature of a message sent by the router contract
+	type MessageSentEventSignature: Get<[u8; 32]>;
+	/// The maximum length of a message payload in bytes
+	type MaxPayloadLength: Get<u32>;
+	/// Submits event claims for Ethereum
+	type EthBridge: EventClaimVerifier;
+	/// Handles messages from Ethereum contracts
+	type MessageHandlers: EthMessageHandler;
+	/// Currency functions
+	type MultiCurrency: MultiCurrency<AccountId = Self::AccountId, Balance = Balance, CurrencyId = AssetId>;
+	/// The treasury account, receives message fees
+	type TreasuryPalletId: Get<PalletId>;
+	/// The overarching event type.
+	type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+}
+
+decl_storage! {
+	trait Store for Module<T: Config> as EthMessaging {
+		/// The router contract address on Ethereum
+		RouterAddress get(fn router_address): EthAddress;
+		/// The fee for sending a message to Ethereum (in fee currency)
+		MessageFee get(fn message_fee): Balance;
+		/// Map from Ethereum contract to the handler of its messages
+		MessageHandler get(fn message_handler): map hasher(twox_64_concat) EthAddress => Option<MessageHandlerId>;
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where AccountId = <T as frame_system::Config>::AccountId {
+		/// A message was sent to Ethereum (message Id, sender, destination)
+		MessageSent(u64, AccountId, EthAddress),
+		/// A claim for a message from Ethereum has started (claim Id, sender)
+		MessageClaim(u64, AccountId),
+		/// A message from Ethereum was handled (claim Id, source, handler)
+		MessageReceived(u64, EthAddress, MessageHandlerId),
+		/// A message from Ethereum failed to be claimed or has no handler (claim Id)
+		MessageFailed(u64),
+		/// The router contract address has been set
+		SetRouterAddress(EthAddress),
+		/// The message fee has been set
+		SetMessageFee(Balance),
+		/// The handler of messages from an Ethereum contract has been set (source, handler)
+		SetMessageHandler(EthAddress, Option<MessageHandlerId>),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Config> {
+		/// The message payload exceeds the max. length
+		PayloadTooLong,
+		/// There's no handler for messages from the source contract
+		NoMessageHandler,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Config> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		#[weight = 50_000_000]
+		/// Send `payload` to the `destination` contract on Ethereum
+		/// The message fee is charged and a proof generated for relaying the message to the router contract
+		#[transactional]
+		pub fn send_message(origin, destination: EthAddress, payload: Vec<u8>) {
+			let origin = ensure_signed(origin)?;
+			<Self as EthMessageSender>::send_message(&origin, &destination, &payload)?;
+		}
+
+		#[weight = 50_000_000]
+		/// Submit a claim for a message sent from Ethereum in tx `tx_hash`
+		/// The message details must be provided for cross-checking by notaries
+		#[transactional]
+		pub fn receive_message(origin, tx_hash: H256, message: InboundMessage) {
+			let origin = ensure_signed(origin)?;
+			Self::validate_inbound_message(&message)?;
+
+			let event_claim_id = T::EthBridge::submit_event_claim(
+				&Self::router_address(),
+				&T::MessageSentEventSignature::get().into(),
+				&tx_hash,
+				&EthAbiCodec::encode(&message),
+			)?;
+
+			Self::deposit_event(<Event<T>>::MessageClaim(event_claim_id, origin));
+		}
+
+		#[weight = 100_000_000]
+		/// Submit a claim for a message sent from Ethereum with a proof of the tx receipt
+		/// The claim is verified against the proven Ethereum block header, notaries need only agree on the header
+		#[transactional]
+		pub fn receive_message_with_proof(origin, message: InboundMessage, proof: EthReceiptProof) {
+			let origin = ensure_signed(origin)?;
+			Self::validate_inbound_message(&message)?;
+
+			let event_claim_id = T::EthBridge::submit_event_claim_with_proof(
+				&Self::router_address(),
+				&T::MessageSentEventSignature::get().into(),
+				&EthAbiCodec::encode(&message),
+				&proof,
+			)?;
+
+			Self::deposit_event(<Event<T>>::MessageClaim(event_claim_id, origin));
+		}
+
+		#[weight = 1_000_000]
+		/// Set the router contract address on Ethereum (requires governance)
+		pub fn set_router_address(origin, eth_address: EthAddress) {
+			ensure_root(origin)?;
+			RouterAddress::put(eth_address);
+			Self::deposit_event(<Event<T>>::SetRouterAddress(eth_address));
+		}
+
+		#[weight = 1_000_000]
+		/// Set the fee for sending a message to Ethereum (requires governance)
+		pub fn set_message_fee(origin, fee: Balance) {
+			ensure_root(origin)?;
+			MessageFee::put(fee);
+			Self::deposit_event(<Event<T>>::SetMessageFee(fee));
+		}
+
+		#[weight = 1_000_000]
+		/// Set the handler of messages from the `source` contract on Ethereum (requires governance)
+		/// `None` stops accepting messages from `source`
+		pub fn set_message_handler(origin, source: EthAddress, handler: Option<MessageHandlerId>) {
+			ensure_root(origin)?;
+			match handler {
+				Some(handler) => MessageHandler::insert(source, handler),
+				None => MessageHandler::remove(source),
+			}
+			Self::deposit_event(<Event<T>>::SetMessageHandler(source, handler));
+		}
+	}
+}
+
+impl<T: Config> Module<T> {
+	/// Check an inbound message may be handled before submitting its claim
+	fn validate_inbound_message(message: &InboundMessage) -> Result<(), DispatchError> {
+		ensure!(
+			message.payload.len() <= T::MaxPayloadLength::get() as usize,
+			Error::<T>::PayloadTooLong
+		);
+		ensure!(
+			MessageHandler::contains_key(message.source),
+			Error::<T>::NoMessageHandler
+		);
+		Ok(())
+	}
+}
+
+impl<T: Config> EthMessageSender for Module<T> {
+	type AccountId = T::AccountId;
+
+	fn send_message(sender: &T::AccountId, destination: &EthAddress, payload: &[u8]) -> Result<u64, DispatchError> {
+		ensure!(
+			payload.len() <= T::MaxPayloadLength::get() as usize,
+			Error::<T>::PayloadTooLong
+		);
+
+		let fee = Self::message_fee();
+		if !fee.is_zero() {
+			T::MultiCurrency::transfer(
+				sender,
+				&T::TreasuryPalletId::get().into_account(),
+				T::MultiCurrency::fee_currency(),
+				fee,
+				ExistenceRequirement::KeepAlive,
+			)?;
+		}
+
+		let message = OutboundMessage {
+			sender: sender.using_encoded(|account| {
+				let mut sender = H256::default();
+				let len = account.len().min(32);
+				sender.0[..len].copy_from_slice(&account[..len]);
+				sender
+			}),
+			destination: *destination,
+			payload: payload.to_vec(),
+		};
+		let message_id = T::EthBridge::generate_event_proof(&message, false)?;
+
+		Self::deposit_event(<Event<T>>::MessageSent(message_id, sender.clone(), *destination));
+
+		Ok(message_id)
+	}
+}
+
+impl<T: Config> EventClaimSubscriber for Module<T> {
+	fn on_success(event_claim_id: u64, contract_address: &EthAddress, event_type: &H256, event_data: &[u8]) {
+		if *contract_address == Self::router_address() && *event_type == H256::from(T::MessageSentEventSignature::get())
+		{
+			if let Some(message) = <InboundMessage as EthAbiCodec>::decode(event_data) {
+				// the handler may have been removed since the claim was made
+				match Self::message_handler(message.source) {
+					Some(handler) => {
+						T::MessageHandlers::on_message(&handler, event_claim_id, &message.source, &message.payload);
+						Self::deposit_event(<Event<T>>::MessageReceived(event_claim_id, message.source, handler));
+					}
+					None => Self::deposit_event(<Event<T>>::MessageFailed(event_claim_id)),
+				}
+			} else {
+				// input data should be valid, we do not expect to fail here
+				log::error!("📌 message claim failed unexpectedly: {:?}", event_data);
+			}
+		}
+	}
+	fn on_failure(event_claim_id: u64, contract_address: &H160, event_type: &H256, _event_data: &[u8]) {
+		if *contract_address == Self::router_address() && *event_type == H256::from(T::MessageSentEventSignature::get())
+		{
+			Self::deposit_event(<Event<T>>::MessageFailed(event_claim_id));
+		}
+	}
+}
@@ -0,0 +1,295 @@
+/* Copyright 2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+use super::*;
+use crate::mock::{
+	AccountId, EthMessaging, Event, ExtBuilder, GenericAsset, MaxPayloadLength, MessageSentEventSignature,
+	MockEthBridge, MockMessageHandler, System, Test, TreasuryPalletId, MOCK_HANDLER_ID,
+};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::traits::BadOrigin;
+
+/// The router contract on Ethereum
+const ROUTER: EthAddress = H160([0x11; 20]);
+/// A contract on Ethereum sending and receiving messages
+const APP_CONTRACT: EthAddress = H160([0x22; 20]);
+/// The message fee
+const FEE: Balance = 100;
+
+// Check the test system contains an event record `event`
+fn has_event(event: crate::Event<Test>) -> bool {
+	System::events()
+		.iter()
+		.find(|e| e.event == Event::EthMessaging(event.clone()))
+		.is_some()
+}
+
+fn fee_currency() -> AssetId {
+	<GenericAsset as MultiCurrency>::fee_currency()
+}
+
+fn treasury_account() -> AccountId {
+	TreasuryPalletId::get().into_account()
+}
+
+/// Setup the router and fee, registering `MOCK_HANDLER_ID` for messages from `APP_CONTRACT`
+fn setup() {
+	assert_ok!(EthMessaging::set_router_address(
+		frame_system::RawOrigin::Root.into(),
+		ROUTER
+	));
+	assert_ok!(EthMessaging::set_message_fee(frame_system::RawOrigin::Root.into(), FEE));
+	assert_ok!(EthMessaging::set_message_handler(
+		frame_system::RawOrigin::Root.into(),
+		APP_CONTRACT,
+		Some(MOCK_HANDLER_ID)
+	));
+}
+
+/// Notify the pallet of a successful claim of `message`
+fn claim_success(event_claim_id: u64, message: &InboundMessage) {
+	<EthMessaging as EventClaimSubscriber>::on_success(
+		event_claim_id,
+		&ROUTER,
+		&H256::from(MessageSentEventSignature::get()),
+		&EthAbiCodec::encode(message),
+	);
+}
+
+#[test]
+fn send_message() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup();
+		let sender: AccountId = 2;
+		let _ = <GenericAsset as MultiCurrency>::deposit_creating(&sender, fee_currency(), 1_000);
+		let payload = b"hello ethereum".to_vec();
+
+		assert_ok!(EthMessaging::send_message(
+			Some(sender).into(),
+			APP_CONTRACT,
+			payload.clone()
+		));
+
+		assert_eq!(GenericAsset::free_balance(fee_currency(), &sender), 1_000 - FEE);
+		assert_eq!(GenericAsset::free_balance(fee_currency(), &treasury_account()), FEE);
+		let mut expected_sender = H256::default();
+		expected_sender.0[..8].copy_from_slice(&sender.encode());
+		assert_eq!(
+			MockEthBridge::event_proofs(),
+			vec![EthAbiCodec::encode(&OutboundMessage {
+				sender: expected_sender,
+				destination: APP_CONTRACT,
+				payload,
+			})]
+		);
+		assert!(has_event(RawEvent::MessageSent(0, sender, APP_CONTRACT)));
+	});
+}
+
+#[test]
+fn send_message_from_pallet() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup();
+		let pallet_account: AccountId = PalletId(*b"py/tests").into_account();
+		let _ = <GenericAsset as MultiCurrency>::deposit_creating(&pallet_account, fee_currency(), 1_000);
+
+		assert_eq!(
+			<EthMessaging as EthMessageSender>::send_message(&pallet_account, &APP_CONTRACT, &[1, 2, 3]),
+			Ok(0)
+		);
+		assert_eq!(
+			<EthMessaging as EthMessageSender>::send_message(&pallet_account, &APP_CONTRACT, &[4, 5, 6]),
+			Ok(1)
+		);
+		assert_eq!(
+			GenericAsset::free_balance(fee_currency(), &pallet_account),
+			1_000 - 2 * FEE
+		);
+		assert!(has_event(RawEvent::MessageSent(1, pallet_account, APP_CONTRACT)));
+	});
+}
+
+#[test]
+fn send_message_fails_prechecks() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup();
+		let sender: AccountId = 2;
+		let _ = <GenericAsset as MultiCurrency>::deposit_creating(&sender, fee_currency(), FEE - 1);
+
+		assert_noop!(
+			EthMessaging::send_message(
+				Some(sender).into(),
+				APP_CONTRACT,
+				vec![1_u8; MaxPayloadLength::get() as usize + 1]
+			),
+			Error::<Test>::PayloadTooLong
+		);
+		assert!(EthMessaging::send_message(Some(sender).into(), APP_CONTRACT, vec![1_u8; 32]).is_err());
+		assert!(MockEthBridge::event_proofs().is_empty());
+
+		// free messages
+		assert_ok!(EthMessaging::set_message_fee(frame_system::RawOrigin::Root.into(), 0));
+		assert_ok!(EthMessaging::send_message(
+			Some(sender).into(),
+			APP_CONTRACT,
+			vec![1_u8; 32]
+		));
+		assert_eq!(MockEthBridge::event_proofs().len(), 1);
+	});
+}
+
+#[test]
+fn receive_message() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup();
+		let message = InboundMessage {
+			source: APP_CONTRACT,
+			payload: b"hello benson".to_vec(),
+		};
+
+		assert_ok!(EthMessaging::receive_message(
+			Some(5).into(),
+			H256::repeat_byte(1),
+			message.clone()
+		));
+		assert_ok!(EthMessaging::receive_message_with_proof(
+			Some(5).into(),
+			message.clone(),
+			Default::default()
+		));
+
+		let expected_claim = (
+			ROUTER,
+			H256::from(MessageSentEventSignature::get()),
+			EthAbiCodec::encode(&message),
+		);
+		assert_eq!(
+			MockEthBridge::event_claims(),
+			vec![expected_claim.clone(), expected_claim]
+		);
+		assert!(has_event(RawEvent::MessageClaim(0, 5)));
+		assert!(has_event(RawEvent::MessageClaim(1, 5)));
+
+		claim_success(0, &message);
+		assert_eq!(
+			MockMessageHandler::messages(),
+			vec![(MOCK_HANDLER_ID, 0, APP_CONTRACT, message.payload.clone())]
+		);
+		assert!(has_event(RawEvent::MessageReceived(0, APP_CONTRACT, MOCK_HANDLER_ID)));
+	});
+}
+
+#[test]
+fn receive_message_fails_prechecks() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup();
+		let message = InboundMessage {
+			source: H160::repeat_byte(9),
+			payload: vec![1_u8; 32],
+		};
+		assert_noop!(
+			EthMessaging::receive_message(Some(5).into(), H256::repeat_byte(1), message),
+			Error::<Test>::NoMessageHandler
+		);
+
+		let message = InboundMessage {
+			source: APP_CONTRACT,
+			payload: vec![1_u8; MaxPayloadLength::get() as usize + 1],
+		};
+		assert_noop!(
+			EthMessaging::receive_message(Some(5).into(), H256::repeat_byte(1), message),
+			Error::<Test>::PayloadTooLong
+		);
+		assert!(MockEthBridge::event_claims().is_empty());
+	});
+}
+
+#[test]
+fn received_message_without_handler_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup();
+		let message = InboundMessage {
+			source: APP_CONTRACT,
+			payload: vec![1_u8; 32],
+		};
+		assert_ok!(EthMessaging::receive_message(
+			Some(5).into(),
+			H256::repeat_byte(1),
+			message.clone()
+		));
+
+		// the handler is removed while the claim is pending
+		assert_ok!(EthMessaging::set_message_handler(
+			frame_system::RawOrigin::Root.into(),
+			APP_CONTRACT,
+			None
+		));
+		assert!(has_event(RawEvent::SetMessageHandler(APP_CONTRACT, None)));
+		claim_success(0, &message);
+
+		assert!(MockMessageHandler::messages().is_empty());
+		assert!(has_event(RawEvent::MessageFailed(0)));
+	});
+}
+
+#[test]
+fn message_claims_ignore_other_events() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup();
+		let message = EthAbiCodec::encode(&InboundMessage {
+			source: APP_CONTRACT,
+			payload: vec![1_u8; 32],
+		});
+
+		<EthMessaging as EventClaimSubscriber>::on_success(
+			1,
+			&H160::repeat_byte(9),
+			&H256::from(MessageSentEventSignature::get()),
+			&message,
+		);
+		<EthMessaging as EventClaimSubscriber>::on_success(1, &ROUTER, &H256::repeat_byte(9), &message);
+		assert!(MockMessageHandler::messages().is_empty());
+
+		<EthMessaging as EventClaimSubscriber>::on_failure(
+			2,
+			&ROUTER,
+			&H256::from(MessageSentEventSignature::get()),
+			&message,
+		);
+		assert!(has_event(RawEvent::MessageFailed(2)));
+		assert!(MockMessageHandler::messages().is_empty());
+	});
+}
+
+#[test]
+fn settings_require_root() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(EthMessaging::set_router_address(Some(1).into(), ROUTER), BadOrigin);
+		assert_noop!(EthMessaging::set_message_fee(Some(1).into(), FEE), BadOrigin);
+		assert_noop!(
+			EthMessaging::set_message_handler(Some(1).into(), APP_CONTRACT, Some(MOCK_HANDLER_ID)),
+			BadOrigin
+		);
+
+		setup();
+		assert_eq!(EthMessaging::router_address(), ROUTER);
+		assert_eq!(EthMessaging::message_fee(), FEE);
+		assert_eq!(EthMessaging::message_handler(APP_CONTRACT), Some(MOCK_HANDLER_ID));
+		assert!(has_event(RawEvent::SetMessageHandler(
+			APP_CONTRACT,
+			Some(MOCK_HANDLER_ID)
+		)));
+	});
+}
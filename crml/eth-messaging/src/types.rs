@@ -0,0 +1,190 @@
+/* Copyright 2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+use codec::{Decode, Encode};
+pub use crml_support::{EthAbiCodec, MessageHandlerId, H160, H256, U256};
+use scale_info::TypeInfo;
+use sp_std::convert::TryInto;
+use sp_std::prelude::*;
+
+/// Ethereum address type
+pub type EthAddress = H160;
+
+/// A message sent to Benson by the router contract on Ethereum
+/// i.e. the `MessageSent(address,bytes)` event
+#[derive(Debug, Default, Clone, PartialEq, Decode, Encode, TypeInfo)]
+pub struct InboundMessage {
+	/// The Ethereum contract which sent the message
+	pub source: EthAddress,
+	/// The message payload
+	pub payload: Vec<u8>,
+}
+
+/// A message from Benson to prove and submit to the router contract on Ethereum
+#[derive(Debug, Default, Clone, PartialEq, Decode, Encode)]
+pub struct OutboundMessage {
+	/// The Benson account which sent the message
+	pub sender: H256,
+	/// The Ethereum contract receiving the message
+	pub destination: EthAddress,
+	/// The message payload
+	pub payload: Vec<u8>,
+}
+
+/// Encode `bytes` as the tail of a dynamic ABI `bytes` value: length word then right padded data
+fn encode_bytes_tail(bytes: &[u8]) -> Vec<u8> {
+	let mut buf = EthAbiCodec::encode(&(bytes.len() as u64));
+	buf.extend_from_slice(bytes);
+	buf.resize(32 + padded_len(bytes.len()), 0);
+	buf
+}
+
+/// `len` rounded up to a multiple of 32-byte words
+fn padded_len(len: usize) -> usize {
+	(len + 31) / 32 * 32
+}
+
+impl EthAbiCodec for OutboundMessage {
+	/// Encode `OutboundMessage` as `abi.encode(bytes32 sender, address destination, bytes payload)`
+	/// https://docs.soliditylang.org/en/v0.5.3/abi-spec.html#formal-specification-of-the-encoding
+	fn encode(&self) -> Vec<u8> {
+		let mut buf = vec![0_u8; 32 * 3];
+		buf[..32].copy_from_slice(&self.sender.to_fixed_bytes());
+		buf[44..64].copy_from_slice(&self.destination.to_fixed_bytes());
+		// offset of `payload`
+		buf[95] = 32 * 3;
+		buf.extend(encode_bytes_tail(&self.payload));
+		buf
+	}
+
+	/// Decode an `OutboundMessage`
+	/// Only the canonical encoding is accepted
+	fn decode(data: &[u8]) -> Option<Self> {
+		// Expect 3 head words and a length word
+		if data.len() < 4 * 32 || U256::from(&data[64..96]) != U256::from(3 * 32) {
+			return None;
+		}
+		let payload_len = U256::from(&data[96..128]);
+		if payload_len > U256::from(data.len()) || data.len() != 4 * 32 + padded_len(payload_len.as_usize()) {
+			return None;
+		}
+		let sender = H256::from(&data[..32].try_into().expect("32 bytes decode"));
+		let destination = H160::from(&data[44..64].try_into().expect("20 bytes decode"));
+		let payload = data[128..128 + payload_len.as_usize()].to_vec();
+
+		Some(Self {
+			sender,
+			destination,
+			payload,
+		})
+	}
+}
+
+impl EthAbiCodec for InboundMessage {
+	/// Encode `InboundMessage` as `abi.encode(address source, bytes payload)`
+	/// https://docs.soliditylang.org/en/v0.5.3/abi-spec.html#formal-specification-of-the-encoding
+	fn encode(&self) -> Vec<u8> {
+		let mut buf = vec![0_u8; 32 * 2];
+		buf[12..32].copy_from_slice(&self.source.to_fixed_bytes());
+		// offset of `payload`
+		buf[63] = 32 * 2;
+		buf.extend(encode_bytes_tail(&self.payload));
+		buf
+	}
+	/// Receives Ethereum log 'data' and decodes it
+	/// Only the canonical encoding is accepted
+	fn decode(data: &[u8]) -> Option<Self> {
+		// Expect 2 head words and a length word
+		if data.len() < 3 * 32 || U256::from(&data[32..64]) != U256::from(2 * 32) {
+			return None;
+		}
+		let payload_len = U256::from(&data[64..96]);
+		if payload_len > U256::from(data.len()) || data.len() != 3 * 32 + padded_len(payload_len.as_usize()) {
+			return None;
+		}
+		let source = H160::from(&data[12..32].try_into().expect("20 bytes decode"));
+		let payload = data[96..96 + payload_len.as_usize()].to_vec();
+
+		Some(Self { source, payload })
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::{EthAbiCodec, InboundMessage, OutboundMessage};
+	use crml_support::{H160, H256};
+
+	/// A 32-byte ABI word holding `value`
+	fn word(value: u64) -> Vec<u8> {
+		H256::from_low_u64_be(value).as_bytes().to_vec()
+	}
+
+	#[test]
+	fn outbound_message_encode() {
+		let message = OutboundMessage {
+			sender: H256::from_low_u64_be(55),
+			destination: H160::from_low_u64_be(77),
+			payload: vec![1_u8; 33],
+		};
+		let mut payload_words = vec![1_u8; 33];
+		payload_words.resize(64, 0);
+		let raw = [word(55), word(77), word(96), word(33), payload_words].concat();
+		assert_eq!(EthAbiCodec::encode(&message), raw);
+		assert_eq!(OutboundMessage::decode(&raw).expect("it decodes"), message);
+		// truncated
+		assert!(OutboundMessage::decode(&raw[..raw.len() - 1]).is_none());
+		// non-canonical payload offset
+		let moved = [
+			word(55),
+			word(77),
+			word(128),
+			word(0),
+			word(33),
+			vec![1_u8; 33],
+			vec![0_u8; 31],
+		]
+		.concat();
+		assert!(OutboundMessage::decode(&moved).is_none());
+	}
+
+	#[test]
+	fn inbound_message_decode() {
+		let message = InboundMessage {
+			source: H160::from_low_u64_be(55),
+			payload: vec![1_u8; 32],
+		};
+		let raw = [word(55), word(64), word(32), vec![1_u8; 32]].concat();
+		assert_eq!(EthAbiCodec::encode(&message), raw);
+		assert_eq!(InboundMessage::decode(&raw).expect("it decodes"), message);
+
+		let empty = InboundMessage {
+			source: H160::from_low_u64_be(55),
+			payload: vec![],
+		};
+		assert_eq!(InboundMessage::decode(&EthAbiCodec::encode(&empty)), Some(empty));
+	}
+
+	#[test]
+	fn inbound_message_decode_rejects_non_canonical_data() {
+		// bad offset
+		assert!(InboundMessage::decode(&[word(55), word(96), word(32), vec![1_u8; 32]].concat()).is_none());
+		// payload too short
+		assert!(InboundMessage::decode(&[word(55), word(64), word(33), vec![1_u8; 32]].concat()).is_none());
+		// trailing data
+		assert!(InboundMessage::decode(&[word(55), word(64), word(1), vec![1_u8; 64]].concat()).is_none());
+		// huge length
+		assert!(InboundMessage::decode(&[word(55), word(64), vec![0xff; 32], vec![1_u8; 32]].concat()).is_none());
+		assert!(InboundMessage::decode(&[word(55), word(64)].concat()).is_none());
+	}
+}
@@ -24,6 +24,7 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use crml_eth_wallet::eip712::TypedCall;
 use crml_support::H160 as EthAddress;
 use sp_std::vec::Vec;
 
@@ -31,5 +32,7 @@ sp_api::decl_runtime_apis! {
 	pub trait EthWalletApi {
 		/// Get Benson nonce for an Ethereum address
 		fn address_nonce(address: &EthAddress) -> u32;
+		/// Get the EIP-712 typed data of a SCALE encoded call for an Ethereum address to sign
+		fn typed_call(call: Vec<u8>, address: &EthAddress) -> Option<TypedCall>;
 	}
 }
@@ -16,12 +16,15 @@
 
 //! RPC interface for the governance module.
 
+use crml_eth_wallet::eip712::{TypedCall, CALL_TYPE, DOMAIN_TYPE, PRIMARY_TYPE};
 pub use crml_eth_wallet_rpc_runtime_api::EthWalletApi as EthWalletRuntimeApi;
 use crml_support::H160 as EthAddress;
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
+use serde_json::{json, Value};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use std::sync::Arc;
 
@@ -30,6 +33,11 @@ pub trait EthWalletApi<BlockHash> {
 	/// Get all governance proposal votes
 	#[rpc(name = "ethWallet_addressNonce")]
 	fn address_nonce(&self, eth_address: EthAddress, at: Option<BlockHash>) -> Result<u32>;
+
+	/// Get the EIP-712 typed data JSON of a SCALE encoded call, for `eth_address` to sign with `eth_signTypedData_v4`
+	/// Returns `null` if the call is invalid
+	#[rpc(name = "ethWallet_typedData")]
+	fn typed_data(&self, call: Bytes, eth_address: EthAddress, at: Option<BlockHash>) -> Result<Option<Value>>;
 }
 
 /// A struct that implements the [`GovernanceApi`].
@@ -48,6 +56,44 @@ impl<C, P> EthWallet<C, P> {
 	}
 }
 
+/// Return the EIP-712 type fields of an encoded type e.g. `Mail(string from,string contents)`
+fn type_fields(encoded_type: &[u8]) -> Value {
+	let encoded_type = String::from_utf8_lossy(encoded_type);
+	let fields = encoded_type
+		.split(|c| c == '(' || c == ')')
+		.nth(1)
+		.unwrap_or_default()
+		.split(',')
+		.filter_map(|field| {
+			let mut parts = field.split(' ');
+			Some(json!({ "type": parts.next()?, "name": parts.next()? }))
+		})
+		.collect();
+	Value::Array(fields)
+}
+
+/// Return `typed_call` as `eth_signTypedData_v4` JSON
+fn typed_data_json(typed_call: TypedCall) -> Value {
+	json!({
+		"types": {
+			"EIP712Domain": type_fields(DOMAIN_TYPE),
+			PRIMARY_TYPE: type_fields(CALL_TYPE),
+		},
+		"primaryType": PRIMARY_TYPE,
+		"domain": {
+			"name": String::from_utf8_lossy(&typed_call.domain.name),
+			"version": String::from_utf8_lossy(&typed_call.domain.version),
+			"salt": typed_call.domain.salt,
+		},
+		"message": {
+			"pallet": String::from_utf8_lossy(&typed_call.pallet),
+			"call": String::from_utf8_lossy(&typed_call.call),
+			"nonce": typed_call.nonce,
+			"data": Bytes(typed_call.data),
+		},
+	})
+}
+
 /// Error type of this RPC api.
 pub enum Error {
 	/// The call to runtime failed.
@@ -72,4 +118,23 @@ where
 				data: Some(format!("{:?}", e).into()),
 			})
 	}
+
+	fn typed_data(
+		&self,
+		call: Bytes,
+		eth_address: EthAddress,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Value>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		self.client
+			.runtime_api()
+			.typed_call(&at, call.to_vec(), &eth_address)
+			.map(|typed_call| typed_call.map(typed_data_json))
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError as i64),
+				message: "Unable to query typed call data.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
 }
@@ -60,7 +60,17 @@ pub fn ecrecover<L: Lazy<[u8]>>(
 	address: &EthAddress,
 ) -> Option<ecdsa::Public> {
 	let msg = keccak_256(&signable_message(&msg.get()));
-	if let Some(public) = secp256k1_ecdsa_recover_compressed(&signature.0, &msg).ok() {
+	ecrecover_digest(signature, &msg, address)
+}
+
+/// Return the ECDSA public key if `signature` is valid for the pre-hashed `digest` from `address`, otherwise None
+/// e.g. an EIP-712 typed data digest
+pub fn ecrecover_digest(
+	signature: &EthereumSignature,
+	digest: &[u8; 32],
+	address: &EthAddress,
+) -> Option<ecdsa::Public> {
+	if let Some(public) = secp256k1_ecdsa_recover_compressed(&signature.0, digest).ok() {
 		let public = ecdsa::Public { 0: public };
 		if let Some(address_) = benson_primitives::eth::EthyEcdsaToEthereum::convert(public.clone()) {
 			if address_ == address.0 {
@@ -2,19 +2,23 @@
 //! Provides shims for Ethereum wallets (e.g. metamask) to interact with Benson
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use crate::ethereum::{ecrecover, EthereumSignature};
-use codec::Encode;
+use crate::{
+	eip712::{Eip712Domain, TypedCall},
+	ethereum::{ecrecover, ecrecover_digest, EthereumSignature},
+};
+use codec::{Decode, Encode};
 use crml_support::{TransactionFeeHandler, H160 as EthAddress};
 use frame_support::{
 	decl_error, decl_event, decl_module, decl_storage,
-	dispatch::{DispatchInfo, Dispatchable},
+	dispatch::{DispatchInfo, Dispatchable, GetCallMetadata},
 	traits::{Get, UnfilteredDispatchable},
 	weights::GetDispatchInfo,
 	Parameter,
 };
 use frame_system::ensure_none;
+use sp_core::ecdsa;
 use sp_runtime::{
-	traits::IdentifyAccount,
+	traits::{IdentifyAccount, Zero},
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
 	},
@@ -22,6 +26,8 @@ use sp_runtime::{
 };
 use sp_std::prelude::*;
 
+/// EIP-712 typed data signing (eth_signTypedData_v4 API call).
+pub mod eip712;
 /// Ethereum-compatible signatures (eth_sign API call).
 pub mod ethereum;
 
@@ -34,7 +40,8 @@ pub trait Config: frame_system::Config {
 	type Call: Parameter
 		+ Dispatchable<Info = DispatchInfo>
 		+ UnfilteredDispatchable<Origin = Self::Origin>
-		+ GetDispatchInfo;
+		+ GetDispatchInfo
+		+ GetCallMetadata;
 
 	/// Provides transaction fee handling
 	type TransactionFeeHandler: TransactionFeeHandler<AccountId = Self::AccountId, Call = <Self as Config>::Call>;
@@ -108,25 +115,36 @@ decl_module! {
 			let message = &(&call, address_nonce).encode()[..];
 
 			if let Some(public_key) = ecrecover(&signature, message, &eth_address) {
-				let account = T::Signer::from(public_key).into_account(); // Benson address
-
-				// it's possible this account is used normally outside of eth signing-
-				// ensure highest known nonce is used
-				let system_nonce = <frame_system::Pallet<T>>::account_nonce(account.clone());
-				let highest_nonce = sp_std::cmp::max(system_nonce.saturated_into(), address_nonce);
-				let new_nonce = highest_nonce.checked_add(1).ok_or(Error::<T>::InvalidNonce)?;
+				Self::execute(call, eth_address, address_nonce, public_key)
+			} else {
+				Err(Error::<T>::InvalidSignature)?
+			}
+		}
 
-				// Pay fee, increment nonce
-				let _ = Self::pay_fee(&call, &account)?;
-				AddressNonce::insert(eth_address, new_nonce);
-				<frame_system::Pallet<T>>::inc_account_nonce(&account);
+		#[weight = {
+			let dispatch_info = call.get_dispatch_info();
+			(dispatch_info.weight + 10_000, dispatch_info.class)
+		}]
+		/// Execute a runtime `call` signed using `eth_signTypedData_v4`
+		/// Expects signature validates the EIP-712 typed data of `(call, nonce)` (see `typed_call`)
+		///
+		/// origin must be `none`
+		/// call - runtime call to execute
+		/// account - signed by this account
+		fn call_typed(
+			origin,
+			call: Box<<T as Config>::Call> ,
+			eth_address: EthAddress,
+			signature: EthereumSignature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
 
-				// execute the call
-				let new_origin = frame_system::RawOrigin::Signed(account.clone()).into();
-				let res = call.dispatch_bypass_filter(new_origin).map(|_| ());
-				Self::deposit_event(RawEvent::Execute(eth_address, account, res.map_err(|e| e.error)));
+			// check the known nonce for this ethereum address
+			let address_nonce = Self::stored_address_nonce(eth_address);
+			let digest = Self::typed_call(&call, address_nonce).signing_digest();
 
-				Ok(())
+			if let Some(public_key) = ecrecover_digest(&signature, &digest, &eth_address) {
+				Self::execute(call, eth_address, address_nonce, public_key)
 			} else {
 				Err(Error::<T>::InvalidSignature)?
 			}
@@ -135,6 +153,33 @@ decl_module! {
 }
 
 impl<T: Config> Module<T> {
+	/// Execute `call` on behalf of the verified signer `public_key`
+	fn execute(
+		call: Box<<T as Config>::Call>,
+		eth_address: EthAddress,
+		address_nonce: u32,
+		public_key: ecdsa::Public,
+	) -> DispatchResult {
+		let account = T::Signer::from(public_key).into_account(); // Benson address
+
+		// it's possible this account is used normally outside of eth signing-
+		// ensure highest known nonce is used
+		let system_nonce = <frame_system::Pallet<T>>::account_nonce(account.clone());
+		let highest_nonce = sp_std::cmp::max(system_nonce.saturated_into(), address_nonce);
+		let new_nonce = highest_nonce.checked_add(1).ok_or(Error::<T>::InvalidNonce)?;
+
+		// Pay fee, increment nonce
+		let _ = Self::pay_fee(&call, &account)?;
+		AddressNonce::insert(eth_address, new_nonce);
+		<frame_system::Pallet<T>>::inc_account_nonce(&account);
+
+		// execute the call
+		let new_origin = frame_system::RawOrigin::Signed(account.clone()).into();
+		let res = call.dispatch_bypass_filter(new_origin).map(|_| ());
+		Self::deposit_event(RawEvent::Execute(eth_address, account, res.map_err(|e| e.error)));
+
+		Ok(())
+	}
 	/// Take required fees from `account` to dispatch `call`
 	fn pay_fee(call: &<T as Config>::Call, account: &T::AccountId) -> DispatchResult {
 		let info = call.get_dispatch_info();
@@ -148,32 +193,74 @@ impl<T: Config> Module<T> {
 	pub fn address_nonce(eth_address: &EthAddress) -> u32 {
 		Self::stored_address_nonce(eth_address)
 	}
+	/// Return the EIP-712 domain for this chain
+	/// (chain name, wallet pallet version, genesis hash)
+	pub fn eip712_domain() -> Eip712Domain {
+		let genesis_hash = <frame_system::Pallet<T>>::block_hash(T::BlockNumber::zero());
+		Eip712Domain {
+			name: T::Version::get().spec_name.as_bytes().to_vec(),
+			version: env!("CARGO_PKG_VERSION").as_bytes().to_vec(),
+			salt: sp_core::H256::from_slice(genesis_hash.as_ref()),
+		}
+	}
+	/// Return `call` with `nonce` as EIP-712 typed data, to be signed for `call_typed`
+	pub fn typed_call(call: &<T as Config>::Call, nonce: u32) -> TypedCall {
+		let metadata = call.get_call_metadata();
+		TypedCall {
+			domain: Self::eip712_domain(),
+			pallet: metadata.pallet_name.as_bytes().to_vec(),
+			call: metadata.function_name.as_bytes().to_vec(),
+			nonce,
+			data: call.encode(),
+		}
+	}
+	/// Return the SCALE encoded `call` as EIP-712 typed data for `eth_address` to sign at its current nonce
+	/// Returns `None` if `call` is not a valid runtime call
+	pub fn typed_call_for(call: Vec<u8>, eth_address: &EthAddress) -> Option<TypedCall> {
+		let call = <T as Config>::Call::decode(&mut &call[..]).ok()?;
+		Some(Self::typed_call(&call, Self::stored_address_nonce(eth_address)))
+	}
 }
 
 impl<T: Config> frame_support::unsigned::ValidateUnsigned for Module<T> {
 	type Call = Call<T>;
 
 	fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
-		if let Call::call {
-			call,
-			eth_address,
-			signature,
-		} = call
-		{
-			let address_nonce = Self::stored_address_nonce(eth_address);
-			let message = &(&call, address_nonce).encode()[..];
-			if let Some(_public_key) = ecrecover(&signature, message, &eth_address) {
-				return ValidTransaction::with_tag_prefix("EthWallet")
-					.priority(T::UnsignedPriority::get())
-					.and_provides((eth_address, address_nonce))
-					.longevity(64_u64)
-					.propagate(true)
-					.build();
-			} else {
-				InvalidTransaction::BadProof.into()
+		let (eth_address, address_nonce, public_key) = match call {
+			Call::call {
+				call,
+				eth_address,
+				signature,
+			} => {
+				let address_nonce = Self::stored_address_nonce(eth_address);
+				let message = &(&call, address_nonce).encode()[..];
+				(eth_address, address_nonce, ecrecover(&signature, message, &eth_address))
 			}
+			Call::call_typed {
+				call,
+				eth_address,
+				signature,
+			} => {
+				let address_nonce = Self::stored_address_nonce(eth_address);
+				let digest = Self::typed_call(&call, address_nonce).signing_digest();
+				(
+					eth_address,
+					address_nonce,
+					ecrecover_digest(&signature, &digest, &eth_address),
+				)
+			}
+			_ => return InvalidTransaction::Call.into(),
+		};
+
+		if public_key.is_some() {
+			ValidTransaction::with_tag_prefix("EthWallet")
+				.priority(T::UnsignedPriority::get())
+				.and_provides((eth_address, address_nonce))
+				.longevity(64_u64)
+				.propagate(true)
+				.build()
 		} else {
-			InvalidTransaction::Call.into()
+			InvalidTransaction::BadProof.into()
 		}
 	}
 }
@@ -284,6 +371,17 @@ mod tests {
 		storage.into()
 	}
 
+	// Simple `eth_signTypedData_v4` implementation over a pre-computed EIP-712 digest
+	fn eth_sign_typed(seed: &[u8; 32], digest: &[u8; 32]) -> Vec<u8> {
+		let ecdsa_msg = secp256k1::Message::parse(digest);
+		let secret = secp256k1::SecretKey::parse(&seed).expect("valid seed");
+		let (signature, recovery_id) = libsecp256k1::sign(&ecdsa_msg, &secret);
+		let mut out = Vec::with_capacity(65);
+		out.extend_from_slice(&signature.serialize()[..]);
+		out.push(recovery_id.serialize() + 27);
+		out
+	}
+
 	// Simple `eth_sign` implementation, should be equal to exported by RPC
 	fn eth_sign(seed: &[u8; 32], data: &[u8]) -> Vec<u8> {
 		let call_msg = ethereum::signable_message(data);
@@ -352,4 +450,75 @@ mod tests {
 			assert_eq!(storage::unhashed::get(&MOCK_FEE_PAID), Some(1u32),);
 		})
 	}
+
+	#[test]
+	fn typed_call_describes_call() {
+		new_test_ext().execute_with(|| {
+			let eth_address: EthAddress = hex!("420aC537F1a4f78d4Dfb3A71e902be0E3d480AFB").into();
+			let call: Call = frame_system::Call::<Test>::remark {
+				remark: b"hello world".to_vec(),
+			}
+			.into();
+
+			let typed_call = EthWallet::typed_call_for(call.encode(), &eth_address).expect("valid call");
+			assert_eq!(typed_call.pallet, b"System".to_vec());
+			assert_eq!(typed_call.call, b"remark".to_vec());
+			assert_eq!(typed_call.nonce, 0);
+			assert_eq!(typed_call.data, call.encode());
+			assert_eq!(typed_call.domain.version, b"2.0.0".to_vec());
+			assert_eq!(
+				typed_call.domain.salt.as_ref(),
+				<frame_system::Pallet<Test>>::block_hash(0).as_ref()
+			);
+
+			assert!(EthWallet::typed_call_for(vec![255, 255], &eth_address).is_none());
+		});
+	}
+
+	#[test]
+	fn typed_remark() {
+		new_test_ext().execute_with(|| {
+			let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
+			let eth_address: EthAddress = hex!("420aC537F1a4f78d4Dfb3A71e902be0E3d480AFB").into();
+			let benson_address = MultiSigner::from(pair.public()).into_account();
+
+			let call: Call = frame_system::Call::<Test>::remark {
+				remark: b"hello world".to_vec(),
+			}
+			.into();
+			let module_nonce = EthWallet::address_nonce(&eth_address);
+			let digest = EthWallet::typed_call(&call, module_nonce).signing_digest();
+			let signature = EthereumSignature::try_from(eth_sign_typed(&ECDSA_SEED, &digest)).expect("valid sig");
+
+			// a typed signature is not valid as a `personal_sign` signature and vice versa
+			assert_err!(
+				EthWallet::call(Origin::none(), Box::new(call.clone()), eth_address, signature.clone()),
+				Error::<Test>::InvalidSignature,
+			);
+			let personal_signature =
+				EthereumSignature::try_from(eth_sign(&ECDSA_SEED, (call.clone(), module_nonce).encode().as_ref()))
+					.expect("valid sig");
+			assert_err!(
+				EthWallet::call_typed(Origin::none(), Box::new(call.clone()), eth_address, personal_signature),
+				Error::<Test>::InvalidSignature,
+			);
+
+			// execute the call
+			assert_ok!(EthWallet::call_typed(
+				Origin::none(),
+				Box::new(call.clone()),
+				eth_address,
+				signature.clone()
+			));
+			assert_eq!(EthWallet::address_nonce(&eth_address), module_nonce + 1);
+			assert_eq!(<frame_system::Pallet<Test>>::account_nonce(&benson_address), 1);
+			assert_eq!(storage::unhashed::get(&MOCK_FEE_PAID), Some(1u32));
+
+			// the signature can't be replayed at the new nonce
+			assert_err!(
+				EthWallet::call_typed(Origin::none(), Box::new(call), eth_address, signature),
+				Error::<Test>::InvalidSignature,
+			);
+		})
+	}
 }
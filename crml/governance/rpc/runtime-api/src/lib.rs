@@ -25,7 +25,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::Codec;
-use crml_governance::{ProposalId, ProposalVoteInfo};
+use crml_governance::{ElectionInfo, ProposalId, ProposalInfo, ProposalVoteInfo};
 use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
@@ -36,5 +36,11 @@ sp_api::decl_runtime_apis! {
 		fn council() -> Vec<AccountId>;
 		/// Get proposal votes
 		fn proposal_votes() -> Vec<(ProposalId, ProposalVoteInfo)>;
+		/// Get council election state
+		fn election_info() -> ElectionInfo<AccountId>;
+		/// Get the lifecycle state of a proposal
+		fn proposal(proposal_id: ProposalId) -> Option<ProposalInfo<AccountId>>;
+		/// Get the lifecycle state of all known proposals
+		fn proposals() -> Vec<ProposalInfo<AccountId>>;
 	}
 }
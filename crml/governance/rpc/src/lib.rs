@@ -17,14 +17,19 @@
 //! RPC interface for the governance module.
 
 pub use self::gen_client::Client as GovernanceClient;
+use benson_primitives::types::Balance;
 use codec::Codec;
-use crml_governance::ProposalId;
+use crml_governance::{
+	CouncilVoteCount, ElectionInfo as RuntimeElectionInfo, ProposalId, ProposalInfo as RuntimeProposalInfo,
+	ProposalStatusInfo, TrackId,
+};
 pub use crml_governance_rpc_runtime_api::GovernanceRuntimeApi;
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
 use serde::{Deserialize, Serialize};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use std::sync::Arc;
 
@@ -33,6 +38,18 @@ pub trait GovernanceApi<AccountId, BlockHash> {
 	/// Get all governance proposal votes
 	#[rpc(name = "governance_getProposalVotes")]
 	fn proposal_votes(&self, at: Option<BlockHash>) -> Result<Vec<ProposalVotes<AccountId>>>;
+
+	/// Get the council election state: candidates by approval stake, council and runners-up
+	#[rpc(name = "governance_getElectionInfo")]
+	fn election_info(&self, at: Option<BlockHash>) -> Result<ElectionInfo<AccountId>>;
+
+	/// Get a proposal's details, call, status, referendum tally and council votes
+	#[rpc(name = "governance_getProposal")]
+	fn proposal(&self, proposal_id: ProposalId, at: Option<BlockHash>) -> Result<Option<ProposalInfo<AccountId>>>;
+
+	/// Get all known proposals ordered by Id
+	#[rpc(name = "governance_listProposals")]
+	fn proposals(&self, at: Option<BlockHash>) -> Result<Vec<ProposalInfo<AccountId>>>;
 }
 
 /// A struct that implements the [`GovernanceApi`].
@@ -63,6 +80,103 @@ pub struct ProposalVotes<AccountId> {
 	votes: Vec<(AccountId, Option<bool>)>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ElectionInfo<AccountId> {
+	candidates: Vec<Candidate<AccountId>>,
+	council: Vec<AccountId>,
+	runners_up: Vec<AccountId>,
+	election_rounds: u32,
+	next_election_block: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Candidate<AccountId> {
+	account_id: AccountId,
+	#[serde(with = "serde_balance")]
+	approval_stake: Balance,
+}
+
+impl<AccountId> From<RuntimeElectionInfo<AccountId>> for ElectionInfo<AccountId> {
+	fn from(info: RuntimeElectionInfo<AccountId>) -> Self {
+		ElectionInfo {
+			candidates: info
+				.candidates
+				.into_iter()
+				.map(|(account_id, approval_stake)| Candidate {
+					account_id,
+					approval_stake,
+				})
+				.collect(),
+			council: info.council,
+			runners_up: info.runners_up,
+			election_rounds: info.election_rounds,
+			next_election_block: info.next_election_block,
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposalInfo<AccountId> {
+	proposal_id: ProposalId,
+	sponsor: Option<AccountId>,
+	justification_uri: String,
+	enactment_delay: u64,
+	call: Option<Bytes>,
+	/// "pallet.function" of the call
+	call_name: Option<String>,
+	track: Option<TrackId>,
+	status: ProposalStatusInfo,
+	deliberation_start_time: Option<u64>,
+	referendum_start_time: Option<u64>,
+	#[serde(with = "serde_balance")]
+	referendum_veto_sum: Balance,
+	council_votes: CouncilVoteCount,
+}
+
+impl<AccountId> From<RuntimeProposalInfo<AccountId>> for ProposalInfo<AccountId> {
+	fn from(info: RuntimeProposalInfo<AccountId>) -> Self {
+		ProposalInfo {
+			proposal_id: info.proposal_id,
+			sponsor: info.sponsor,
+			justification_uri: String::from_utf8_lossy(&info.justification_uri).into_owned(),
+			enactment_delay: info.enactment_delay,
+			call: info.call.map(Into::into),
+			call_name: info.call_name.map(|(pallet, function)| {
+				format!(
+					"{}.{}",
+					String::from_utf8_lossy(&pallet),
+					String::from_utf8_lossy(&function)
+				)
+			}),
+			track: info.track,
+			status: info.status,
+			deliberation_start_time: info.deliberation_start_time,
+			referendum_start_time: info.referendum_start_time,
+			referendum_veto_sum: info.referendum_veto_sum,
+			council_votes: info.council_votes,
+		}
+	}
+}
+
+/// (de)serialize balances as strings, JSON numbers can't hold a `u128`
+mod serde_balance {
+	use super::Balance;
+	use serde::{Deserialize, Deserializer, Serializer};
+
+	pub fn serialize<S: Serializer>(t: &Balance, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+		serializer.serialize_str(&t.to_string())
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Balance, D::Error> {
+		let s = String::deserialize(deserializer)?;
+		s.parse::<Balance>()
+			.map_err(|_| serde::de::Error::custom("Parse from string failed"))
+	}
+}
+
 impl<C, Block, AccountId> GovernanceApi<AccountId, <Block as BlockT>::Hash> for Governance<C, (Block, AccountId)>
 where
 	Block: BlockT,
@@ -100,4 +214,50 @@ where
 			})
 			.collect())
 	}
+
+	fn election_info(&self, at: Option<<Block as BlockT>::Hash>) -> Result<ElectionInfo<AccountId>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		self.client
+			.runtime_api()
+			.election_info(&at)
+			.map(Into::into)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError as i64),
+				message: "Unable to query election info.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+
+	fn proposal(
+		&self,
+		proposal_id: ProposalId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<ProposalInfo<AccountId>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		self.client
+			.runtime_api()
+			.proposal(&at, proposal_id)
+			.map(|info| info.map(Into::into))
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError as i64),
+				message: "Unable to query proposal.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+
+	fn proposals(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<ProposalInfo<AccountId>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		self.client
+			.runtime_api()
+			.proposals(&at)
+			.map(|proposals| proposals.into_iter().map(Into::into).collect())
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError as i64),
+				message: "Unable to query proposals.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
 }
@@ -27,30 +27,39 @@ pub use types::*;
 
 use benson_primitives::types::Balance;
 use codec::{Decode, Encode};
-use crml_support::{RegistrationInfo, StakingAmount};
+use crml_support::{CouncilProvider, RegistrationInfo, StakingAmount};
 use frame_support::{
 	decl_error, decl_event, decl_module, decl_storage,
-	dispatch::{DispatchResult, Dispatchable},
+	dispatch::{DispatchError, DispatchResult, Dispatchable, GetCallMetadata},
 	pallet_prelude::*,
 	traits::{
 		schedule::{DispatchTime, Named as ScheduleNamed},
-		Currency, LockIdentifier, ReservableCurrency,
+		Currency, CurrencyToVote, LockIdentifier, LockableCurrency, ReservableCurrency, WithdrawReasons,
 	},
 };
 use frame_system::pallet_prelude::*;
 use log::warn;
-use sp_runtime::traits::Zero;
-use sp_runtime::Permill;
+use sp_npos_elections::{seq_phragmen, VoteWeight};
+use sp_runtime::traits::{Saturating, UniqueSaturatedInto, Zero};
+use sp_runtime::{Perbill, Permill};
 use sp_std::prelude::*;
 
 /// Identifies governance scheduled calls
 const GOVERNANCE_ID: LockIdentifier = *b"governan";
-/// Minimum number of registered identities required to become a council member
-const MINIMUM_REGISTERED_IDENTITIES: u32 = 2;
-/// The length in blocks of a referendum voting cycle
-const REFERENDUM_LENGTH: u32 = 21600;
-/// The interval in which the referendum ending is checked
-const REFERENDUM_CHECK_INTERVAL: u32 = 1000;
+/// The default minimum number of registered identities required to become a council member
+const DEFAULT_MINIMUM_REGISTERED_IDENTITIES: u32 = 2;
+/// The default length in blocks of a referendum voting cycle
+const DEFAULT_REFERENDUM_LENGTH: u32 = 21600;
+/// The default interval in which the referendum ending is checked
+const DEFAULT_REFERENDUM_CHECK_INTERVAL: u32 = 1000;
+/// The default length in blocks a proposal may remain in council deliberation
+const DEFAULT_DELIBERATION_PERIOD: u32 = 100_800;
+/// Identifies locks placed on staked CENNZ by conviction voting
+const VOTING_LOCK_ID: LockIdentifier = *b"govvote ";
+/// The max. length of a delegation chain
+const MAX_DELEGATION_DEPTH: u32 = 16;
+/// The max. depth of wrapper calls followed when classifying a proposal's call
+const MAX_CALL_DEPTH: u32 = 8;
 
 pub trait Config: frame_system::Config {
 	/// Maximum size of the council
@@ -61,8 +70,10 @@ pub trait Config: frame_system::Config {
 	type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>>;
 	/// Runtime currency system
 	type Currency: Currency<Self::AccountId, Balance = Balance> + ReservableCurrency<Self::AccountId>;
+	/// The staking asset, locked by conviction voting
+	type StakingCurrency: LockableCurrency<Self::AccountId, Balance = Balance, Moment = Self::BlockNumber>;
 	/// Runtime call type
-	type Call: Parameter + Dispatchable<Origin = Self::Origin> + From<Call<Self>>;
+	type Call: Parameter + Dispatchable<Origin = Self::Origin> + From<Call<Self>> + GetCallMetadata;
 	/// The system event type
 	type Event: From<Event> + Into<<Self as frame_system::Config>::Event>;
 	/// Weight information for extrinsics in this module.
@@ -71,6 +82,31 @@ pub trait Config: frame_system::Config {
 	type Registration: RegistrationInfo<AccountId = Self::AccountId>;
 	/// staking information of an account
 	type StakingAmount: StakingAmount<AccountId = Self::AccountId, Balance = Balance>;
+	/// Deposit reserved when submitting council candidacy
+	type CandidacyBond: Get<Balance>;
+	/// The length of a council term in blocks, an election is held at the end of each term
+	type TermDuration: Get<Self::BlockNumber>;
+	/// Number of runners-up to keep after an election
+	type DesiredRunnersUp: Get<u32>;
+	/// Max. number of candidates standing for an election, excluding the council and runners-up
+	type MaxCandidates: Get<u32>;
+	/// Max. number of accounts voting in council elections
+	type MaxVoters: Get<u32>;
+	/// Converts staked amounts into election vote weights
+	type CurrencyToVote: CurrencyToVote<Balance>;
+	/// Finds the calls dispatched by wrapper calls e.g. `Utility::batch` when classifying proposals
+	type WrappedCalls: WrappedCalls<<Self as Config>::Call>;
+}
+
+/// Unwraps calls which dispatch other calls
+pub trait WrappedCalls<Call> {
+	/// Returns the calls dispatched by `call` or `None` if it isn't a wrapper
+	fn wrapped_calls(call: &Call) -> Option<Vec<Call>>;
+}
+impl<Call> WrappedCalls<Call> for () {
+	fn wrapped_calls(_call: &Call) -> Option<Vec<Call>> {
+		None
+	}
 }
 
 /// TODO: move to weights
@@ -91,6 +127,10 @@ decl_event! {
 		ReferendumCreated(ProposalId),
 		/// A referendum has been approved and is awaiting enactment
 		ReferendumApproved(ProposalId),
+		/// A new council was elected (election round)
+		CouncilElected(u32),
+		/// A proposal expired without a council decision
+		ProposalExpired(ProposalId),
 	}
 }
 
@@ -118,6 +158,40 @@ decl_error! {
 		ReferendumNotDeliberating,
 		/// This account does not meet the required amount of registered identities
 		NotEnoughRegistrations,
+		/// This account is delegating its votes
+		AlreadyDelegating,
+		/// This account is not delegating its votes
+		NotDelegating,
+		/// The delegation would create a cycle or exceed the max. delegation depth
+		InvalidDelegation,
+		/// Other accounts delegate to this account, it can't delegate its votes
+		HasDelegators,
+		/// This account has no voting lock or it has not expired yet
+		VotingLockActive,
+		/// This account is already a candidate, councilor or runner-up
+		AlreadyCandidate,
+		/// This account is not a council candidate
+		NotCandidate,
+		/// No council candidates were voted for
+		NoVotes,
+		/// Voted for more council candidates than allowed
+		TooManyVotes,
+		/// Voted for the same council candidate more than once
+		DuplicateVote,
+		/// The max. number of council candidates has been reached
+		TooManyCandidates,
+		/// The max. number of council election voters has been reached
+		TooManyVoters,
+		/// The enactment delay is outside the bounds of the proposal's track
+		EnactmentDelayOutOfBounds,
+		/// Governance track was not found
+		TrackMissing,
+		/// The track's min. enactment delay exceeds its max. enactment delay
+		InvalidTrack,
+		/// The proposal's call dispatches calls from different tracks
+		MixedTracks,
+		/// The proposal's call nests too many wrapper calls
+		CallTooDeep,
 	}
 }
 
@@ -135,10 +209,33 @@ decl_storage! {
 		ReferendumVotes get(fn referendum_votes): double_map hasher(twox_64_concat) ProposalId, hasher(twox_64_concat) T::AccountId => VotingPower;
 		/// Running tally of referendum votes
 		ReferendumVetoSum get(fn referendum_veto_sum): map hasher(twox_64_concat) ProposalId => Balance;
+		/// Map from proposal id to council deliberation start time
+		DeliberationStartTime get(fn deliberation_start_time): map hasher(twox_64_concat) ProposalId => Option<T::BlockNumber>;
 		/// Map from proposal id to referendum start time
 		ReferendumStartTime get(fn referendum_start_time): map hasher(twox_64_concat) ProposalId => Option<T::BlockNumber>;
+		/// Map from account to its referendum vote delegation
+		Delegations get(fn delegations): map hasher(twox_64_concat) T::AccountId => Option<Delegation<T::AccountId>>;
+		/// Map from account to the voting power (transitively) delegated to it and its number of direct delegators
+		/// The power is as of delegating, referendums tally delegators' current stake
+		DelegatedVotes get(fn delegated_votes): map hasher(twox_64_concat) T::AccountId => (VotingPower, u32);
+		/// Map from account to the accounts directly delegating to it
+		Delegators get(fn delegators): double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) T::AccountId => ();
+		/// Map from account to its conviction voting lock (amount, unlock block)
+		VotingLocks get(fn voting_locks): map hasher(twox_64_concat) T::AccountId => Option<(Balance, T::BlockNumber)>;
 		/// Ordered set of active council members
 		Council get(fn council): Vec<T::AccountId>;
+		/// Ordered set of council candidates for the next election
+		Candidates get(fn candidates): Vec<T::AccountId>;
+		/// Runners-up from the last election, most backed first
+		RunnersUp get(fn runners_up): Vec<T::AccountId>;
+		/// Map from voter to the council candidates they approve
+		ElectionVotes get(fn election_votes): map hasher(twox_64_concat) T::AccountId => Vec<T::AccountId>;
+		/// The number of accounts in `ElectionVotes`
+		ElectionVoterCount get(fn election_voter_count): u32;
+		/// Map from candidate, councilor or runner-up to their reserved candidacy bond
+		CandidacyDeposits get(fn candidacy_deposits): map hasher(twox_64_concat) T::AccountId => Balance;
+		/// Number of council elections held
+		ElectionRounds get(fn election_rounds): u32;
 		/// Next available ID for proposal
 		NextProposalId get(fn next_proposal_id): ProposalId;
 		/// Proposal bond amount in 'wei'
@@ -149,6 +246,23 @@ decl_storage! {
 		ReferendumThreshold get(fn referendum_threshold): Permill = Permill::from_percent(33);
 		/// Minimum stake required to create a new council member
 		MinimumCouncilStake get(fn minimum_council_stake): Balance = 10_000_000;
+		/// Number of blocks a proposal may remain in council deliberation before it expires
+		DeliberationPeriod get(fn deliberation_period): T::BlockNumber = T::BlockNumber::from(DEFAULT_DELIBERATION_PERIOD);
+		/// The length in blocks of a referendum voting cycle on the default track
+		ReferendumLength get(fn referendum_length): T::BlockNumber = T::BlockNumber::from(DEFAULT_REFERENDUM_LENGTH);
+		/// The interval in blocks in which proposal expiry and referendum ending is checked
+		ReferendumCheckInterval get(fn referendum_check_interval): T::BlockNumber = T::BlockNumber::from(DEFAULT_REFERENDUM_CHECK_INTERVAL);
+		/// Minimum number of registered identities required to become a council member or vote
+		MinimumRegisteredIdentities get(fn minimum_registered_identities): u32 = DEFAULT_MINIMUM_REGISTERED_IDENTITIES;
+		/// Map from track Id to its referendum parameters
+		Tracks get(fn tracks): map hasher(twox_64_concat) TrackId => Option<TrackInfo<T::BlockNumber>>;
+		/// Map from call (pallet name, function name) to its track
+		/// An empty function name matches all calls of the pallet
+		CallTracks get(fn call_tracks): double_map hasher(twox_64_concat) Vec<u8>, hasher(twox_64_concat) Vec<u8> => Option<TrackId>;
+		/// Map from proposal Id to its track, proposals without a track follow the default track
+		ProposalTrack get(fn proposal_track): map hasher(twox_64_concat) ProposalId => Option<TrackId>;
+		/// Storage version of the pallet, see `Releases`
+		StorageVersion: u32;
 	}
 }
 
@@ -157,17 +271,54 @@ decl_module! {
 
 		fn deposit_event() = default;
 
-		/// Check whether any referendums have finished
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get() != Releases::V0 as u32 {
+				return Zero::zero();
+			}
+			StorageVersion::put(Releases::V1 as u32);
+
+			// Proposals submitted before `DeliberationStartTime` existed would never expire,
+			// their submission time is unknown so the deliberation period starts now
+			let now = <frame_system::Pallet<T>>::block_number();
+			let mut reads = 1_u64;
+			let mut writes = 1_u64;
+			for (proposal_id, status) in ProposalStatus::iter() {
+				reads += 2;
+				if status == ProposalStatusInfo::Deliberation && !<DeliberationStartTime<T>>::contains_key(proposal_id) {
+					<DeliberationStartTime<T>>::insert(proposal_id, now);
+					writes += 1;
+				}
+			}
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		/// Check whether any referendums have finished and whether a council term has ended
 		fn on_initialize(block_number: T::BlockNumber) -> Weight {
-			if (block_number % T::BlockNumber::from(REFERENDUM_CHECK_INTERVAL)).is_zero() {
+			let term_duration = T::TermDuration::get();
+			let election_weight = if !term_duration.is_zero() && (block_number % term_duration).is_zero() {
+				Self::do_election()
+			} else {
+				0
+			};
+
+			let check_interval = Self::referendum_check_interval();
+			election_weight + if !check_interval.is_zero() && (block_number % check_interval).is_zero() {
+				// Check proposals awaiting a council decision
+				let deliberation_period = Self::deliberation_period();
+				let expired: Vec<ProposalId> = <DeliberationStartTime<T>>::iter()
+					.filter(|(_, block)| block_number >= *block + deliberation_period)
+					.map(|(proposal_id, _)| proposal_id)
+					.collect();
+				let mut weight_count = expired.len() as u64;
+				expired.into_iter().for_each(Self::expire_proposal);
+
 				// Check referendums
-				let mut weight_count = 0;
 				let proposal_ids = <ReferendumStartTime<T>>::iter();
 				proposal_ids.for_each(|(proposal_id, block)| {
-					if block_number >= block + T::BlockNumber::from(REFERENDUM_LENGTH) {
+					if block_number >= block + Self::referendum_length_of(proposal_id) {
 						if Self::proposal_status(proposal_id) == Some(ProposalStatusInfo::ReferendumDeliberation) {
-							Self::end_referendum(proposal_id);
-							weight_count += 1;
+							// one unit per referendum and per veto and delegation tallied
+							weight_count += 1 + Self::end_referendum(proposal_id) as u64;
 						}
 					}
 				});
@@ -177,6 +328,95 @@ decl_module! {
 			}
 		}
 
+		/// Stand for election to the council
+		/// A candidacy bond is reserved until the candidate renounces or loses an election
+		#[weight = 1_000_000]
+		fn submit_candidacy(origin) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			Self::check_council_account_validity(&origin)?;
+			ensure!(!Self::is_candidate(&origin), Error::<T>::AlreadyCandidate);
+			ensure!(Self::candidates().len() < T::MaxCandidates::get() as usize, Error::<T>::TooManyCandidates);
+
+			let bond = T::CandidacyBond::get();
+			T::Currency::reserve(&origin, bond)?;
+			CandidacyDeposits::<T>::insert(&origin, bond);
+			<Candidates<T>>::mutate(|candidates| {
+				if let Err(idx) = candidates.binary_search(&origin) {
+					candidates.insert(idx, origin);
+				}
+			});
+			Ok(())
+		}
+
+		/// Withdraw candidacy for the next council election and return the candidacy bond
+		/// A councilor gives up their seat to the most backed runner-up
+		#[weight = 1_000_000]
+		fn renounce_candidacy(origin) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let mut candidates = Self::candidates();
+			let mut council = Self::council();
+			let mut runners_up = Self::runners_up();
+			if let Ok(idx) = candidates.binary_search(&origin) {
+				candidates.remove(idx);
+				<Candidates<T>>::put(candidates);
+			} else if let Ok(idx) = council.binary_search(&origin) {
+				ensure!(council.len() > 1 || !runners_up.is_empty(), Error::<T>::MinCouncilReached);
+				let old_council = council.clone();
+				council.remove(idx);
+				if !runners_up.is_empty() {
+					let replacement = runners_up.remove(0);
+					if let Err(idx) = council.binary_search(&replacement) {
+						council.insert(idx, replacement);
+					}
+					<RunnersUp<T>>::put(runners_up);
+				}
+				Self::remap_council_votes(&old_council, &council);
+				<Council<T>>::put(council);
+			} else if let Some(idx) = runners_up.iter().position(|r| r == &origin) {
+				runners_up.remove(idx);
+				<RunnersUp<T>>::put(runners_up);
+			} else {
+				return Err(Error::<T>::NotCandidate.into());
+			}
+
+			let _ = T::Currency::unreserve(&origin, CandidacyDeposits::<T>::take(&origin));
+			Ok(())
+		}
+
+		/// Approve a set of council candidates, replacing any previous approvals
+		/// Votes are weighted by the voter's staked amount at election time
+		#[weight = 1_000_000]
+		fn vote_council_candidates(origin, votes: Vec<T::AccountId>) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			ensure!(!votes.is_empty(), Error::<T>::NoVotes);
+			ensure!(votes.len() <= T::MaxCouncilSize::get() as usize, Error::<T>::TooManyVotes);
+			let mut unique_votes = votes.clone();
+			unique_votes.sort();
+			unique_votes.dedup();
+			ensure!(unique_votes.len() == votes.len(), Error::<T>::DuplicateVote);
+			let staked_amount: Balance = T::StakingAmount::active_balance(&origin);
+			Self::check_voter_account_validity(&origin, staked_amount)?;
+			ensure!(votes.iter().all(Self::is_candidate), Error::<T>::NotCandidate);
+			if !<ElectionVotes<T>>::contains_key(&origin) {
+				let voters = Self::election_voter_count();
+				ensure!(voters < T::MaxVoters::get(), Error::<T>::TooManyVoters);
+				ElectionVoterCount::put(voters.saturating_add(1));
+			}
+
+			<ElectionVotes<T>>::insert(&origin, votes);
+			Ok(())
+		}
+
+		/// Remove all council candidate approvals
+		#[weight = 100_000]
+		fn remove_council_votes(origin) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			ensure!(<ElectionVotes<T>>::contains_key(&origin), Error::<T>::NoVotes);
+			<ElectionVotes<T>>::remove(&origin);
+			ElectionVoterCount::mutate(|n| *n = n.saturating_sub(1));
+			Ok(())
+		}
+
 		#[weight = 1_000_000]
 		/// Submit a proposal for consideration by the council
 		/// Caller must be a council member
@@ -192,8 +432,15 @@ decl_module! {
 			let sponsor_idx = Self::council().binary_search(&origin);
 			ensure!(sponsor_idx.is_ok(), Error::<T>::NotCouncilor);
 			let proposal_id = Self::next_proposal_id();
-			let _ = T::Currency::reserve(&origin, Self::proposal_bond())?;
 			let enactment_delay = sp_std::cmp::max(T::BlockNumber::from(1u32), enactment_delay);
+			let track = Self::classify_call(&call)?;
+			if let Some(track_info) = track.and_then(Self::tracks) {
+				ensure!(
+					enactment_delay >= track_info.min_enactment_delay && enactment_delay <= track_info.max_enactment_delay,
+					Error::<T>::EnactmentDelayOutOfBounds
+				);
+			}
+			let _ = T::Currency::reserve(&origin, Self::proposal_bond())?;
 
 			<Proposals<T>>::insert(proposal_id, Proposal {
 				sponsor: origin,
@@ -201,12 +448,16 @@ decl_module! {
 				enactment_delay,
 			});
 			ProposalCalls::insert(proposal_id, call);
+			if let Some(track) = track {
+				ProposalTrack::insert(proposal_id, track);
+			}
 
 			// sponsor should vote yes
 			let mut votes = ProposalVoteInfo::default();
 			votes.record_vote(sponsor_idx.unwrap() as u8, true);
 			ProposalVotes::insert(proposal_id, votes);
 			ProposalStatus::insert(proposal_id, ProposalStatusInfo::Deliberation);
+			<DeliberationStartTime<T>>::insert(proposal_id, <frame_system::Pallet<T>>::block_number());
 
 			NextProposalId::put(proposal_id.saturating_add(1));
 		}
@@ -237,6 +488,9 @@ decl_module! {
 
 			// if we have more than 50% approval
 			let threshold = <Council<T>>::decode_len().unwrap_or(1) as u32 / 2;
+			if tally.yes > threshold || tally.no > threshold {
+				<DeliberationStartTime<T>>::remove(proposal_id);
+			}
 			if tally.yes > threshold {
 				if ProposalCalls::contains_key(proposal_id) {
 					let start_time: T::BlockNumber = <frame_system::Pallet<T>>::block_number();
@@ -269,14 +523,15 @@ decl_module! {
 		) {
 			ensure_root(origin)?;
 			let mut council = Self::council();
-			// TODO: add voter to all active proposals
 
 			// Validate council members identity and staking assets
 			Self::check_council_account_validity(&new_member)?;
 
 			ensure!(council.len() < T::MaxCouncilSize::get() as usize, Error::<T>::MaxCouncilReached);
 			if let Err(idx) = council.binary_search(&new_member) {
+				let old_council = council.clone();
 				council.insert(idx, new_member);
+				Self::remap_council_votes(&old_council, &council);
 				Council::<T>::put(council);
 			}
 		}
@@ -291,9 +546,10 @@ decl_module! {
 			ensure_root(origin)?;
 			let mut council = Self::council();
 			ensure!(council.len() > 1, Error::<T>::MinCouncilReached);
-			// TODO: remove voter from all active proposals
 			if let Ok(idx) = council.binary_search(&remove_member) {
+				let old_council = council.clone();
 				council.remove(idx);
+				Self::remap_council_votes(&old_council, &council);
 				Council::<T>::put(council);
 			}
 		}
@@ -316,27 +572,114 @@ decl_module! {
 		}
 
 		/// Submit a veto for a referendum
+		/// The veto is weighted 1x by the staked amount, without locking it
 		#[weight = 1_000_000]
 		fn vote_against_referendum(
 			origin,
 			proposal_id: ProposalId,
+		) -> DispatchResult {
+			Self::do_vote_against_referendum(ensure_signed(origin)?, proposal_id, Conviction::None)
+		}
+
+		/// Submit a veto for a referendum
+		/// `conviction` multiplies the voting power in exchange for locking the staked amount
+		#[weight = 1_000_000]
+		fn vote_against_referendum_with_conviction(
+			origin,
+			proposal_id: ProposalId,
+			conviction: Conviction,
+		) -> DispatchResult {
+			Self::do_vote_against_referendum(ensure_signed(origin)?, proposal_id, conviction)
+		}
+
+		/// Delegate referendum voting power to `target`
+		/// The delegated power counts toward any veto `target`, or an account it delegates to, submits.
+		/// It is tallied from the delegator's stake when the referendum ends, up to the stake when delegating,
+		/// and only while the delegator may still vote. The delegated stake is locked until the conviction
+		/// period passes after `undelegate`.
+		/// An account which others delegate to can't delegate itself.
+		#[weight = 1_000_000]
+		fn delegate(
+			origin,
+			target: T::AccountId,
+			conviction: Conviction,
 		) -> DispatchResult {
 			let origin = ensure_signed(origin)?;
-			let start_time = Self::referendum_start_time(proposal_id).ok_or(Error::<T>::ProposalMissing)?;
-			ensure!(Self::proposal_status(proposal_id) == Some(ProposalStatusInfo::ReferendumDeliberation), Error::<T>::ReferendumNotDeliberating);
-			ensure!(!ReferendumVotes::<T>::contains_key(proposal_id, &origin), Error::<T>::DoubleVote);
-			// Validate council members identity and staking assets
-			let staked_amount: VotingPower = T::StakingAmount::active_balance(&origin);
+			ensure!(!<Delegations<T>>::contains_key(&origin), Error::<T>::AlreadyDelegating);
+			ensure!(origin != target, Error::<T>::InvalidDelegation);
+			// with no delegators, `origin` can't be part of `target`'s delegation chain
+			// and existing chains never grow longer than `MAX_DELEGATION_DEPTH`
+			ensure!(Self::delegated_votes(&origin).1.is_zero(), Error::<T>::HasDelegators);
+			let staked_amount: Balance = T::StakingAmount::active_balance(&origin);
 			Self::check_voter_account_validity(&origin, staked_amount)?;
-			let block_number = <frame_system::Pallet<T>>::block_number();
-			ensure!(block_number >= start_time, Error::<T>::ReferendumNotStarted);
-			// Enter vote in storage
-			ReferendumVotes::<T>::insert(
-				proposal_id,
-				origin,
-				staked_amount,
-			);
-			ReferendumVetoSum::mutate(proposal_id, |n| *n += staked_amount);
+
+			let mut chain = vec![target.clone()];
+			let mut next = target.clone();
+			while let Some(delegation) = Self::delegations(&next) {
+				ensure!(chain.len() < MAX_DELEGATION_DEPTH as usize, Error::<T>::InvalidDelegation);
+				next = delegation.target;
+				chain.push(next.clone());
+			}
+
+			let power = conviction.votes(staked_amount);
+			<DelegatedVotes<T>>::mutate(&target, |(_, delegators)| *delegators = delegators.saturating_add(1));
+			for account in chain {
+				<DelegatedVotes<T>>::mutate(account, |(delegated, _)| *delegated = delegated.saturating_add(power));
+			}
+
+			// the lock is held until the delegation ends, see `undelegate`
+			Self::extend_voting_lock(&origin, staked_amount, <frame_system::Pallet<T>>::block_number());
+			<Delegators<T>>::insert(&target, &origin, ());
+			<Delegations<T>>::insert(&origin, Delegation { target, conviction, power });
+			Ok(())
+		}
+
+		/// Stop delegating referendum voting power
+		/// Any conviction lock expires after the conviction period from now
+		#[weight = 1_000_000]
+		fn undelegate(origin) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let delegation = <Delegations<T>>::take(&origin).ok_or(Error::<T>::NotDelegating)?;
+
+			// remove our own and our delegators' power from the chain
+			let power = delegation.power.saturating_add(Self::delegated_votes(&origin).0);
+			<DelegatedVotes<T>>::mutate(&delegation.target, |(_, delegators)| *delegators = delegators.saturating_sub(1));
+			<Delegators<T>>::remove(&delegation.target, &origin);
+			let mut next = Some(delegation.target);
+			for _ in 0..MAX_DELEGATION_DEPTH {
+				let account = match next {
+					Some(account) => account,
+					None => break,
+				};
+				<DelegatedVotes<T>>::mutate_exists(&account, |votes| {
+					if let Some((delegated, delegators)) = votes {
+						*delegated = delegated.saturating_sub(power);
+						if delegated.is_zero() && delegators.is_zero() {
+							*votes = None;
+						}
+					}
+				});
+				next = Self::delegations(&account).map(|d| d.target);
+			}
+
+			if let Some((amount, unlock_at)) = Self::voting_locks(&origin) {
+				let delegation_unlock_at = <frame_system::Pallet<T>>::block_number() + Self::conviction_lock_duration(delegation.conviction);
+				<VotingLocks<T>>::insert(&origin, (amount, unlock_at.max(delegation_unlock_at)));
+			}
+			Ok(())
+		}
+
+		/// Remove an expired conviction voting lock
+		#[weight = 100_000]
+		fn unlock(origin, target: T::AccountId) -> DispatchResult {
+			ensure_signed(origin)?;
+			let (_, unlock_at) = Self::voting_locks(&target).ok_or(Error::<T>::VotingLockActive)?;
+			ensure!(<frame_system::Pallet<T>>::block_number() >= unlock_at, Error::<T>::VotingLockActive);
+			// an active delegation keeps the stake locked
+			ensure!(!<Delegations<T>>::contains_key(&target), Error::<T>::VotingLockActive);
+
+			T::StakingCurrency::remove_lock(VOTING_LOCK_ID, &target);
+			<VotingLocks<T>>::remove(&target);
 			Ok(())
 		}
 
@@ -373,6 +716,88 @@ decl_module! {
 			ProposalBond::put(new_proposal_bond);
 		}
 
+		/// Adjust the length in blocks of a referendum on the default track
+		/// This must be submitted like any other proposal
+		#[weight = 100_000]
+		fn set_referendum_length(
+			origin,
+			new_referendum_length: T::BlockNumber,
+		) {
+			ensure_root(origin)?;
+			<ReferendumLength<T>>::put(new_referendum_length);
+		}
+
+		/// Adjust the interval in blocks in which referendums are checked for ending
+		/// This must be submitted like any other proposal
+		#[weight = 100_000]
+		fn set_referendum_check_interval(
+			origin,
+			new_referendum_check_interval: T::BlockNumber,
+		) {
+			ensure_root(origin)?;
+			<ReferendumCheckInterval<T>>::put(new_referendum_check_interval);
+		}
+
+		/// Adjust the minimum registered identities required for council members and voters
+		/// This must be submitted like any other proposal
+		#[weight = 100_000]
+		fn set_minimum_registered_identities(
+			origin,
+			new_minimum_registered_identities: u32,
+		) {
+			ensure_root(origin)?;
+			MinimumRegisteredIdentities::put(new_minimum_registered_identities);
+		}
+
+		/// Create, update (`Some`) or remove (`None`) a governance track
+		/// This must be submitted like any other proposal
+		#[weight = 100_000]
+		fn set_track(
+			origin,
+			track_id: TrackId,
+			track_info: Option<TrackInfo<T::BlockNumber>>,
+		) {
+			ensure_root(origin)?;
+			match track_info {
+				Some(track_info) => {
+					ensure!(track_info.min_enactment_delay <= track_info.max_enactment_delay, Error::<T>::InvalidTrack);
+					<Tracks<T>>::insert(track_id, track_info);
+				}
+				None => <Tracks<T>>::remove(track_id),
+			}
+		}
+
+		/// Assign (`Some`) or unassign (`None`) the track for calls of `pallet_name` and `function_name`
+		/// An empty `function_name` assigns all calls of the pallet
+		/// This must be submitted like any other proposal
+		#[weight = 100_000]
+		fn set_call_track(
+			origin,
+			pallet_name: Vec<u8>,
+			function_name: Vec<u8>,
+			track_id: Option<TrackId>,
+		) {
+			ensure_root(origin)?;
+			match track_id {
+				Some(track_id) => {
+					ensure!(<Tracks<T>>::contains_key(track_id), Error::<T>::TrackMissing);
+					CallTracks::insert(pallet_name, function_name, track_id);
+				}
+				None => CallTracks::remove(pallet_name, function_name),
+			}
+		}
+
+		/// Adjust the number of blocks a proposal may remain in council deliberation
+		/// This must be submitted like any other proposal
+		#[weight = 100_000]
+		fn set_deliberation_period(
+			origin,
+			new_deliberation_period: T::BlockNumber,
+		) {
+			ensure_root(origin)?;
+			<DeliberationPeriod<T>>::put(new_deliberation_period);
+		}
+
 		/// Adjust the minimum stake required for new council members
 		#[weight = 100_000]
 		fn set_minimum_council_stake(
@@ -413,6 +838,88 @@ impl<T: Config> Module<T> {
 	pub fn get_council() -> Vec<T::AccountId> {
 		Self::council()
 	}
+	/// Return the current council election state
+	pub fn get_election_info() -> ElectionInfo<T::AccountId> {
+		let approvals = <ElectionVotes<T>>::iter().fold(
+			Vec::<(T::AccountId, Balance)>::new(),
+			|mut approvals, (voter, votes)| {
+				let stake = T::StakingAmount::active_balance(&voter);
+				for candidate in votes {
+					match approvals.iter_mut().find(|(c, _)| *c == candidate) {
+						Some((_, total)) => *total = total.saturating_add(stake),
+						None => approvals.push((candidate, stake)),
+					}
+				}
+				approvals
+			},
+		);
+		let candidates = Self::election_candidates()
+			.into_iter()
+			.map(|candidate| {
+				let stake = approvals
+					.iter()
+					.find(|(c, _)| *c == candidate)
+					.map_or(Zero::zero(), |(_, stake)| *stake);
+				(candidate, stake)
+			})
+			.collect();
+
+		let term_duration = T::TermDuration::get();
+		let block_number = <frame_system::Pallet<T>>::block_number();
+		let next_election_block = if term_duration.is_zero() {
+			Zero::zero()
+		} else {
+			block_number - (block_number % term_duration) + term_duration
+		};
+
+		ElectionInfo {
+			candidates,
+			council: Self::council(),
+			runners_up: Self::runners_up(),
+			election_rounds: Self::election_rounds(),
+			next_election_block: next_election_block.unique_saturated_into(),
+		}
+	}
+	/// Return the lifecycle state of a proposal
+	pub fn get_proposal_info(proposal_id: ProposalId) -> Option<ProposalInfo<T::AccountId>> {
+		let status = Self::proposal_status(proposal_id)?;
+		let proposal = Self::proposals(proposal_id);
+		let call = Self::proposal_calls(proposal_id);
+		let call_name = call
+			.as_ref()
+			.and_then(|call| <T as Config>::Call::decode(&mut &call[..]).ok())
+			.map(|call| {
+				let metadata = call.get_call_metadata();
+				(
+					metadata.pallet_name.as_bytes().to_vec(),
+					metadata.function_name.as_bytes().to_vec(),
+				)
+			});
+
+		Some(ProposalInfo {
+			proposal_id,
+			sponsor: proposal.as_ref().map(|p| p.sponsor.clone()),
+			justification_uri: proposal
+				.as_ref()
+				.map(|p| p.justification_uri.clone())
+				.unwrap_or_default(),
+			enactment_delay: proposal.map_or(0, |p| p.enactment_delay.unique_saturated_into()),
+			call,
+			call_name,
+			track: Self::proposal_track(proposal_id),
+			status,
+			deliberation_start_time: Self::deliberation_start_time(proposal_id).map(|b| b.unique_saturated_into()),
+			referendum_start_time: Self::referendum_start_time(proposal_id).map(|b| b.unique_saturated_into()),
+			referendum_veto_sum: Self::referendum_veto_sum(proposal_id),
+			council_votes: Self::proposal_votes(proposal_id).count_votes(),
+		})
+	}
+	/// Return the lifecycle state of all known proposals, ordered by Id
+	pub fn get_proposals() -> Vec<ProposalInfo<T::AccountId>> {
+		let mut proposal_ids: Vec<ProposalId> = ProposalStatus::iter_keys().collect();
+		proposal_ids.sort();
+		proposal_ids.into_iter().filter_map(Self::get_proposal_info).collect()
+	}
 	/// Return all vote information on active proposals
 	pub fn get_proposal_votes() -> Vec<(ProposalId, ProposalVoteInfo)> {
 		ProposalVotes::iter().collect()
@@ -429,7 +936,7 @@ impl<T: Config> Module<T> {
 		// Check their verified identities
 		let registration: u32 = T::Registration::registered_identity_count(account);
 		ensure!(
-			registration >= MINIMUM_REGISTERED_IDENTITIES,
+			registration >= Self::minimum_registered_identities(),
 			Error::<T>::NotEnoughRegistrations
 		);
 		Ok(())
@@ -445,25 +952,287 @@ impl<T: Config> Module<T> {
 		// Check their verified identities
 		let registration: u32 = T::Registration::registered_identity_count(account);
 		ensure!(
-			registration >= MINIMUM_REGISTERED_IDENTITIES,
+			registration >= Self::minimum_registered_identities(),
 			Error::<T>::NotEnoughRegistrations
 		);
 		Ok(())
 	}
+	/// The number of blocks a vote with `conviction` is locked for
+	fn conviction_lock_duration(conviction: Conviction) -> T::BlockNumber {
+		Self::referendum_length().saturating_mul(T::BlockNumber::from(conviction.lock_periods()))
+	}
+	/// Lock at least `amount` of `who`s staking asset until at least block `unlock_at`
+	fn extend_voting_lock(who: &T::AccountId, amount: Balance, unlock_at: T::BlockNumber) {
+		let (amount, unlock_at) = match Self::voting_locks(who) {
+			Some((locked, locked_until)) => (locked.max(amount), locked_until.max(unlock_at)),
+			None => (amount, unlock_at),
+		};
+		T::StakingCurrency::set_lock(VOTING_LOCK_ID, who, amount, WithdrawReasons::all());
+		<VotingLocks<T>>::insert(who, (amount, unlock_at));
+	}
+	/// Record a veto for a referendum weighted by `conviction`
+	fn do_vote_against_referendum(
+		origin: T::AccountId,
+		proposal_id: ProposalId,
+		conviction: Conviction,
+	) -> DispatchResult {
+		let start_time = Self::referendum_start_time(proposal_id).ok_or(Error::<T>::ProposalMissing)?;
+		ensure!(
+			Self::proposal_status(proposal_id) == Some(ProposalStatusInfo::ReferendumDeliberation),
+			Error::<T>::ReferendumNotDeliberating
+		);
+		ensure!(
+			!ReferendumVotes::<T>::contains_key(proposal_id, &origin),
+			Error::<T>::DoubleVote
+		);
+		ensure!(!<Delegations<T>>::contains_key(&origin), Error::<T>::AlreadyDelegating);
+		// Validate council members identity and staking assets
+		let staked_amount: Balance = T::StakingAmount::active_balance(&origin);
+		Self::check_voter_account_validity(&origin, staked_amount)?;
+		let block_number = <frame_system::Pallet<T>>::block_number();
+		ensure!(block_number >= start_time, Error::<T>::ReferendumNotStarted);
+		// Enter vote in storage
+		let voting_power = conviction.votes(staked_amount);
+		ReferendumVotes::<T>::insert(proposal_id, &origin, voting_power);
+		ReferendumVetoSum::mutate(proposal_id, |n| *n = n.saturating_add(voting_power));
+
+		if conviction.lock_periods() > 0 {
+			let unlock_at = block_number + Self::conviction_lock_duration(conviction);
+			Self::extend_voting_lock(&origin, staked_amount, unlock_at);
+		}
+		Ok(())
+	}
+
+	/// Tally the voting power delegated (transitively) to accounts which vetoed `proposal_id`
+	/// Returns the delegated power and the number of vetoes and delegations tallied
+	pub fn delegated_veto_sum(proposal_id: ProposalId) -> (VotingPower, u32) {
+		ReferendumVotes::<T>::iter_prefix(proposal_id).fold(
+			(Zero::zero(), 0),
+			|(sum, count): (VotingPower, u32), (voter, _)| {
+				let (delegated, delegations) = Self::delegated_power(&voter);
+				(
+					sum.saturating_add(delegated),
+					count.saturating_add(1).saturating_add(delegations),
+				)
+			},
+		)
+	}
+	/// The current voting power delegated (transitively) to `who`
+	/// Returns the delegated power and the number of delegations tallied
+	fn delegated_power(who: &T::AccountId) -> (VotingPower, u32) {
+		let mut power: VotingPower = Zero::zero();
+		let mut count = 0_u32;
+		let mut targets = vec![who.clone()];
+		// delegation chains are at most `MAX_DELEGATION_DEPTH` long
+		for _ in 0..MAX_DELEGATION_DEPTH {
+			if targets.is_empty() {
+				break;
+			}
+			let mut next = Vec::new();
+			for target in targets {
+				for (delegator, _) in <Delegators<T>>::iter_prefix(&target) {
+					if let Some(delegation) = Self::delegations(&delegator) {
+						power = power.saturating_add(Self::delegation_power(&delegator, &delegation));
+					}
+					count = count.saturating_add(1);
+					next.push(delegator);
+				}
+			}
+			targets = next;
+		}
+		(power, count)
+	}
+	/// The voting power of `delegator`s current stake, up to the power delegated
+	/// Zero if `delegator` no longer meets the voter requirements
+	fn delegation_power(delegator: &T::AccountId, delegation: &Delegation<T::AccountId>) -> VotingPower {
+		let staked_amount: Balance = T::StakingAmount::active_balance(delegator);
+		if Self::check_voter_account_validity(delegator, staked_amount).is_err() {
+			return Zero::zero();
+		}
+		delegation.conviction.votes(staked_amount).min(delegation.power)
+	}
+	/// Whether `who` is a council candidate, councilor or runner-up
+	fn is_candidate(who: &T::AccountId) -> bool {
+		Self::candidates().binary_search(who).is_ok()
+			|| Self::council().binary_search(who).is_ok()
+			|| Self::runners_up().contains(who)
+	}
+	/// All accounts standing in the next election
+	/// councilors and runners-up stand again automatically
+	fn election_candidates() -> Vec<T::AccountId> {
+		let mut candidates: Vec<T::AccountId> = Self::candidates()
+			.into_iter()
+			.chain(Self::council())
+			.chain(Self::runners_up())
+			.collect();
+		candidates.sort();
+		candidates.dedup();
+		candidates
+	}
+	/// The weight of a council election with the max. number of candidates and voters
+	pub fn election_weight() -> Weight {
+		let council_size = T::MaxCouncilSize::get() as Weight;
+		let seats = council_size.saturating_add(T::DesiredRunnersUp::get() as Weight);
+		let candidates = (T::MaxCandidates::get() as Weight).saturating_add(seats);
+		let voters = T::MaxVoters::get() as Weight;
+		// seq-phragmen visits every approval once per seat elected
+		let approvals = voters.saturating_mul(council_size.min(candidates));
+		let rounds = seats.min(candidates);
+		T::DbWeight::get()
+			// voters, their stake and candidates' validity, deposits
+			.reads_writes(
+				voters.saturating_mul(2).saturating_add(candidates.saturating_mul(3)),
+				candidates.saturating_add(4),
+			)
+			.saturating_add(candidates.saturating_add(voters).saturating_mul(1_000_000))
+			.saturating_add(rounds.saturating_mul(approvals).saturating_mul(20_000))
+	}
+	/// Elect a new council and runners-up by approval stake using seq-phragmen
+	/// The existing council is kept if no candidate receives any approval
+	/// Candidates and voters are bounded by `MaxCandidates` and `MaxVoters`, see `election_weight`
+	pub fn do_election() -> Weight {
+		let all_candidates = Self::election_candidates();
+		let eligible_candidates: Vec<T::AccountId> = all_candidates
+			.iter()
+			.filter(|c| Self::check_council_account_validity(c).is_ok())
+			.cloned()
+			.collect();
+
+		let total_staked: Balance = T::StakingAmount::total_staked();
+		let voters: Vec<(T::AccountId, VoteWeight, Vec<T::AccountId>)> = <ElectionVotes<T>>::iter()
+			.map(|(voter, votes)| {
+				let stake = T::StakingAmount::active_balance(&voter);
+				(voter, T::CurrencyToVote::to_vote(stake, total_staked), votes)
+			})
+			.collect();
+		let weight = Self::election_weight();
+
+		let council_size = T::MaxCouncilSize::get() as usize;
+		let to_elect = council_size + T::DesiredRunnersUp::get() as usize;
+		let mut winners = match seq_phragmen::<T::AccountId, Perbill>(to_elect, eligible_candidates, voters, None) {
+			Ok(result) => result.winners,
+			Err(err) => {
+				warn!("council election failed: {:?}", err);
+				return weight;
+			}
+		};
+		if winners.is_empty() {
+			warn!("council election has no winners, keeping the current council");
+			return weight;
+		}
+		// most backed first
+		winners.sort_by(|(_, a), (_, b)| b.cmp(a));
+		let mut winners: Vec<T::AccountId> = winners.into_iter().map(|(winner, _)| winner).collect();
+		let runners_up = winners.split_off(council_size.min(winners.len()));
+		winners.sort();
+
+		// return bonds to everyone who wasn't re-elected
+		for account in all_candidates {
+			if winners.binary_search(&account).is_err() && !runners_up.contains(&account) {
+				let _ = T::Currency::unreserve(&account, CandidacyDeposits::<T>::take(&account));
+			}
+		}
+
+		// council positions change, keep the votes of re-elected councilors
+		let remapped = Self::remap_council_votes(&Self::council(), &winners);
+		<Council<T>>::put(winners);
+		<RunnersUp<T>>::put(runners_up);
+		<Candidates<T>>::kill();
+		let round = Self::election_rounds().saturating_add(1);
+		ElectionRounds::put(round);
+		Self::deposit_event(Event::CouncilElected(round));
+
+		weight.saturating_add(remapped as Weight * 1_000_000)
+	}
+	/// Move council votes on proposals in deliberation to the voters' positions in `new_council`
+	/// Votes of members who left the council are dropped
+	/// Returns the number of proposals updated
+	fn remap_council_votes(old_council: &[T::AccountId], new_council: &[T::AccountId]) -> u32 {
+		let deliberating: Vec<(ProposalId, ProposalVoteInfo)> = ProposalVotes::iter()
+			.filter(|(proposal_id, _)| Self::proposal_status(proposal_id) == Some(ProposalStatusInfo::Deliberation))
+			.collect();
+		let count = deliberating.len() as u32;
+		for (proposal_id, votes) in deliberating {
+			let mut new_votes = ProposalVoteInfo::default();
+			for (old_idx, member) in old_council.iter().enumerate() {
+				if let (Some(vote), Ok(new_idx)) = (votes.get_vote(old_idx as u8), new_council.binary_search(member)) {
+					new_votes.record_vote(new_idx as u8, vote);
+				}
+			}
+			ProposalVotes::insert(proposal_id, new_votes);
+		}
+		count
+	}
+	/// Find the track for an encoded call by its (pallet, function) name, then by pallet name
+	/// Wrapper calls follow the track of the calls they dispatch, which must not belong to different tracks
+	/// Returns `None` for the default track
+	pub fn classify_call(call: &[u8]) -> Result<Option<TrackId>, DispatchError> {
+		match <T as Config>::Call::decode(&mut &call[..]) {
+			Ok(call) => Self::classify_decoded_call(&call, 0),
+			Err(_) => Ok(None),
+		}
+	}
+	fn classify_decoded_call(call: &<T as Config>::Call, depth: u32) -> Result<Option<TrackId>, DispatchError> {
+		let metadata = call.get_call_metadata();
+		let pallet_name = metadata.pallet_name.as_bytes().to_vec();
+		let mut track = Self::call_tracks(&pallet_name, metadata.function_name.as_bytes().to_vec())
+			.or_else(|| Self::call_tracks(&pallet_name, Vec::<u8>::new()));
+
+		if let Some(wrapped_calls) = T::WrappedCalls::wrapped_calls(call) {
+			ensure!(depth < MAX_CALL_DEPTH, Error::<T>::CallTooDeep);
+			for wrapped_call in wrapped_calls.iter() {
+				match (track, Self::classify_decoded_call(wrapped_call, depth + 1)?) {
+					(_, None) => (),
+					(None, wrapped_track) => track = wrapped_track,
+					(Some(track), Some(wrapped_track)) => ensure!(track == wrapped_track, Error::<T>::MixedTracks),
+				}
+			}
+		}
+		Ok(track)
+	}
+	/// The referendum length of a proposal according to its track
+	fn referendum_length_of(proposal_id: ProposalId) -> T::BlockNumber {
+		Self::proposal_track(proposal_id)
+			.and_then(Self::tracks)
+			.map_or_else(Self::referendum_length, |track| track.referendum_length)
+	}
+	/// The referendum veto threshold of a proposal according to its track
+	fn veto_threshold_of(proposal_id: ProposalId) -> Permill {
+		Self::proposal_track(proposal_id)
+			.and_then(Self::tracks)
+			.map_or_else(Self::referendum_threshold, |track| track.veto_threshold)
+	}
+	/// Expire a proposal the council did not decide on in time, returning the sponsor's bond
+	fn expire_proposal(proposal_id: ProposalId) {
+		<DeliberationStartTime<T>>::remove(proposal_id);
+		if Self::proposal_status(proposal_id) != Some(ProposalStatusInfo::Deliberation) {
+			return;
+		}
+		if let Some(proposal) = <Proposals<T>>::take(proposal_id) {
+			let _ = T::Currency::unreserve(&proposal.sponsor, Self::proposal_bond());
+		}
+		ProposalCalls::remove(proposal_id);
+		ProposalVotes::remove(proposal_id);
+		ProposalStatus::insert(proposal_id, ProposalStatusInfo::Disapproved);
+		Self::deposit_event(Event::ProposalExpired(proposal_id));
+	}
 	/// Finish up a referendum and tally vetos
-	pub fn end_referendum(proposal_id: ProposalId) {
+	/// Returns the number of vetoes and delegations tallied
+	pub fn end_referendum(proposal_id: ProposalId) -> u32 {
 		let proposal = match Self::proposals(proposal_id) {
 			Some(proposal) => proposal,
 			None => {
 				warn!("clean up proposal: {:?} failed, not found", proposal_id);
-				return;
+				return 0;
 			}
 		};
 		let total_staked: Balance = T::StakingAmount::total_staked();
+		let (delegated_veto_sum, vetoes) = Self::delegated_veto_sum(proposal_id);
+		let veto_sum = Self::referendum_veto_sum(proposal_id).saturating_add(delegated_veto_sum);
+		ReferendumVetoSum::insert(proposal_id, veto_sum);
 		ReferendumVotes::<T>::remove_prefix(proposal_id, None);
 
-		if Permill::from_rational(Self::referendum_veto_sum(proposal_id), total_staked) >= Self::referendum_threshold()
-		{
+		if Permill::from_rational(veto_sum, total_staked) >= Self::veto_threshold_of(proposal_id) {
 			// Too many veto votes, not going ahead
 			Self::deposit_event(Event::ReferendumVeto(proposal_id));
 			let _ = T::Currency::slash_reserved(&proposal.sponsor, Self::proposal_bond());
@@ -493,5 +1262,15 @@ impl<T: Config> Module<T> {
 				ProposalStatus::insert(proposal_id, ProposalStatusInfo::ApprovedEnacted(true));
 			}
 		}
+
+		vetoes
+	}
+}
+
+impl<T: Config> CouncilProvider for Module<T> {
+	type AccountId = T::AccountId;
+
+	fn council() -> Vec<Self::AccountId> {
+		Self::council()
 	}
 }
@@ -25,6 +25,7 @@ use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup},
 	Perbill,
 };
+use std::{cell::RefCell, collections::BTreeMap};
 
 pub type AccountId = u64;
 
@@ -103,18 +104,32 @@ impl pallet_scheduler::Config for Test {
 	type OriginPrivilegeCmp = frame_support::traits::EqualPrivilegeOnly;
 }
 
+thread_local! {
+	static ACTIVE_BALANCES: RefCell<BTreeMap<AccountId, Balance>> = RefCell::new(BTreeMap::new());
+}
+
 pub struct MockStakingAmount;
+impl MockStakingAmount {
+	/// Change the active balance of `controller` e.g. after unbonding
+	pub fn set_active_balance(controller: AccountId, amount: Balance) {
+		ACTIVE_BALANCES.with(|balances| balances.borrow_mut().insert(controller, amount));
+	}
+}
 impl StakingAmount for MockStakingAmount {
 	type AccountId = AccountId;
 	type Balance = Balance;
 
 	fn active_balance(controller: &Self::AccountId) -> Self::Balance {
+		if let Some(amount) = ACTIVE_BALANCES.with(|balances| balances.borrow().get(controller).copied()) {
+			return amount;
+		}
 		match controller {
 			1 => 1_000,
 			2 => 20_000_000,
 			3 => 30_000_000,
 			4 => 30_000_000,
 			5 => 30_000_000,
+			6 => 30_000_000,
 			_ => 0,
 		}
 	}
@@ -135,6 +150,7 @@ impl RegistrationInfo for MockRegistrationImplementation {
 			3 => 3,
 			4 => 2,
 			5 => 2,
+			6 => 2,
 			_ => 0,
 		}
 	}
@@ -144,10 +160,16 @@ parameter_types! {
 	pub const DefaultListingDuration: u64 = 5;
 	pub const MaxAttributeLength: u8 = 140;
 	pub const MaxCouncilSize: u16 = 3;
+	pub const CandidacyBond: Balance = 100;
+	pub const TermDuration: u64 = 50_000;
+	pub const DesiredRunnersUp: u32 = 1;
+	pub const MaxCandidates: u32 = 4;
+	pub const MaxVoters: u32 = 4;
 }
 impl crate::Config for Test {
 	type Call = Call;
 	type Currency = StakingAssetCurrency<Self>;
+	type StakingCurrency = StakingAssetCurrency<Self>;
 	type MaxCouncilSize = MaxCouncilSize;
 	type Scheduler = Scheduler;
 	type PalletsOrigin = OriginCaller;
@@ -155,6 +177,24 @@ impl crate::Config for Test {
 	type WeightInfo = ();
 	type Registration = MockRegistrationImplementation;
 	type StakingAmount = MockStakingAmount;
+	type CandidacyBond = CandidacyBond;
+	type TermDuration = TermDuration;
+	type DesiredRunnersUp = DesiredRunnersUp;
+	type MaxCandidates = MaxCandidates;
+	type MaxVoters = MaxVoters;
+	type CurrencyToVote = frame_support::traits::SaturatingCurrencyToVote;
+	type WrappedCalls = MockWrappedCalls;
+}
+
+/// Treats scheduled calls as wrapped calls
+pub struct MockWrappedCalls;
+impl crml_governance::WrappedCalls<Call> for MockWrappedCalls {
+	fn wrapped_calls(call: &Call) -> Option<Vec<Call>> {
+		match call {
+			Call::Scheduler(pallet_scheduler::Call::schedule { call, .. }) => Some(vec![*call.clone()]),
+			_ => None,
+		}
+	}
 }
 
 #[derive(Default)]
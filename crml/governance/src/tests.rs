@@ -1,6 +1,9 @@
 use super::*;
-use crate::mock::{ExtBuilder, Governance, MockStakingAmount, Test};
-use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
+use crate::mock::{CandidacyBond, ExtBuilder, Governance, MockStakingAmount, System, TermDuration, Test};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{OnInitialize, OnRuntimeUpgrade},
+};
 use sp_runtime::DispatchError;
 
 // Helper function to setup a vector of accounts as council members
@@ -591,3 +594,1066 @@ fn end_referendum_with_0_enactment_delay() {
 		);
 	});
 }
+
+#[test]
+fn vote_against_referendum_with_conviction_locks_stake() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_account = 3_u64;
+		let voting_account = 4_u64;
+		let justification_uri: Vec<u8> = vec![0];
+		let enactment_delay = 1;
+		let call = "0x123456"; // Invalid call
+		let proposal_id = setup_referendum(
+			proposal_account,
+			voting_account,
+			call.into(),
+			justification_uri,
+			enactment_delay,
+		);
+
+		let non_councilor_account = 5_u64;
+		assert_ok!(Governance::vote_against_referendum_with_conviction(
+			frame_system::RawOrigin::Signed(non_councilor_account).into(),
+			proposal_id,
+			Conviction::Locked2x
+		));
+		let staked_amount = MockStakingAmount::active_balance(&non_councilor_account);
+		assert_eq!(
+			Governance::referendum_votes(proposal_id, &non_councilor_account),
+			staked_amount * 2
+		);
+		assert_eq!(Governance::referendum_veto_sum(proposal_id), staked_amount * 2);
+
+		let unlock_at = 1 + Governance::referendum_length();
+		assert_eq!(
+			Governance::voting_locks(non_councilor_account),
+			Some((staked_amount, unlock_at))
+		);
+		assert_noop!(
+			Governance::unlock(
+				frame_system::RawOrigin::Signed(non_councilor_account).into(),
+				non_councilor_account
+			),
+			Error::<Test>::VotingLockActive
+		);
+
+		System::set_block_number(unlock_at);
+		assert_ok!(Governance::unlock(
+			frame_system::RawOrigin::Signed(non_councilor_account).into(),
+			non_councilor_account
+		));
+		assert_eq!(Governance::voting_locks(non_councilor_account), None);
+	});
+}
+
+#[test]
+fn vote_against_referendum_without_conviction_is_not_locked() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_account = 3_u64;
+		let voting_account = 4_u64;
+		let justification_uri: Vec<u8> = vec![0];
+		let enactment_delay = 1;
+		let call = "0x123456"; // Invalid call
+		let proposal_id = setup_referendum(
+			proposal_account,
+			voting_account,
+			call.into(),
+			justification_uri,
+			enactment_delay,
+		);
+
+		let non_councilor_account = 5_u64;
+		assert_ok!(Governance::vote_against_referendum_with_conviction(
+			frame_system::RawOrigin::Signed(non_councilor_account).into(),
+			proposal_id,
+			Conviction::None
+		));
+		// same weight as a plain veto
+		assert_eq!(
+			Governance::referendum_votes(proposal_id, &non_councilor_account),
+			MockStakingAmount::active_balance(&non_councilor_account)
+		);
+		assert_eq!(Governance::voting_locks(non_councilor_account), None);
+	});
+}
+
+#[test]
+fn vote_against_referendum_while_delegating_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_account = 3_u64;
+		let voting_account = 4_u64;
+		let justification_uri: Vec<u8> = vec![0];
+		let enactment_delay = 1;
+		let call = "0x123456"; // Invalid call
+		let proposal_id = setup_referendum(
+			proposal_account,
+			voting_account,
+			call.into(),
+			justification_uri,
+			enactment_delay,
+		);
+
+		let delegator = 5_u64;
+		assert_ok!(Governance::delegate(
+			frame_system::RawOrigin::Signed(delegator).into(),
+			voting_account,
+			Conviction::None
+		));
+		assert_noop!(
+			Governance::vote_against_referendum(frame_system::RawOrigin::Signed(delegator).into(), proposal_id),
+			Error::<Test>::AlreadyDelegating
+		);
+	});
+}
+
+#[test]
+fn delegated_votes_are_counted_transitively() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_account = 3_u64;
+		let voting_account = 4_u64;
+		let justification_uri: Vec<u8> = vec![0];
+		let enactment_delay = 1;
+		let call = "0x123456"; // Invalid call
+		let proposal_id = setup_referendum(
+			proposal_account,
+			voting_account,
+			call.into(),
+			justification_uri,
+			enactment_delay,
+		);
+
+		// 6 -> 5 -> 4
+		let delegator = 5_u64;
+		let transitive_delegator = 6_u64;
+		assert_ok!(Governance::delegate(
+			frame_system::RawOrigin::Signed(delegator).into(),
+			voting_account,
+			Conviction::Locked2x
+		));
+		assert_ok!(Governance::delegate(
+			frame_system::RawOrigin::Signed(transitive_delegator).into(),
+			delegator,
+			Conviction::None
+		));
+		let expected_delegated = MockStakingAmount::active_balance(&delegator) * 2
+			+ MockStakingAmount::active_balance(&transitive_delegator);
+		assert_eq!(Governance::delegated_votes(voting_account), (expected_delegated, 1));
+		assert_eq!(
+			Governance::delegated_votes(delegator),
+			(MockStakingAmount::active_balance(&transitive_delegator), 1)
+		);
+		assert_eq!(Governance::delegated_veto_sum(proposal_id), (0, 0));
+
+		assert_ok!(Governance::vote_against_referendum(
+			frame_system::RawOrigin::Signed(voting_account).into(),
+			proposal_id
+		));
+		// 1 veto and 2 delegations
+		assert_eq!(Governance::delegated_veto_sum(proposal_id), (expected_delegated, 3));
+
+		Governance::on_initialize(22000);
+		assert_eq!(
+			Governance::proposal_status(proposal_id),
+			Some(ProposalStatusInfo::ReferendumVetoed)
+		);
+		assert_eq!(
+			Governance::referendum_veto_sum(proposal_id),
+			0,
+			"vetoed referendum is cleaned up"
+		);
+	});
+}
+
+#[test]
+fn delegated_votes_are_tallied_with_current_stake() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_account = 3_u64;
+		let voting_account = 4_u64;
+		let justification_uri: Vec<u8> = vec![0];
+		let enactment_delay = 1;
+		let call = "0x123456"; // Invalid call
+		let proposal_id = setup_referendum(
+			proposal_account,
+			voting_account,
+			call.into(),
+			justification_uri,
+			enactment_delay,
+		);
+
+		// 6 -> 5 -> 4
+		let delegator = 5_u64;
+		let transitive_delegator = 6_u64;
+		assert_ok!(Governance::delegate(
+			frame_system::RawOrigin::Signed(delegator).into(),
+			voting_account,
+			Conviction::Locked2x
+		));
+		assert_ok!(Governance::delegate(
+			frame_system::RawOrigin::Signed(transitive_delegator).into(),
+			delegator,
+			Conviction::None
+		));
+		assert_ok!(Governance::vote_against_referendum(
+			frame_system::RawOrigin::Signed(voting_account).into(),
+			proposal_id
+		));
+
+		// the delegator unbonds some stake, the transitive delegator too much to vote
+		let delegator_stake = MockStakingAmount::active_balance(&delegator) / 2;
+		MockStakingAmount::set_active_balance(delegator, delegator_stake);
+		MockStakingAmount::set_active_balance(transitive_delegator, 0);
+		assert_eq!(Governance::delegated_veto_sum(proposal_id), (delegator_stake * 2, 3));
+
+		// stake bonded after delegating doesn't count
+		MockStakingAmount::set_active_balance(delegator, delegator_stake * 4);
+		assert_eq!(Governance::delegated_veto_sum(proposal_id), (delegator_stake * 4, 3));
+
+		// delegated power is what's left
+		assert_ok!(Governance::undelegate(
+			frame_system::RawOrigin::Signed(transitive_delegator).into()
+		));
+		assert_eq!(Governance::delegated_veto_sum(proposal_id), (delegator_stake * 4, 2));
+	});
+}
+
+#[test]
+fn undelegate_removes_power_from_delegation_chain() {
+	ExtBuilder::default().build().execute_with(|| {
+		// 6 -> 5 -> 4
+		assert_ok!(Governance::delegate(
+			frame_system::RawOrigin::Signed(5).into(),
+			4,
+			Conviction::None
+		));
+		assert_ok!(Governance::delegate(
+			frame_system::RawOrigin::Signed(6).into(),
+			5,
+			Conviction::Locked3x
+		));
+		let power_5 = MockStakingAmount::active_balance(&5);
+		let power_6 = MockStakingAmount::active_balance(&6) * 3;
+		assert_eq!(Governance::delegated_votes(4), (power_5 + power_6, 1));
+
+		// 5 stops delegating, taking 6's delegated power with it
+		assert_ok!(Governance::undelegate(frame_system::RawOrigin::Signed(5).into()));
+		assert!(!DelegatedVotes::<Test>::contains_key(4));
+		assert_eq!(Governance::delegated_votes(5), (power_6, 1));
+
+		assert_ok!(Governance::undelegate(frame_system::RawOrigin::Signed(6).into()));
+		assert!(!DelegatedVotes::<Test>::contains_key(5));
+	});
+}
+
+#[test]
+fn delegate_cycle_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Governance::delegate(frame_system::RawOrigin::Signed(4).into(), 4, Conviction::None),
+			Error::<Test>::InvalidDelegation
+		);
+		// 4 -> 5 -> 6
+		assert_ok!(Governance::delegate(
+			frame_system::RawOrigin::Signed(5).into(),
+			6,
+			Conviction::None
+		));
+		assert_ok!(Governance::delegate(
+			frame_system::RawOrigin::Signed(4).into(),
+			5,
+			Conviction::None
+		));
+		assert_noop!(
+			Governance::delegate(frame_system::RawOrigin::Signed(6).into(), 4, Conviction::None),
+			Error::<Test>::HasDelegators
+		);
+		assert_noop!(
+			Governance::delegate(frame_system::RawOrigin::Signed(4).into(), 3, Conviction::None),
+			Error::<Test>::AlreadyDelegating
+		);
+	});
+}
+
+#[test]
+fn delegation_chain_too_long_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		// build a chain of max. length by inserting delegations directly
+		let chain_end = 100_u64;
+		for account in chain_end..chain_end + MAX_DELEGATION_DEPTH as u64 {
+			<Delegations<Test>>::insert(
+				account,
+				Delegation {
+					target: account + 1,
+					conviction: Conviction::None,
+					power: 0,
+				},
+			);
+		}
+		assert_noop!(
+			Governance::delegate(frame_system::RawOrigin::Signed(4).into(), chain_end, Conviction::None),
+			Error::<Test>::InvalidDelegation
+		);
+		assert_ok!(Governance::delegate(
+			frame_system::RawOrigin::Signed(4).into(),
+			chain_end + 1,
+			Conviction::None
+		));
+	});
+}
+
+#[test]
+fn undelegate_starts_conviction_lock_period() {
+	ExtBuilder::default().build().execute_with(|| {
+		let delegator = 5_u64;
+		assert_ok!(Governance::delegate(
+			frame_system::RawOrigin::Signed(delegator).into(),
+			4,
+			Conviction::Locked2x
+		));
+		let staked_amount = MockStakingAmount::active_balance(&delegator);
+		assert_eq!(Governance::voting_locks(delegator), Some((staked_amount, 1)));
+
+		// still delegating
+		assert_noop!(
+			Governance::unlock(frame_system::RawOrigin::Signed(delegator).into(), delegator),
+			Error::<Test>::VotingLockActive
+		);
+
+		assert_ok!(Governance::undelegate(
+			frame_system::RawOrigin::Signed(delegator).into()
+		));
+		assert_eq!(Governance::delegations(delegator), None);
+		let unlock_at = 1 + Governance::referendum_length();
+		assert_eq!(Governance::voting_locks(delegator), Some((staked_amount, unlock_at)));
+		assert_noop!(
+			Governance::undelegate(frame_system::RawOrigin::Signed(delegator).into()),
+			Error::<Test>::NotDelegating
+		);
+
+		System::set_block_number(unlock_at);
+		assert_ok!(Governance::unlock(
+			frame_system::RawOrigin::Signed(delegator).into(),
+			delegator
+		));
+		assert_eq!(Governance::voting_locks(delegator), None);
+	});
+}
+
+#[test]
+fn on_runtime_upgrade_sets_deliberation_start_time() {
+	ExtBuilder::default().build().execute_with(|| {
+		// proposals from before deliberation start times were tracked
+		ProposalStatus::insert(1, ProposalStatusInfo::Deliberation);
+		ProposalStatus::insert(2, ProposalStatusInfo::ReferendumDeliberation);
+		ProposalStatus::insert(3, ProposalStatusInfo::Deliberation);
+		<DeliberationStartTime<Test>>::insert(3, 5);
+		System::set_block_number(10);
+
+		let _ = Governance::on_runtime_upgrade();
+
+		assert_eq!(StorageVersion::get(), Releases::V1 as u32);
+		assert_eq!(Governance::deliberation_start_time(1), Some(10));
+		assert_eq!(Governance::deliberation_start_time(2), None);
+		assert_eq!(Governance::deliberation_start_time(3), Some(5));
+
+		// the migration only runs once
+		ProposalStatus::insert(4, ProposalStatusInfo::Deliberation);
+		let _ = Governance::on_runtime_upgrade();
+		assert_eq!(Governance::deliberation_start_time(4), None);
+	});
+}
+
+// Helper function to give accounts enough balance to bond candidacy and submit it
+fn setup_candidates(accounts: Vec<u64>) {
+	accounts.iter().for_each(|a| {
+		let _ = <Test as Config>::Currency::deposit_creating(a, 1_000);
+		assert_ok!(Governance::submit_candidacy(frame_system::RawOrigin::Signed(*a).into()));
+	});
+}
+
+#[test]
+fn submit_and_renounce_candidacy() {
+	ExtBuilder::default().build().execute_with(|| {
+		let candidate = 3_u64;
+		setup_candidates(vec![candidate]);
+
+		assert_eq!(Governance::candidates(), vec![candidate]);
+		assert_eq!(Governance::candidacy_deposits(candidate), CandidacyBond::get());
+		assert_eq!(
+			<Test as Config>::Currency::reserved_balance(&candidate),
+			CandidacyBond::get()
+		);
+		assert_noop!(
+			Governance::submit_candidacy(frame_system::RawOrigin::Signed(candidate).into()),
+			Error::<Test>::AlreadyCandidate
+		);
+
+		assert_ok!(Governance::renounce_candidacy(
+			frame_system::RawOrigin::Signed(candidate).into()
+		));
+		assert!(Governance::candidates().is_empty());
+		assert_eq!(<Test as Config>::Currency::reserved_balance(&candidate), 0);
+		assert_noop!(
+			Governance::renounce_candidacy(frame_system::RawOrigin::Signed(candidate).into()),
+			Error::<Test>::NotCandidate
+		);
+	});
+}
+
+#[test]
+fn submit_candidacy_not_enough_registrations_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Governance::submit_candidacy(frame_system::RawOrigin::Signed(2).into()),
+			Error::<Test>::NotEnoughRegistrations
+		);
+	});
+}
+
+#[test]
+fn council_candidates_and_voters_are_bounded() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_candidates(vec![3, 4, 5]);
+		// a 4th candidate
+		<Candidates<Test>>::mutate(|candidates| candidates.push(10));
+		let _ = <Test as Config>::Currency::deposit_creating(&6, 1_000);
+		assert_noop!(
+			Governance::submit_candidacy(frame_system::RawOrigin::Signed(6).into()),
+			Error::<Test>::TooManyCandidates
+		);
+		assert_ok!(Governance::renounce_candidacy(
+			frame_system::RawOrigin::Signed(3).into()
+		));
+		assert_ok!(Governance::submit_candidacy(frame_system::RawOrigin::Signed(6).into()));
+
+		for voter in vec![4_u64, 5, 6] {
+			assert_ok!(Governance::vote_council_candidates(
+				frame_system::RawOrigin::Signed(voter).into(),
+				vec![voter]
+			));
+		}
+		// a 4th voter without stake
+		<ElectionVotes<Test>>::insert(10, vec![4]);
+		ElectionVoterCount::put(4);
+		assert_noop!(
+			Governance::vote_council_candidates(frame_system::RawOrigin::Signed(3).into(), vec![4]),
+			Error::<Test>::TooManyVoters
+		);
+		// voters may change their votes
+		assert_ok!(Governance::vote_council_candidates(
+			frame_system::RawOrigin::Signed(4).into(),
+			vec![5]
+		));
+		assert_eq!(Governance::election_voter_count(), 4);
+
+		assert_ok!(Governance::remove_council_votes(
+			frame_system::RawOrigin::Signed(4).into()
+		));
+		assert_eq!(Governance::election_voter_count(), 3);
+		assert_ok!(Governance::vote_council_candidates(
+			frame_system::RawOrigin::Signed(3).into(),
+			vec![4]
+		));
+		assert_eq!(Governance::election_voter_count(), 4);
+	});
+}
+
+#[test]
+fn vote_council_candidates_invalid_votes_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_candidates(vec![3, 4, 5, 6]);
+		let voter = 4_u64;
+
+		assert_noop!(
+			Governance::vote_council_candidates(frame_system::RawOrigin::Signed(voter).into(), vec![]),
+			Error::<Test>::NoVotes
+		);
+		assert_noop!(
+			Governance::vote_council_candidates(frame_system::RawOrigin::Signed(voter).into(), vec![3, 4, 5, 6]),
+			Error::<Test>::TooManyVotes
+		);
+		assert_noop!(
+			Governance::vote_council_candidates(frame_system::RawOrigin::Signed(voter).into(), vec![3, 3]),
+			Error::<Test>::DuplicateVote
+		);
+		assert_noop!(
+			Governance::vote_council_candidates(frame_system::RawOrigin::Signed(voter).into(), vec![2]),
+			Error::<Test>::NotCandidate
+		);
+		assert_noop!(
+			Governance::vote_council_candidates(frame_system::RawOrigin::Signed(1).into(), vec![3]),
+			Error::<Test>::NotEnoughStaked
+		);
+		assert_noop!(
+			Governance::remove_council_votes(frame_system::RawOrigin::Signed(voter).into()),
+			Error::<Test>::NoVotes
+		);
+	});
+}
+
+#[test]
+fn council_election_elects_council_and_runners_up() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_candidates(vec![3, 4, 5, 6]);
+		for voter in vec![3_u64, 4, 5] {
+			assert_ok!(Governance::vote_council_candidates(
+				frame_system::RawOrigin::Signed(voter).into(),
+				vec![voter]
+			));
+		}
+		assert_ok!(Governance::vote_council_candidates(
+			frame_system::RawOrigin::Signed(6).into(),
+			vec![3, 4, 6]
+		));
+
+		let stake = MockStakingAmount::active_balance(&3);
+		let info = Governance::get_election_info();
+		assert_eq!(
+			info.candidates,
+			vec![(3, stake * 2), (4, stake * 2), (5, stake), (6, stake)]
+		);
+		assert_eq!(info.next_election_block, TermDuration::get());
+
+		Governance::on_initialize(TermDuration::get());
+
+		assert_eq!(Governance::council(), vec![3, 4, 5]);
+		assert_eq!(Governance::runners_up(), vec![6]);
+		assert!(Governance::candidates().is_empty());
+		assert_eq!(Governance::election_rounds(), 1);
+		// councilors and runners-up keep their bonds reserved
+		for account in vec![3_u64, 4, 5, 6] {
+			assert_eq!(
+				<Test as Config>::Currency::reserved_balance(&account),
+				CandidacyBond::get()
+			);
+		}
+		// councilors and runners-up can't stand again
+		assert_noop!(
+			Governance::submit_candidacy(frame_system::RawOrigin::Signed(6).into()),
+			Error::<Test>::AlreadyCandidate
+		);
+	});
+}
+
+#[test]
+fn council_election_returns_losing_bonds() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_candidates(vec![3, 4, 5, 6]);
+		for voter in vec![3_u64, 4, 5] {
+			assert_ok!(Governance::vote_council_candidates(
+				frame_system::RawOrigin::Signed(voter).into(),
+				vec![voter]
+			));
+		}
+
+		Governance::on_initialize(TermDuration::get());
+
+		assert_eq!(Governance::council(), vec![3, 4, 5]);
+		assert!(Governance::runners_up().is_empty());
+		assert_eq!(<Test as Config>::Currency::reserved_balance(&6), 0);
+		assert_eq!(Governance::candidacy_deposits(6), 0);
+	});
+}
+
+#[test]
+fn council_election_remaps_proposal_votes() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_council_members(vec![3, 4, 5]);
+		let proposal_id = Governance::next_proposal_id();
+		assert_ok!(Governance::submit_proposal(
+			frame_system::RawOrigin::Signed(5).into(),
+			vec![1_u8, 2, 3],
+			vec![],
+			1
+		));
+		assert_ok!(Governance::vote_on_proposal(
+			frame_system::RawOrigin::Signed(4).into(),
+			proposal_id,
+			false,
+		));
+
+		setup_candidates(vec![6]);
+		for voter in vec![4_u64, 5, 6] {
+			assert_ok!(Governance::vote_council_candidates(
+				frame_system::RawOrigin::Signed(voter).into(),
+				vec![voter]
+			));
+		}
+		Governance::on_initialize(TermDuration::get());
+		assert_eq!(Governance::council(), vec![4, 5, 6]);
+
+		// votes follow the councilors to their new positions
+		let votes = Governance::proposal_votes(proposal_id);
+		assert_eq!(votes.get_vote(0), Some(false));
+		assert_eq!(votes.get_vote(1), Some(true));
+		assert_eq!(votes.get_vote(2), None);
+
+		// the new councilor can vote, re-elected councilors can't vote twice
+		assert_noop!(
+			Governance::vote_on_proposal(frame_system::RawOrigin::Signed(4).into(), proposal_id, true),
+			Error::<Test>::DoubleVote
+		);
+		assert_ok!(Governance::vote_on_proposal(
+			frame_system::RawOrigin::Signed(6).into(),
+			proposal_id,
+			true,
+		));
+		assert_eq!(
+			Governance::proposal_status(proposal_id),
+			Some(ProposalStatusInfo::ReferendumDeliberation)
+		);
+	});
+}
+
+#[test]
+fn renounce_councilor_promotes_runner_up() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_candidates(vec![3, 4, 5, 6]);
+		for voter in vec![3_u64, 4, 5] {
+			assert_ok!(Governance::vote_council_candidates(
+				frame_system::RawOrigin::Signed(voter).into(),
+				vec![voter]
+			));
+		}
+		assert_ok!(Governance::vote_council_candidates(
+			frame_system::RawOrigin::Signed(6).into(),
+			vec![6]
+		));
+		Governance::on_initialize(TermDuration::get());
+		assert_eq!(Governance::council(), vec![3, 4, 5]);
+		assert_eq!(Governance::runners_up(), vec![6]);
+
+		let proposal_id = Governance::next_proposal_id();
+		assert_ok!(Governance::submit_proposal(
+			frame_system::RawOrigin::Signed(5).into(),
+			vec![1_u8, 2, 3],
+			vec![],
+			1
+		));
+
+		assert_ok!(Governance::renounce_candidacy(
+			frame_system::RawOrigin::Signed(3).into()
+		));
+		assert_eq!(Governance::council(), vec![4, 5, 6]);
+		assert!(Governance::runners_up().is_empty());
+		assert_eq!(<Test as Config>::Currency::reserved_balance(&3), 0);
+		assert_eq!(Governance::candidacy_deposits(3), 0);
+		// the sponsor's vote moved with them
+		assert_eq!(Governance::proposal_votes(proposal_id).get_vote(1), Some(true));
+		assert_eq!(Governance::proposal_votes(proposal_id).get_vote(2), None);
+	});
+}
+
+#[test]
+fn renounce_runner_up() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_candidates(vec![3, 4, 5, 6]);
+		for voter in vec![3_u64, 4, 5, 6] {
+			assert_ok!(Governance::vote_council_candidates(
+				frame_system::RawOrigin::Signed(voter).into(),
+				vec![voter]
+			));
+		}
+		Governance::on_initialize(TermDuration::get());
+		let runner_up = Governance::runners_up()[0];
+		let council = Governance::council();
+
+		assert_ok!(Governance::renounce_candidacy(
+			frame_system::RawOrigin::Signed(runner_up).into()
+		));
+		assert!(Governance::runners_up().is_empty());
+		assert_eq!(Governance::council(), council);
+		assert_eq!(<Test as Config>::Currency::reserved_balance(&runner_up), 0);
+		assert_noop!(
+			Governance::renounce_candidacy(frame_system::RawOrigin::Signed(runner_up).into()),
+			Error::<Test>::NotCandidate
+		);
+	});
+}
+
+#[test]
+fn renounce_last_councilor_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_council_members(vec![3]);
+		assert_noop!(
+			Governance::renounce_candidacy(frame_system::RawOrigin::Signed(3).into()),
+			Error::<Test>::MinCouncilReached
+		);
+	});
+}
+
+#[test]
+fn council_election_without_votes_keeps_council() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_council_members(vec![3, 4]);
+		setup_candidates(vec![5]);
+
+		Governance::on_initialize(TermDuration::get());
+
+		assert_eq!(Governance::council(), vec![3, 4]);
+		assert_eq!(Governance::candidates(), vec![5]);
+		assert_eq!(Governance::election_rounds(), 0);
+	});
+}
+
+#[test]
+fn proposal_expires_after_deliberation_period() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_account = 3_u64;
+		let proposal_bond: Balance = 100;
+		let _ = <Test as Config>::Currency::deposit_creating(&proposal_account, 1_000);
+		assert_ok!(Governance::set_proposal_bond(
+			frame_system::RawOrigin::Root.into(),
+			proposal_bond
+		));
+		assert_ok!(Governance::set_deliberation_period(
+			frame_system::RawOrigin::Root.into(),
+			10
+		));
+		let proposal_id = Governance::next_proposal_id();
+		setup_council_members(vec![proposal_account, 4]);
+
+		assert_ok!(Governance::submit_proposal(
+			frame_system::RawOrigin::Signed(proposal_account).into(),
+			"0x123456".into(),
+			vec![0],
+			1
+		));
+		assert_eq!(Governance::deliberation_start_time(proposal_id), Some(1));
+		assert_eq!(
+			<Test as Config>::Currency::reserved_balance(&proposal_account),
+			proposal_bond
+		);
+
+		Governance::on_initialize(1000);
+
+		assert_eq!(
+			Governance::proposal_status(proposal_id),
+			Some(ProposalStatusInfo::Disapproved)
+		);
+		assert!(!Proposals::<Test>::contains_key(proposal_id));
+		assert!(!ProposalCalls::contains_key(proposal_id));
+		assert!(!ProposalVotes::contains_key(proposal_id));
+		assert_eq!(Governance::deliberation_start_time(proposal_id), None);
+		assert_eq!(<Test as Config>::Currency::reserved_balance(&proposal_account), 0);
+	});
+}
+
+#[test]
+fn proposal_in_referendum_does_not_expire() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Governance::set_deliberation_period(
+			frame_system::RawOrigin::Root.into(),
+			10
+		));
+		let proposal_id = setup_referendum(3, 4, "0x123456".into(), vec![0], 1);
+		assert_eq!(Governance::deliberation_start_time(proposal_id), None);
+
+		Governance::on_initialize(1000);
+
+		assert_eq!(
+			Governance::proposal_status(proposal_id),
+			Some(ProposalStatusInfo::ReferendumDeliberation)
+		);
+	});
+}
+
+#[test]
+fn set_deliberation_period_not_root_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Governance::set_deliberation_period(frame_system::RawOrigin::Signed(4).into(), 10),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn get_proposal_info() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_account = 3_u64;
+		let call: <Test as Config>::Call = (Call::add_council_member::<Test> { new_member: 5 }).into();
+		let call = call.encode();
+		let justification_uri: Vec<u8> = b"https://example.com".to_vec();
+		let proposal_id = setup_referendum(proposal_account, 4, call.clone(), justification_uri.clone(), 5);
+
+		assert_eq!(
+			Governance::get_proposal_info(proposal_id),
+			Some(ProposalInfo {
+				proposal_id,
+				sponsor: Some(proposal_account),
+				justification_uri,
+				enactment_delay: 5,
+				call: Some(call),
+				call_name: Some((b"Governance".to_vec(), b"add_council_member".to_vec())),
+				track: None,
+				status: ProposalStatusInfo::ReferendumDeliberation,
+				deliberation_start_time: None,
+				referendum_start_time: Some(1),
+				referendum_veto_sum: 0,
+				council_votes: CouncilVoteCount::default(),
+			})
+		);
+		assert_eq!(Governance::get_proposal_info(proposal_id + 1), None);
+		assert_eq!(Governance::get_proposals().len(), 1);
+	});
+}
+
+#[test]
+fn set_referendum_parameters() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Governance::set_referendum_length(
+			frame_system::RawOrigin::Root.into(),
+			10
+		));
+		assert_ok!(Governance::set_referendum_check_interval(
+			frame_system::RawOrigin::Root.into(),
+			5
+		));
+		assert_ok!(Governance::set_minimum_registered_identities(
+			frame_system::RawOrigin::Root.into(),
+			3
+		));
+		assert_eq!(Governance::referendum_length(), 10);
+		assert_eq!(Governance::referendum_check_interval(), 5);
+		assert_eq!(Governance::minimum_registered_identities(), 3);
+
+		// accounts only have 2 registered identities in the mock
+		assert_noop!(
+			Governance::add_council_member(frame_system::RawOrigin::Root.into(), 4),
+			Error::<Test>::NotEnoughRegistrations
+		);
+		assert_noop!(
+			Governance::set_referendum_length(frame_system::RawOrigin::Signed(4).into(), 10),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn referendum_ends_after_configured_length() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Governance::set_referendum_length(
+			frame_system::RawOrigin::Root.into(),
+			10
+		));
+		assert_ok!(Governance::set_referendum_check_interval(
+			frame_system::RawOrigin::Root.into(),
+			5
+		));
+		let proposal_id = setup_referendum(3, 4, "0x123456".into(), vec![0], 1);
+
+		Governance::on_initialize(5);
+		assert_eq!(
+			Governance::proposal_status(proposal_id),
+			Some(ProposalStatusInfo::ReferendumDeliberation)
+		);
+
+		Governance::on_initialize(15);
+		assert_eq!(
+			Governance::proposal_status(proposal_id),
+			Some(ProposalStatusInfo::ApprovedWaitingEnactment)
+		);
+	});
+}
+
+#[test]
+fn proposal_follows_call_track() {
+	ExtBuilder::default().build().execute_with(|| {
+		let track_id: TrackId = 1;
+		assert_ok!(Governance::set_track(
+			frame_system::RawOrigin::Root.into(),
+			track_id,
+			Some(TrackInfo {
+				referendum_length: 5,
+				veto_threshold: Permill::from_percent(50),
+				min_enactment_delay: 10,
+				max_enactment_delay: 100,
+			})
+		));
+		// assign the whole governance pallet
+		assert_ok!(Governance::set_call_track(
+			frame_system::RawOrigin::Root.into(),
+			b"Governance".to_vec(),
+			vec![],
+			Some(track_id)
+		));
+
+		let call: <Test as Config>::Call = (Call::add_council_member::<Test> { new_member: 5 }).into();
+		let call = call.encode();
+		assert_eq!(Governance::classify_call(&call), Ok(Some(track_id)));
+		assert_eq!(Governance::classify_call(b"0x123456"), Ok(None));
+
+		setup_council_members(vec![3, 4]);
+		assert_noop!(
+			Governance::submit_proposal(frame_system::RawOrigin::Signed(3).into(), call.clone(), vec![0], 5),
+			Error::<Test>::EnactmentDelayOutOfBounds
+		);
+		assert_noop!(
+			Governance::submit_proposal(frame_system::RawOrigin::Signed(3).into(), call.clone(), vec![0], 101),
+			Error::<Test>::EnactmentDelayOutOfBounds
+		);
+
+		let proposal_id = Governance::next_proposal_id();
+		assert_ok!(Governance::submit_proposal(
+			frame_system::RawOrigin::Signed(3).into(),
+			call,
+			vec![0],
+			10
+		));
+		assert_eq!(Governance::proposal_track(proposal_id), Some(track_id));
+		assert_eq!(Governance::referendum_length_of(proposal_id), 5);
+		assert_eq!(Governance::veto_threshold_of(proposal_id), Permill::from_percent(50));
+	});
+}
+
+#[test]
+fn call_track_exact_match_takes_precedence() {
+	ExtBuilder::default().build().execute_with(|| {
+		let track = TrackInfo {
+			referendum_length: 5,
+			veto_threshold: Permill::from_percent(50),
+			min_enactment_delay: 1,
+			max_enactment_delay: 100,
+		};
+		assert_ok!(Governance::set_track(
+			frame_system::RawOrigin::Root.into(),
+			1,
+			Some(track.clone())
+		));
+		assert_ok!(Governance::set_track(
+			frame_system::RawOrigin::Root.into(),
+			2,
+			Some(track)
+		));
+		assert_ok!(Governance::set_call_track(
+			frame_system::RawOrigin::Root.into(),
+			b"Governance".to_vec(),
+			vec![],
+			Some(1)
+		));
+		assert_ok!(Governance::set_call_track(
+			frame_system::RawOrigin::Root.into(),
+			b"Governance".to_vec(),
+			b"add_council_member".to_vec(),
+			Some(2)
+		));
+
+		let add_member: <Test as Config>::Call = (Call::add_council_member::<Test> { new_member: 5 }).into();
+		let remove_member: <Test as Config>::Call = (Call::remove_council_member::<Test> { remove_member: 5 }).into();
+		assert_eq!(Governance::classify_call(&add_member.encode()), Ok(Some(2)));
+		assert_eq!(Governance::classify_call(&remove_member.encode()), Ok(Some(1)));
+
+		// unassigning the exact match falls back to the pallet track
+		assert_ok!(Governance::set_call_track(
+			frame_system::RawOrigin::Root.into(),
+			b"Governance".to_vec(),
+			b"add_council_member".to_vec(),
+			None
+		));
+		assert_eq!(Governance::classify_call(&add_member.encode()), Ok(Some(1)));
+	});
+}
+
+#[test]
+fn wrapped_calls_follow_call_track() {
+	ExtBuilder::default().build().execute_with(|| {
+		let track = TrackInfo {
+			referendum_length: 5,
+			veto_threshold: Permill::from_percent(50),
+			min_enactment_delay: 10,
+			max_enactment_delay: 100,
+		};
+		assert_ok!(Governance::set_track(
+			frame_system::RawOrigin::Root.into(),
+			1,
+			Some(track.clone())
+		));
+		assert_ok!(Governance::set_call_track(
+			frame_system::RawOrigin::Root.into(),
+			b"Governance".to_vec(),
+			b"add_council_member".to_vec(),
+			Some(1)
+		));
+
+		let add_member: <Test as Config>::Call = (Call::add_council_member::<Test> { new_member: 5 }).into();
+		let wrap = |call: <Test as Config>::Call| -> <Test as Config>::Call {
+			pallet_scheduler::Call::<Test>::schedule {
+				when: 10,
+				maybe_periodic: None,
+				priority: 63,
+				call: Box::new(call),
+			}
+			.into()
+		};
+		let wrapped = wrap(add_member.clone()).encode();
+		assert_eq!(Governance::classify_call(&wrapped), Ok(Some(1)));
+
+		// a wrapper can't hide a restricted call from its track's bounds
+		setup_council_members(vec![3, 4]);
+		assert_noop!(
+			Governance::submit_proposal(frame_system::RawOrigin::Signed(3).into(), wrapped, vec![0], 1),
+			Error::<Test>::EnactmentDelayOutOfBounds
+		);
+
+		// a wrapper on another track can't mix tracks
+		assert_ok!(Governance::set_track(
+			frame_system::RawOrigin::Root.into(),
+			2,
+			Some(track)
+		));
+		assert_ok!(Governance::set_call_track(
+			frame_system::RawOrigin::Root.into(),
+			b"Scheduler".to_vec(),
+			vec![],
+			Some(2)
+		));
+		assert_eq!(
+			Governance::classify_call(&wrap(add_member.clone()).encode()),
+			Err(Error::<Test>::MixedTracks.into())
+		);
+
+		// wrappers can't nest indefinitely
+		let mut deep_call = add_member;
+		for _ in 0..=MAX_CALL_DEPTH {
+			deep_call = wrap(deep_call);
+		}
+		assert_eq!(
+			Governance::classify_call(&deep_call.encode()),
+			Err(Error::<Test>::CallTooDeep.into())
+		);
+	});
+}
+
+#[test]
+fn set_track_invalid_enactment_delays_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Governance::set_track(
+				frame_system::RawOrigin::Root.into(),
+				1,
+				Some(TrackInfo {
+					referendum_length: 5,
+					veto_threshold: Permill::from_percent(50),
+					min_enactment_delay: 100,
+					max_enactment_delay: 10,
+				})
+			),
+			Error::<Test>::InvalidTrack
+		);
+	});
+}
+
+#[test]
+fn set_call_track_missing_track_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Governance::set_call_track(
+				frame_system::RawOrigin::Root.into(),
+				b"Governance".to_vec(),
+				vec![],
+				Some(1)
+			),
+			Error::<Test>::TrackMissing
+		);
+	});
+}
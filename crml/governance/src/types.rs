@@ -16,6 +16,9 @@
 use benson_primitives::types::Balance;
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::Permill;
 use sp_std::prelude::*;
 
 /// Identifies proposals
@@ -24,6 +27,97 @@ pub type ProposalId = u64;
 /// Voting power for a referendum
 pub type VotingPower = Balance;
 
+/// Identifies governance tracks
+pub type TrackId = u8;
+
+/// Referendum parameters for proposals of a certain call type
+#[derive(Debug, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct TrackInfo<BlockNumber> {
+	/// The length in blocks of the referendum voting cycle
+	pub referendum_length: BlockNumber,
+	/// Permill of vetos needed for the referendum to fail
+	pub veto_threshold: Permill,
+	/// Minimum enactment delay in blocks
+	pub min_enactment_delay: BlockNumber,
+	/// Maximum enactment delay in blocks
+	pub max_enactment_delay: BlockNumber,
+}
+
+/// The conviction of a referendum vote
+/// Higher conviction locks the voter's stake for longer in exchange for a larger vote weight
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum Conviction {
+	/// 1x votes, no lock
+	None,
+	/// 2x votes, locked for 1 referendum length
+	Locked2x,
+	/// 3x votes, locked for 2 referendum lengths
+	Locked3x,
+	/// 4x votes, locked for 4 referendum lengths
+	Locked4x,
+	/// 5x votes, locked for 8 referendum lengths
+	Locked5x,
+	/// 6x votes, locked for 16 referendum lengths
+	Locked6x,
+}
+
+impl Default for Conviction {
+	fn default() -> Self {
+		Conviction::None
+	}
+}
+
+impl Conviction {
+	/// The number of referendum lengths a vote with this conviction is locked for
+	pub fn lock_periods(self) -> u32 {
+		match self {
+			Conviction::None => 0,
+			Conviction::Locked2x => 1,
+			Conviction::Locked3x => 2,
+			Conviction::Locked4x => 4,
+			Conviction::Locked5x => 8,
+			Conviction::Locked6x => 16,
+		}
+	}
+	/// The voting power of `amount` staked with this conviction
+	pub fn votes(self, amount: Balance) -> VotingPower {
+		match self {
+			Conviction::None => amount,
+			Conviction::Locked2x => amount.saturating_mul(2),
+			Conviction::Locked3x => amount.saturating_mul(3),
+			Conviction::Locked4x => amount.saturating_mul(4),
+			Conviction::Locked5x => amount.saturating_mul(5),
+			Conviction::Locked6x => amount.saturating_mul(6),
+		}
+	}
+}
+
+/// A delegation of referendum voting power to another account
+#[derive(Debug, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct Delegation<AccountId> {
+	/// The account receiving the voting power
+	pub target: AccountId,
+	/// The conviction the voting power is delegated with
+	pub conviction: Conviction,
+	/// The voting power delegated, fixed when delegating
+	pub power: VotingPower,
+}
+
+/// The current state of council elections
+#[derive(Debug, Default, PartialEq, Encode, Decode, TypeInfo)]
+pub struct ElectionInfo<AccountId> {
+	/// Candidates (including councilors and runners-up) and their approval stake
+	pub candidates: Vec<(AccountId, Balance)>,
+	/// The active council
+	pub council: Vec<AccountId>,
+	/// Runners-up from the last election
+	pub runners_up: Vec<AccountId>,
+	/// Number of elections held so far
+	pub election_rounds: u32,
+	/// The block at which the next election is held
+	pub next_election_block: u64,
+}
+
 /// A governance proposal
 #[derive(Debug, Default, PartialEq, Encode, Decode, TypeInfo)]
 #[scale_info(skip_type_params(T))]
@@ -37,7 +131,8 @@ pub struct Proposal<T: crate::Config> {
 	pub enactment_delay: T::BlockNumber,
 }
 
-#[derive(Debug, PartialEq, Encode, Decode, TypeInfo)]
+#[derive(Debug, Clone, PartialEq, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum ProposalStatusInfo {
 	/// Council is deliberating
 	Deliberation,
@@ -55,6 +150,46 @@ pub enum ProposalStatusInfo {
 	ReferendumVetoed,
 }
 
+// A value placed in storage that represents the current version of the governance storage.
+// This value is used by the `on_runtime_upgrade` logic to determine whether we run storage migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, TypeInfo)]
+pub enum Releases {
+	/// storage version before council deliberation could expire
+	V0 = 0,
+	/// proposals in council deliberation have a deliberation start time
+	V1 = 1,
+}
+
+/// The full lifecycle state of a proposal
+/// Proposal details and calls are removed once a proposal is finished, only its status remains
+#[derive(Debug, PartialEq, Encode, Decode, TypeInfo)]
+pub struct ProposalInfo<AccountId> {
+	/// The proposal Id
+	pub proposal_id: ProposalId,
+	/// The submitter of the proposal
+	pub sponsor: Option<AccountId>,
+	/// Justification document URI
+	pub justification_uri: Vec<u8>,
+	/// Enactment delay in blocks
+	pub enactment_delay: u64,
+	/// The encoded call, if any
+	pub call: Option<Vec<u8>>,
+	/// The (pallet, function) name of the call, if it decodes
+	pub call_name: Option<(Vec<u8>, Vec<u8>)>,
+	/// The governance track the proposal follows, `None` for the default track
+	pub track: Option<TrackId>,
+	/// Current status
+	pub status: ProposalStatusInfo,
+	/// The block council deliberation started
+	pub deliberation_start_time: Option<u64>,
+	/// The block the referendum started
+	pub referendum_start_time: Option<u64>,
+	/// Running tally of referendum vetos
+	pub referendum_veto_sum: Balance,
+	/// Council votes during deliberation
+	pub council_votes: CouncilVoteCount,
+}
+
 /// Votes on a proposal
 /// Tracks vote and participation of council member by index
 #[derive(Debug, Default, PartialEq, Encode, Decode, TypeInfo)]
@@ -65,7 +200,8 @@ pub struct ProposalVoteInfo {
 	/// distinguishes between a vote of 0 as intentional or absent
 	active_bits: (u128, u128),
 }
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Default, PartialEq, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 /// Represents current status of council counted votes
 pub struct CouncilVoteCount {
 	pub yes: u32,
@@ -171,7 +307,7 @@ impl ProposalVoteInfo {
 
 #[cfg(test)]
 mod tests {
-	use super::{CouncilVoteCount, ProposalVoteInfo};
+	use super::{Conviction, CouncilVoteCount, ProposalVoteInfo};
 
 	#[test]
 	fn add_voter() {
@@ -267,4 +403,20 @@ mod tests {
 		assert_eq!(votes.get_vote(2), Some(true));
 		assert_eq!(votes.get_vote(3), None);
 	}
+
+	#[test]
+	fn conviction_votes() {
+		assert_eq!(Conviction::None.votes(1_000), 1_000);
+		assert_eq!(Conviction::Locked2x.votes(1_000), 2_000);
+		assert_eq!(Conviction::Locked6x.votes(1_000), 6_000);
+		assert_eq!(Conviction::Locked6x.votes(u128::MAX), u128::MAX);
+	}
+
+	#[test]
+	fn conviction_lock_periods() {
+		assert_eq!(Conviction::None.lock_periods(), 0);
+		assert_eq!(Conviction::Locked2x.lock_periods(), 1);
+		assert_eq!(Conviction::Locked4x.lock_periods(), 4);
+		assert_eq!(Conviction::Locked6x.lock_periods(), 16);
+	}
 }
@@ -0,0 +1,303 @@
+/* Copyright 2019-2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+//!
+//! Benson treasury grants
+//!
+//! Grants are created by root, i.e. as a crml-governance proposal which passed council approval and referendum.
+//! The full grant amount is escrowed from the treasury on creation and paid out to the beneficiary milestone
+//! by milestone as the council approves each one. Cancelling a grant claws back all unpaid funds.
+//!
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+mod types;
+pub use types::*;
+
+use benson_primitives::types::{AssetId, Balance};
+use codec::Encode;
+use crml_support::{CouncilProvider, MultiCurrency};
+use frame_support::{
+	decl_error, decl_event, decl_module, decl_storage,
+	dispatch::DispatchResult,
+	pallet_prelude::*,
+	traits::{
+		schedule::{DispatchTime, Named as ScheduleNamed},
+		ExistenceRequirement, LockIdentifier,
+	},
+	transactional, PalletId,
+};
+use frame_system::{ensure_root, ensure_signed};
+use sp_runtime::traits::{AccountIdConversion, Zero};
+use sp_std::prelude::*;
+
+/// Identifies grant milestone payouts in the scheduler
+const GRANTS_ID: LockIdentifier = *b"grants  ";
+
+pub trait Config: frame_system::Config {
+	/// An onchain address for this pallet, holds escrowed grant funds
+	type GrantsPalletId: Get<PalletId>;
+	/// The treasury address grants are funded from and clawed back to
+	type TreasuryPalletId: Get<PalletId>;
+	/// Currency functions
+	type MultiCurrency: MultiCurrency<AccountId = Self::AccountId, Balance = Balance, CurrencyId = AssetId>;
+	/// Provides the council which approves milestones
+	type Council: CouncilProvider<AccountId = Self::AccountId>;
+	/// The Scheduler.
+	type Scheduler: ScheduleNamed<Self::BlockNumber, <Self as Config>::Call, Self::PalletsOrigin>;
+	/// Overarching type of all pallets origins.
+	type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>>;
+	/// Runtime call type
+	type Call: From<Call<Self>>;
+	/// Delay in blocks between a milestone's approval and its payout
+	type MilestonePayoutDelay: Get<Self::BlockNumber>;
+	/// Max. number of milestones per grant
+	type MaxMilestones: Get<u32>;
+	/// The system event type
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
+}
+
+decl_event! {
+	pub enum Event<T> where AccountId = <T as frame_system::Config>::AccountId {
+		/// A grant was created and its funds escrowed (grant, beneficiary, asset, total)
+		GrantCreated(GrantId, AccountId, AssetId, Balance),
+		/// A milestone was approved by the council and its payout scheduled
+		MilestoneApproved(GrantId, MilestoneIndex),
+		/// A milestone was rejected by the council
+		MilestoneRejected(GrantId, MilestoneIndex),
+		/// A milestone was paid out (grant, milestone, amount)
+		MilestonePaid(GrantId, MilestoneIndex, Balance),
+		/// A grant was cancelled and unpaid funds returned to the treasury (grant, amount)
+		GrantCancelled(GrantId, Balance),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Config> {
+		/// Operation can only be performed by an active council member
+		NotCouncilor,
+		/// Grant was not found
+		GrantMissing,
+		/// Milestone was not found
+		MilestoneMissing,
+		/// The grant is not active
+		GrantNotActive,
+		/// The milestone is not awaiting approval
+		MilestoneNotPending,
+		/// The milestone is not approved for payout
+		MilestoneNotApproved,
+		/// Cannot vote twice
+		DoubleVote,
+		/// A grant must have at least one milestone with a non-zero amount
+		InvalidMilestones,
+		/// Reached the max. number of milestones
+		TooManyMilestones,
+		/// The milestone payout could not be scheduled
+		ScheduleFailed,
+	}
+}
+
+decl_storage! {
+	trait Store for Module<T: Config> as Grants {
+		/// Map from grant Id to grant info
+		Grants get(fn grants): map hasher(twox_64_concat) GrantId => Option<Grant<T::AccountId>>;
+		/// Council votes on a grant milestone
+		MilestoneVotes get(fn milestone_votes): double_map hasher(twox_64_concat) GrantId, hasher(twox_64_concat) MilestoneIndex => Vec<(T::AccountId, bool)>;
+		/// Next available ID for grant
+		NextGrantId get(fn next_grant_id): GrantId;
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Config> for enum Call where origin: T::Origin {
+
+		fn deposit_event() = default;
+
+		/// Create a grant for `beneficiary` paid in `asset_id` with (amount, description URI) milestones
+		/// The full grant amount is escrowed from the treasury
+		/// This must be submitted like any other proposal
+		#[weight = 1_000_000]
+		#[transactional]
+		fn create_grant(
+			origin,
+			beneficiary: T::AccountId,
+			asset_id: AssetId,
+			milestones: Vec<(Balance, Vec<u8>)>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(milestones.len() <= T::MaxMilestones::get() as usize, Error::<T>::TooManyMilestones);
+			ensure!(
+				!milestones.is_empty() && milestones.iter().all(|(amount, _)| !amount.is_zero()),
+				Error::<T>::InvalidMilestones
+			);
+
+			let grant = Grant {
+				beneficiary,
+				asset_id,
+				milestones: milestones
+					.into_iter()
+					.map(|(amount, description_uri)| Milestone {
+						amount,
+						description_uri,
+						status: MilestoneStatus::Pending,
+					})
+					.collect(),
+				status: GrantStatus::Active,
+			};
+			let total = grant.total();
+			T::MultiCurrency::transfer(
+				&Self::treasury_account(),
+				&Self::escrow_account(),
+				asset_id,
+				total,
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			let grant_id = Self::next_grant_id();
+			Self::deposit_event(RawEvent::GrantCreated(grant_id, grant.beneficiary.clone(), asset_id, total));
+			<Grants<T>>::insert(grant_id, grant);
+			NextGrantId::put(grant_id.saturating_add(1));
+
+			Ok(())
+		}
+
+		/// Vote on paying out a grant milestone
+		/// Caller must be a council member
+		#[weight = 1_000_000]
+		#[transactional]
+		fn vote_on_milestone(
+			origin,
+			grant_id: GrantId,
+			milestone_index: MilestoneIndex,
+			approve: bool,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let council = T::Council::council();
+			ensure!(council.binary_search(&origin).is_ok(), Error::<T>::NotCouncilor);
+
+			let mut grant = Self::grants(grant_id).ok_or(Error::<T>::GrantMissing)?;
+			ensure!(grant.status == GrantStatus::Active, Error::<T>::GrantNotActive);
+			let milestone = grant.milestones.get_mut(milestone_index as usize).ok_or(Error::<T>::MilestoneMissing)?;
+			ensure!(milestone.status == MilestoneStatus::Pending, Error::<T>::MilestoneNotPending);
+
+			let mut votes = Self::milestone_votes(grant_id, milestone_index);
+			ensure!(votes.iter().all(|(voter, _)| *voter != origin), Error::<T>::DoubleVote);
+			votes.push((origin, approve));
+
+			// if we have more than 50% approval
+			let threshold = council.len() / 2;
+			let yes = votes.iter().filter(|(_, vote)| *vote).count();
+			let no = votes.len() - yes;
+			if yes > threshold {
+				milestone.status = MilestoneStatus::Approved;
+				T::Scheduler::schedule_named(
+					(GRANTS_ID, grant_id, milestone_index).encode(),
+					DispatchTime::At(<frame_system::Pallet<T>>::block_number() + T::MilestonePayoutDelay::get()),
+					None,
+					63,
+					frame_system::RawOrigin::Root.into(),
+					Call::pay_milestone { grant_id, milestone_index }.into(),
+				)
+				.map_err(|_| Error::<T>::ScheduleFailed)?;
+				<Grants<T>>::insert(grant_id, grant);
+				<MilestoneVotes<T>>::remove(grant_id, milestone_index);
+				Self::deposit_event(RawEvent::MilestoneApproved(grant_id, milestone_index));
+			} else if no > threshold {
+				// the milestone may be voted on again e.g. once the beneficiary addresses feedback
+				<MilestoneVotes<T>>::remove(grant_id, milestone_index);
+				Self::deposit_event(RawEvent::MilestoneRejected(grant_id, milestone_index));
+			} else {
+				<MilestoneVotes<T>>::insert(grant_id, milestone_index, votes);
+			}
+
+			Ok(())
+		}
+
+		/// Pay out an approved milestone from escrow
+		/// Scheduled when the council approves a milestone
+		#[weight = 1_000_000]
+		#[transactional]
+		fn pay_milestone(origin, grant_id: GrantId, milestone_index: MilestoneIndex) -> DispatchResult {
+			ensure_root(origin)?;
+			let mut grant = Self::grants(grant_id).ok_or(Error::<T>::GrantMissing)?;
+			ensure!(grant.status == GrantStatus::Active, Error::<T>::GrantNotActive);
+			let milestone = grant.milestones.get_mut(milestone_index as usize).ok_or(Error::<T>::MilestoneMissing)?;
+			ensure!(milestone.status == MilestoneStatus::Approved, Error::<T>::MilestoneNotApproved);
+
+			T::MultiCurrency::transfer(
+				&Self::escrow_account(),
+				&grant.beneficiary,
+				grant.asset_id,
+				milestone.amount,
+				ExistenceRequirement::AllowDeath,
+			)?;
+			milestone.status = MilestoneStatus::Paid;
+			let amount = milestone.amount;
+
+			if grant.milestones.iter().all(|m| m.status == MilestoneStatus::Paid) {
+				grant.status = GrantStatus::Completed;
+			}
+			<Grants<T>>::insert(grant_id, grant);
+			Self::deposit_event(RawEvent::MilestonePaid(grant_id, milestone_index, amount));
+
+			Ok(())
+		}
+
+		/// Cancel a grant, cancelling scheduled payouts and returning all unpaid funds to the treasury
+		/// This must be submitted like any other proposal
+		#[weight = 1_000_000]
+		#[transactional]
+		fn cancel_grant(origin, grant_id: GrantId) -> DispatchResult {
+			ensure_root(origin)?;
+			let mut grant = Self::grants(grant_id).ok_or(Error::<T>::GrantMissing)?;
+			ensure!(grant.status == GrantStatus::Active, Error::<T>::GrantNotActive);
+
+			for (milestone_index, milestone) in grant.milestones.iter().enumerate() {
+				if milestone.status == MilestoneStatus::Approved {
+					let _ = T::Scheduler::cancel_named((GRANTS_ID, grant_id, milestone_index as MilestoneIndex).encode());
+				}
+			}
+			let unpaid = grant.unpaid();
+			T::MultiCurrency::transfer(
+				&Self::escrow_account(),
+				&Self::treasury_account(),
+				grant.asset_id,
+				unpaid,
+				ExistenceRequirement::AllowDeath,
+			)?;
+
+			grant.status = GrantStatus::Cancelled;
+			<Grants<T>>::insert(grant_id, grant);
+			<MilestoneVotes<T>>::remove_prefix(grant_id, None);
+			Self::deposit_event(RawEvent::GrantCancelled(grant_id, unpaid));
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Module<T> {
+	/// The account holding escrowed grant funds
+	pub fn escrow_account() -> T::AccountId {
+		T::GrantsPalletId::get().into_account()
+	}
+	/// The treasury account grants are funded from
+	pub fn treasury_account() -> T::AccountId {
+		T::TreasuryPalletId::get().into_account()
+	}
+}
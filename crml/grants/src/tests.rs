@@ -0,0 +1,296 @@
+/* Copyright 2019-2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+use super::*;
+use crate::mock::{ExtBuilder, GenericAsset, Grants, MilestonePayoutDelay, Scheduler, Test};
+use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
+use sp_runtime::DispatchError;
+
+const ASSET_ID: AssetId = 16_000;
+const BENEFICIARY: u64 = 10;
+
+// Helper function to fund the treasury and create a grant with milestones of 100 and 200
+fn setup_grant() -> GrantId {
+	let _ = <GenericAsset as MultiCurrency>::deposit_creating(&Grants::treasury_account(), ASSET_ID, 1_000);
+	let grant_id = Grants::next_grant_id();
+	assert_ok!(Grants::create_grant(
+		frame_system::RawOrigin::Root.into(),
+		BENEFICIARY,
+		ASSET_ID,
+		vec![(100, b"milestone 1".to_vec()), (200, b"milestone 2".to_vec())],
+	));
+	grant_id
+}
+
+// Helper function to approve a milestone with a council majority
+fn approve_milestone(grant_id: GrantId, milestone_index: MilestoneIndex) {
+	assert_ok!(Grants::vote_on_milestone(
+		frame_system::RawOrigin::Signed(1).into(),
+		grant_id,
+		milestone_index,
+		true
+	));
+	assert_ok!(Grants::vote_on_milestone(
+		frame_system::RawOrigin::Signed(2).into(),
+		grant_id,
+		milestone_index,
+		true
+	));
+}
+
+#[test]
+fn create_grant_escrows_funds() {
+	ExtBuilder::default().build().execute_with(|| {
+		let grant_id = setup_grant();
+
+		let grant = Grants::grants(grant_id).expect("grant exists");
+		assert_eq!(grant.beneficiary, BENEFICIARY);
+		assert_eq!(grant.asset_id, ASSET_ID);
+		assert_eq!(grant.status, GrantStatus::Active);
+		assert_eq!(grant.total(), 300);
+		assert!(grant.milestones.iter().all(|m| m.status == MilestoneStatus::Pending));
+		assert_eq!(Grants::next_grant_id(), grant_id + 1);
+
+		assert_eq!(GenericAsset::free_balance(ASSET_ID, &Grants::escrow_account()), 300);
+		assert_eq!(GenericAsset::free_balance(ASSET_ID, &Grants::treasury_account()), 700);
+	});
+}
+
+#[test]
+fn create_grant_not_root_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Grants::create_grant(
+				frame_system::RawOrigin::Signed(1).into(),
+				BENEFICIARY,
+				ASSET_ID,
+				vec![(100, vec![])],
+			),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn create_grant_invalid_milestones_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Grants::create_grant(frame_system::RawOrigin::Root.into(), BENEFICIARY, ASSET_ID, vec![]),
+			Error::<Test>::InvalidMilestones
+		);
+		assert_noop!(
+			Grants::create_grant(
+				frame_system::RawOrigin::Root.into(),
+				BENEFICIARY,
+				ASSET_ID,
+				vec![(100, vec![]), (0, vec![])],
+			),
+			Error::<Test>::InvalidMilestones
+		);
+		assert_noop!(
+			Grants::create_grant(
+				frame_system::RawOrigin::Root.into(),
+				BENEFICIARY,
+				ASSET_ID,
+				vec![(1, vec![]); 4],
+			),
+			Error::<Test>::TooManyMilestones
+		);
+	});
+}
+
+#[test]
+fn create_grant_insufficient_treasury_funds_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Grants::create_grant(
+				frame_system::RawOrigin::Root.into(),
+				BENEFICIARY,
+				ASSET_ID,
+				vec![(100, vec![])],
+			),
+			crml_generic_asset::Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn approved_milestones_are_paid_out() {
+	ExtBuilder::default().build().execute_with(|| {
+		let grant_id = setup_grant();
+
+		approve_milestone(grant_id, 0);
+		assert_eq!(
+			Grants::grants(grant_id).unwrap().milestones[0].status,
+			MilestoneStatus::Approved
+		);
+		assert!(Grants::milestone_votes(grant_id, 0).is_empty());
+
+		// payout is scheduled after the delay
+		let payout_block = 1 + MilestonePayoutDelay::get();
+		Scheduler::on_initialize(payout_block - 1);
+		assert_eq!(GenericAsset::free_balance(ASSET_ID, &BENEFICIARY), 0);
+		Scheduler::on_initialize(payout_block);
+		assert_eq!(GenericAsset::free_balance(ASSET_ID, &BENEFICIARY), 100);
+		assert_eq!(
+			Grants::grants(grant_id).unwrap().milestones[0].status,
+			MilestoneStatus::Paid
+		);
+
+		approve_milestone(grant_id, 1);
+		assert_ok!(Grants::pay_milestone(frame_system::RawOrigin::Root.into(), grant_id, 1));
+		assert_eq!(GenericAsset::free_balance(ASSET_ID, &BENEFICIARY), 300);
+		assert_eq!(GenericAsset::free_balance(ASSET_ID, &Grants::escrow_account()), 0);
+		assert_eq!(Grants::grants(grant_id).unwrap().status, GrantStatus::Completed);
+	});
+}
+
+#[test]
+fn pay_unapproved_milestone_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		let grant_id = setup_grant();
+		assert_noop!(
+			Grants::pay_milestone(frame_system::RawOrigin::Root.into(), grant_id, 0),
+			Error::<Test>::MilestoneNotApproved
+		);
+		assert_noop!(
+			Grants::pay_milestone(frame_system::RawOrigin::Root.into(), grant_id, 2),
+			Error::<Test>::MilestoneMissing
+		);
+	});
+}
+
+#[test]
+fn vote_on_milestone_not_councilor_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		let grant_id = setup_grant();
+		assert_noop!(
+			Grants::vote_on_milestone(frame_system::RawOrigin::Signed(4).into(), grant_id, 0, true),
+			Error::<Test>::NotCouncilor
+		);
+	});
+}
+
+#[test]
+fn vote_on_milestone_twice_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		let grant_id = setup_grant();
+		assert_ok!(Grants::vote_on_milestone(
+			frame_system::RawOrigin::Signed(1).into(),
+			grant_id,
+			0,
+			true
+		));
+		assert_noop!(
+			Grants::vote_on_milestone(frame_system::RawOrigin::Signed(1).into(), grant_id, 0, false),
+			Error::<Test>::DoubleVote
+		);
+	});
+}
+
+#[test]
+fn vote_on_milestone_schedule_failure_rolls_back() {
+	ExtBuilder::default().build().execute_with(|| {
+		let grant_id = setup_grant();
+		// occupy the milestone payout's schedule name
+		assert_ok!(<Scheduler as ScheduleNamed<_, _, _>>::schedule_named(
+			(GRANTS_ID, grant_id, 0 as MilestoneIndex).encode(),
+			DispatchTime::At(100),
+			None,
+			63,
+			frame_system::RawOrigin::Root.into(),
+			frame_system::Call::remark { remark: vec![] }.into(),
+		));
+
+		assert_ok!(Grants::vote_on_milestone(
+			frame_system::RawOrigin::Signed(1).into(),
+			grant_id,
+			0,
+			true
+		));
+		assert_noop!(
+			Grants::vote_on_milestone(frame_system::RawOrigin::Signed(2).into(), grant_id, 0, true),
+			Error::<Test>::ScheduleFailed
+		);
+		assert_eq!(
+			Grants::grants(grant_id).unwrap().milestones[0].status,
+			MilestoneStatus::Pending
+		);
+		assert_eq!(Grants::milestone_votes(grant_id, 0).len(), 1);
+	});
+}
+
+#[test]
+fn rejected_milestone_can_be_voted_again() {
+	ExtBuilder::default().build().execute_with(|| {
+		let grant_id = setup_grant();
+		assert_ok!(Grants::vote_on_milestone(
+			frame_system::RawOrigin::Signed(1).into(),
+			grant_id,
+			0,
+			false
+		));
+		assert_ok!(Grants::vote_on_milestone(
+			frame_system::RawOrigin::Signed(2).into(),
+			grant_id,
+			0,
+			false
+		));
+		assert!(Grants::milestone_votes(grant_id, 0).is_empty());
+		assert_eq!(
+			Grants::grants(grant_id).unwrap().milestones[0].status,
+			MilestoneStatus::Pending
+		);
+
+		approve_milestone(grant_id, 0);
+		assert_eq!(
+			Grants::grants(grant_id).unwrap().milestones[0].status,
+			MilestoneStatus::Approved
+		);
+	});
+}
+
+#[test]
+fn cancel_grant_claws_back_unpaid_funds() {
+	ExtBuilder::default().build().execute_with(|| {
+		let grant_id = setup_grant();
+		approve_milestone(grant_id, 0);
+		assert_ok!(Grants::vote_on_milestone(
+			frame_system::RawOrigin::Signed(1).into(),
+			grant_id,
+			1,
+			true
+		));
+
+		assert_ok!(Grants::cancel_grant(frame_system::RawOrigin::Root.into(), grant_id));
+		assert_eq!(Grants::grants(grant_id).unwrap().status, GrantStatus::Cancelled);
+		assert!(Grants::milestone_votes(grant_id, 1).is_empty());
+		assert_eq!(GenericAsset::free_balance(ASSET_ID, &Grants::treasury_account()), 1_000);
+		assert_eq!(GenericAsset::free_balance(ASSET_ID, &Grants::escrow_account()), 0);
+
+		// the scheduled payout was cancelled
+		Scheduler::on_initialize(1 + MilestonePayoutDelay::get());
+		assert_eq!(GenericAsset::free_balance(ASSET_ID, &BENEFICIARY), 0);
+
+		assert_noop!(
+			Grants::cancel_grant(frame_system::RawOrigin::Root.into(), grant_id),
+			Error::<Test>::GrantNotActive
+		);
+		assert_noop!(
+			Grants::vote_on_milestone(frame_system::RawOrigin::Signed(3).into(), grant_id, 1, true),
+			Error::<Test>::GrantNotActive
+		);
+	});
+}
@@ -0,0 +1,85 @@
+/* Copyright 2019-2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+use benson_primitives::types::{AssetId, Balance};
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_std::prelude::*;
+
+/// Identifies grants
+pub type GrantId = u64;
+
+/// Identifies a milestone within a grant
+pub type MilestoneIndex = u32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Encode, Decode, TypeInfo)]
+pub enum MilestoneStatus {
+	/// Awaiting council approval
+	Pending,
+	/// Approved by the council, payout is scheduled
+	Approved,
+	/// Paid out to the beneficiary
+	Paid,
+}
+
+/// A grant milestone
+#[derive(Debug, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct Milestone {
+	/// Amount paid to the beneficiary when the milestone is approved
+	pub amount: Balance,
+	/// Milestone description URI
+	pub description_uri: Vec<u8>,
+	/// Current status
+	pub status: MilestoneStatus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Encode, Decode, TypeInfo)]
+pub enum GrantStatus {
+	/// Funds are escrowed, milestones are being approved
+	Active,
+	/// All milestones have been paid out
+	Completed,
+	/// The grant was cancelled and unpaid funds returned to the treasury
+	Cancelled,
+}
+
+/// A treasury grant paid out in milestones
+#[derive(Debug, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct Grant<AccountId> {
+	/// Receives milestone payouts
+	pub beneficiary: AccountId,
+	/// The generic asset the grant is paid in
+	pub asset_id: AssetId,
+	/// Ordered milestones
+	pub milestones: Vec<Milestone>,
+	/// Current status
+	pub status: GrantStatus,
+}
+
+impl<AccountId> Grant<AccountId> {
+	/// The total amount of all milestones
+	pub fn total(&self) -> Balance {
+		self.milestones
+			.iter()
+			.fold(0, |total, milestone| total.saturating_add(milestone.amount))
+	}
+	/// The amount of all milestones not yet paid out
+	pub fn unpaid(&self) -> Balance {
+		self.milestones
+			.iter()
+			.filter(|milestone| milestone.status != MilestoneStatus::Paid)
+			.fold(0, |total, milestone| total.saturating_add(milestone.amount))
+	}
+}
@@ -0,0 +1,317 @@
+/* Copyright 2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Decode;
+use crml_nft::{CollectionId, CollectionNameType, TokenId, TokenLockReason, TokenLocks, TokenOwner};
+use crml_support::{EthReceiptProof, EventClaimSubscriber, EventClaimVerifier};
+use frame_support::{
+	decl_error, decl_event, decl_module, decl_storage, ensure, log,
+	traits::{Get, IsType},
+	transactional, PalletId,
+};
+use frame_system::{ensure_root, ensure_signed};
+use sp_runtime::{traits::AccountIdConversion, DispatchError};
+use sp_std::prelude::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+mod types;
+pub use types::*;
+
+pub trait Config: frame_system::Config + crml_nft::Config {
+	/// An onchain address for this pallet
+	type PegPalletId: Get<PalletId>;
+	/// The EVM event signature of an ERC721 deposit
+	type DepositEventSignature: Get<[u8; 32]>;
+	/// Submits event claims for Ethereum
+	type EthBridge: EventClaimVerifier;
+	/// The overarching event type.
+	type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+}
+
+decl_storage! {
+	trait Store for Module<T: Config> as NftPeg {
+		/// Whether deposit are active
+		DepositsActive get(fn deposits_active): bool;
+		/// Whether withdrawals are active
+		WithdrawalsActive get(fn withdrawals_active): bool;
+		/// Map ERC721 address to NFT collection Id
+		Erc721ToCollectionId get(fn erc721_to_collection): map hasher(twox_64_concat) EthAddress => Option<CollectionId>;
+		/// Map NFT collection Id to ERC721 address
+		CollectionIdToErc721 get(fn collection_to_erc721): map hasher(twox_64_concat) CollectionId => Option<EthAddress>;
+		/// The peg contract address on Ethereum
+		ContractAddress get(fn contract_address): EthAddress;
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where AccountId = <T as frame_system::Config>::AccountId {
+		/// An erc721 deposit claim has started. (deposit Id, sender)
+		Erc721Claim(u64, AccountId),
+		/// A bridged erc721 deposit succeeded. (deposit Id, token, beneficiary)
+		Erc721Deposit(u64, TokenId, AccountId),
+		/// A token was locked for withdrawal on Ethereum as an ERC721 (withdrawal Id, token, beneficiary)
+		Erc721Withdraw(u64, TokenId, EthAddress),
+		/// A bridged erc721 deposit failed. (deposit Id)
+		Erc721DepositFail(u64),
+		/// The peg contract address has been set
+		SetContractAddress(EthAddress),
+		/// An ERC721 address has been mapped to an NFT collection (ERC721 address, collection)
+		SetCollectionMapping(EthAddress, CollectionId),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Config> {
+		/// Claim has bad account
+		InvalidAddress,
+		/// Claim has a token Id which can't be represented on Benson
+		InvalidTokenId,
+		/// Deposits are inactive
+		DepositsPaused,
+		/// Withdrawals are inactive
+		WithdrawalsPaused,
+		/// The ERC721 or NFT collection is not mapped by the peg
+		UnsupportedCollection,
+		/// The ERC721 or NFT collection is already mapped by the peg
+		CollectionAlreadyMapped,
+		/// The NFT collection does not exist
+		NoCollection,
+		/// Caller is not the token owner
+		NoPermission,
+		/// Cannot withdraw a locked token
+		TokenLocked,
+		/// The deposited token is not held by the peg and cannot be minted
+		TokenNotBridged,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Config> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// Activate/deactivate deposits (root only)
+		#[weight = 10_000_000]
+		pub fn activate_deposits(origin, activate: bool) {
+			ensure_root(origin)?;
+			DepositsActive::put(activate);
+		}
+
+		/// Activate/deactivate withdrawals (root only)
+		#[weight = 10_000_000]
+		pub fn activate_withdrawals(origin, activate: bool) {
+			ensure_root(origin)?;
+			WithdrawalsActive::put(activate);
+		}
+
+		#[weight = 50_000_000]
+		/// Submit deposit claim for an ethereum tx hash
+		/// The deposit details must be provided for cross-checking by notaries
+		/// Any caller may initiate a claim while only the intended beneficiary will receive the token.
+		#[transactional]
+		pub fn deposit_claim(origin, tx_hash: H256, claim: Erc721DepositEvent) {
+			let origin = ensure_signed(origin)?;
+			Self::validate_deposit_claim(&claim)?;
+
+			let event_claim_id = T::EthBridge::submit_event_claim(
+					&Self::contract_address().into(),
+					&T::DepositEventSignature::get().into(),
+					&tx_hash,
+					&EthAbiCodec::encode(&claim),
+			)?;
+
+			Self::deposit_event(<Event<T>>::Erc721Claim(event_claim_id, origin));
+		}
+
+		#[weight = 100_000_000]
+		/// Submit deposit claim with a proof of the deposit tx receipt
+		/// The claim is verified against the proven Ethereum block header, notaries need only agree on the header
+		/// Any caller may initiate a claim while only the intended beneficiary will receive the token.
+		#[transactional]
+		pub fn deposit_claim_with_proof(origin, claim: Erc721DepositEvent, proof: EthReceiptProof) {
+			let origin = ensure_signed(origin)?;
+			Self::validate_deposit_claim(&claim)?;
+
+			let event_claim_id = T::EthBridge::submit_event_claim_with_proof(
+					&Self::contract_address().into(),
+					&T::DepositEventSignature::get().into(),
+					&EthAbiCodec::encode(&claim),
+					&proof,
+			)?;
+
+			Self::deposit_event(<Event<T>>::Erc721Claim(event_claim_id, origin));
+		}
+
+		#[weight = 50_000_000]
+		/// Withdraw an NFT from Benson in exchange for an ERC721
+		/// The token is held and locked by the peg, and a proof generated to allow redemption of the ERC721 on Ethereum
+		/// Caller must be the token owner
+		#[transactional]
+		pub fn withdraw(origin, token_id: TokenId, beneficiary: EthAddress) {
+			let origin = ensure_signed(origin)?;
+			ensure!(Self::withdrawals_active(), Error::<T>::WithdrawalsPaused);
+
+			let (collection_id, series_id, serial_number) = token_id;
+			let token_address = Self::collection_to_erc721(collection_id).ok_or(Error::<T>::UnsupportedCollection)?;
+			ensure!(!TokenLocks::contains_key(token_id), Error::<T>::TokenLocked);
+			ensure!(
+				<TokenOwner<T>>::contains_key((collection_id, series_id), serial_number)
+					&& <TokenOwner<T>>::get((collection_id, series_id), serial_number) == origin,
+				Error::<T>::NoPermission
+			);
+
+			crml_nft::Module::<T>::do_transfer_unchecked(&[token_id], &Self::account_id());
+			TokenLocks::insert(token_id, TokenLockReason::Bridged);
+
+			let message = Erc721WithdrawMessage {
+				token_address,
+				token_id: to_erc721_token_id(token_id),
+				beneficiary,
+			};
+			let event_proof_id = T::EthBridge::generate_event_proof(&message, false)?;
+
+			Self::deposit_event(<Event<T>>::Erc721Withdraw(event_proof_id, token_id, beneficiary));
+		}
+
+		#[weight = 1_000_000]
+		#[transactional]
+		/// Set the peg contract address on Ethereum (requires governance)
+		pub fn set_contract_address(origin, eth_address: EthAddress) {
+			ensure_root(origin)?;
+			ContractAddress::put(eth_address);
+			Self::deposit_event(<Event<T>>::SetContractAddress(eth_address));
+		}
+
+		#[weight = 5_000_000]
+		#[transactional]
+		/// Map an ERC721 contract to an existing NFT collection (requires governance)
+		/// Used for Benson collections, the ERC721 contract is the collection's wrapped counterpart on Ethereum
+		pub fn set_collection_mapping(origin, token_address: EthAddress, collection_id: CollectionId) {
+			ensure_root(origin)?;
+			ensure!(crml_nft::Module::<T>::collection_owner(collection_id).is_some(), Error::<T>::NoCollection);
+			Self::insert_collection_mapping(token_address, collection_id)?;
+		}
+
+		#[weight = 10_000_000]
+		#[transactional]
+		/// Create an NFT collection owned by the peg for an existing ERC721 contract (requires governance)
+		/// Tokens of the collection are minted on their first deposit
+		pub fn register_erc721(origin, token_address: EthAddress, name: CollectionNameType) {
+			ensure_root(origin)?;
+			ensure!(!Erc721ToCollectionId::contains_key(token_address), Error::<T>::CollectionAlreadyMapped);
+			let collection_id = crml_nft::Module::<T>::do_create_collection(Self::account_id(), name, None)?;
+			Self::insert_collection_mapping(token_address, collection_id)?;
+		}
+	}
+}
+
+impl<T: Config> Module<T> {
+	/// The account Id of the peg, it holds tokens while they are bridged
+	pub fn account_id() -> T::AccountId {
+		T::PegPalletId::get().into_account()
+	}
+
+	/// Check a deposit claim may be fulfilled before submitting it
+	fn validate_deposit_claim(claim: &Erc721DepositEvent) -> Result<(), DispatchError> {
+		ensure!(Self::deposits_active(), Error::<T>::DepositsPaused);
+		// fail a claim early for an unknown token
+		let collection_id = Self::erc721_to_collection(claim.token_address).ok_or(Error::<T>::UnsupportedCollection)?;
+		ensure!(
+			from_erc721_token_id(collection_id, claim.token_id).is_some(),
+			Error::<T>::InvalidTokenId
+		);
+		// fail a claim if beneficiary is not a valid Benson address
+		ensure!(
+			T::AccountId::decode(&mut &claim.beneficiary.0[..]).is_ok(),
+			Error::<T>::InvalidAddress
+		);
+		Ok(())
+	}
+
+	/// Map `token_address` and `collection_id` to each other
+	fn insert_collection_mapping(token_address: EthAddress, collection_id: CollectionId) -> Result<(), DispatchError> {
+		ensure!(
+			!Erc721ToCollectionId::contains_key(token_address) && !CollectionIdToErc721::contains_key(collection_id),
+			Error::<T>::CollectionAlreadyMapped
+		);
+		Erc721ToCollectionId::insert(token_address, collection_id);
+		CollectionIdToErc721::insert(collection_id, token_address);
+		Self::deposit_event(<Event<T>>::SetCollectionMapping(token_address, collection_id));
+		Ok(())
+	}
+
+	/// fulfil a deposit claim for the given event
+	/// A token held by the peg is unlocked and released, otherwise tokens of peg owned collections are minted
+	pub fn do_deposit(verified_event: Erc721DepositEvent) -> Result<(TokenId, T::AccountId), DispatchError> {
+		let collection_id =
+			Self::erc721_to_collection(verified_event.token_address).ok_or(Error::<T>::UnsupportedCollection)?;
+		let token_id =
+			from_erc721_token_id(collection_id, verified_event.token_id).ok_or(Error::<T>::InvalidTokenId)?;
+		// checked at the time of initiating the verified_event that beneficiary value is valid and this op will not fail qed.
+		let beneficiary: T::AccountId = T::AccountId::decode(&mut &verified_event.beneficiary.0[..]).unwrap();
+
+		let (_, series_id, serial_number) = token_id;
+		if <TokenOwner<T>>::contains_key((collection_id, series_id), serial_number) {
+			ensure!(Self::token_is_bridged(token_id), Error::<T>::TokenNotBridged);
+			TokenLocks::remove(token_id);
+			crml_nft::Module::<T>::do_transfer_unchecked(&[token_id], &beneficiary);
+		} else {
+			// only collections native to Ethereum may have tokens minted by the peg
+			ensure!(
+				crml_nft::Module::<T>::collection_owner(collection_id) == Some(Self::account_id()),
+				Error::<T>::TokenNotBridged
+			);
+			crml_nft::Module::<T>::do_mint_serial(&beneficiary, token_id)?;
+		}
+
+		Ok((token_id, beneficiary))
+	}
+
+	/// Whether `token_id` is held and locked by the peg
+	fn token_is_bridged(token_id: TokenId) -> bool {
+		let (collection_id, series_id, serial_number) = token_id;
+		TokenLocks::get(token_id) == Some(TokenLockReason::Bridged)
+			&& <TokenOwner<T>>::get((collection_id, series_id), serial_number) == Self::account_id()
+	}
+}
+
+impl<T: Config> EventClaimSubscriber for Module<T> {
+	fn on_success(event_claim_id: u64, contract_address: &EthAddress, event_type: &H256, event_data: &[u8]) {
+		if *contract_address == Self::contract_address() && *event_type == H256::from(T::DepositEventSignature::get()) {
+			if let Some(deposit_event) = EthAbiCodec::decode(event_data) {
+				match Self::do_deposit(deposit_event) {
+					Ok((token_id, beneficiary)) => {
+						Self::deposit_event(<Event<T>>::Erc721Deposit(event_claim_id, token_id, beneficiary))
+					}
+					Err(_err) => Self::deposit_event(<Event<T>>::Erc721DepositFail(event_claim_id)),
+				}
+			} else {
+				// input data should be valid, we do not expect to fail here
+				log::error!("📌 ERC721 deposit claim failed unexpectedly: {:?}", event_data);
+			}
+		}
+	}
+	fn on_failure(event_claim_id: u64, contract_address: &H160, event_type: &H256, _event_data: &[u8]) {
+		if *contract_address == Self::contract_address() && *event_type == H256::from(T::DepositEventSignature::get()) {
+			Self::deposit_event(<Event<T>>::Erc721DepositFail(event_claim_id));
+		}
+	}
+}
@@ -0,0 +1,177 @@
+/* Copyright 2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+use crate as crml_nft_peg;
+use benson_primitives::types::{AssetId, Balance};
+use crml_support::{EthAbiCodec, EthReceiptProof, EventClaimVerifier, H160};
+use frame_support::{parameter_types, PalletId};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	DispatchError,
+};
+use std::cell::RefCell;
+
+pub type AccountId = u64;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		GenericAsset: crml_generic_asset::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Nft: crml_nft::{Pallet, Call, Storage, Event<T>},
+		NftPeg: crml_nft_peg::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+impl frame_system::Config for Test {
+	type BlockWeights = ();
+	type BlockLength = ();
+	type BaseCallFilter = frame_support::traits::Everything;
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type BlockHashCount = BlockHashCount;
+	type Event = Event;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+impl crml_generic_asset::Config for Test {
+	type AssetId = AssetId;
+	type Balance = Balance;
+	type Event = Event;
+	type OnDustImbalance = ();
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const DefaultListingDuration: u64 = 5;
+	pub const MaxAttributeLength: u8 = 140;
+}
+impl crml_nft::Config for Test {
+	type Event = Event;
+	type MultiCurrency = GenericAsset;
+	type MaxAttributeLength = MaxAttributeLength;
+	type DefaultListingDuration = DefaultListingDuration;
+	type WeightInfo = ();
+}
+
+/// A submitted event claim (contract address, event signature, event data)
+pub type MockEventClaim = (H160, H256, Vec<u8>);
+
+thread_local! {
+	static EVENT_CLAIMS: RefCell<Vec<MockEventClaim>> = RefCell::new(Vec::new());
+	static EVENT_PROOFS: RefCell<Vec<Vec<u8>>> = RefCell::new(Vec::new());
+}
+
+/// Records event claims and proof requests in place of the eth-bridge
+pub struct MockEthBridge;
+
+impl MockEthBridge {
+	/// Event claims submitted so far, indexed by claim Id
+	pub fn event_claims() -> Vec<MockEventClaim> {
+		EVENT_CLAIMS.with(|claims| claims.borrow().clone())
+	}
+	/// ABI encoded messages of event proofs requested so far, indexed by proof Id
+	pub fn event_proofs() -> Vec<Vec<u8>> {
+		EVENT_PROOFS.with(|proofs| proofs.borrow().clone())
+	}
+	fn record_event_claim(contract_address: &H160, event_signature: &H256, event_data: &[u8]) -> u64 {
+		EVENT_CLAIMS.with(|claims| {
+			let mut claims = claims.borrow_mut();
+			claims.push((*contract_address, *event_signature, event_data.to_vec()));
+			claims.len() as u64 - 1
+		})
+	}
+}
+
+impl EventClaimVerifier for MockEthBridge {
+	fn submit_event_claim(
+		contract_address: &H160,
+		event_signature: &H256,
+		_tx_hash: &H256,
+		event_data: &[u8],
+	) -> Result<u64, DispatchError> {
+		Ok(Self::record_event_claim(contract_address, event_signature, event_data))
+	}
+	fn submit_event_claim_with_proof(
+		contract_address: &H160,
+		event_signature: &H256,
+		event_data: &[u8],
+		_proof: &EthReceiptProof,
+	) -> Result<u64, DispatchError> {
+		Ok(Self::record_event_claim(contract_address, event_signature, event_data))
+	}
+	fn generate_event_proof<M: EthAbiCodec>(message: &M, _batchable: bool) -> Result<u64, DispatchError> {
+		EVENT_PROOFS.with(|proofs| {
+			let mut proofs = proofs.borrow_mut();
+			proofs.push(message.encode());
+			Ok(proofs.len() as u64 - 1)
+		})
+	}
+}
+
+parameter_types! {
+	pub const NftPegPalletId: PalletId = PalletId(*b"nft-peg_");
+	pub const Erc721DepositEventSignature: [u8; 32] = [1_u8; 32];
+}
+impl crate::Config for Test {
+	type PegPalletId = NftPegPalletId;
+	type DepositEventSignature = Erc721DepositEventSignature;
+	type EthBridge = MockEthBridge;
+	type Event = Event;
+}
+
+#[derive(Default)]
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut ext: sp_io::TestExternalities = frame_system::GenesisConfig::default()
+			.build_storage::<Test>()
+			.unwrap()
+			.into();
+
+		ext.execute_with(|| {
+			System::initialize(&1, &[0u8; 32].into(), &Default::default(), frame_system::InitKind::Full);
+		});
+
+		ext
+	}
+}
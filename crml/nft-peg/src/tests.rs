@@ -0,0 +1,373 @@
+/* Copyright 2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+use super::*;
+use crate::mock::{
+	AccountId, Erc721DepositEventSignature, Event, ExtBuilder, MockEthBridge, Nft, NftPeg, System, Test,
+};
+use codec::Encode;
+use crml_nft::MetadataScheme;
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::traits::BadOrigin;
+
+/// The NFT peg contract on Ethereum
+const PEG_CONTRACT: EthAddress = H160([0x11; 20]);
+/// The ERC721 counterpart of a Benson collection
+const WRAPPED_ERC721: EthAddress = H160([0x22; 20]);
+/// An ERC721 collection native to Ethereum
+const NATIVE_ERC721: EthAddress = H160([0x33; 20]);
+/// The Ethereum beneficiary of withdrawals
+const ETH_BENEFICIARY: EthAddress = H160([0x44; 20]);
+
+// Check the test system contains an event record `event`
+fn has_event(event: crate::Event<Test>) -> bool {
+	System::events()
+		.iter()
+		.find(|e| e.event == Event::NftPeg(event.clone()))
+		.is_some()
+}
+
+/// The deposit beneficiary field for a Benson `account`
+fn beneficiary(account: AccountId) -> H256 {
+	let mut beneficiary = H256::default();
+	beneficiary.0[..8].copy_from_slice(&account.encode());
+	beneficiary
+}
+
+/// Setup the peg and a Benson collection mapped to `WRAPPED_ERC721`
+/// Returns a token of the collection owned by `token_owner`
+fn setup_token(token_owner: AccountId) -> TokenId {
+	assert_ok!(NftPeg::set_contract_address(
+		frame_system::RawOrigin::Root.into(),
+		PEG_CONTRACT
+	));
+	assert_ok!(NftPeg::activate_deposits(frame_system::RawOrigin::Root.into(), true));
+	assert_ok!(NftPeg::activate_withdrawals(frame_system::RawOrigin::Root.into(), true));
+
+	let collection_owner = 1_u64;
+	let collection_id = Nft::next_collection_id();
+	assert_ok!(Nft::create_collection(
+		Some(collection_owner).into(),
+		b"test-collection".to_vec(),
+		None
+	));
+	assert_ok!(Nft::mint_series(
+		Some(collection_owner).into(),
+		collection_id,
+		2,
+		Some(token_owner),
+		MetadataScheme::IpfsDir(b"<CID>".to_vec()),
+		None,
+	));
+	assert_ok!(NftPeg::set_collection_mapping(
+		frame_system::RawOrigin::Root.into(),
+		WRAPPED_ERC721,
+		collection_id
+	));
+
+	(collection_id, 0, 1)
+}
+
+/// Notify the peg of a successful deposit claim of `token_id` from `token_address` to `account`
+fn deposit(event_claim_id: u64, token_address: EthAddress, token_id: U256, account: AccountId) {
+	let deposit_event = Erc721DepositEvent {
+		token_address,
+		token_id,
+		beneficiary: beneficiary(account),
+	};
+	<NftPeg as EventClaimSubscriber>::on_success(
+		event_claim_id,
+		&PEG_CONTRACT,
+		&H256::from(Erc721DepositEventSignature::get()),
+		&EthAbiCodec::encode(&deposit_event),
+	);
+}
+
+#[test]
+fn withdraw() {
+	ExtBuilder::default().build().execute_with(|| {
+		let token_owner = 2_u64;
+		let token_id = setup_token(token_owner);
+
+		assert_ok!(NftPeg::withdraw(Some(token_owner).into(), token_id, ETH_BENEFICIARY));
+
+		assert_eq!(
+			Nft::token_owner((token_id.0, token_id.1), token_id.2),
+			NftPeg::account_id()
+		);
+		assert_eq!(Nft::token_locks(token_id), Some(TokenLockReason::Bridged));
+		assert_eq!(
+			MockEthBridge::event_proofs(),
+			vec![EthAbiCodec::encode(&Erc721WithdrawMessage {
+				token_address: WRAPPED_ERC721,
+				token_id: U256::from(1),
+				beneficiary: ETH_BENEFICIARY,
+			})]
+		);
+		assert!(has_event(RawEvent::Erc721Withdraw(0, token_id, ETH_BENEFICIARY)));
+
+		// a bridged token cannot be transferred, or withdrawn again
+		assert_noop!(
+			Nft::transfer(Some(token_owner).into(), token_id, 3),
+			crml_nft::Error::<Test>::TokenListingProtection
+		);
+		assert_noop!(
+			NftPeg::withdraw(Some(token_owner).into(), token_id, ETH_BENEFICIARY),
+			Error::<Test>::TokenLocked
+		);
+	});
+}
+
+#[test]
+fn withdraw_fails_prechecks() {
+	ExtBuilder::default().build().execute_with(|| {
+		let token_owner = 2_u64;
+		let token_id = setup_token(token_owner);
+
+		// not the owner
+		assert_noop!(
+			NftPeg::withdraw(Some(3).into(), token_id, ETH_BENEFICIARY),
+			Error::<Test>::NoPermission
+		);
+		// no such token
+		assert_noop!(
+			NftPeg::withdraw(Some(token_owner).into(), (token_id.0, 0, 5), ETH_BENEFICIARY),
+			Error::<Test>::NoPermission
+		);
+		// collection is not mapped to an ERC721
+		assert_noop!(
+			NftPeg::withdraw(Some(token_owner).into(), (token_id.0 + 1, 0, 0), ETH_BENEFICIARY),
+			Error::<Test>::UnsupportedCollection
+		);
+		// listed tokens are locked
+		assert_ok!(Nft::sell(
+			Some(token_owner).into(),
+			token_id,
+			None,
+			16_000,
+			1_000,
+			None,
+			None
+		));
+		assert_noop!(
+			NftPeg::withdraw(Some(token_owner).into(), token_id, ETH_BENEFICIARY),
+			Error::<Test>::TokenLocked
+		);
+		// withdrawals paused
+		assert_ok!(NftPeg::activate_withdrawals(
+			frame_system::RawOrigin::Root.into(),
+			false
+		));
+		assert_noop!(
+			NftPeg::withdraw(Some(token_owner).into(), (token_id.0, 0, 0), ETH_BENEFICIARY),
+			Error::<Test>::WithdrawalsPaused
+		);
+	});
+}
+
+#[test]
+fn deposit_claim() {
+	ExtBuilder::default().build().execute_with(|| {
+		let token_id = setup_token(2);
+		let claim = Erc721DepositEvent {
+			token_address: WRAPPED_ERC721,
+			token_id: to_erc721_token_id(token_id),
+			beneficiary: beneficiary(3),
+		};
+
+		assert_ok!(NftPeg::deposit_claim(
+			Some(5).into(),
+			H256::repeat_byte(1),
+			claim.clone()
+		));
+		assert_ok!(NftPeg::deposit_claim_with_proof(
+			Some(5).into(),
+			claim.clone(),
+			Default::default()
+		));
+
+		let expected_claim = (
+			PEG_CONTRACT,
+			H256::from(Erc721DepositEventSignature::get()),
+			EthAbiCodec::encode(&claim),
+		);
+		assert_eq!(
+			MockEthBridge::event_claims(),
+			vec![expected_claim.clone(), expected_claim]
+		);
+		assert!(has_event(RawEvent::Erc721Claim(0, 5)));
+		assert!(has_event(RawEvent::Erc721Claim(1, 5)));
+	});
+}
+
+#[test]
+fn deposit_claim_fails_prechecks() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_token(2);
+		let claim = Erc721DepositEvent {
+			token_address: NATIVE_ERC721,
+			token_id: U256::from(1),
+			beneficiary: beneficiary(3),
+		};
+		assert_noop!(
+			NftPeg::deposit_claim(Some(5).into(), H256::repeat_byte(1), claim),
+			Error::<Test>::UnsupportedCollection
+		);
+
+		let claim = Erc721DepositEvent {
+			token_address: WRAPPED_ERC721,
+			token_id: U256::from(u64::max_value()) + 1,
+			beneficiary: beneficiary(3),
+		};
+		assert_noop!(
+			NftPeg::deposit_claim(Some(5).into(), H256::repeat_byte(1), claim.clone()),
+			Error::<Test>::InvalidTokenId
+		);
+
+		assert_ok!(NftPeg::activate_deposits(frame_system::RawOrigin::Root.into(), false));
+		assert_noop!(
+			NftPeg::deposit_claim(Some(5).into(), H256::repeat_byte(1), claim),
+			Error::<Test>::DepositsPaused
+		);
+		assert!(MockEthBridge::event_claims().is_empty());
+	});
+}
+
+#[test]
+fn deposit_releases_bridged_token() {
+	ExtBuilder::default().build().execute_with(|| {
+		let token_owner = 2_u64;
+		let token_id = setup_token(token_owner);
+		assert_ok!(NftPeg::withdraw(Some(token_owner).into(), token_id, ETH_BENEFICIARY));
+
+		let new_owner = 3_u64;
+		deposit(7, WRAPPED_ERC721, to_erc721_token_id(token_id), new_owner);
+
+		assert!(has_event(RawEvent::Erc721Deposit(7, token_id, new_owner)));
+		assert_eq!(Nft::token_owner((token_id.0, token_id.1), token_id.2), new_owner);
+		assert!(Nft::token_locks(token_id).is_none());
+		assert_ok!(Nft::transfer(Some(new_owner).into(), token_id, token_owner));
+	});
+}
+
+#[test]
+fn deposit_fails_for_tokens_not_bridged() {
+	ExtBuilder::default().build().execute_with(|| {
+		let token_owner = 2_u64;
+		let token_id = setup_token(token_owner);
+
+		// the token is still on Benson
+		deposit(7, WRAPPED_ERC721, to_erc721_token_id(token_id), 3);
+		assert!(has_event(RawEvent::Erc721DepositFail(7)));
+		assert_eq!(Nft::token_owner((token_id.0, token_id.1), token_id.2), token_owner);
+
+		// the token was never minted, the peg can't mint tokens of Benson collections
+		let unknown_token_id = (token_id.0, 0, 5);
+		deposit(8, WRAPPED_ERC721, to_erc721_token_id(unknown_token_id), 3);
+		assert!(has_event(RawEvent::Erc721DepositFail(8)));
+		assert!(Nft::collected_tokens(token_id.0, &3).is_empty());
+	});
+}
+
+#[test]
+fn deposit_mints_ethereum_native_tokens() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_token(2);
+		let collection_id = Nft::next_collection_id();
+		assert_ok!(NftPeg::register_erc721(
+			frame_system::RawOrigin::Root.into(),
+			NATIVE_ERC721,
+			b"native-collection".to_vec()
+		));
+		assert_eq!(Nft::collection_owner(collection_id), Some(NftPeg::account_id()));
+		assert_eq!(NftPeg::erc721_to_collection(NATIVE_ERC721), Some(collection_id));
+		assert_eq!(NftPeg::collection_to_erc721(collection_id), Some(NATIVE_ERC721));
+
+		let token_owner = 3_u64;
+		let token_id = (collection_id, 0, 1234);
+		deposit(7, NATIVE_ERC721, U256::from(1234), token_owner);
+		assert!(has_event(RawEvent::Erc721Deposit(7, token_id, token_owner)));
+		assert_eq!(Nft::collected_tokens(collection_id, &token_owner), vec![token_id]);
+
+		// round trip
+		assert_ok!(NftPeg::withdraw(Some(token_owner).into(), token_id, ETH_BENEFICIARY));
+		assert_eq!(Nft::token_locks(token_id), Some(TokenLockReason::Bridged));
+		deposit(8, NATIVE_ERC721, U256::from(1234), 4);
+		assert!(has_event(RawEvent::Erc721Deposit(8, token_id, 4)));
+		assert_eq!(Nft::series_issuance(collection_id, 0), 1);
+	});
+}
+
+#[test]
+fn deposit_ignores_other_events() {
+	ExtBuilder::default().build().execute_with(|| {
+		let token_id = setup_token(2);
+		let deposit_event = EthAbiCodec::encode(&Erc721DepositEvent {
+			token_address: WRAPPED_ERC721,
+			token_id: to_erc721_token_id(token_id),
+			beneficiary: beneficiary(3),
+		});
+
+		<NftPeg as EventClaimSubscriber>::on_success(
+			1,
+			&H160::repeat_byte(9),
+			&H256::from(Erc721DepositEventSignature::get()),
+			&deposit_event,
+		);
+		<NftPeg as EventClaimSubscriber>::on_success(1, &PEG_CONTRACT, &H256::repeat_byte(9), &deposit_event);
+		assert!(!System::events().iter().any(|e| matches!(
+			e.event,
+			Event::NftPeg(RawEvent::Erc721Deposit(..)) | Event::NftPeg(RawEvent::Erc721DepositFail(..))
+		)));
+
+		<NftPeg as EventClaimSubscriber>::on_failure(
+			2,
+			&PEG_CONTRACT,
+			&H256::from(Erc721DepositEventSignature::get()),
+			&deposit_event,
+		);
+		assert!(has_event(RawEvent::Erc721DepositFail(2)));
+	});
+}
+
+#[test]
+fn set_collection_mapping() {
+	ExtBuilder::default().build().execute_with(|| {
+		let token_id = setup_token(2);
+		let collection_id = token_id.0;
+		assert!(has_event(RawEvent::SetCollectionMapping(WRAPPED_ERC721, collection_id)));
+
+		assert_noop!(
+			NftPeg::set_collection_mapping(Some(1).into(), NATIVE_ERC721, collection_id),
+			BadOrigin
+		);
+		assert_noop!(
+			NftPeg::set_collection_mapping(frame_system::RawOrigin::Root.into(), NATIVE_ERC721, collection_id),
+			Error::<Test>::CollectionAlreadyMapped
+		);
+		assert_noop!(
+			NftPeg::register_erc721(
+				frame_system::RawOrigin::Root.into(),
+				WRAPPED_ERC721,
+				b"native-collection".to_vec()
+			),
+			Error::<Test>::CollectionAlreadyMapped
+		);
+		assert_noop!(
+			NftPeg::set_collection_mapping(frame_system::RawOrigin::Root.into(), NATIVE_ERC721, collection_id + 1),
+			Error::<Test>::NoCollection
+		);
+	});
+}
@@ -0,0 +1,186 @@
+/* Copyright 2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+use codec::{Decode, Encode};
+use crml_nft::{CollectionId, TokenId};
+pub use crml_support::{EthAbiCodec, ERC721_WITHDRAW_TAG, H160, H256, U256};
+use scale_info::TypeInfo;
+use sp_std::convert::TryInto;
+use sp_std::prelude::*;
+
+/// Ethereum address type
+pub type EthAddress = H160;
+
+/// A deposit event made by the NFT peg contract on Ethereum
+#[derive(Debug, Default, Clone, PartialEq, Decode, Encode, TypeInfo)]
+pub struct Erc721DepositEvent {
+	/// The ERC721 contract address / collection deposited
+	pub token_address: EthAddress,
+	/// The ERC721 token Id
+	pub token_id: U256,
+	/// The Benson beneficiary address
+	pub beneficiary: H256,
+}
+
+/// A withdraw message to prove and submit to Ethereum
+/// Allowing redemption of an ERC721
+#[derive(Debug, Default, Clone, PartialEq, Decode, Encode)]
+pub struct Erc721WithdrawMessage {
+	/// The ERC721 contract address / collection withdrawn
+	pub token_address: EthAddress,
+	/// The ERC721 token Id
+	pub token_id: U256,
+	/// The Ethereum beneficiary address
+	pub beneficiary: EthAddress,
+}
+
+impl EthAbiCodec for Erc721WithdrawMessage {
+	/// Encode `Erc721WithdrawMessage` into 32-byte words, prefixed by `ERC721_WITHDRAW_TAG`
+	/// https://docs.soliditylang.org/en/v0.5.3/abi-spec.html#formal-specification-of-the-encoding
+	fn encode(&self) -> Vec<u8> {
+		let mut buf = [0_u8; 32 * 4];
+		buf[..32].copy_from_slice(&ERC721_WITHDRAW_TAG);
+		buf[44..64].copy_from_slice(&self.token_address.to_fixed_bytes());
+		buf[64..96].copy_from_slice(&Into::<[u8; 32]>::into(self.token_id));
+		buf[108..128].copy_from_slice(&self.beneficiary.to_fixed_bytes());
+		buf.to_vec()
+	}
+
+	/// Decode `Erc721WithdrawMessage` from 32-byte words
+	fn decode(data: &[u8]) -> Option<Self> {
+		// Expect the tag and 3 words of data
+		if data.len() != 4 * 32 || data[..32] != ERC721_WITHDRAW_TAG {
+			return None;
+		}
+		let token_address = H160::from(&data[44..64].try_into().expect("20 bytes decode"));
+		let token_id = data[64..96].into();
+		let beneficiary = H160::from(&data[108..128].try_into().expect("20 bytes decode"));
+
+		Some(Self {
+			token_address,
+			token_id,
+			beneficiary,
+		})
+	}
+}
+
+impl EthAbiCodec for Erc721DepositEvent {
+	/// Encode `Erc721DepositEvent` into 32-byte words
+	/// https://docs.soliditylang.org/en/v0.5.3/abi-spec.html#formal-specification-of-the-encoding
+	fn encode(&self) -> Vec<u8> {
+		let mut buf = [0_u8; 32 * 3];
+		buf[12..32].copy_from_slice(&self.token_address.to_fixed_bytes());
+		buf[32..64].copy_from_slice(&Into::<[u8; 32]>::into(self.token_id));
+		buf[64..96].copy_from_slice(&self.beneficiary.to_fixed_bytes());
+		buf.to_vec()
+	}
+	/// Receives Ethereum log 'data' and decodes it
+	fn decode(data: &[u8]) -> Option<Self> {
+		// Expect 3 words of data
+		if data.len() != 3 * 32 {
+			return None;
+		}
+		let token_address = H160::from(&data[12..32].try_into().expect("20 bytes decode"));
+		let token_id = data[32..64].into();
+		let beneficiary = H256::from(&data[64..96].try_into().expect("32 bytes decode"));
+
+		Some(Self {
+			token_address,
+			token_id,
+			beneficiary,
+		})
+	}
+}
+
+/// Convert a Benson `token_id` into its ERC721 token Id
+/// The ERC721 Id packs the series Id and serial number: `series_id << 32 | serial_number`
+/// The collection is implied by the ERC721 contract address
+pub fn to_erc721_token_id(token_id: TokenId) -> U256 {
+	let (_collection_id, series_id, serial_number) = token_id;
+	U256::from(((series_id as u64) << 32) | serial_number as u64)
+}
+
+/// Convert an ERC721 token Id of a `collection_id` into a Benson `TokenId`
+/// Returns `None` if the ERC721 token Id cannot be represented on Benson
+pub fn from_erc721_token_id(collection_id: CollectionId, erc721_token_id: U256) -> Option<TokenId> {
+	if erc721_token_id > U256::from(u64::max_value()) {
+		return None;
+	}
+	let packed = erc721_token_id.low_u64();
+	Some((collection_id, (packed >> 32) as u32, packed as u32))
+}
+
+#[cfg(test)]
+mod test {
+	use super::{
+		from_erc721_token_id, to_erc721_token_id, Erc721DepositEvent, Erc721WithdrawMessage, EthAbiCodec,
+		ERC721_WITHDRAW_TAG,
+	};
+	use crml_support::{ERC20_WITHDRAW_TAG, H160, H256, U256};
+
+	#[test]
+	fn deposit_event_decode() {
+		let event = Erc721DepositEvent {
+			token_address: H160::from_low_u64_be(55),
+			token_id: U256::from(123),
+			beneficiary: H256::from_low_u64_be(77),
+		};
+		let raw = [
+			H256::from_low_u64_be(55).as_bytes(),
+			H256::from_low_u64_be(123).as_bytes(),
+			H256::from_low_u64_be(77).as_bytes(),
+		]
+		.concat();
+		assert_eq!(EthAbiCodec::encode(&event), raw);
+		assert_eq!(Erc721DepositEvent::decode(&raw).expect("it decodes"), event);
+		assert!(Erc721DepositEvent::decode(&raw[1..]).is_none());
+	}
+
+	#[test]
+	fn withdraw_message_encode() {
+		let message = Erc721WithdrawMessage {
+			token_address: H160::from_low_u64_be(55),
+			token_id: U256::from(123),
+			beneficiary: H160::from_low_u64_be(77),
+		};
+		let raw = [
+			&ERC721_WITHDRAW_TAG[..],
+			H256::from_low_u64_be(55).as_bytes(),
+			H256::from_low_u64_be(123).as_bytes(),
+			H256::from_low_u64_be(77).as_bytes(),
+		]
+		.concat();
+		assert_eq!(EthAbiCodec::encode(&message), raw);
+		assert_eq!(Erc721WithdrawMessage::decode(&raw).expect("it decodes"), message);
+		assert!(Erc721WithdrawMessage::decode(&raw[1..]).is_none());
+
+		// the same words tagged as an ERC20 withdrawal are not an ERC721 withdrawal
+		let erc20_raw = [&ERC20_WITHDRAW_TAG[..], &raw[32..]].concat();
+		assert!(Erc721WithdrawMessage::decode(&erc20_raw).is_none());
+	}
+
+	#[test]
+	fn erc721_token_id_conversion() {
+		let token_id = (1, 2, 3);
+		let erc721_token_id = to_erc721_token_id(token_id);
+		assert_eq!(erc721_token_id, U256::from((2_u64 << 32) + 3));
+		assert_eq!(from_erc721_token_id(1, erc721_token_id), Some(token_id));
+
+		assert_eq!(
+			from_erc721_token_id(1, U256::from(u64::max_value())),
+			Some((1, u32::max_value(), u32::max_value()))
+		);
+		assert_eq!(from_erc721_token_id(1, U256::from(u64::max_value()) + 1), None);
+	}
+}
@@ -44,7 +44,7 @@ use frame_support::{
 use frame_system::pallet_prelude::*;
 use sp_runtime::{
 	traits::{One, Saturating, Zero},
-	DispatchResult, PerThing, Permill,
+	DispatchError, DispatchResult, PerThing, Permill,
 };
 use sp_std::prelude::*;
 
@@ -152,6 +152,8 @@ decl_error! {
 		RoyaltiesProtection,
 		/// The account_id hasn't been registered as a marketplace
 		MarketplaceNotRegistered,
+		/// The token already exists
+		TokenExists,
 	}
 }
 
@@ -335,23 +337,7 @@ decl_module! {
 			royalties_schedule: Option<RoyaltiesSchedule<T::AccountId>>,
 		) -> DispatchResult {
 			let origin = ensure_signed(origin)?;
-
-			ensure!(!name.is_empty() && name.len() <= MAX_COLLECTION_NAME_LENGTH as usize, Error::<T>::CollectionNameInvalid);
-			ensure!(core::str::from_utf8(&name).is_ok(), Error::<T>::CollectionNameInvalid);
-
-			let collection_id = NextCollectionId::get();
-			ensure!(collection_id.checked_add(One::one()).is_some(), Error::<T>::NoAvailableIds);
-
-			// Create the collection, update ownership, and bookkeeping
-			if let Some(royalties_schedule) = royalties_schedule {
-				ensure!(royalties_schedule.validate(), Error::<T>::RoyaltiesInvalid);
-				<CollectionRoyalties<T>>::insert(collection_id, royalties_schedule);
-			}
-			<CollectionOwner<T>>::insert(collection_id, &origin);
-			CollectionName::insert(collection_id, &name);
-			NextCollectionId::mutate(|c| *c += 1);
-
-			Self::deposit_event(RawEvent::CreateCollection(collection_id, name, origin));
+			Self::do_create_collection(origin, name, royalties_schedule)?;
 
 			Ok(())
 		}
@@ -899,9 +885,41 @@ impl<T: Config> Module<T> {
 		};
 		Ok(royalties)
 	}
+	/// Create a new token collection owned by `owner`
+	/// Returns the new collection Id
+	pub fn do_create_collection(
+		owner: T::AccountId,
+		name: CollectionNameType,
+		royalties_schedule: Option<RoyaltiesSchedule<T::AccountId>>,
+	) -> Result<CollectionId, DispatchError> {
+		ensure!(
+			!name.is_empty() && name.len() <= MAX_COLLECTION_NAME_LENGTH as usize,
+			Error::<T>::CollectionNameInvalid
+		);
+		ensure!(core::str::from_utf8(&name).is_ok(), Error::<T>::CollectionNameInvalid);
+
+		let collection_id = NextCollectionId::get();
+		ensure!(
+			collection_id.checked_add(One::one()).is_some(),
+			Error::<T>::NoAvailableIds
+		);
+
+		// Create the collection, update ownership, and bookkeeping
+		if let Some(royalties_schedule) = royalties_schedule {
+			ensure!(royalties_schedule.validate(), Error::<T>::RoyaltiesInvalid);
+			<CollectionRoyalties<T>>::insert(collection_id, royalties_schedule);
+		}
+		<CollectionOwner<T>>::insert(collection_id, &owner);
+		CollectionName::insert(collection_id, &name);
+		NextCollectionId::mutate(|c| *c += 1);
+
+		Self::deposit_event(RawEvent::CreateCollection(collection_id, name, owner));
+
+		Ok(collection_id)
+	}
 	/// Transfer the given tokens from `current_owner` to `new_owner`
 	/// Does no verification
-	fn do_transfer_unchecked(tokens: &[TokenId], new_owner: &T::AccountId) {
+	pub fn do_transfer_unchecked(tokens: &[TokenId], new_owner: &T::AccountId) {
 		for token_id in tokens.iter() {
 			<TokenOwner<T>>::insert((token_id.0, token_id.1), token_id.2, new_owner);
 		}
@@ -927,6 +945,31 @@ impl<T: Config> Module<T> {
 		Ok(())
 	}
 
+	/// Mint a single token with a given serial number e.g. a token bridged from another chain
+	/// The series is created if it does not yet exist, the token must not exist
+	pub fn do_mint_serial(owner: &T::AccountId, token_id: TokenId) -> DispatchResult {
+		let (collection_id, series_id, serial_number) = token_id;
+		ensure!(
+			<CollectionOwner<T>>::contains_key(collection_id),
+			Error::<T>::NoCollection
+		);
+		ensure!(
+			!<TokenOwner<T>>::contains_key((collection_id, series_id), serial_number),
+			Error::<T>::TokenExists
+		);
+		let next_serial_number = serial_number
+			.checked_add(One::one())
+			.ok_or(Error::<T>::NoAvailableIds)?;
+		let next_series_id = series_id.checked_add(One::one()).ok_or(Error::<T>::NoAvailableIds)?;
+
+		<TokenOwner<T>>::insert((collection_id, series_id), serial_number, owner);
+		SeriesIssuance::mutate(collection_id, series_id, |q| *q = q.saturating_add(One::one()));
+		NextSerialNumber::mutate(collection_id, series_id, |n| *n = (*n).max(next_serial_number));
+		NextSeriesId::mutate(collection_id, |s| *s = (*s).max(next_series_id));
+
+		Ok(())
+	}
+
 	/// Find the tokens owned by an `address` in the given collection
 	pub fn collected_tokens(collection_id: CollectionId, address: &T::AccountId) -> Vec<TokenId> {
 		let next_series_id = Self::next_series_id(collection_id);
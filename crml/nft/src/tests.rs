@@ -416,6 +416,38 @@ fn burn_fails_prechecks() {
 	});
 }
 
+#[test]
+fn mint_serial() {
+	ExtBuilder::default().build().execute_with(|| {
+		let collection_id = setup_collection(1_u64);
+		let token_owner = 2_u64;
+
+		// a series is created on demand and serials needn't be contiguous
+		assert_ok!(Nft::do_mint_serial(&token_owner, (collection_id, 1, 5)));
+		assert_eq!(Nft::token_owner((collection_id, 1), 5), token_owner);
+		assert_eq!(Nft::series_issuance(collection_id, 1), 1);
+		assert_eq!(Nft::next_serial_number(collection_id, 1), 6);
+		assert_eq!(Nft::next_series_id(collection_id), 2);
+
+		assert_ok!(Nft::do_mint_serial(&token_owner, (collection_id, 1, 2)));
+		assert_eq!(Nft::series_issuance(collection_id, 1), 2);
+		assert_eq!(Nft::next_serial_number(collection_id, 1), 6);
+		assert_eq!(
+			Nft::collected_tokens(collection_id, &token_owner),
+			vec![(collection_id, 1, 2), (collection_id, 1, 5)]
+		);
+
+		assert_noop!(
+			Nft::do_mint_serial(&token_owner, (collection_id, 1, 5)),
+			Error::<Test>::TokenExists
+		);
+		assert_noop!(
+			Nft::do_mint_serial(&token_owner, (collection_id + 1, 0, 0)),
+			Error::<Test>::NoCollection
+		);
+	});
+}
+
 #[test]
 fn sell_bundle() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -104,6 +104,8 @@ pub struct TokenInfo<AccountId> {
 pub enum TokenLockReason {
 	/// Token is listed for sale
 	Listed(ListingId),
+	/// Token is held by the NFT peg while bridged to Ethereum
+	Bridged,
 }
 
 /// The supported attribute data types for an NFT
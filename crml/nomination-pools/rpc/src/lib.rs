@@ -0,0 +1,235 @@
+// Copyright 2019-2021
+//     by  Annie Lai Investments Ltd.
+//     and Parity Technologies (UK) Ltd.
+// This file is part of Plug-blockchain.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC interface for the nomination pools module.
+
+pub use self::gen_client::Client as NominationPoolsClient;
+use benson_primitives::types::Balance;
+use codec::Codec;
+use crml_nomination_pools::{
+	EraIndex, MemberInfo as RuntimeMemberInfo, PoolId, PoolInfo as RuntimePoolInfo, PoolState,
+};
+pub use crml_nomination_pools_rpc_runtime_api::NominationPoolsApi as NominationPoolsRuntimeApi;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+#[rpc]
+pub trait NominationPoolsApi<AccountId, BlockHash> {
+	/// Get a pool's roles, state, stake and unbonding funds
+	#[rpc(name = "nominationPools_getPool")]
+	fn pool(&self, pool_id: PoolId, at: Option<BlockHash>) -> Result<Option<PoolInfo<AccountId>>>;
+
+	/// Get all pools ordered by Id
+	#[rpc(name = "nominationPools_listPools")]
+	fn pools(&self, at: Option<BlockHash>) -> Result<Vec<PoolInfo<AccountId>>>;
+
+	/// Get an account's pool membership, stake, unbonding funds and pending rewards
+	#[rpc(name = "nominationPools_getMember")]
+	fn member(&self, who: AccountId, at: Option<BlockHash>) -> Result<Option<MemberInfo>>;
+
+	/// Get the rewards an account may claim from its pool
+	#[rpc(name = "nominationPools_pendingRewards")]
+	fn pending_rewards(&self, who: AccountId, at: Option<BlockHash>) -> Result<String>;
+}
+
+/// A struct that implements the [`NominationPoolsApi`].
+pub struct NominationPools<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> NominationPools<C, P> {
+	/// Create new `NominationPools` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		NominationPools {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolInfo<AccountId> {
+	pool_id: PoolId,
+	bonded_account: AccountId,
+	reward_account: AccountId,
+	depositor: AccountId,
+	root: AccountId,
+	nominator: AccountId,
+	state: PoolState,
+	#[serde(with = "serde_balance")]
+	points: Balance,
+	member_count: u32,
+	#[serde(with = "serde_balance")]
+	active_stake: Balance,
+	unbonding: Vec<Unbonding>,
+	#[serde(with = "serde_balance")]
+	unclaimed_rewards: Balance,
+}
+
+impl<AccountId> From<RuntimePoolInfo<AccountId>> for PoolInfo<AccountId> {
+	fn from(info: RuntimePoolInfo<AccountId>) -> Self {
+		PoolInfo {
+			pool_id: info.pool_id,
+			bonded_account: info.bonded_account,
+			reward_account: info.reward_account,
+			depositor: info.roles.depositor,
+			root: info.roles.root,
+			nominator: info.roles.nominator,
+			state: info.state,
+			points: info.points,
+			member_count: info.member_count,
+			active_stake: info.active_stake,
+			unbonding: info.unbonding.into_iter().map(Into::into).collect(),
+			unclaimed_rewards: info.unclaimed_rewards,
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MemberInfo {
+	pool_id: PoolId,
+	#[serde(with = "serde_balance")]
+	points: Balance,
+	#[serde(with = "serde_balance")]
+	active_stake: Balance,
+	unbonding: Vec<Unbonding>,
+	#[serde(with = "serde_balance")]
+	pending_rewards: Balance,
+}
+
+impl From<RuntimeMemberInfo> for MemberInfo {
+	fn from(info: RuntimeMemberInfo) -> Self {
+		MemberInfo {
+			pool_id: info.pool_id,
+			points: info.points,
+			active_stake: info.active_stake,
+			unbonding: info.unbonding.into_iter().map(Into::into).collect(),
+			pending_rewards: info.pending_rewards,
+		}
+	}
+}
+
+/// Funds unlocking in an era
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Unbonding {
+	era: EraIndex,
+	#[serde(with = "serde_balance")]
+	balance: Balance,
+}
+
+impl From<(EraIndex, Balance)> for Unbonding {
+	fn from((era, balance): (EraIndex, Balance)) -> Self {
+		Unbonding { era, balance }
+	}
+}
+
+/// (de)serialize balances as strings, JSON numbers can't hold a `u128`
+mod serde_balance {
+	use super::Balance;
+	use serde::{Deserialize, Deserializer, Serializer};
+
+	pub fn serialize<S: Serializer>(t: &Balance, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+		serializer.serialize_str(&t.to_string())
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Balance, D::Error> {
+		let s = String::deserialize(deserializer)?;
+		s.parse::<Balance>()
+			.map_err(|_| serde::de::Error::custom("Parse from string failed"))
+	}
+}
+
+impl<C, Block, AccountId> NominationPoolsApi<AccountId, <Block as BlockT>::Hash>
+	for NominationPools<C, (Block, AccountId)>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: NominationPoolsRuntimeApi<Block, AccountId>,
+	AccountId: Codec + Send + Sync + 'static,
+{
+	fn pool(&self, pool_id: PoolId, at: Option<<Block as BlockT>::Hash>) -> Result<Option<PoolInfo<AccountId>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		self.client
+			.runtime_api()
+			.pool(&at, pool_id)
+			.map(|info| info.map(Into::into))
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError as i64),
+				message: "Unable to query pool.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+
+	fn pools(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<PoolInfo<AccountId>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		self.client
+			.runtime_api()
+			.pools(&at)
+			.map(|pools| pools.into_iter().map(Into::into).collect())
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError as i64),
+				message: "Unable to query pools.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+
+	fn member(&self, who: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<Option<MemberInfo>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		self.client
+			.runtime_api()
+			.member(&at, who)
+			.map(|info| info.map(Into::into))
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError as i64),
+				message: "Unable to query pool member.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+
+	fn pending_rewards(&self, who: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<String> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		self.client
+			.runtime_api()
+			.pending_rewards(&at, who)
+			.map(|rewards| rewards.to_string())
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError as i64),
+				message: "Unable to query pending rewards.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+}
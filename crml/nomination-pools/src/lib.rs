@@ -0,0 +1,694 @@
+/* Copyright 2019-2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+//!
+//! Benson nomination pools
+//!
+//! Pools let CENNZ holders below the staking minimum bond nominate together.
+//! A pool bonds its members' funds from a shared stash and nominates on their behalf.
+//! Members hold points in the pool's active stake, the pool's staking rewards accrue to a reward account
+//! and are claimed pro-rata to points. Unbonding funds move into per-era unbonding pools, so slashes
+//! applied to the stash's active and unlocking funds are shared pro-rata by the affected members.
+//!
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+mod types;
+pub use types::*;
+mod weights;
+pub use weights::WeightInfo;
+
+use benson_primitives::types::{AssetId, Balance};
+use crml_support::{MultiCurrency, OnStakerSlash, StakingInterface};
+use frame_support::{
+	decl_error, decl_event, decl_module, decl_storage, dispatch::DispatchResult, pallet_prelude::*,
+	storage::IterableStorageDoubleMap, traits::ExistenceRequirement, transactional, PalletId,
+};
+use frame_system::ensure_signed;
+use sp_runtime::traits::{AccountIdConversion, Zero};
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
+
+pub trait Config: frame_system::Config {
+	/// An onchain address for this pallet, pool accounts are derived from it
+	type PoolsPalletId: Get<PalletId>;
+	/// Currency functions
+	type MultiCurrency: MultiCurrency<AccountId = Self::AccountId, Balance = Balance, CurrencyId = AssetId>;
+	/// Bonds and nominates pool funds
+	type Staking: StakingInterface<AccountId = Self::AccountId, Balance = Balance>;
+	/// Min. amount to join a pool
+	type MinJoinBond: Get<Balance>;
+	/// Min. amount to create a pool, the staking minimum bond also applies
+	type MinCreateBond: Get<Balance>;
+	/// Max. number of pools
+	type MaxPools: Get<u32>;
+	/// Max. number of members per pool
+	type MaxMembersPerPool: Get<u32>;
+	/// The system event type
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
+	/// Extrinsic weight info
+	type WeightInfo: WeightInfo;
+}
+
+decl_event! {
+	pub enum Event<T> where AccountId = <T as frame_system::Config>::AccountId {
+		/// A pool was created (pool, depositor)
+		Created(PoolId, AccountId),
+		/// A member bonded funds into a pool (pool, member, amount, points issued)
+		Bonded(PoolId, AccountId, Balance, Balance),
+		/// A member was paid out rewards (pool, member, amount)
+		PaidOut(PoolId, AccountId, Balance),
+		/// A member unbonded funds from a pool (pool, member, amount, era unlocked at)
+		Unbonded(PoolId, AccountId, Balance, EraIndex),
+		/// A member withdrew unlocked funds from a pool (pool, member, amount)
+		Withdrawn(PoolId, AccountId, Balance),
+		/// A member left a pool
+		MemberRemoved(PoolId, AccountId),
+		/// The active stake of a pool was slashed (pool, new active stake)
+		PoolSlashed(PoolId, Balance),
+		/// The funds of a pool unbonding in an era were slashed (pool, era, new balance)
+		UnbondingPoolSlashed(PoolId, EraIndex, Balance),
+		/// The state of a pool changed
+		StateChanged(PoolId, PoolState),
+		/// A pool was destroyed after its last member left
+		Destroyed(PoolId),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Config> {
+		/// The pool was not found
+		PoolNotFound,
+		/// The account is not a pool member
+		NotMember,
+		/// The account is already a member of a pool
+		AlreadyMember,
+		/// The amount is below the min. bond
+		MinimumBondNotMet,
+		/// Reached the max. number of pools
+		MaxPools,
+		/// Reached the max. number of members of the pool
+		MaxPoolMembers,
+		/// The pool is not open to new members
+		NotOpen,
+		/// The pool is being destroyed
+		PoolDestroying,
+		/// The caller does not have the pool role required
+		NoPermission,
+		/// Points must be non-zero and at most the member's points
+		InvalidPoints,
+		/// The depositor may only leave once all other members have left
+		DepositorMustLeaveLast,
+		/// Unbonding would leave the pool's active stake below the staking minimum bond
+		PoolBelowMinimumBond,
+		/// The pool's stake was slashed to zero, it can't take new funds
+		PoolSlashedToZero,
+		/// The member has no unlocked funds to withdraw
+		NothingToWithdraw,
+	}
+}
+
+decl_storage! {
+	trait Store for Module<T: Config> as NominationPools {
+		/// Map from pool Id to bonded pool info
+		BondedPools get(fn bonded_pools): map hasher(twox_64_concat) PoolId => Option<BondedPool<T::AccountId>>;
+		/// Map from pool Id to reward accounting
+		RewardPools get(fn reward_pools): map hasher(twox_64_concat) PoolId => RewardPool;
+		/// Unbonding funds of a pool by era they unlock at
+		UnbondingPools get(fn unbonding_pools): double_map hasher(twox_64_concat) PoolId, hasher(twox_64_concat) EraIndex => Option<UnbondPool>;
+		/// Map from account to pool membership
+		PoolMembers get(fn pool_members): map hasher(blake2_128_concat) T::AccountId => Option<PoolMember>;
+		/// Number of pools
+		PoolCount get(fn pool_count): u32;
+		/// Next available ID for pool
+		NextPoolId get(fn next_pool_id): PoolId;
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Config> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// Create a pool bonding `amount` from the caller, who becomes its depositor and first member
+		/// `root` may change the pool's state and nominate, `nominator` may nominate
+		#[weight = T::WeightInfo::create()]
+		#[transactional]
+		fn create(origin, amount: Balance, root: T::AccountId, nominator: T::AccountId) -> DispatchResult {
+			let depositor = ensure_signed(origin)?;
+			ensure!(!<PoolMembers<T>>::contains_key(&depositor), Error::<T>::AlreadyMember);
+			ensure!(amount >= Self::min_create_bond(), Error::<T>::MinimumBondNotMet);
+			ensure!(Self::pool_count() < T::MaxPools::get(), Error::<T>::MaxPools);
+
+			let pool_id = Self::next_pool_id();
+			let bonded_account = Self::bonded_account(pool_id);
+			T::MultiCurrency::transfer(
+				&depositor,
+				&bonded_account,
+				T::MultiCurrency::staking_currency(),
+				amount,
+				ExistenceRequirement::AllowDeath,
+			)?;
+			T::Staking::bond(&bonded_account, amount, &Self::reward_account(pool_id))?;
+
+			// any funds already in the reward account aren't rewards
+			let mut reward_pool = RewardPool::default();
+			reward_pool.update_records(Self::reward_balance(pool_id), Zero::zero());
+
+			<PoolMembers<T>>::insert(
+				&depositor,
+				PoolMember {
+					pool_id,
+					points: amount,
+					last_recorded_reward_counter: reward_pool.last_recorded_reward_counter,
+					unbonding_eras: Default::default(),
+				},
+			);
+			<BondedPools<T>>::insert(
+				pool_id,
+				BondedPool {
+					roles: PoolRoles { depositor: depositor.clone(), root, nominator },
+					state: PoolState::Open,
+					points: amount,
+					member_count: 1,
+				},
+			);
+			RewardPools::insert(pool_id, reward_pool);
+			NextPoolId::put(pool_id.saturating_add(1));
+			PoolCount::mutate(|count| *count = count.saturating_add(1));
+
+			Self::deposit_event(RawEvent::Created(pool_id, depositor.clone()));
+			Self::deposit_event(RawEvent::Bonded(pool_id, depositor, amount, amount));
+
+			Ok(())
+		}
+
+		/// Join pool `pool_id` bonding `amount` from the caller
+		#[weight = T::WeightInfo::join()]
+		#[transactional]
+		fn join(origin, pool_id: PoolId, amount: Balance) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!<PoolMembers<T>>::contains_key(&who), Error::<T>::AlreadyMember);
+			ensure!(amount >= T::MinJoinBond::get(), Error::<T>::MinimumBondNotMet);
+			let mut pool = Self::bonded_pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			ensure!(pool.state == PoolState::Open, Error::<T>::NotOpen);
+			ensure!(pool.member_count < T::MaxMembersPerPool::get(), Error::<T>::MaxPoolMembers);
+
+			let mut reward_pool = Self::reward_pools(pool_id);
+			reward_pool.update_records(Self::reward_balance(pool_id), pool.points);
+			let points = Self::bond_funds(pool_id, &mut pool, &who, amount)?;
+			pool.member_count = pool.member_count.saturating_add(1);
+
+			<PoolMembers<T>>::insert(
+				&who,
+				PoolMember {
+					pool_id,
+					points,
+					last_recorded_reward_counter: reward_pool.last_recorded_reward_counter,
+					unbonding_eras: Default::default(),
+				},
+			);
+			<BondedPools<T>>::insert(pool_id, pool);
+			RewardPools::insert(pool_id, reward_pool);
+			Self::deposit_event(RawEvent::Bonded(pool_id, who, amount, points));
+
+			Ok(())
+		}
+
+		/// Bond `amount` more from the caller into their pool
+		/// Pending rewards are paid out first
+		#[weight = T::WeightInfo::bond_extra()]
+		#[transactional]
+		fn bond_extra(origin, amount: Balance) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut member = Self::pool_members(&who).ok_or(Error::<T>::NotMember)?;
+			let pool_id = member.pool_id;
+			let mut pool = Self::bonded_pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			ensure!(pool.state != PoolState::Destroying, Error::<T>::PoolDestroying);
+
+			let mut reward_pool = Self::reward_pools(pool_id);
+			Self::do_reward_payout(&who, &mut member, &pool, &mut reward_pool)?;
+			let points = Self::bond_funds(pool_id, &mut pool, &who, amount)?;
+			member.points = member.points.saturating_add(points);
+
+			<PoolMembers<T>>::insert(&who, member);
+			<BondedPools<T>>::insert(pool_id, pool);
+			RewardPools::insert(pool_id, reward_pool);
+			Self::deposit_event(RawEvent::Bonded(pool_id, who, amount, points));
+
+			Ok(())
+		}
+
+		/// Pay out the caller's pending rewards from their pool
+		#[weight = T::WeightInfo::claim_payout()]
+		#[transactional]
+		fn claim_payout(origin) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut member = Self::pool_members(&who).ok_or(Error::<T>::NotMember)?;
+			let pool_id = member.pool_id;
+			let pool = Self::bonded_pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+
+			let mut reward_pool = Self::reward_pools(pool_id);
+			Self::do_reward_payout(&who, &mut member, &pool, &mut reward_pool)?;
+
+			<PoolMembers<T>>::insert(&who, member);
+			RewardPools::insert(pool_id, reward_pool);
+
+			Ok(())
+		}
+
+		/// Unbond `points` of the caller's pool stake, pending rewards are paid out first
+		/// The funds may be withdrawn once they unlock after the staking bonding duration
+		/// Any of the pool's unlocked funds are withdrawn from staking first, freeing their unlocking chunks
+		/// The depositor may only unbond entirely once all other members have left, which destroys the pool
+		#[weight = T::WeightInfo::unbond()]
+		#[transactional]
+		fn unbond(origin, points: Balance) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut member = Self::pool_members(&who).ok_or(Error::<T>::NotMember)?;
+			ensure!(!points.is_zero() && points <= member.points, Error::<T>::InvalidPoints);
+			let pool_id = member.pool_id;
+			let mut pool = Self::bonded_pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			let is_depositor = who == pool.roles.depositor;
+
+			let bonded_account = Self::bonded_account(pool_id);
+			// members share the stash's unlocking chunks, clear unlocked chunks so unbonding never runs out
+			Self::withdraw_unlocked(pool_id, &bonded_account, T::Staking::current_era())?;
+			let active = T::Staking::active_stake(&bonded_account);
+			let balance = points_to_balance(active, pool.points, points);
+			let remaining_points = member.points.saturating_sub(points);
+			if remaining_points.is_zero() {
+				ensure!(!is_depositor || pool.member_count == 1, Error::<T>::DepositorMustLeaveLast);
+			} else {
+				let min_bond = if is_depositor { Self::min_create_bond() } else { T::MinJoinBond::get() };
+				ensure!(
+					points_to_balance(active, pool.points, remaining_points) >= min_bond,
+					Error::<T>::MinimumBondNotMet
+				);
+			}
+			// staking would unbond the pool's entire stake if it fell below the minimum bond
+			let remaining_active = active.saturating_sub(balance);
+			ensure!(
+				remaining_active.is_zero() || remaining_active >= T::Staking::minimum_bond(),
+				Error::<T>::PoolBelowMinimumBond
+			);
+
+			let mut reward_pool = Self::reward_pools(pool_id);
+			Self::do_reward_payout(&who, &mut member, &pool, &mut reward_pool)?;
+
+			let era = T::Staking::current_era().saturating_add(T::Staking::bonding_duration());
+			let mut unbond_pool = Self::unbonding_pools(pool_id, era).unwrap_or_default();
+			// issue points against the unbonding balance net of any slash
+			unbond_pool.balance = Self::unlocking_balance(&bonded_account, era);
+			let unbond_points = balance_to_points(unbond_pool.balance, unbond_pool.points, balance);
+			if !balance.is_zero() {
+				T::Staking::unbond(&bonded_account, balance)?;
+			}
+			unbond_pool.points = unbond_pool.points.saturating_add(unbond_points);
+			unbond_pool.balance = unbond_pool.balance.saturating_add(balance);
+
+			pool.points = pool.points.saturating_sub(points);
+			member.points = remaining_points;
+			let era_points = member.unbonding_eras.entry(era).or_default();
+			*era_points = era_points.saturating_add(unbond_points);
+
+			UnbondingPools::insert(pool_id, era, unbond_pool);
+			<PoolMembers<T>>::insert(&who, member);
+			RewardPools::insert(pool_id, reward_pool);
+			Self::deposit_event(RawEvent::Unbonded(pool_id, who, balance, era));
+			if is_depositor && remaining_points.is_zero() && pool.state != PoolState::Destroying {
+				pool.state = PoolState::Destroying;
+				Self::deposit_event(RawEvent::StateChanged(pool_id, PoolState::Destroying));
+			}
+			<BondedPools<T>>::insert(pool_id, pool);
+
+			Ok(())
+		}
+
+		/// Withdraw the caller's unlocked funds from their pool
+		/// Members leave the pool once all their funds are withdrawn, the pool is destroyed when the last member leaves
+		#[weight = T::WeightInfo::withdraw_unbonded()]
+		#[transactional]
+		fn withdraw_unbonded(origin) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut member = Self::pool_members(&who).ok_or(Error::<T>::NotMember)?;
+			let pool_id = member.pool_id;
+			let mut pool = Self::bonded_pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+
+			let current_era = T::Staking::current_era();
+			let unlocked_eras: Vec<EraIndex> = member
+				.unbonding_eras
+				.keys()
+				.copied()
+				.filter(|era| *era <= current_era)
+				.collect();
+			ensure!(!unlocked_eras.is_empty(), Error::<T>::NothingToWithdraw);
+
+			let bonded_account = Self::bonded_account(pool_id);
+			Self::withdraw_unlocked(pool_id, &bonded_account, current_era)?;
+
+			let mut amount: Balance = Zero::zero();
+			for era in unlocked_eras {
+				let points = member.unbonding_eras.remove(&era).unwrap_or_default();
+				let mut unbond_pool = Self::unbonding_pools(pool_id, era).unwrap_or_default();
+				let share = points_to_balance(unbond_pool.balance, unbond_pool.points, points);
+				unbond_pool.points = unbond_pool.points.saturating_sub(points);
+				unbond_pool.balance = unbond_pool.balance.saturating_sub(share);
+				if unbond_pool.points.is_zero() {
+					UnbondingPools::remove(pool_id, era);
+				} else {
+					UnbondingPools::insert(pool_id, era, unbond_pool);
+				}
+				amount = amount.saturating_add(share);
+			}
+
+			if !amount.is_zero() {
+				T::MultiCurrency::transfer(
+					&bonded_account,
+					&who,
+					T::MultiCurrency::staking_currency(),
+					amount,
+					ExistenceRequirement::AllowDeath,
+				)?;
+			}
+			Self::deposit_event(RawEvent::Withdrawn(pool_id, who.clone(), amount));
+
+			if member.points.is_zero() && member.unbonding_eras.is_empty() {
+				<PoolMembers<T>>::remove(&who);
+				pool.member_count = pool.member_count.saturating_sub(1);
+				Self::deposit_event(RawEvent::MemberRemoved(pool_id, who.clone()));
+				if pool.member_count.is_zero() {
+					Self::destroy_pool(pool_id, &who)?;
+					return Ok(());
+				}
+			} else {
+				<PoolMembers<T>>::insert(&who, member);
+			}
+			<BondedPools<T>>::insert(pool_id, pool);
+
+			Ok(())
+		}
+
+		/// Nominate `targets` with the stake of pool `pool_id`
+		/// Caller must be the pool's root or nominator
+		#[weight = T::WeightInfo::nominate(targets.len() as u32)]
+		fn nominate(origin, pool_id: PoolId, targets: Vec<T::AccountId>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let pool = Self::bonded_pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			ensure!(pool.roles.root == who || pool.roles.nominator == who, Error::<T>::NoPermission);
+
+			T::Staking::nominate(&Self::bonded_account(pool_id), targets)
+		}
+
+		/// Stop pool `pool_id` nominating
+		/// Caller must be the pool's root or nominator
+		#[weight = T::WeightInfo::chill()]
+		fn chill(origin, pool_id: PoolId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let pool = Self::bonded_pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			ensure!(pool.roles.root == who || pool.roles.nominator == who, Error::<T>::NoPermission);
+
+			T::Staking::chill(&Self::bonded_account(pool_id))
+		}
+
+		/// Set the state of pool `pool_id`, a destroying pool can't be reopened
+		/// Caller must be the pool's root
+		#[weight = T::WeightInfo::set_state()]
+		fn set_state(origin, pool_id: PoolId, state: PoolState) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut pool = Self::bonded_pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			ensure!(pool.roles.root == who, Error::<T>::NoPermission);
+			ensure!(pool.state != PoolState::Destroying, Error::<T>::PoolDestroying);
+
+			pool.state = state;
+			<BondedPools<T>>::insert(pool_id, pool);
+			Self::deposit_event(RawEvent::StateChanged(pool_id, state));
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Module<T> {
+	/// The account holding and bonding the stake of pool `pool_id`
+	pub fn bonded_account(pool_id: PoolId) -> T::AccountId {
+		T::PoolsPalletId::get().into_sub_account((pool_id, PoolAccount::Bonded))
+	}
+	/// The account receiving the staking rewards of pool `pool_id`
+	pub fn reward_account(pool_id: PoolId) -> T::AccountId {
+		T::PoolsPalletId::get().into_sub_account((pool_id, PoolAccount::Reward))
+	}
+	/// The pool bonding from `stash`, if any
+	fn pool_of_bonded_account(stash: &T::AccountId) -> Option<PoolId> {
+		let (pallet_id, (pool_id, account)) =
+			<PalletId as AccountIdConversion<T::AccountId>>::try_from_sub_account::<(PoolId, PoolAccount)>(stash)?;
+		if pallet_id == T::PoolsPalletId::get()
+			&& account == PoolAccount::Bonded
+			&& <BondedPools<T>>::contains_key(pool_id)
+		{
+			Some(pool_id)
+		} else {
+			None
+		}
+	}
+	/// The min. amount to create a pool
+	pub fn min_create_bond() -> Balance {
+		T::MinCreateBond::get().max(T::Staking::minimum_bond())
+	}
+	/// The reward balance of pool `pool_id`, earned or not yet claimed
+	fn reward_balance(pool_id: PoolId) -> Balance {
+		T::MultiCurrency::free_balance(&Self::reward_account(pool_id), T::MultiCurrency::fee_currency())
+	}
+	/// The balance of `bonded_account` unlocking in `era`
+	fn unlocking_balance(bonded_account: &T::AccountId, era: EraIndex) -> Balance {
+		T::Staking::unlocking(bonded_account)
+			.into_iter()
+			.filter(|(unlock_era, _)| *unlock_era == era)
+			.fold(Zero::zero(), |total: Balance, (_, value)| total.saturating_add(value))
+	}
+
+	/// Transfer `amount` from `who` into pool `pool_id` and bond it, returns the points issued
+	fn bond_funds(
+		pool_id: PoolId,
+		pool: &mut BondedPool<T::AccountId>,
+		who: &T::AccountId,
+		amount: Balance,
+	) -> Result<Balance, DispatchError> {
+		let bonded_account = Self::bonded_account(pool_id);
+		let active = T::Staking::active_stake(&bonded_account);
+		ensure!(
+			!active.is_zero() || pool.points.is_zero(),
+			Error::<T>::PoolSlashedToZero
+		);
+		let points = balance_to_points(active, pool.points, amount);
+
+		T::MultiCurrency::transfer(
+			who,
+			&bonded_account,
+			T::MultiCurrency::staking_currency(),
+			amount,
+			ExistenceRequirement::AllowDeath,
+		)?;
+		T::Staking::bond_extra(&bonded_account, amount)?;
+		pool.points = pool.points.saturating_add(points);
+
+		Ok(points)
+	}
+
+	/// Pay out the pending rewards of `who`
+	/// Must be called before the member's or the pool's points change
+	fn do_reward_payout(
+		who: &T::AccountId,
+		member: &mut PoolMember,
+		pool: &BondedPool<T::AccountId>,
+		reward_pool: &mut RewardPool,
+	) -> Result<Balance, DispatchError> {
+		let reward_balance = Self::reward_balance(member.pool_id);
+		let reward_counter = reward_pool.current_reward_counter(reward_balance, pool.points);
+		let pending = member.pending_rewards(reward_counter);
+		member.last_recorded_reward_counter = reward_counter;
+		reward_pool.update_records(reward_balance, pool.points);
+
+		if !pending.is_zero() {
+			T::MultiCurrency::transfer(
+				&Self::reward_account(member.pool_id),
+				who,
+				T::MultiCurrency::fee_currency(),
+				pending,
+				ExistenceRequirement::AllowDeath,
+			)?;
+			reward_pool.total_rewards_claimed = reward_pool.total_rewards_claimed.saturating_add(pending);
+			Self::deposit_event(RawEvent::PaidOut(member.pool_id, who.clone(), pending));
+		}
+
+		Ok(pending)
+	}
+
+	/// Sync the unbonding pools of pool `pool_id` with the stash's unlocking funds, applying any slashes
+	/// then withdraw funds unlocked by `current_era` from staking
+	fn withdraw_unlocked(pool_id: PoolId, bonded_account: &T::AccountId, current_era: EraIndex) -> DispatchResult {
+		let unlocking = T::Staking::unlocking(bonded_account);
+		let unbond_pools: Vec<(EraIndex, UnbondPool)> = UnbondingPools::iter_prefix(pool_id)
+			.filter(|(_, unbond_pool)| !unbond_pool.withdrawn)
+			.collect();
+		if unbond_pools.iter().all(|(era, _)| *era > current_era) {
+			return Ok(());
+		}
+
+		for (era, mut unbond_pool) in unbond_pools {
+			// once unlocked the funds are no longer tracked by staking
+			let balance = unlocking
+				.iter()
+				.filter(|(unlock_era, _)| *unlock_era == era)
+				.fold(Zero::zero(), |total: Balance, (_, value)| total.saturating_add(*value));
+			if balance < unbond_pool.balance {
+				Self::deposit_event(RawEvent::UnbondingPoolSlashed(pool_id, era, balance));
+			}
+			unbond_pool.balance = balance;
+			unbond_pool.withdrawn = era <= current_era;
+			UnbondingPools::insert(pool_id, era, unbond_pool);
+		}
+
+		T::Staking::withdraw_unbonded(bonded_account)
+	}
+
+	/// Remove pool `pool_id` once its last member, the depositor, has left
+	/// Any remaining funds go to the depositor
+	fn destroy_pool(pool_id: PoolId, depositor: &T::AccountId) -> DispatchResult {
+		let reward_account = Self::reward_account(pool_id);
+		let fee_currency = T::MultiCurrency::fee_currency();
+		let rewards = T::MultiCurrency::free_balance(&reward_account, fee_currency);
+		if !rewards.is_zero() {
+			T::MultiCurrency::transfer(
+				&reward_account,
+				depositor,
+				fee_currency,
+				rewards,
+				ExistenceRequirement::AllowDeath,
+			)?;
+		}
+		let bonded_account = Self::bonded_account(pool_id);
+		let staking_currency = T::MultiCurrency::staking_currency();
+		// funds left over from rounding, once the stash is no longer bonded
+		let dust = T::MultiCurrency::free_balance(&bonded_account, staking_currency);
+		let is_bonded =
+			!T::Staking::active_stake(&bonded_account).is_zero() || !T::Staking::unlocking(&bonded_account).is_empty();
+		if !dust.is_zero() && !is_bonded {
+			T::MultiCurrency::transfer(
+				&bonded_account,
+				depositor,
+				staking_currency,
+				dust,
+				ExistenceRequirement::AllowDeath,
+			)?;
+		}
+
+		<BondedPools<T>>::remove(pool_id);
+		RewardPools::remove(pool_id);
+		UnbondingPools::remove_prefix(pool_id, None);
+		PoolCount::mutate(|count| *count = count.saturating_sub(1));
+		Self::deposit_event(RawEvent::Destroyed(pool_id));
+
+		Ok(())
+	}
+
+	/// The state of pool `pool_id`
+	pub fn pool_info(pool_id: PoolId) -> Option<PoolInfo<T::AccountId>> {
+		let pool = Self::bonded_pools(pool_id)?;
+		let bonded_account = Self::bonded_account(pool_id);
+		let unbonding = UnbondingPools::iter_prefix(pool_id)
+			.filter(|(_, unbond_pool)| !unbond_pool.withdrawn)
+			.map(|(era, _)| (era, Self::unlocking_balance(&bonded_account, era)))
+			.collect();
+
+		Some(PoolInfo {
+			pool_id,
+			active_stake: T::Staking::active_stake(&bonded_account),
+			bonded_account,
+			reward_account: Self::reward_account(pool_id),
+			roles: pool.roles,
+			state: pool.state,
+			points: pool.points,
+			member_count: pool.member_count,
+			unbonding,
+			unclaimed_rewards: Self::reward_balance(pool_id),
+		})
+	}
+	/// The state of all pools
+	pub fn pools() -> Vec<PoolInfo<T::AccountId>> {
+		(0..Self::next_pool_id()).filter_map(Self::pool_info).collect()
+	}
+	/// The pool membership of `who`
+	pub fn member_info(who: &T::AccountId) -> Option<MemberInfo> {
+		let member = Self::pool_members(who)?;
+		let pool = Self::bonded_pools(member.pool_id)?;
+		let bonded_account = Self::bonded_account(member.pool_id);
+		let unbonding = member
+			.unbonding_eras
+			.iter()
+			.map(|(era, points)| {
+				let unbond_pool = Self::unbonding_pools(member.pool_id, era).unwrap_or_default();
+				let balance = if unbond_pool.withdrawn {
+					unbond_pool.balance
+				} else {
+					Self::unlocking_balance(&bonded_account, *era)
+				};
+				(*era, points_to_balance(balance, unbond_pool.points, *points))
+			})
+			.collect();
+
+		Some(MemberInfo {
+			pool_id: member.pool_id,
+			points: member.points,
+			active_stake: points_to_balance(T::Staking::active_stake(&bonded_account), pool.points, member.points),
+			unbonding,
+			pending_rewards: Self::pending_rewards(who),
+		})
+	}
+	/// The rewards `who` may claim from their pool
+	pub fn pending_rewards(who: &T::AccountId) -> Balance {
+		Self::pool_members(who)
+			.and_then(|member| {
+				let pool = Self::bonded_pools(member.pool_id)?;
+				let reward_counter = Self::reward_pools(member.pool_id)
+					.current_reward_counter(Self::reward_balance(member.pool_id), pool.points);
+				Some(member.pending_rewards(reward_counter))
+			})
+			.unwrap_or_default()
+	}
+}
+
+impl<T: Config> OnStakerSlash<T::AccountId, Balance> for Module<T> {
+	/// Staking slashes a pool's active and unlocking funds pro-rata, sync the unbonding pools so their
+	/// members share the slash of the era they unbonded in
+	fn on_slash(stash: &T::AccountId, slashed_active: Balance, slashed_unlocking: &BTreeMap<EraIndex, Balance>) {
+		let pool_id = match Self::pool_of_bonded_account(stash) {
+			Some(pool_id) => pool_id,
+			None => return,
+		};
+		for (era, balance) in slashed_unlocking {
+			UnbondingPools::mutate(pool_id, era, |maybe_unbond_pool| {
+				if let Some(unbond_pool) = maybe_unbond_pool.as_mut().filter(|unbond_pool| !unbond_pool.withdrawn) {
+					unbond_pool.balance = *balance;
+					Self::deposit_event(RawEvent::UnbondingPoolSlashed(pool_id, *era, *balance));
+				}
+			});
+		}
+		Self::deposit_event(RawEvent::PoolSlashed(pool_id, slashed_active));
+	}
+}
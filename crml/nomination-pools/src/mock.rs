@@ -0,0 +1,280 @@
+/* Copyright 2019-2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+use crate as crml_nomination_pools;
+use crate::EraIndex;
+use benson_primitives::types::{AssetId, Balance};
+use crml_support::{MultiCurrency, OnStakerSlash, StakingInterface};
+use frame_support::{parameter_types, PalletId};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	DispatchError, DispatchResult,
+};
+use std::{cell::RefCell, collections::BTreeMap};
+
+pub type AccountId = u64;
+
+pub const STAKING_ASSET_ID: AssetId = 16_000;
+pub const SPENDING_ASSET_ID: AssetId = 16_001;
+pub const MINIMUM_BOND: Balance = 10;
+pub const BONDING_DURATION: EraIndex = 3;
+/// Like crml-staking, the max. number of chunks a stash may be unbonding
+pub const MAX_UNLOCKING_CHUNKS: usize = 3;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		GenericAsset: crml_generic_asset::{Pallet, Call, Storage, Config<T>, Event<T>},
+		NominationPools: crml_nomination_pools::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+impl frame_system::Config for Test {
+	type BlockWeights = ();
+	type BlockLength = ();
+	type BaseCallFilter = frame_support::traits::Everything;
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type BlockHashCount = BlockHashCount;
+	type Event = Event;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+impl crml_generic_asset::Config for Test {
+	type AssetId = AssetId;
+	type Balance = Balance;
+	type Event = Event;
+	type OnDustImbalance = ();
+	type WeightInfo = ();
+}
+
+/// A stash's staking ledger
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MockLedger {
+	pub active: Balance,
+	/// (era unlocked at, value)
+	pub unlocking: Vec<(EraIndex, Balance)>,
+	pub payee: AccountId,
+	pub nominations: Vec<AccountId>,
+}
+
+thread_local! {
+	static LEDGERS: RefCell<BTreeMap<AccountId, MockLedger>> = RefCell::new(BTreeMap::new());
+	static CURRENT_ERA: RefCell<EraIndex> = RefCell::new(0);
+}
+
+/// Tracks bonded funds in place of crml-staking, bonded funds stay in the stash's free balance
+pub struct MockStaking;
+
+impl MockStaking {
+	/// The ledger of `stash`, if bonded
+	pub fn ledger(stash: &AccountId) -> Option<MockLedger> {
+		LEDGERS.with(|ledgers| ledgers.borrow().get(stash).cloned())
+	}
+	/// Start era `era`
+	pub fn set_current_era(era: EraIndex) {
+		CURRENT_ERA.with(|current_era| *current_era.borrow_mut() = era);
+	}
+	/// Pay a staking reward to the payee of `stash`
+	pub fn reward(stash: &AccountId, amount: Balance) {
+		let payee = Self::ledger(stash).expect("stash is bonded").payee;
+		let _ = GenericAsset::deposit_creating(&payee, SPENDING_ASSET_ID, amount);
+	}
+	/// Slash `amount` of `stash`'s active stake, notifying the pools like crml-staking
+	/// Slashes of unbonding funds are tested against crml-staking in the runtime tests
+	pub fn slash(stash: &AccountId, amount: Balance) {
+		let mut active = 0;
+		Self::mutate_ledger(stash, |ledger| {
+			ledger.active -= amount;
+			active = ledger.active;
+			Ok(())
+		})
+		.expect("stash is bonded");
+		let balance = GenericAsset::free_balance(STAKING_ASSET_ID, stash);
+		GenericAsset::make_free_balance_be(stash, STAKING_ASSET_ID, balance - amount);
+		NominationPools::on_slash(stash, active, &BTreeMap::new());
+	}
+	fn mutate_ledger(stash: &AccountId, f: impl FnOnce(&mut MockLedger) -> DispatchResult) -> DispatchResult {
+		LEDGERS.with(|ledgers| {
+			let mut ledgers = ledgers.borrow_mut();
+			let ledger = ledgers.get_mut(stash).ok_or(DispatchError::Other("not bonded"))?;
+			f(ledger)
+		})
+	}
+}
+
+impl StakingInterface for MockStaking {
+	type AccountId = AccountId;
+	type Balance = Balance;
+
+	fn minimum_bond() -> Balance {
+		MINIMUM_BOND
+	}
+	fn current_era() -> EraIndex {
+		CURRENT_ERA.with(|current_era| *current_era.borrow())
+	}
+	fn bonding_duration() -> EraIndex {
+		BONDING_DURATION
+	}
+	fn active_stake(stash: &AccountId) -> Balance {
+		Self::ledger(stash).map(|ledger| ledger.active).unwrap_or_default()
+	}
+	fn unlocking(stash: &AccountId) -> Vec<(EraIndex, Balance)> {
+		Self::ledger(stash).map(|ledger| ledger.unlocking).unwrap_or_default()
+	}
+	fn bond(stash: &AccountId, value: Balance, payee: &AccountId) -> DispatchResult {
+		assert!(Self::ledger(stash).is_none(), "already bonded");
+		assert!(GenericAsset::free_balance(STAKING_ASSET_ID, stash) >= value);
+		LEDGERS.with(|ledgers| {
+			ledgers.borrow_mut().insert(
+				*stash,
+				MockLedger {
+					active: value,
+					payee: *payee,
+					..Default::default()
+				},
+			)
+		});
+		Ok(())
+	}
+	fn bond_extra(stash: &AccountId, value: Balance) -> DispatchResult {
+		Self::mutate_ledger(stash, |ledger| {
+			ledger.active += value;
+			Ok(())
+		})
+	}
+	fn unbond(stash: &AccountId, value: Balance) -> DispatchResult {
+		let era = Self::current_era() + BONDING_DURATION;
+		Self::mutate_ledger(stash, |ledger| {
+			// like crml-staking, unbond everything once the active stake falls below the minimum bond
+			let value = if ledger.active.saturating_sub(value) < MINIMUM_BOND {
+				ledger.active
+			} else {
+				value
+			};
+			match ledger.unlocking.last_mut() {
+				Some((last_era, last_value)) if *last_era == era => *last_value += value,
+				_ if ledger.unlocking.len() >= MAX_UNLOCKING_CHUNKS => {
+					return Err(DispatchError::Other("NoMoreChunks"))
+				}
+				_ => ledger.unlocking.push((era, value)),
+			}
+			ledger.active -= value;
+			Ok(())
+		})
+	}
+	fn withdraw_unbonded(stash: &AccountId) -> DispatchResult {
+		let current_era = Self::current_era();
+		Self::mutate_ledger(stash, |ledger| {
+			ledger.unlocking.retain(|(era, _)| *era > current_era);
+			Ok(())
+		})?;
+		if Self::ledger(stash).map_or(false, |ledger| ledger.active == 0 && ledger.unlocking.is_empty()) {
+			LEDGERS.with(|ledgers| ledgers.borrow_mut().remove(stash));
+		}
+		Ok(())
+	}
+	fn nominate(stash: &AccountId, targets: Vec<AccountId>) -> DispatchResult {
+		Self::mutate_ledger(stash, |ledger| {
+			ledger.nominations = targets;
+			Ok(())
+		})
+	}
+	fn chill(stash: &AccountId) -> DispatchResult {
+		Self::mutate_ledger(stash, |ledger| {
+			ledger.nominations.clear();
+			Ok(())
+		})
+	}
+}
+
+parameter_types! {
+	pub const PoolsPalletId: PalletId = PalletId(*b"py/nopls");
+	pub const MinJoinBond: Balance = 5;
+	pub const MinCreateBond: Balance = 20;
+	pub const MaxPools: u32 = 2;
+	pub const MaxMembersPerPool: u32 = 3;
+}
+impl crate::Config for Test {
+	type PoolsPalletId = PoolsPalletId;
+	type MultiCurrency = GenericAsset;
+	type Staking = MockStaking;
+	type MinJoinBond = MinJoinBond;
+	type MinCreateBond = MinCreateBond;
+	type MaxPools = MaxPools;
+	type MaxMembersPerPool = MaxMembersPerPool;
+	type Event = Event;
+	type WeightInfo = ();
+}
+
+/// Accounts endowed with 1,000 of the staking asset
+pub const ENDOWED_ACCOUNTS: [AccountId; 5] = [1, 2, 3, 4, 5];
+
+#[derive(Default)]
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+		crml_generic_asset::GenesisConfig::<Test> {
+			assets: vec![STAKING_ASSET_ID, SPENDING_ASSET_ID],
+			initial_balance: 1_000,
+			endowed_accounts: ENDOWED_ACCOUNTS.to_vec(),
+			next_asset_id: 17_000,
+			staking_asset_id: STAKING_ASSET_ID,
+			spending_asset_id: SPENDING_ASSET_ID,
+			permissions: vec![],
+			asset_meta: vec![],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		let mut ext: sp_io::TestExternalities = t.into();
+		ext.execute_with(|| {
+			System::initialize(&1, &[0u8; 32].into(), &Default::default(), frame_system::InitKind::Full);
+			LEDGERS.with(|ledgers| ledgers.borrow_mut().clear());
+			MockStaking::set_current_era(0);
+		});
+
+		ext
+	}
+}
@@ -0,0 +1,484 @@
+/* Copyright 2019-2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+use super::*;
+use crate::mock::{
+	Event as MockEvent, ExtBuilder, GenericAsset, MockLedger, MockStaking, NominationPools, System, Test,
+	MAX_UNLOCKING_CHUNKS, SPENDING_ASSET_ID, STAKING_ASSET_ID,
+};
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+
+const DEPOSITOR: u64 = 1;
+const ROOT: u64 = 10;
+const NOMINATOR: u64 = 11;
+
+// Helper function to create a pool from `DEPOSITOR` bonding `amount`
+fn create_pool(amount: Balance) -> PoolId {
+	let pool_id = NominationPools::next_pool_id();
+	assert_ok!(NominationPools::create(
+		RawOrigin::Signed(DEPOSITOR).into(),
+		amount,
+		ROOT,
+		NOMINATOR
+	));
+	pool_id
+}
+
+fn join(who: u64, pool_id: PoolId, amount: Balance) {
+	assert_ok!(NominationPools::join(RawOrigin::Signed(who).into(), pool_id, amount));
+}
+
+fn unbond(who: u64, points: Balance) {
+	assert_ok!(NominationPools::unbond(RawOrigin::Signed(who).into(), points));
+}
+
+fn withdraw(who: u64) {
+	assert_ok!(NominationPools::withdraw_unbonded(RawOrigin::Signed(who).into()));
+}
+
+fn has_event(event: RawEvent<u64>) -> bool {
+	System::events()
+		.iter()
+		.any(|record| record.event == MockEvent::NominationPools(event.clone()))
+}
+
+#[test]
+fn create_pool_bonds_depositor_funds() {
+	ExtBuilder::default().build().execute_with(|| {
+		let pool_id = create_pool(100);
+		let bonded_account = NominationPools::bonded_account(pool_id);
+
+		assert_eq!(
+			NominationPools::bonded_pools(pool_id),
+			Some(BondedPool {
+				roles: PoolRoles {
+					depositor: DEPOSITOR,
+					root: ROOT,
+					nominator: NOMINATOR,
+				},
+				state: PoolState::Open,
+				points: 100,
+				member_count: 1,
+			})
+		);
+		let member = NominationPools::pool_members(DEPOSITOR).expect("depositor is a member");
+		assert_eq!(member.pool_id, pool_id);
+		assert_eq!(member.points, 100);
+		assert_eq!(
+			MockStaking::ledger(&bonded_account),
+			Some(MockLedger {
+				active: 100,
+				payee: NominationPools::reward_account(pool_id),
+				..Default::default()
+			})
+		);
+		assert_eq!(GenericAsset::free_balance(STAKING_ASSET_ID, &DEPOSITOR), 900);
+		assert_eq!(GenericAsset::free_balance(STAKING_ASSET_ID, &bonded_account), 100);
+		assert_eq!(NominationPools::next_pool_id(), pool_id + 1);
+		assert_eq!(NominationPools::pool_count(), 1);
+		assert!(has_event(RawEvent::Created(pool_id, DEPOSITOR)));
+		assert!(has_event(RawEvent::Bonded(pool_id, DEPOSITOR, 100, 100)));
+	});
+}
+
+#[test]
+fn create_pool_fails_validation() {
+	ExtBuilder::default().build().execute_with(|| {
+		// below `MinCreateBond`
+		assert_noop!(
+			NominationPools::create(RawOrigin::Signed(DEPOSITOR).into(), 19, ROOT, NOMINATOR),
+			Error::<Test>::MinimumBondNotMet
+		);
+
+		create_pool(100);
+		assert_noop!(
+			NominationPools::create(RawOrigin::Signed(DEPOSITOR).into(), 100, ROOT, NOMINATOR),
+			Error::<Test>::AlreadyMember
+		);
+
+		assert_ok!(NominationPools::create(
+			RawOrigin::Signed(2).into(),
+			100,
+			ROOT,
+			NOMINATOR
+		));
+		assert_noop!(
+			NominationPools::create(RawOrigin::Signed(3).into(), 100, ROOT, NOMINATOR),
+			Error::<Test>::MaxPools
+		);
+	});
+}
+
+#[test]
+fn join_issues_points_pro_rata() {
+	ExtBuilder::default().build().execute_with(|| {
+		let pool_id = create_pool(100);
+		let bonded_account = NominationPools::bonded_account(pool_id);
+		MockStaking::slash(&bonded_account, 50);
+
+		// 100 points are worth 50, so 50 buys 100 points
+		join(2, pool_id, 50);
+		assert_eq!(NominationPools::pool_members(2).unwrap().points, 100);
+		assert_eq!(NominationPools::bonded_pools(pool_id).unwrap().points, 200);
+		assert_eq!(MockStaking::active_stake(&bonded_account), 100);
+		assert!(has_event(RawEvent::Bonded(pool_id, 2, 50, 100)));
+
+		assert_ok!(NominationPools::bond_extra(RawOrigin::Signed(2).into(), 25));
+		assert_eq!(NominationPools::pool_members(2).unwrap().points, 150);
+		assert_eq!(MockStaking::active_stake(&bonded_account), 125);
+		assert_eq!(GenericAsset::free_balance(STAKING_ASSET_ID, &2), 925);
+	});
+}
+
+#[test]
+fn join_fails_validation() {
+	ExtBuilder::default().build().execute_with(|| {
+		let pool_id = create_pool(100);
+		assert_noop!(
+			NominationPools::join(RawOrigin::Signed(2).into(), pool_id + 1, 50),
+			Error::<Test>::PoolNotFound
+		);
+		assert_noop!(
+			NominationPools::join(RawOrigin::Signed(2).into(), pool_id, 4),
+			Error::<Test>::MinimumBondNotMet
+		);
+		assert_noop!(
+			NominationPools::join(RawOrigin::Signed(DEPOSITOR).into(), pool_id, 50),
+			Error::<Test>::AlreadyMember
+		);
+
+		join(2, pool_id, 50);
+		join(3, pool_id, 50);
+		assert_noop!(
+			NominationPools::join(RawOrigin::Signed(4).into(), pool_id, 50),
+			Error::<Test>::MaxPoolMembers
+		);
+	});
+}
+
+#[test]
+fn rewards_are_shared_pro_rata() {
+	ExtBuilder::default().build().execute_with(|| {
+		let pool_id = create_pool(100);
+		let bonded_account = NominationPools::bonded_account(pool_id);
+		join(2, pool_id, 300);
+
+		MockStaking::reward(&bonded_account, 40);
+		assert_eq!(NominationPools::pending_rewards(&DEPOSITOR), 10);
+		assert_eq!(NominationPools::pending_rewards(&2), 30);
+
+		assert_ok!(NominationPools::claim_payout(RawOrigin::Signed(DEPOSITOR).into()));
+		assert_eq!(GenericAsset::free_balance(SPENDING_ASSET_ID, &DEPOSITOR), 1_010);
+		assert_eq!(NominationPools::pending_rewards(&DEPOSITOR), 0);
+		assert_eq!(NominationPools::pending_rewards(&2), 30);
+		assert!(has_event(RawEvent::PaidOut(pool_id, DEPOSITOR, 10)));
+
+		// a new member only shares in rewards from when they joined
+		join(3, pool_id, 100);
+		assert_eq!(NominationPools::pending_rewards(&3), 0);
+		MockStaking::reward(&bonded_account, 50);
+		assert_eq!(NominationPools::pending_rewards(&DEPOSITOR), 10);
+		assert_eq!(NominationPools::pending_rewards(&2), 60);
+		assert_eq!(NominationPools::pending_rewards(&3), 10);
+
+		for who in &[DEPOSITOR, 2, 3] {
+			assert_ok!(NominationPools::claim_payout(RawOrigin::Signed(*who).into()));
+		}
+		assert_eq!(GenericAsset::free_balance(SPENDING_ASSET_ID, &DEPOSITOR), 1_020);
+		assert_eq!(GenericAsset::free_balance(SPENDING_ASSET_ID, &2), 1_060);
+		assert_eq!(GenericAsset::free_balance(SPENDING_ASSET_ID, &3), 1_010);
+		assert_eq!(
+			GenericAsset::free_balance(SPENDING_ASSET_ID, &NominationPools::reward_account(pool_id)),
+			0
+		);
+		assert_eq!(NominationPools::reward_pools(pool_id).total_rewards_claimed, 90);
+	});
+}
+
+#[test]
+fn unbond_and_withdraw() {
+	ExtBuilder::default().build().execute_with(|| {
+		let pool_id = create_pool(100);
+		let bonded_account = NominationPools::bonded_account(pool_id);
+		join(2, pool_id, 50);
+		MockStaking::reward(&bonded_account, 15);
+
+		// pending rewards are paid out on unbond
+		unbond(2, 50);
+		assert_eq!(GenericAsset::free_balance(SPENDING_ASSET_ID, &2), 1_005);
+		assert_eq!(MockStaking::active_stake(&bonded_account), 100);
+		assert_eq!(MockStaking::unlocking(&bonded_account), vec![(3, 50)]);
+		assert_eq!(
+			NominationPools::unbonding_pools(pool_id, 3),
+			Some(UnbondPool {
+				points: 50,
+				balance: 50,
+				withdrawn: false,
+			})
+		);
+		let member = NominationPools::pool_members(2).unwrap();
+		assert_eq!(member.points, 0);
+		assert_eq!(member.unbonding_eras.get(&3), Some(&50));
+		assert!(has_event(RawEvent::Unbonded(pool_id, 2, 50, 3)));
+		assert_noop!(
+			NominationPools::unbond(RawOrigin::Signed(2).into(), 1),
+			Error::<Test>::InvalidPoints
+		);
+
+		assert_noop!(
+			NominationPools::withdraw_unbonded(RawOrigin::Signed(2).into()),
+			Error::<Test>::NothingToWithdraw
+		);
+
+		MockStaking::set_current_era(3);
+		withdraw(2);
+		assert_eq!(GenericAsset::free_balance(STAKING_ASSET_ID, &2), 1_000);
+		assert!(MockStaking::unlocking(&bonded_account).is_empty());
+		assert!(NominationPools::unbonding_pools(pool_id, 3).is_none());
+		assert!(NominationPools::pool_members(2).is_none());
+		assert_eq!(NominationPools::bonded_pools(pool_id).unwrap().member_count, 1);
+		assert!(has_event(RawEvent::Withdrawn(pool_id, 2, 50)));
+		assert!(has_event(RawEvent::MemberRemoved(pool_id, 2)));
+	});
+}
+
+#[test]
+fn unbond_withdraws_unlocked_chunks() {
+	ExtBuilder::default().build().execute_with(|| {
+		let pool_id = create_pool(100);
+		let bonded_account = NominationPools::bonded_account(pool_id);
+		join(2, pool_id, 50);
+		join(3, pool_id, 50);
+
+		// 2 fills the stash's unlocking chunks and doesn't withdraw
+		for era in 0..MAX_UNLOCKING_CHUNKS as EraIndex {
+			MockStaking::set_current_era(era);
+			unbond(2, 1);
+		}
+		assert_eq!(MockStaking::unlocking(&bonded_account), vec![(3, 1), (4, 1), (5, 1)]);
+
+		// 3 has nothing to withdraw, unbonding frees the unlocked chunks
+		MockStaking::set_current_era(4);
+		unbond(3, 10);
+		assert_eq!(MockStaking::unlocking(&bonded_account), vec![(5, 1), (7, 10)]);
+		assert!(NominationPools::unbonding_pools(pool_id, 3).unwrap().withdrawn);
+		assert!(NominationPools::unbonding_pools(pool_id, 4).unwrap().withdrawn);
+
+		// the withdrawn funds stay with the pool for 2
+		withdraw(2);
+		assert_eq!(GenericAsset::free_balance(STAKING_ASSET_ID, &2), 952);
+		assert!(NominationPools::unbonding_pools(pool_id, 3).is_none());
+		assert!(NominationPools::unbonding_pools(pool_id, 4).is_none());
+	});
+}
+
+#[test]
+fn unbond_fails_validation() {
+	ExtBuilder::default().build().execute_with(|| {
+		let pool_id = create_pool(20);
+		let bonded_account = NominationPools::bonded_account(pool_id);
+		join(2, pool_id, 10);
+
+		assert_noop!(
+			NominationPools::unbond(RawOrigin::Signed(3).into(), 10),
+			Error::<Test>::NotMember
+		);
+		assert_noop!(
+			NominationPools::unbond(RawOrigin::Signed(2).into(), 0),
+			Error::<Test>::InvalidPoints
+		);
+		// leaves 4 bonded, below `MinJoinBond`
+		assert_noop!(
+			NominationPools::unbond(RawOrigin::Signed(2).into(), 6),
+			Error::<Test>::MinimumBondNotMet
+		);
+		// leaves the depositor below `MinCreateBond`
+		assert_noop!(
+			NominationPools::unbond(RawOrigin::Signed(DEPOSITOR).into(), 1),
+			Error::<Test>::MinimumBondNotMet
+		);
+		assert_noop!(
+			NominationPools::unbond(RawOrigin::Signed(DEPOSITOR).into(), 20),
+			Error::<Test>::DepositorMustLeaveLast
+		);
+
+		// staking would unbond the entire pool if its stake fell below the minimum bond
+		MockStaking::slash(&bonded_account, 22);
+		assert_noop!(
+			NominationPools::unbond(RawOrigin::Signed(2).into(), 10),
+			Error::<Test>::PoolBelowMinimumBond
+		);
+
+		// the pool is worthless, it can't take new funds
+		MockStaking::slash(&bonded_account, 8);
+		assert_noop!(
+			NominationPools::join(RawOrigin::Signed(3).into(), pool_id, 10),
+			Error::<Test>::PoolSlashedToZero
+		);
+	});
+}
+
+#[test]
+fn slashes_are_recorded_for_pools_only() {
+	ExtBuilder::default().build().execute_with(|| {
+		let pool_id = create_pool(100);
+		let bonded_account = NominationPools::bonded_account(pool_id);
+		MockStaking::slash(&bonded_account, 30);
+		assert!(has_event(RawEvent::PoolSlashed(pool_id, 70)));
+
+		// not a pool's bonded account
+		let events = System::events().len();
+		NominationPools::on_slash(&NominationPools::reward_account(pool_id), 0, &Default::default());
+		NominationPools::on_slash(&2, 0, &Default::default());
+		assert_eq!(System::events().len(), events);
+	});
+}
+
+#[test]
+fn depositor_leaves_last_and_destroys_pool() {
+	ExtBuilder::default().build().execute_with(|| {
+		let pool_id = create_pool(100);
+		let bonded_account = NominationPools::bonded_account(pool_id);
+		let reward_account = NominationPools::reward_account(pool_id);
+		join(2, pool_id, 50);
+		MockStaking::reward(&bonded_account, 30);
+
+		unbond(2, 50);
+		// 2 is a member until their funds are withdrawn
+		assert_noop!(
+			NominationPools::unbond(RawOrigin::Signed(DEPOSITOR).into(), 100),
+			Error::<Test>::DepositorMustLeaveLast
+		);
+		MockStaking::set_current_era(3);
+		withdraw(2);
+
+		unbond(DEPOSITOR, 100);
+		assert_eq!(GenericAsset::free_balance(SPENDING_ASSET_ID, &DEPOSITOR), 1_020);
+		assert_eq!(
+			NominationPools::bonded_pools(pool_id).unwrap().state,
+			PoolState::Destroying
+		);
+		assert!(has_event(RawEvent::StateChanged(pool_id, PoolState::Destroying)));
+		assert_eq!(MockStaking::unlocking(&bonded_account), vec![(6, 100)]);
+
+		// rewards after the last member unbonded
+		MockStaking::reward(&bonded_account, 5);
+		MockStaking::set_current_era(6);
+		withdraw(DEPOSITOR);
+
+		assert!(has_event(RawEvent::Destroyed(pool_id)));
+		assert!(NominationPools::bonded_pools(pool_id).is_none());
+		assert!(NominationPools::pool_members(DEPOSITOR).is_none());
+		assert_eq!(NominationPools::pool_count(), 0);
+		assert!(MockStaking::ledger(&bonded_account).is_none());
+		assert_eq!(GenericAsset::free_balance(STAKING_ASSET_ID, &DEPOSITOR), 1_000);
+		assert_eq!(GenericAsset::free_balance(SPENDING_ASSET_ID, &DEPOSITOR), 1_025);
+		assert_eq!(GenericAsset::free_balance(SPENDING_ASSET_ID, &reward_account), 0);
+	});
+}
+
+#[test]
+fn pool_roles() {
+	ExtBuilder::default().build().execute_with(|| {
+		let pool_id = create_pool(100);
+		let bonded_account = NominationPools::bonded_account(pool_id);
+
+		assert_noop!(
+			NominationPools::nominate(RawOrigin::Signed(DEPOSITOR).into(), pool_id, vec![20]),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(NominationPools::nominate(
+			RawOrigin::Signed(NOMINATOR).into(),
+			pool_id,
+			vec![20, 21]
+		));
+		assert_eq!(MockStaking::ledger(&bonded_account).unwrap().nominations, vec![20, 21]);
+		assert_ok!(NominationPools::chill(RawOrigin::Signed(ROOT).into(), pool_id));
+		assert!(MockStaking::ledger(&bonded_account).unwrap().nominations.is_empty());
+
+		assert_noop!(
+			NominationPools::set_state(RawOrigin::Signed(NOMINATOR).into(), pool_id, PoolState::Blocked),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(NominationPools::set_state(
+			RawOrigin::Signed(ROOT).into(),
+			pool_id,
+			PoolState::Blocked
+		));
+		assert!(has_event(RawEvent::StateChanged(pool_id, PoolState::Blocked)));
+		assert_noop!(
+			NominationPools::join(RawOrigin::Signed(2).into(), pool_id, 50),
+			Error::<Test>::NotOpen
+		);
+
+		assert_ok!(NominationPools::set_state(
+			RawOrigin::Signed(ROOT).into(),
+			pool_id,
+			PoolState::Destroying
+		));
+		assert_noop!(
+			NominationPools::set_state(RawOrigin::Signed(ROOT).into(), pool_id, PoolState::Open),
+			Error::<Test>::PoolDestroying
+		);
+		assert_noop!(
+			NominationPools::bond_extra(RawOrigin::Signed(DEPOSITOR).into(), 10),
+			Error::<Test>::PoolDestroying
+		);
+	});
+}
+
+#[test]
+fn pool_and_member_info() {
+	ExtBuilder::default().build().execute_with(|| {
+		let pool_id = create_pool(100);
+		let bonded_account = NominationPools::bonded_account(pool_id);
+		join(2, pool_id, 100);
+		unbond(2, 40);
+		MockStaking::reward(&bonded_account, 32);
+
+		assert_eq!(
+			NominationPools::pool_info(pool_id),
+			Some(PoolInfo {
+				pool_id,
+				bonded_account,
+				reward_account: NominationPools::reward_account(pool_id),
+				roles: PoolRoles {
+					depositor: DEPOSITOR,
+					root: ROOT,
+					nominator: NOMINATOR,
+				},
+				state: PoolState::Open,
+				points: 160,
+				member_count: 2,
+				active_stake: 160,
+				unbonding: vec![(3, 40)],
+				unclaimed_rewards: 32,
+			})
+		);
+		assert_eq!(NominationPools::pools().len(), 1);
+		assert_eq!(
+			NominationPools::member_info(&2),
+			Some(MemberInfo {
+				pool_id,
+				points: 60,
+				active_stake: 60,
+				unbonding: vec![(3, 40)],
+				pending_rewards: 12,
+			})
+		);
+		assert!(NominationPools::member_info(&3).is_none());
+	});
+}
@@ -0,0 +1,199 @@
+/* Copyright 2019-2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+use benson_primitives::types::Balance;
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational,
+	traits::{Saturating, Zero},
+	FixedPointNumber, FixedU128,
+};
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
+
+/// Identifies nomination pools
+pub type PoolId = u32;
+
+/// Staking era index
+pub type EraIndex = u32;
+
+/// The accounts of a pool, derived from the pallet Id
+#[derive(PartialEq, Encode, Decode)]
+pub enum PoolAccount {
+	/// Holds and bonds the pool's stake
+	Bonded,
+	/// Receives the pool's staking rewards
+	Reward,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PoolState {
+	/// Anyone may join the pool
+	Open,
+	/// No new members may join the pool
+	Blocked,
+	/// The pool is winding down, members may only leave
+	Destroying,
+}
+
+/// Accounts with privileges over a pool
+#[derive(Debug, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct PoolRoles<AccountId> {
+	/// Created the pool, their bond is the last to leave
+	pub depositor: AccountId,
+	/// May change the pool state and nominate
+	pub root: AccountId,
+	/// May nominate on behalf of the pool
+	pub nominator: AccountId,
+}
+
+/// A pool bonding its members' funds from a shared stash
+#[derive(Debug, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct BondedPool<AccountId> {
+	/// Privileged accounts
+	pub roles: PoolRoles<AccountId>,
+	/// Current state
+	pub state: PoolState,
+	/// Total points of all members, each point is a claim on the pool's active stake
+	pub points: Balance,
+	/// Number of members (bonded or unbonding)
+	pub member_count: u32,
+}
+
+/// Reward accounting for a pool
+/// Rewards are never pushed to members, instead a reward counter tracks the cumulative reward per point
+#[derive(Debug, Default, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct RewardPool {
+	/// The reward counter when the pool's points last changed
+	pub last_recorded_reward_counter: FixedU128,
+	/// All rewards ever received by the pool when its points last changed
+	pub last_recorded_total_payouts: Balance,
+	/// All rewards ever claimed by members
+	pub total_rewards_claimed: Balance,
+}
+
+impl RewardPool {
+	/// The reward counter now, given the reward account's `reward_balance` and the pool's bonded `points`
+	pub fn current_reward_counter(&self, reward_balance: Balance, points: Balance) -> FixedU128 {
+		let total_payouts = reward_balance.saturating_add(self.total_rewards_claimed);
+		let new_rewards = total_payouts.saturating_sub(self.last_recorded_total_payouts);
+		if points.is_zero() {
+			return self.last_recorded_reward_counter;
+		}
+		self.last_recorded_reward_counter
+			.saturating_add(FixedU128::saturating_from_rational(new_rewards, points))
+	}
+	/// Record the reward counter and payouts now
+	/// Must be called before the pool's bonded points change
+	pub fn update_records(&mut self, reward_balance: Balance, points: Balance) {
+		self.last_recorded_reward_counter = self.current_reward_counter(reward_balance, points);
+		self.last_recorded_total_payouts = reward_balance.saturating_add(self.total_rewards_claimed);
+	}
+}
+
+/// Funds of a pool unbonding in the same era
+#[derive(Debug, Default, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct UnbondPool {
+	/// Total points of members unbonding, each point is a claim on the unbonding balance
+	pub points: Balance,
+	/// The unbonding balance, net of slashes
+	pub balance: Balance,
+	/// Whether the funds have been withdrawn from staking into the pool stash
+	pub withdrawn: bool,
+}
+
+/// A member of a pool
+#[derive(Debug, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct PoolMember {
+	/// The pool joined
+	pub pool_id: PoolId,
+	/// Points in the pool's active stake
+	pub points: Balance,
+	/// The pool reward counter when the member's rewards were last settled
+	pub last_recorded_reward_counter: FixedU128,
+	/// Points in the pool's unbonding funds, by era the funds unlock at
+	pub unbonding_eras: BTreeMap<EraIndex, Balance>,
+}
+
+impl PoolMember {
+	/// The member's unclaimed rewards given the pool's `current_reward_counter`
+	pub fn pending_rewards(&self, current_reward_counter: FixedU128) -> Balance {
+		current_reward_counter
+			.saturating_sub(self.last_recorded_reward_counter)
+			.saturating_mul_int(self.points)
+	}
+}
+
+/// Pool state returned by the runtime API
+#[derive(Debug, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct PoolInfo<AccountId> {
+	/// The pool Id
+	pub pool_id: PoolId,
+	/// Account holding and bonding the pool's stake
+	pub bonded_account: AccountId,
+	/// Account receiving the pool's staking rewards
+	pub reward_account: AccountId,
+	/// Privileged accounts
+	pub roles: PoolRoles<AccountId>,
+	/// Current state
+	pub state: PoolState,
+	/// Total points of bonded members
+	pub points: Balance,
+	/// Number of members
+	pub member_count: u32,
+	/// The pool's active stake
+	pub active_stake: Balance,
+	/// Funds being unbonded as (era unlocked at, balance)
+	pub unbonding: Vec<(EraIndex, Balance)>,
+	/// Rewards not yet claimed by members
+	pub unclaimed_rewards: Balance,
+}
+
+/// Pool member state returned by the runtime API
+#[derive(Debug, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct MemberInfo {
+	/// The pool joined
+	pub pool_id: PoolId,
+	/// Points in the pool's active stake
+	pub points: Balance,
+	/// The member's share of the pool's active stake
+	pub active_stake: Balance,
+	/// The member's share of the pool's unbonding funds as (era unlocked at, balance)
+	pub unbonding: Vec<(EraIndex, Balance)>,
+	/// Rewards the member may claim
+	pub pending_rewards: Balance,
+}
+
+/// Convert `new_funds` to points given a pool's `current_balance` and `current_points`
+/// An empty pool issues points 1:1
+pub fn balance_to_points(current_balance: Balance, current_points: Balance, new_funds: Balance) -> Balance {
+	if current_balance.is_zero() || current_points.is_zero() {
+		new_funds
+	} else {
+		multiply_by_rational(new_funds, current_points, current_balance).unwrap_or_default()
+	}
+}
+
+/// Convert `points` to their share of a pool's `current_balance` given its `current_points`
+pub fn points_to_balance(current_balance: Balance, current_points: Balance, points: Balance) -> Balance {
+	if current_points.is_zero() {
+		Zero::zero()
+	} else {
+		multiply_by_rational(points, current_balance, current_points).unwrap_or_default()
+	}
+}
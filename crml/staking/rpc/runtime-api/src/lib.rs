@@ -19,12 +19,21 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use benson_primitives::types::Balance;
 use codec::Codec;
+use crml_staking::{AccountStakingInfo, RewardEstimate, ValidatorInfo};
+use sp_std::prelude::*;
 
 sp_api::decl_runtime_apis! {
 	pub trait StakingApi<AccountId> where
 		AccountId: Codec,
 	{
 		fn accrued_payout(stash: &AccountId) -> u64;
+		/// Return the staking position of a stash or controller account
+		fn account_info(who: AccountId) -> Option<AccountStakingInfo<AccountId, Balance>>;
+		/// Return all validator candidates, elected or waiting
+		fn validators() -> Vec<ValidatorInfo<AccountId, Balance>>;
+		/// Return an estimate of annual staking returns
+		fn reward_estimate() -> RewardEstimate<Balance>;
 	}
 }
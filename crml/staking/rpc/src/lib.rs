@@ -17,14 +17,20 @@
 
 //! RPC interface for the staking module.
 
+use benson_primitives::types::Balance;
 use codec::Codec;
+use crml_staking::{
+	AccountStakingInfo as RuntimeAccountStakingInfo, EraIndex, RewardEstimate as RuntimeRewardEstimate, RewardPoint,
+	ValidatorInfo as RuntimeValidatorInfo,
+};
 
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
 
+use serde::{Deserialize, Serialize};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
-use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT, FixedPointNumber, FixedU128, Perbill};
 
 use std::sync::Arc;
 
@@ -42,6 +48,19 @@ pub trait StakingApi<BlockHash, AccountId> {
 	// TODO: we should return Result<Balance>, however we need to update Plug to bring in the latest sp-rpc package before that
 	#[rpc(name = "staking_accruedPayout")]
 	fn accrued_payout(&self, stash: AccountId, at: Option<BlockHash>) -> Result<u64>;
+
+	/// Return the ledger, unlocking chunks, nominations and active era exposures of a stash or
+	/// controller account
+	#[rpc(name = "staking_getAccountInfo")]
+	fn account_info(&self, who: AccountId, at: Option<BlockHash>) -> Result<Option<AccountStakingInfo<AccountId>>>;
+
+	/// Return all validator candidates with their commission, active era exposure and era points
+	#[rpc(name = "staking_getValidators")]
+	fn validators(&self, at: Option<BlockHash>) -> Result<Vec<ValidatorInfo<AccountId>>>;
+
+	/// Return an estimate of the annualised staking return
+	#[rpc(name = "staking_getRewardEstimate")]
+	fn reward_estimate(&self, at: Option<BlockHash>) -> Result<RewardEstimate>;
 }
 
 /// A struct that implements [`StakingApi`].
@@ -77,6 +96,162 @@ impl From<Error> for i64 {
 	}
 }
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountStakingInfo<AccountId> {
+	stash: AccountId,
+	controller: AccountId,
+	payee: AccountId,
+	#[serde(with = "serde_balance")]
+	total: Balance,
+	#[serde(with = "serde_balance")]
+	active: Balance,
+	unlocking: Vec<Unbonding>,
+	validator_commission: Option<Perbill>,
+	nominations: Vec<AccountId>,
+	exposures: Vec<Exposure<AccountId>>,
+	#[serde(with = "serde_balance")]
+	accrued_payout: Balance,
+}
+
+impl<AccountId> From<RuntimeAccountStakingInfo<AccountId, Balance>> for AccountStakingInfo<AccountId> {
+	fn from(info: RuntimeAccountStakingInfo<AccountId, Balance>) -> Self {
+		AccountStakingInfo {
+			stash: info.stash,
+			controller: info.controller,
+			payee: info.payee,
+			total: info.total,
+			active: info.active,
+			unlocking: info.unlocking.into_iter().map(Into::into).collect(),
+			validator_commission: info.validator_commission,
+			nominations: info.nominations,
+			exposures: info.exposures.into_iter().map(Into::into).collect(),
+			accrued_payout: info.accrued_payout,
+		}
+	}
+}
+
+/// Funds unlocking in an era
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Unbonding {
+	era: EraIndex,
+	#[serde(with = "serde_balance")]
+	value: Balance,
+}
+
+impl From<(EraIndex, Balance)> for Unbonding {
+	fn from((era, value): (EraIndex, Balance)) -> Self {
+		Unbonding { era, value }
+	}
+}
+
+/// Stake exposed to a validator
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Exposure<AccountId> {
+	validator: AccountId,
+	#[serde(with = "serde_balance")]
+	value: Balance,
+}
+
+impl<AccountId> From<(AccountId, Balance)> for Exposure<AccountId> {
+	fn from((validator, value): (AccountId, Balance)) -> Self {
+		Exposure { validator, value }
+	}
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorInfo<AccountId> {
+	stash: AccountId,
+	commission: Perbill,
+	elected: bool,
+	#[serde(with = "serde_balance")]
+	total_stake: Balance,
+	#[serde(with = "serde_balance")]
+	own_stake: Balance,
+	nominator_count: u32,
+	era_points: RewardPoint,
+}
+
+impl<AccountId> From<RuntimeValidatorInfo<AccountId, Balance>> for ValidatorInfo<AccountId> {
+	fn from(info: RuntimeValidatorInfo<AccountId, Balance>) -> Self {
+		ValidatorInfo {
+			stash: info.stash,
+			commission: info.commission,
+			elected: info.elected,
+			total_stake: info.total_stake,
+			own_stake: info.own_stake,
+			nominator_count: info.nominator_count,
+			era_points: info.era_points,
+		}
+	}
+}
+
+/// Rates are given as decimal strings e.g. "0.05" for 5%
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardEstimate {
+	inflation_rate: String,
+	#[serde(with = "serde_balance")]
+	era_inflation: Balance,
+	#[serde(with = "serde_balance")]
+	era_transaction_fees: Balance,
+	#[serde(with = "serde_balance")]
+	era_stakers_reward: Balance,
+	eras_per_year: u32,
+	#[serde(with = "serde_balance")]
+	annual_stakers_reward: Balance,
+	#[serde(with = "serde_balance")]
+	total_staked: Balance,
+	annual_return: String,
+}
+
+impl From<RuntimeRewardEstimate<Balance>> for RewardEstimate {
+	fn from(estimate: RuntimeRewardEstimate<Balance>) -> Self {
+		RewardEstimate {
+			inflation_rate: fixed_to_decimal(estimate.inflation_rate),
+			era_inflation: estimate.era_inflation,
+			era_transaction_fees: estimate.era_transaction_fees,
+			era_stakers_reward: estimate.era_stakers_reward,
+			eras_per_year: estimate.eras_per_year,
+			annual_stakers_reward: estimate.annual_stakers_reward,
+			total_staked: estimate.total_staked,
+			annual_return: fixed_to_decimal(estimate.annual_return),
+		}
+	}
+}
+
+/// Format a fixed point number as a decimal string, trimming trailing zeros
+fn fixed_to_decimal(value: FixedU128) -> String {
+	let inner = value.into_inner();
+	let accuracy = FixedU128::accuracy();
+	let fraction = format!("{:018}", inner % accuracy);
+	let fraction = fraction.trim_end_matches('0');
+	if fraction.is_empty() {
+		format!("{}", inner / accuracy)
+	} else {
+		format!("{}.{}", inner / accuracy, fraction)
+	}
+}
+
+/// (de)serialize balances as strings, JSON numbers can't hold a `u128`
+mod serde_balance {
+	use super::Balance;
+	use serde::{Deserialize, Deserializer, Serializer};
+
+	pub fn serialize<S: Serializer>(t: &Balance, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+		serializer.serialize_str(&t.to_string())
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Balance, D::Error> {
+		let s = String::deserialize(deserializer)?;
+		s.parse::<Balance>()
+			.map_err(|_| serde::de::Error::custom("Parse from string failed"))
+	}
+}
+
 impl<C, Block, AccountId> StakingApi<<Block as BlockT>::Hash, AccountId> for Staking<C, Block>
 where
 	Block: BlockT,
@@ -103,4 +278,76 @@ where
 			data: Some(format!("{:?}", e).into()),
 		})
 	}
+
+	fn account_info(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<AccountStakingInfo<AccountId>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		self.client
+			.runtime_api()
+			.account_info(&at, who)
+			.map(|info| info.map(Into::into))
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to query account staking info.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+
+	fn validators(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<ValidatorInfo<AccountId>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		self.client
+			.runtime_api()
+			.validators(&at)
+			.map(|validators| validators.into_iter().map(Into::into).collect())
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to query validators.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+
+	fn reward_estimate(&self, at: Option<<Block as BlockT>::Hash>) -> Result<RewardEstimate> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		self.client
+			.runtime_api()
+			.reward_estimate(&at)
+			.map(Into::into)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to estimate staking rewards.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn fixed_to_decimal_works() {
+		assert_eq!(fixed_to_decimal(FixedU128::from_inner(0)), "0");
+		assert_eq!(fixed_to_decimal(FixedU128::saturating_from_integer(2)), "2");
+		assert_eq!(fixed_to_decimal(FixedU128::saturating_from_rational(1, 20)), "0.05");
+		assert_eq!(fixed_to_decimal(FixedU128::saturating_from_rational(3, 2)), "1.5");
+	}
+
+	#[test]
+	fn reward_estimate_serializes_balances_as_strings() {
+		let estimate = RewardEstimate {
+			inflation_rate: "0.01".into(),
+			total_staked: u128::max_value(),
+			..Default::default()
+		};
+		let json = serde_json::to_string(&estimate).unwrap();
+		assert!(json.contains(r#""totalStaked":"340282366920938463463374607431768211455""#));
+		assert!(json.contains(r#""inflationRate":"0.01""#));
+		assert_eq!(serde_json::from_str::<RewardEstimate>(&json).unwrap(), estimate);
+	}
 }
@@ -166,7 +166,8 @@
 //!
 //! - Controller account, (obviously) not increasing the staked value.
 //! - Stash account, not increasing the staked value.
-//! - Stash account, also increasing the staked value.
+//! - Stash account, also increasing the staked value. Rewards are sold for the staking asset via
+//!   CENNZX and bonded, falling back to a stash payment if the sale fails.
 //!
 //! ### Additional Fund Management Operations
 //!
@@ -216,13 +217,16 @@ mod tests;
 
 mod offchain_election;
 pub mod rewards;
-pub use rewards::{HandlePayee, OnEndEra, RewardCalculation};
+pub use rewards::{HandlePayee, OnEndEra, RewardCalculation, RewardEstimate, RewardPoint};
 
 mod slashing;
 pub use slashing::REWARD_F1;
 
+/// Staking call weights, also used to weigh compounding rewards
+pub use pallet_staking::WeightInfo;
+
 use codec::HasCompact;
-use crml_support::StakingAmount;
+use crml_support::{OnStakerSlash, StakingAmount, StakingInterface};
 use frame_support::{
 	decl_error, decl_event, decl_module, decl_storage,
 	dispatch::{DispatchErrorWithPostInfo, WithPostDispatchInfo},
@@ -231,18 +235,19 @@ use frame_support::{
 		Currency, CurrencyToVote, EstimateNextNewSession, IsSubType, LockIdentifier, LockableCurrency, OnUnbalanced,
 		UnixTime, WithdrawReasons,
 	},
-	weights::constants::{WEIGHT_PER_MICROS, WEIGHT_PER_NANOS},
+	weights::constants::{RocksDbWeight, WEIGHT_PER_MICROS, WEIGHT_PER_NANOS},
 };
 use frame_system::{self as system, offchain::SendTransactionTypes, pallet_prelude::*};
 use pallet_session::historical;
-use pallet_staking::WeightInfo;
 use sp_npos_elections::{
 	generate_solution_type, is_score_better, seq_phragmen, to_supports, Assignment,
 	ElectionResult as PrimitiveElectionResult, ElectionScore, EvaluateSupport, ExtendedBalance, NposSolution,
 	PerThing128, Supports, VoteWeight,
 };
 use sp_runtime::{
-	traits::{AtLeast32Bit, CheckedSub, Convert, Dispatchable, SaturatedConversion, Saturating, Zero},
+	traits::{
+		AtLeast32Bit, AtLeast32BitUnsigned, CheckedSub, Convert, Dispatchable, SaturatedConversion, Saturating, Zero,
+	},
 	InnerOf, PerU16, Perbill,
 };
 #[cfg(feature = "std")]
@@ -251,7 +256,14 @@ use sp_staking::{
 	offence::{DisableStrategy, Offence, OffenceDetails, OffenceError, OnOffenceHandler, ReportOffence},
 	SessionIndex,
 };
-use sp_std::{collections::btree_set::BTreeSet, convert::TryInto, iter::FromIterator, mem::size_of, prelude::*, vec};
+use sp_std::{
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	convert::TryInto,
+	iter::FromIterator,
+	mem::size_of,
+	prelude::*,
+	vec,
+};
 
 const STAKING_ID: LockIdentifier = *b"staking ";
 const MAX_UNLOCKING_CHUNKS: usize = 32;
@@ -381,6 +393,10 @@ pub enum RewardDestination<AccountId> {
 	Controller,
 	/// Pay into a specified account.
 	Account(AccountId),
+	/// Sell the reward for the staking asset and bond it, increasing the amount at stake.
+	/// Falls back to paying into the stash account if the sale would cost more than `max_slippage` of the
+	/// reward's value or otherwise fails.
+	Compound { max_slippage: Perbill },
 }
 
 impl<AccountId> Default for RewardDestination<AccountId> {
@@ -431,6 +447,82 @@ impl<T: Config> StakingAmount for Module<T> {
 	}
 }
 
+impl<T: Config> StakingInterface for Module<T> {
+	type AccountId = T::AccountId;
+	type Balance = BalanceOf<T>;
+
+	fn minimum_bond() -> Self::Balance {
+		Self::minimum_bond()
+	}
+
+	fn current_era() -> EraIndex {
+		Self::current_era().unwrap_or(0)
+	}
+
+	fn bonding_duration() -> EraIndex {
+		T::BondingDuration::get()
+	}
+
+	fn active_stake(stash: &Self::AccountId) -> Self::Balance {
+		Self::bonded(stash)
+			.and_then(Self::ledger)
+			.map(|ledger| ledger.active)
+			.unwrap_or_else(Zero::zero)
+	}
+
+	fn unlocking(stash: &Self::AccountId) -> Vec<(EraIndex, Self::Balance)> {
+		Self::bonded(stash)
+			.and_then(Self::ledger)
+			.map(|ledger| ledger.unlocking.iter().map(|chunk| (chunk.era, chunk.value)).collect())
+			.unwrap_or_default()
+	}
+
+	fn bond(stash: &Self::AccountId, value: Self::Balance, payee: &Self::AccountId) -> DispatchResult {
+		Self::bond(
+			system::RawOrigin::Signed(stash.clone()).into(),
+			stash.clone(),
+			value,
+			RewardDestination::Account(payee.clone()),
+		)
+	}
+
+	fn bond_extra(stash: &Self::AccountId, value: Self::Balance) -> DispatchResult {
+		Self::do_bond_extra(stash, value).map(|_| ())
+	}
+
+	fn unbond(stash: &Self::AccountId, value: Self::Balance) -> DispatchResult {
+		Self::unbond(system::RawOrigin::Signed(stash.clone()).into(), value)?;
+
+		// a stash unbonding on behalf of many accounts would quickly hit `MAX_UNLOCKING_CHUNKS`,
+		// merge chunks unlocking in the same era (chunks are pushed in era order)
+		if let Some(mut ledger) = Self::ledger(stash) {
+			let mut unlocking: Vec<UnlockChunk<BalanceOf<T>>> = Vec::with_capacity(ledger.unlocking.len());
+			for chunk in ledger.unlocking.drain(..) {
+				match unlocking.last_mut() {
+					Some(last) if last.era == chunk.era => last.value = last.value.saturating_add(chunk.value),
+					_ => unlocking.push(chunk),
+				}
+			}
+			ledger.unlocking = unlocking;
+			<Ledger<T>>::insert(stash, ledger);
+		}
+
+		Ok(())
+	}
+
+	fn withdraw_unbonded(stash: &Self::AccountId) -> DispatchResult {
+		Self::withdraw_unbonded(system::RawOrigin::Signed(stash.clone()).into())
+	}
+
+	fn nominate(stash: &Self::AccountId, targets: Vec<Self::AccountId>) -> DispatchResult {
+		Self::nominate(system::RawOrigin::Signed(stash.clone()).into(), targets)
+	}
+
+	fn chill(stash: &Self::AccountId) -> DispatchResult {
+		Self::chill(system::RawOrigin::Signed(stash.clone()).into())
+	}
+}
+
 /// The ledger of a (bonded) stash.
 #[derive(PartialEq, Eq, Clone, Encode, Decode, TypeInfo, RuntimeDebug)]
 pub struct StakingLedger<AccountId, Balance: HasCompact> {
@@ -450,11 +542,6 @@ pub struct StakingLedger<AccountId, Balance: HasCompact> {
 }
 
 impl<AccountId, Balance: HasCompact + Copy + Saturating + AtLeast32Bit> StakingLedger<AccountId, Balance> {
-	/// The amount of stash's funds slashable as of right now.
-	/// It should remain slashable until the bonding duration expires and it is withdrawn.
-	fn slashable_balance(&self) -> Balance {
-		self.total
-	}
 	/// Remove entries from `unlocking` that are sufficiently old and reduce the
 	/// total by the sum of their balances.
 	fn consolidate_unlocked(self, current_era: EraIndex) -> Self {
@@ -507,64 +594,96 @@ impl<AccountId, Balance: HasCompact + Copy + Saturating + AtLeast32Bit> StakingL
 
 impl<AccountId, Balance> StakingLedger<AccountId, Balance>
 where
-	Balance: AtLeast32Bit + Saturating + Copy,
+	Balance: AtLeast32BitUnsigned + Saturating + Copy,
 {
 	/// Slash the validator for a given amount of balance. This can grow the value
 	/// of the slash in the case that the validator has less than `minimum_balance`
-	/// active funds. Returns the amount of funds actually slashed.
+	/// active funds. Returns the amount of funds actually slashed and the remaining
+	/// balance of each era of `unlocking` that was slashed.
 	///
-	/// Slashes from `active` funds first, and then `unlocking`, starting with the
-	/// chunks that are closest to unlocking.
-	fn slash(&mut self, mut value: Balance, minimum_balance: Balance) -> Balance {
+	/// Funds at stake in the slashed era i.e. `active` and the chunks unlocking from
+	/// `slashable_chunks_start`, are slashed pro-rata. Any remainder is slashed from
+	/// the other chunks, starting with the latest to unlock.
+	fn slash(
+		&mut self,
+		value: Balance,
+		minimum_balance: Balance,
+		slashable_chunks_start: EraIndex,
+	) -> (Balance, BTreeMap<EraIndex, Balance>) {
 		let total_before_slash = self.total;
-		let total_after_slash = &mut self.slashable_balance();
-		let active = &mut self.active;
+		let mut remaining_slash = value;
 
-		value = Self::apply_slash(total_after_slash, active, value, minimum_balance);
-
-		let i = self
+		// `Some(ratio)` when the stash had funds unbonding in or after the slashed era
+		let (maybe_proportional, slash_chunks_priority) = match self
 			.unlocking
-			.iter_mut()
-			.map(|chunk| {
-				value = Self::apply_slash(total_after_slash, &mut chunk.value, value, minimum_balance);
-				chunk.value
-			})
-			.take_while(|value| value.is_zero()) // take all fully-consumed chunks out.
-			.count();
+			.iter()
+			.position(|chunk| chunk.era >= slashable_chunks_start)
+		{
+			Some(first_slashable_index) => {
+				let affected_indices = first_slashable_index..self.unlocking.len();
+				let affected_balance = self.unlocking[affected_indices.clone()]
+					.iter()
+					.fold(self.active, |total, chunk| total.saturating_add(chunk.value));
+				(
+					Some(Perbill::from_rational(value, affected_balance)),
+					affected_indices
+						.chain((0..first_slashable_index).rev())
+						.collect::<Vec<_>>(),
+				)
+			}
+			None => (None, (0..self.unlocking.len()).rev().collect::<Vec<_>>()),
+		};
 
-		// kill all drained chunks.
-		let _ = self.unlocking.drain(..i);
+		let total = &mut self.total;
+		let mut slash_out_of = |target_funds: &mut Balance, remaining_slash: &mut Balance| {
+			let slash_from_target = maybe_proportional
+				.map_or(*remaining_slash, |ratio| ratio.mul_ceil(*target_funds))
+				.min(*target_funds)
+				.min(*remaining_slash);
+
+			if !slash_from_target.is_zero() {
+				*remaining_slash = remaining_slash.saturating_sub(slash_from_target);
+				*total = total.saturating_sub(slash_from_target);
+				*target_funds -= slash_from_target;
+
+				// don't leave a dust balance in the staking system.
+				if *target_funds <= minimum_balance {
+					*total = total.saturating_sub(*target_funds);
+					*target_funds = Zero::zero();
+				}
+			}
+		};
 
-		self.total = *total_after_slash;
-		// return the slashed amount
-		total_before_slash.saturating_sub(*total_after_slash)
-	}
+		slash_out_of(&mut self.active, &mut remaining_slash);
 
-	/// Apply slash to a target set of funds
-	///
-	/// Ensures dust isn't left in the balance of the `target_funds`
-	/// Returns the remainder of `slash` if the full `slash` was not applied
-	fn apply_slash(
-		total_funds: &mut Balance,
-		target_funds: &mut Balance,
-		slash: Balance,
-		minimum_balance: Balance,
-	) -> Balance {
-		let slash_from_target = (slash).min(*target_funds);
-		let mut slash_remainder = slash;
-
-		if !slash_from_target.is_zero() {
-			slash_remainder = slash - slash_from_target;
-			*total_funds = total_funds.saturating_sub(slash_from_target);
-			*target_funds -= slash_from_target;
-
-			// don't leave a dust balance in the staking system.
-			if *target_funds <= minimum_balance {
-				*total_funds = total_funds.saturating_sub(*target_funds);
-				*target_funds = Zero::zero();
+		let mut slashed_eras = BTreeSet::new();
+		for i in slash_chunks_priority {
+			if remaining_slash.is_zero() {
+				break;
+			}
+			if let Some(chunk) = self.unlocking.get_mut(i) {
+				slash_out_of(&mut chunk.value, &mut remaining_slash);
+				slashed_eras.insert(chunk.era);
 			}
 		}
-		slash_remainder
+
+		// kill all drained chunks.
+		self.unlocking.retain(|chunk| !chunk.value.is_zero());
+
+		let slashed_unlocking = slashed_eras
+			.into_iter()
+			.map(|era| {
+				let unlocking = self
+					.unlocking
+					.iter()
+					.filter(|chunk| chunk.era == era)
+					.fold(Zero::zero(), |total: Balance, chunk| total.saturating_add(chunk.value));
+				(era, unlocking)
+			})
+			.collect();
+
+		// return the slashed amount
+		(total_before_slash.saturating_sub(self.total), slashed_unlocking)
 	}
 }
 
@@ -620,6 +739,8 @@ pub struct UnappliedSlash<AccountId, Balance: HasCompact> {
 	reporters: Vec<AccountId>,
 	/// The amount of payout.
 	payout: Balance,
+	/// The era the offence occurred in.
+	slash_era: EraIndex,
 }
 
 /// Indicate how an election round was computed.
@@ -650,6 +771,50 @@ pub struct ActiveEraInfo {
 	start: Option<u64>,
 }
 
+/// A summary of an account's staking position
+#[derive(PartialEq, Eq, Clone, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub struct AccountStakingInfo<AccountId, Balance> {
+	/// The stash account
+	pub stash: AccountId,
+	/// The controller account
+	pub controller: AccountId,
+	/// The account rewards are paid to
+	pub payee: AccountId,
+	/// The total bonded balance (`active` plus any `unlocking` balances)
+	pub total: Balance,
+	/// The balance at stake in forthcoming eras
+	pub active: Balance,
+	/// Unbonded balances and the era at which they may be withdrawn
+	pub unlocking: Vec<(EraIndex, Balance)>,
+	/// The stash's commission, if it is a validator candidate
+	pub validator_commission: Option<Perbill>,
+	/// The validators nominated by the stash, if it is a nominator
+	pub nominations: Vec<AccountId>,
+	/// The validators the stash is exposed to in the active era, and the amount exposed
+	pub exposures: Vec<(AccountId, Balance)>,
+	/// The payout accrued by the stash so far in the active era
+	pub accrued_payout: Balance,
+}
+
+/// A summary of a validator candidate
+#[derive(PartialEq, Eq, Clone, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub struct ValidatorInfo<AccountId, Balance> {
+	/// The validator stash account
+	pub stash: AccountId,
+	/// The validator's commission, as charged in the active era if elected
+	pub commission: Perbill,
+	/// Whether the validator is part of the active era's elected set
+	pub elected: bool,
+	/// The total stake backing the validator in the active era
+	pub total_stake: Balance,
+	/// The validator's own stake in the active era
+	pub own_stake: Balance,
+	/// The number of nominators backing the validator in the active era
+	pub nominator_count: u32,
+	/// Reward points earned by the validator so far in the current era
+	pub era_points: RewardPoint,
+}
+
 /// Means for interacting with a specialized version of the `session` trait.
 ///
 /// This is needed because `Staking` sets the `ValidatorIdOf` of the `pallet_session::Config`
@@ -731,6 +896,10 @@ pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
 	/// applied immediately, without opportunity for intervention.
 	type SlashDeferDuration: Get<EraIndex>;
 
+	/// Number of eras a validator's commission increase is delayed by, giving nominators notice
+	/// to change their nominations before it takes effect.
+	type CommissionChangeDelay: Get<EraIndex>;
+
 	/// Interface for interacting with a session module.
 	type SessionInterface: self::SessionInterface<Self::AccountId>;
 
@@ -775,8 +944,11 @@ pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
 	/// enough to fit in the block.
 	type OffchainSolutionWeightLimit: Get<Weight>;
 
+	/// Notified of slashes to a stash's bonded and unlocking funds
+	type OnStakerSlash: OnStakerSlash<Self::AccountId, BalanceOf<Self>>;
+
 	/// Extrinsic weight info
-	type WeightInfo: WeightInfo + ElectionWeightExt;
+	type WeightInfo: WeightInfo + ElectionWeightExt + CommissionWeightExt;
 }
 
 /// Mode of era-forcing.
@@ -810,11 +982,13 @@ enum Releases {
 	V1 = 1,
 	/// storage version runtime v39
 	V2 = 2,
+	/// `UnappliedSlash` records the slashed era
+	V3 = 3,
 }
 
 impl Default for Releases {
 	fn default() -> Self {
-		Releases::V2
+		Releases::V3
 	}
 }
 
@@ -823,7 +997,8 @@ impl From<u32> for Releases {
 		match val {
 			0 => Releases::V0,
 			1 => Releases::V1,
-			2 | _ => Releases::V2,
+			2 => Releases::V2,
+			3 | _ => Releases::V3,
 		}
 	}
 }
@@ -865,6 +1040,22 @@ decl_storage! {
 		pub Validators get(fn validators):
 		map hasher(twox_64_concat) T::AccountId => ValidatorPrefs;
 
+		/// Commission increases scheduled by validator stashes and the era they take effect.
+		pub PendingCommission get(fn pending_commission):
+			map hasher(twox_64_concat) T::AccountId => Option<(EraIndex, Perbill)>;
+
+		/// The commission of validator stashes when they stopped validating, increases on
+		/// re-validating are delayed from it.
+		pub LastCommission get(fn last_commission):
+			map hasher(twox_64_concat) T::AccountId => Option<Perbill>;
+
+		/// The maximum commission a validator may set.
+		pub MaxCommission get(fn max_commission): Perbill = Perbill::one();
+
+		/// The maximum a validator's commission may be increased by, per era of
+		/// `CommissionChangeDelay`.
+		pub MaxCommissionIncrease get(fn max_commission_increase): Perbill = Perbill::one();
+
 		/// The map from nominator stash key to the set of stash keys of all validators to nominate.
 		pub Nominators get(fn nominators):
 			map hasher(twox_64_concat) T::AccountId => Option<Nominations<T::AccountId>>;
@@ -1000,7 +1191,7 @@ decl_storage! {
 		/// Storage version of the pallet.
 		///
 		/// This is set to v2 for new networks.
-		StorageVersion build(|_: &GenesisConfig<T>| Releases::V2 as u32): u32;
+		StorageVersion build(|_: &GenesisConfig<T>| Releases::V3 as u32): u32;
 	}
 	add_extra_genesis {
 		config(stakers):
@@ -1066,6 +1257,12 @@ decl_event!(
 		/// An account has called `withdraw_unbonded` and removed unbonding chunks worth `Balance`
 		/// from the unlocking queue. \[stash, amount\]
 		Withdrawn(AccountId, Balance),
+		/// A validator has scheduled a commission increase. \[stash, commission, effective era\]
+		CommissionChangeScheduled(AccountId, Perbill, EraIndex),
+		/// A validator's commission has changed. \[stash, commission\]
+		CommissionChanged(AccountId, Perbill),
+		/// The commission limits have changed. \[max commission, max increase per era\]
+		SetCommissionLimits(Perbill, Perbill),
 	}
 );
 
@@ -1131,6 +1328,10 @@ decl_error! {
 		CallNotAllowed,
 		/// Incorrect previous history depth input provided.
 		IncorrectHistoryDepth,
+		/// Commission is above the maximum allowed.
+		CommissionTooHigh,
+		/// Commission increase is above the maximum allowed before it takes effect.
+		CommissionIncreaseTooLarge,
 	}
 }
 
@@ -1179,7 +1380,11 @@ decl_module! {
 		fn on_runtime_upgrade() -> Weight {
 			match StorageVersion::get().into() {
 				// upgraded!
-				Releases::V2 | Releases::V1 | Releases::V0 => Zero::zero(),
+				Releases::V3 => Zero::zero(),
+				Releases::V2 | Releases::V1 | Releases::V0 => {
+					StorageVersion::put(Releases::V3 as u32);
+					Self::migrate_unapplied_slashes()
+				}
 			}
 		}
 
@@ -1335,12 +1540,7 @@ decl_module! {
 			// you actually validate/nominate and remove once you unbond __everything__.
 			<Bonded<T>>::insert(&stash, &controller);
 
-			let id = match payee {
-				RewardDestination::Stash => stash.clone(),
-				RewardDestination::Controller => controller.clone(),
-				RewardDestination::Account(account) => account,
-			};
-			T::Rewarder::set_payee(&stash, &id);
+			Self::set_reward_destination(&stash, &controller, payee);
 
 			let stash_balance = T::Currency::free_balance(&stash);
 			let value = value.min(stash_balance);
@@ -1372,25 +1572,8 @@ decl_module! {
 		/// # </weight>
 		#[weight = T::WeightInfo::bond_extra()]
 		fn bond_extra(origin, #[compact] max_additional: BalanceOf<T>) {
-			ensure!(Self::era_election_status().is_closed(), Error::<T>::CallNotAllowed);
 			let stash = ensure_signed(origin)?;
-
-			let controller = Self::bonded(&stash).ok_or(Error::<T>::NotStash)?;
-			let mut ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
-
-			let stash_balance = T::Currency::free_balance(&stash);
-
-			if let Some(extra) = stash_balance.checked_sub(&ledger.total) {
-				let extra = extra.min(max_additional);
-				ensure!((ledger.active + extra) >= Self::minimum_bond(), Error::<T>::InsufficientBond);
-				ledger.total += extra;
-				ledger.active += extra;
-				Self::deposit_event(RawEvent::Bonded(stash, extra));
-				Self::update_ledger(&controller, &ledger);
-			} else {
-				// use doesn't have enough balance, better to retun some failure
-				return Err(Error::<T>::InsufficientFreeBalance.into())
-			}
+			Self::do_bond_extra(&stash, max_additional)?;
 		}
 
 		/// Schedule a portion of the stash to be unlocked ready for transfer out after the bond
@@ -1529,6 +1712,12 @@ decl_module! {
 		///
 		/// Effects will be felt at the beginning of the next era.
 		///
+		/// `prefs.commission` may not be above [`MaxCommission`]. A commission increase by a
+		/// validator, or by a stash re-validating, is instead scheduled to take effect
+		/// `CommissionChangeDelay` eras later, so nominators have notice, and may not be more than
+		/// [`MaxCommissionIncrease`] per era of delay. Emits `CommissionChangeScheduled` when an
+		/// increase is scheduled.
+		///
 		/// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
 		/// And, it can be only called when [`EraElectionStatus`] is `Closed`.
 		///
@@ -1539,10 +1728,11 @@ decl_module! {
 		/// -----------
 		/// Weight: O(1)
 		/// DB Weight:
-		/// - Read: Era Election Status, Ledger
-		/// - Write: Nominators, Validators
+		/// - Read: Era Election Status, Ledger, Validators, Current Era, Max Commission, Max Commission
+		///   Increase, Last Commission, Eras Validator Prefs
+		/// - Write: Nominators, Validators, Pending Commission, Last Commission
 		/// # </weight>
-		#[weight = T::WeightInfo::validate()]
+		#[weight = T::WeightInfo::validate().saturating_add(T::WeightInfo::validate_commission())]
 		fn validate(origin, prefs: ValidatorPrefs) {
 			ensure!(Self::era_election_status().is_closed(), Error::<T>::CallNotAllowed);
 			let controller = ensure_signed(origin)?;
@@ -1550,12 +1740,53 @@ decl_module! {
 			ensure!(ledger.active >= Self::minimum_bond(), Error::<T>::InsufficientBond);
 			let stash = &ledger.stash;
 
-			let prefs = ValidatorPrefs {
-				commission: prefs.commission.min(Perbill::one())
+			let commission = prefs.commission;
+			ensure!(commission <= Self::max_commission(), Error::<T>::CommissionTooHigh);
+
+			// compare against the commission nominators last saw, otherwise a validator could chill
+			// and re-validate at a higher commission immediately
+			let current_era = Self::current_era().unwrap_or(0);
+			let current_commission = if <Validators<T>>::contains_key(stash) {
+				Some(Self::validators(stash).commission)
+			} else {
+				Self::last_commission(stash).or_else(|| {
+					<ErasValidatorPrefs<T>>::contains_key(current_era, stash)
+						.then(|| Self::eras_validator_prefs(current_era, stash).commission)
+				})
 			};
 
+			let delay = T::CommissionChangeDelay::get();
+			if let Some(current_commission) = current_commission {
+				let max_increase = Perbill::from_parts(
+					Self::max_commission_increase().deconstruct().saturating_mul(delay.max(1))
+				);
+				ensure!(
+					commission.saturating_sub(current_commission) <= max_increase,
+					Error::<T>::CommissionIncreaseTooLarge,
+				);
+			}
+
 			<Nominators<T>>::remove(stash);
-			<Validators<T>>::insert(stash, prefs);
+			<LastCommission<T>>::remove(stash);
+			match current_commission {
+				Some(current_commission) if commission > current_commission => {
+					let effective_era = current_era.saturating_add(1).saturating_add(delay);
+					if !<Validators<T>>::contains_key(stash) {
+						<Validators<T>>::insert(stash, ValidatorPrefs { commission: current_commission });
+					}
+					<PendingCommission<T>>::insert(stash, (effective_era, commission));
+					Self::deposit_event(
+						RawEvent::CommissionChangeScheduled(stash.clone(), commission, effective_era)
+					);
+				}
+				_ => {
+					<PendingCommission<T>>::remove(stash);
+					<Validators<T>>::insert(stash, prefs);
+					if current_commission.is_some() && current_commission != Some(commission) {
+						Self::deposit_event(RawEvent::CommissionChanged(stash.clone(), commission));
+					}
+				}
+			}
 		}
 
 		/// Declare the desire to nominate `targets` for the origin controller.
@@ -1599,7 +1830,7 @@ decl_module! {
 				submitted_in: Self::current_era().unwrap_or(0),
 			};
 
-			<Validators<T>>::remove(stash);
+			Self::stop_validating(stash);
 			<Nominators<T>>::insert(stash, &nominations);
 		}
 
@@ -1648,13 +1879,7 @@ decl_module! {
 		fn set_payee(origin, payee: RewardDestination<T::AccountId>) {
 			let controller = ensure_signed(origin)?;
 			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
-			let stash = &ledger.stash;
-			let id = match payee {
-				RewardDestination::Stash => stash.clone(),
-				RewardDestination::Controller => controller,
-				RewardDestination::Account(account) => account,
-			};
-			T::Rewarder::set_payee(stash, &id);
+			Self::set_reward_destination(&ledger.stash, &controller, payee);
 		}
 
 		/// (Re-)set the controller of a stash.
@@ -1741,6 +1966,20 @@ decl_module! {
 			Self::deposit_event(RawEvent::SetMinimumBond(value));
 		}
 
+		/// Set the maximum commission validators may set and the maximum a commission may be
+		/// increased by per era of `CommissionChangeDelay`.
+		///
+		/// Validators already above `max_commission` keep their commission until they change it.
+		///
+		/// The dispatch origin must be Root.
+		#[weight = T::WeightInfo::set_commission_limits()]
+		fn set_commission_limits(origin, max_commission: Perbill, max_increase: Perbill) {
+			ensure_root(origin)?;
+			MaxCommission::put(max_commission);
+			MaxCommissionIncrease::put(max_increase);
+			Self::deposit_event(RawEvent::SetCommissionLimits(max_commission, max_increase));
+		}
+
 		/// Set the validators who cannot be slashed (if any).
 		#[weight = T::WeightInfo::set_invulnerables(validators.len() as u32)]
 		fn set_invulnerables(origin, validators: Vec<T::AccountId>) {
@@ -2029,6 +2268,38 @@ decl_module! {
 }
 
 impl<T: Config> Module<T> {
+	/// Record the slashed era of deferred slashes queued before `UnappliedSlash` stored it
+	fn migrate_unapplied_slashes() -> Weight {
+		#[derive(Decode)]
+		struct OldUnappliedSlash<AccountId, Balance: HasCompact> {
+			validator: AccountId,
+			own: Balance,
+			others: Vec<(AccountId, Balance)>,
+			reporters: Vec<AccountId>,
+			payout: Balance,
+		}
+
+		let mut count = 0_u64;
+		<UnappliedSlashes<T>>::translate::<Vec<OldUnappliedSlash<T::AccountId, BalanceOf<T>>>, _>(|era, slashes| {
+			count += 1;
+			Some(
+				slashes
+					.into_iter()
+					.map(|old| UnappliedSlash {
+						validator: old.validator,
+						own: old.own,
+						others: old.others,
+						reporters: old.reporters,
+						payout: old.payout,
+						// queued by the era it was reported in, the offence can't be any later
+						slash_era: era,
+					})
+					.collect(),
+			)
+		});
+		T::DbWeight::get().reads_writes(count + 1, count + 1)
+	}
+
 	/// The total balance that can be slashed from a stash account as of right now.
 	pub fn slashable_balance_of(stash: &T::AccountId) -> BalanceOf<T> {
 		// Weight note: consider making the stake accessible through stash.
@@ -2113,6 +2384,97 @@ impl<T: Config> Module<T> {
 		T::Rewarder::calculate_individual_reward(stash, validator_commission_stake_map.as_slice())
 	}
 
+	/// Return a summary of the staking position of `who`, which may be either a stash or a controller
+	pub fn account_staking_info(who: &T::AccountId) -> Option<AccountStakingInfo<T::AccountId, BalanceOf<T>>> {
+		let (controller, ledger) = match Self::ledger(who) {
+			Some(ledger) => (who.clone(), ledger),
+			None => {
+				let controller = Self::bonded(who)?;
+				(controller.clone(), Self::ledger(&controller)?)
+			}
+		};
+		let stash = ledger.stash;
+		let era_index = Self::active_era().map(|e| e.index).unwrap_or(0);
+		let exposures = <ErasStakers<T>>::iter_prefix(era_index)
+			.filter_map(|(validator, exposure)| {
+				if validator == stash {
+					Some((validator, exposure.own))
+				} else {
+					exposure
+						.others
+						.into_iter()
+						.find(|individual| individual.who == stash)
+						.map(|individual| (validator, individual.value))
+				}
+			})
+			.collect();
+
+		Some(AccountStakingInfo {
+			payee: T::Rewarder::payee(&stash),
+			controller,
+			total: ledger.total,
+			active: ledger.active,
+			unlocking: ledger
+				.unlocking
+				.into_iter()
+				.map(|chunk| (chunk.era, chunk.value))
+				.collect(),
+			validator_commission: if <Validators<T>>::contains_key(&stash) {
+				Some(Self::validators(&stash).commission)
+			} else {
+				None
+			},
+			nominations: Self::nominators(&stash)
+				.map(|nominations| nominations.targets)
+				.unwrap_or_default(),
+			exposures,
+			accrued_payout: Self::accrued_payout(&stash),
+			stash,
+		})
+	}
+
+	/// Return a summary of all validator candidates, including those elected in the active era
+	pub fn validators_info() -> Vec<ValidatorInfo<T::AccountId, BalanceOf<T>>> {
+		let era_index = Self::active_era().map(|e| e.index).unwrap_or(0);
+		let mut validators: Vec<ValidatorInfo<T::AccountId, BalanceOf<T>>> =
+			<ErasValidatorPrefs<T>>::iter_prefix(era_index)
+				.map(|(stash, prefs)| {
+					let exposure = Self::eras_stakers(era_index, &stash);
+					ValidatorInfo {
+						commission: prefs.commission,
+						elected: true,
+						total_stake: exposure.total,
+						own_stake: exposure.own,
+						nominator_count: exposure.others.len().saturated_into(),
+						era_points: T::Rewarder::validator_era_points(&stash),
+						stash,
+					}
+				})
+				.collect();
+
+		let waiting = <Validators<T>>::iter()
+			.filter(|(stash, _)| !<ErasValidatorPrefs<T>>::contains_key(era_index, stash))
+			.map(|(stash, prefs)| ValidatorInfo {
+				commission: prefs.commission,
+				elected: false,
+				total_stake: Zero::zero(),
+				own_stake: Zero::zero(),
+				nominator_count: 0,
+				era_points: 0,
+				stash,
+			})
+			.collect::<Vec<_>>();
+		validators.extend(waiting);
+
+		validators
+	}
+
+	/// Estimate annual staking returns given the stake backing the active era's validators
+	pub fn reward_estimate() -> RewardEstimate<BalanceOf<T>> {
+		let era_index = Self::active_era().map(|e| e.index).unwrap_or(0);
+		T::Rewarder::estimate_reward(Self::eras_total_stake(era_index))
+	}
+
 	/// Update the ledger for a controller.
 	///
 	/// This will also update the stash lock.
@@ -2123,10 +2485,36 @@ impl<T: Config> Module<T> {
 
 	/// Chill a stash account.
 	fn chill_stash(stash: &T::AccountId) {
-		<Validators<T>>::remove(stash);
+		Self::stop_validating(stash);
 		<Nominators<T>>::remove(stash);
 	}
 
+	/// Remove a stash from the validator candidates, keeping the commission its nominators last saw.
+	fn stop_validating(stash: &T::AccountId) {
+		if <Validators<T>>::contains_key(stash) {
+			<LastCommission<T>>::insert(stash, Self::validators(stash).commission);
+			<Validators<T>>::remove(stash);
+		}
+		<PendingCommission<T>>::remove(stash);
+	}
+
+	/// Apply the commission changes of validators due to take effect by `era`.
+	///
+	/// Changes are capped at the current [`MaxCommission`].
+	fn apply_commission_changes(era: EraIndex) {
+		let due = <PendingCommission<T>>::iter()
+			.filter(|(_, (effective_era, _))| *effective_era <= era)
+			.collect::<Vec<_>>();
+		for (stash, (_, commission)) in due {
+			<PendingCommission<T>>::remove(&stash);
+			if <Validators<T>>::contains_key(&stash) {
+				let commission = commission.min(Self::max_commission());
+				<Validators<T>>::insert(&stash, ValidatorPrefs { commission });
+				Self::deposit_event(RawEvent::CommissionChanged(stash, commission));
+			}
+		}
+	}
+
 	/// Plan a new session potentially trigger a new era.
 	fn new_session(session_index: SessionIndex) -> Option<Vec<T::AccountId>> {
 		if let Some(current_era) = Self::current_era() {
@@ -2629,6 +3017,9 @@ impl<T: Config> Module<T> {
 			Self::clear_era_information(old_era);
 		}
 
+		// Apply commission changes before the new era's validator prefs are recorded.
+		Self::apply_commission_changes(current_era);
+
 		// Set staking information for new era.
 		let maybe_new_validators = Self::select_and_update_validators(current_era);
 
@@ -2747,6 +3138,48 @@ impl<T: Config> Module<T> {
 			.collect::<Vec<(T::AccountId, Exposure<_, _>)>>()
 	}
 
+	/// Set where rewards for `stash` are paid
+	fn set_reward_destination(stash: &T::AccountId, controller: &T::AccountId, payee: RewardDestination<T::AccountId>) {
+		let max_slippage = match payee {
+			RewardDestination::Compound { max_slippage } => Some(max_slippage),
+			_ => None,
+		};
+		let id = match payee {
+			RewardDestination::Stash | RewardDestination::Compound { .. } => stash.clone(),
+			RewardDestination::Controller => controller.clone(),
+			RewardDestination::Account(account) => account,
+		};
+		T::Rewarder::set_payee(stash, &id);
+		T::Rewarder::set_compound(stash, max_slippage);
+	}
+
+	/// Bond up to `max_additional` of the free balance of `stash` which isn't yet at stake
+	/// It is the implementation of `bond_extra`, also used to compound rewards
+	pub fn do_bond_extra(stash: &T::AccountId, max_additional: BalanceOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+		ensure!(Self::era_election_status().is_closed(), Error::<T>::CallNotAllowed);
+
+		let controller = Self::bonded(stash).ok_or(Error::<T>::NotStash)?;
+		let mut ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+
+		let stash_balance = T::Currency::free_balance(stash);
+
+		if let Some(extra) = stash_balance.checked_sub(&ledger.total) {
+			let extra = extra.min(max_additional);
+			ensure!(
+				(ledger.active + extra) >= Self::minimum_bond(),
+				Error::<T>::InsufficientBond
+			);
+			ledger.total += extra;
+			ledger.active += extra;
+			Self::deposit_event(RawEvent::Bonded(stash.clone(), extra));
+			Self::update_ledger(&controller, &ledger);
+			Ok(extra)
+		} else {
+			// use doesn't have enough balance, better to retun some failure
+			Err(Error::<T>::InsufficientFreeBalance.into())
+		}
+	}
+
 	/// Remove all associated data of a stash account from the staking system.
 	///
 	/// Assumes storage is upgraded before calling.
@@ -2765,6 +3198,8 @@ impl<T: Config> Module<T> {
 		T::Rewarder::remove_payee(stash);
 		<Validators<T>>::remove(stash);
 		<Nominators<T>>::remove(stash);
+		<PendingCommission<T>>::remove(stash);
+		<LastCommission<T>>::remove(stash);
 
 		Ok(())
 	}
@@ -3230,3 +3665,19 @@ impl ElectionWeightExt for () {
 			.saturating_add(1_000_000 as Weight)
 	}
 }
+
+pub trait CommissionWeightExt {
+	fn validate_commission() -> Weight;
+	fn set_commission_limits() -> Weight;
+}
+
+impl CommissionWeightExt for () {
+	fn validate_commission() -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn set_commission_limits() -> Weight {
+		(10_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+}
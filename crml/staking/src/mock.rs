@@ -18,7 +18,7 @@
 //! Test utilities
 
 use crate as staking;
-use crate::rewards::{HandlePayee, RewardCalculation, RewardParts};
+use crate::rewards::{HandlePayee, RewardCalculation, RewardEstimate, RewardParts, RewardPoint};
 use crate::*;
 use frame_support::{
 	assert_ok, parameter_types,
@@ -274,6 +274,7 @@ impl pallet_timestamp::Config for Test {
 
 parameter_types! {
 	pub const BondingDuration: EraIndex = 3;
+	pub const CommissionChangeDelay: EraIndex = 2;
 	pub const MaxNominatorRewardedPerValidator: u32 = 64;
 	pub const UnsignedPriority: u64 = 1 << 20;
 	pub const MinSolutionScoreBump: Perbill = Perbill::zero();
@@ -293,6 +294,7 @@ impl Config for Test {
 	type SessionsPerEra = SessionsPerEra;
 	type SlashDeferDuration = SlashDeferDuration;
 	type BondingDuration = BondingDuration;
+	type CommissionChangeDelay = CommissionChangeDelay;
 	type SessionInterface = Self;
 	type Rewarder = NoopRewarder<Self>;
 	type NextNewSession = Session;
@@ -303,6 +305,7 @@ impl Config for Test {
 	type MaxNominatorRewardedPerValidator = MaxNominatorRewardedPerValidator;
 	type UnsignedPriority = UnsignedPriority;
 	type OffchainSolutionWeightLimit = OffchainSolutionWeightLimit;
+	type OnStakerSlash = ();
 	type WeightInfo = ();
 }
 
@@ -1012,11 +1015,27 @@ impl<T: Config> RewardCalculation for NoopRewarder<T> {
 	) -> Self::Balance {
 		Zero::zero()
 	}
+	fn validator_era_points(_validator: &Self::AccountId) -> RewardPoint {
+		0
+	}
+	fn estimate_reward(total_staked: Self::Balance) -> RewardEstimate<Self::Balance> {
+		RewardEstimate {
+			inflation_rate: Zero::zero(),
+			era_inflation: Zero::zero(),
+			era_transaction_fees: Zero::zero(),
+			era_stakers_reward: Zero::zero(),
+			eras_per_year: 0,
+			annual_stakers_reward: Zero::zero(),
+			total_staked,
+			annual_return: Zero::zero(),
+		}
+	}
 }
 
 impl<T: Config> HandlePayee for NoopRewarder<T> {
 	type AccountId = T::AccountId;
 	fn set_payee(_stash: &Self::AccountId, _payee: &Self::AccountId) {}
+	fn set_compound(_stash: &Self::AccountId, _max_slippage: Option<Perbill>) {}
 	fn remove_payee(_stash: &Self::AccountId) {}
 	fn payee(stash: &Self::AccountId) -> Self::AccountId {
 		stash.clone()
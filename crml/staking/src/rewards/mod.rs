@@ -23,6 +23,7 @@ use crate::{EraIndex, Exposure};
 use crml_support::NotarizationRewardHandler;
 use frame_support::{
 	decl_event, decl_module, decl_storage,
+	storage::{with_transaction, TransactionOutcome},
 	traits::{Currency, Get, Imbalance},
 	weights::{DispatchClass, Weight},
 	PalletId,
@@ -61,6 +62,8 @@ pub trait Config: frame_system::Config {
 	type FiscalEraLength: Get<u32>;
 	/// Handles running a scheduled payout
 	type ScheduledPayoutRunner: RunScheduledPayout<AccountId = Self::AccountId, Balance = BalanceOf<Self>>;
+	/// Converts rewards into stake for stashes which compound their rewards
+	type RewardCompounder: CompoundReward<AccountId = Self::AccountId, Balance = BalanceOf<Self>>;
 	/// Extrinsic weight info
 	type WeightInfo: WeightInfo;
 }
@@ -73,6 +76,10 @@ decl_event!(
 	{
 		/// Staker payout (nominator/validator account, amount)
 		EraStakerPayout(AccountId, Balance),
+		/// A staker payout was converted into stake (stash, reward amount, amount bonded)
+		RewardCompounded(AccountId, Balance, Balance),
+		/// A staker payout could not be converted into stake and was left in the stash (stash, reward amount)
+		RewardCompoundFailed(AccountId, Balance),
 		/// Era reward payout the total (amount to treasury, amount to stakers)
 		EraPayout(Balance, Balance),
 		/// Era ended abruptly e.g. due to early re-election, this amount will be deferred to the next full era
@@ -95,6 +102,8 @@ decl_storage! {
 		/// Where the reward payment should be made. Keyed by stash.
 		// TODO: migrate to blake2 to prevent trie unbalancing
 		pub Payee: map hasher(twox_64_concat) T::AccountId => T::AccountId;
+		/// Stashes which compound their rewards, with the max. slippage accepted converting rewards to stake
+		pub CompoundSlippage get(fn compound_slippage): map hasher(twox_64_concat) T::AccountId => Option<Perbill>;
 		/// Upcoming reward payouts scheduled for block number to a validator and it's stakers of amount earned in era
 		pub ScheduledPayouts: map hasher(twox_64_concat) T::BlockNumber => Option<(T::AccountId, BalanceOf<T>)>;
 		/// The era index for current payouts
@@ -270,6 +279,54 @@ impl<T: Config> RewardCalculation for Module<T> {
 
 		payee_cut
 	}
+
+	/// Return the reward points earned by `validator` so far in the current era
+	fn validator_era_points(validator: &Self::AccountId) -> RewardPoint {
+		Self::current_era_points()
+			.individual
+			.get(validator)
+			.copied()
+			.unwrap_or_default()
+	}
+
+	/// Estimate annual staking returns from this era's inflation target and the average of recent
+	/// transaction fee payouts (falling back to the current fee pot if none have been paid yet)
+	fn estimate_reward(total_staked: Self::Balance) -> RewardEstimate<Self::Balance> {
+		let fee_history = Self::transaction_fee_pot_history();
+		let era_transaction_fees = if fee_history.is_empty() {
+			Self::transaction_fee_pot()
+		} else {
+			fee_history
+				.iter()
+				.fold(Zero::zero(), |acc: BalanceOf<T>, fees| acc.saturating_add(*fees))
+				/ (fee_history.len() as u32).into()
+		};
+		let next_reward = Self::calculate_total_reward();
+		let era_reward = RewardParts::new(next_reward.inflation, era_transaction_fees, next_reward.treasury_rate);
+
+		let eras_per_year = T::FiscalEraLength::get();
+		let annual_stakers_reward = era_reward.stakers_cut.saturating_mul(eras_per_year.into());
+		// rewards and stake are different assets, compare them in the staking asset
+		let annual_return = T::RewardCompounder::staking_value(annual_stakers_reward)
+			.and_then(|annual_reward_value| {
+				FixedU128::checked_from_rational::<u128, u128>(
+					annual_reward_value.unique_saturated_into(),
+					total_staked.unique_saturated_into(),
+				)
+			})
+			.unwrap_or_default();
+
+		RewardEstimate {
+			inflation_rate: Self::inflation_rate(),
+			era_inflation: era_reward.inflation,
+			era_transaction_fees,
+			era_stakers_reward: era_reward.stakers_cut,
+			eras_per_year,
+			annual_stakers_reward,
+			total_staked,
+			annual_return,
+		}
+	}
 }
 
 impl<T: Config> HandlePayee for Module<T> {
@@ -280,9 +337,15 @@ impl<T: Config> HandlePayee for Module<T> {
 		Payee::<T>::insert(stash, payee);
 	}
 
+	/// Set whether rewards for a stash account are compounded into its stake
+	fn set_compound(stash: &Self::AccountId, max_slippage: Option<Perbill>) {
+		CompoundSlippage::<T>::set(stash, max_slippage);
+	}
+
 	/// Remove the corresponding stash-payee from the look up
 	fn remove_payee(stash: &Self::AccountId) {
 		Payee::<T>::remove(stash);
+		CompoundSlippage::<T>::remove(stash);
 	}
 
 	/// Return the payee account for the given stash account.
@@ -333,24 +396,48 @@ impl<T: Config> Module<T> {
 
 	/// Process the reward payout for the given validator stash and all its supporting nominators
 	/// Requires `O(nominators)` writes
+	/// Returns the weight of compounding rewards, on top of `WeightInfo::process_reward_payouts`
 	pub fn process_reward_payout(
 		validator_stash: &T::AccountId,
 		validator_commission: Perbill,
 		exposures: &Exposure<T::AccountId, BalanceOf<T>>,
 		total_payout: BalanceOf<T>,
-	) {
+	) -> Weight {
 		if total_payout.is_zero() {
-			return;
+			return Zero::zero();
 		}
+		let mut compound_weight: Weight = Zero::zero();
 		let mut total_payout_imbalance = T::CurrencyToReward::burn(Zero::zero());
 		for (stash, amount) in
 			Self::calculate_npos_payouts(validator_stash, validator_commission, exposures, total_payout)
 		{
 			total_payout_imbalance.subsume(T::CurrencyToReward::deposit_creating(&Self::payee(&stash), amount));
-			Self::deposit_event(RawEvent::EraStakerPayout(stash, amount));
+			Self::deposit_event(RawEvent::EraStakerPayout(stash.clone(), amount));
+			if let Some(max_slippage) = Self::compound_slippage(&stash) {
+				Self::compound_reward(&stash, amount, max_slippage);
+				compound_weight = compound_weight.saturating_add(T::RewardCompounder::compound_weight());
+			}
 		}
 		let remainder = total_payout.saturating_sub(total_payout_imbalance.peek());
 		T::CurrencyToReward::deposit_creating(&T::TreasuryPalletId::get().into_account(), remainder);
+
+		compound_weight
+	}
+
+	/// Convert a reward paid to `stash` into stake
+	/// On failure any changes are reverted, leaving the reward in the stash
+	fn compound_reward(stash: &T::AccountId, amount: BalanceOf<T>, max_slippage: Perbill) {
+		let result = with_transaction(|| match T::RewardCompounder::compound(stash, amount, max_slippage) {
+			Ok(staked) => TransactionOutcome::Commit(Ok(staked)),
+			Err(err) => TransactionOutcome::Rollback(Err(err)),
+		});
+		match result {
+			Ok(staked) => Self::deposit_event(RawEvent::RewardCompounded(stash.clone(), amount, staked)),
+			Err(err) => {
+				log::debug!(target: "runtime::rewards", "failed compounding reward for: {:?}: {:?}", stash, err);
+				Self::deposit_event(RawEvent::RewardCompoundFailed(stash.clone(), amount));
+			}
+		}
 	}
 
 	/// Given a list of validator stashes, calculate the value of stake reward for
@@ -549,14 +636,16 @@ mod tests {
 	use crate::{rewards, IndividualExposure};
 	use crml_generic_asset::impls::TransferDustImbalance;
 	use frame_support::{
-		assert_err, assert_noop, assert_ok, parameter_types, traits::Currency, PalletId, StorageValue,
+		assert_err, assert_noop, assert_ok, parameter_types,
+		traits::{Currency, ExistenceRequirement, WithdrawReasons},
+		PalletId, StorageValue,
 	};
 	use pallet_authorship::EventHandler;
 	use sp_core::H256;
 	use sp_runtime::{
 		testing::Header,
 		traits::{AccountIdConversion, BadOrigin, BlakeTwo256, IdentityLookup, Zero},
-		FixedPointNumber, FixedU128, Perbill,
+		DispatchError, FixedPointNumber, FixedU128, Perbill,
 	};
 
 	/// The account Id type in this test runtime
@@ -639,6 +728,7 @@ mod tests {
 		type FiscalEraLength = FiscalEraLength;
 		type HistoricalPayoutEras = HistoricalPayoutEras;
 		type ScheduledPayoutRunner = MockPayoutRunner<Self>;
+		type RewardCompounder = MockRewardCompounder;
 		type TreasuryPalletId = TreasuryPalletId;
 		type WeightInfo = ();
 	}
@@ -657,6 +747,35 @@ mod tests {
 		}
 	}
 
+	/// A reward compounder which swaps rewards 1:1 for the staking asset
+	/// It fails after taking the reward if no slippage is accepted
+	pub struct MockRewardCompounder;
+
+	impl CompoundReward for MockRewardCompounder {
+		type AccountId = AccountId;
+		type Balance = Balance;
+
+		fn compound(stash: &AccountId, amount: Balance, max_slippage: Perbill) -> Result<Balance, DispatchError> {
+			let _ = <Test as Config>::CurrencyToReward::withdraw(
+				stash,
+				amount,
+				WithdrawReasons::TRANSFER,
+				ExistenceRequirement::AllowDeath,
+			)?;
+			if max_slippage.is_zero() {
+				return Err(DispatchError::Other("slippage exceeded"));
+			}
+			let _ = crml_generic_asset::StakingAssetCurrency::<Test>::deposit_creating(stash, amount);
+			Ok(amount)
+		}
+		fn compound_weight() -> Weight {
+			1_000_000
+		}
+		fn staking_value(amount: Balance) -> Option<Balance> {
+			Some(amount)
+		}
+	}
+
 	// Provides configurable mock genesis storage data.
 	#[derive(Default)]
 	pub struct ExtBuilder {
@@ -973,6 +1092,59 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn estimate_reward_uses_average_fee_history() {
+		ExtBuilder::default().build().execute_with(|| {
+			let base_inflation = 50;
+			TargetInflationPerStakingEra::<Test>::put(base_inflation);
+			TransactionFeePot::<Test>::put(1_000);
+			let eras_per_year = <Test as Config>::FiscalEraLength::get();
+
+			// no fee payouts yet, the current pot is used
+			let estimate = Rewards::estimate_reward(10_000);
+			let expected_stakers_cut = RewardParts::new(base_inflation, 1_000, DevelopmentFundTake::get()).stakers_cut;
+			assert_eq!(estimate.era_transaction_fees, 1_000);
+			assert_eq!(estimate.era_stakers_reward, expected_stakers_cut);
+			assert_eq!(estimate.eras_per_year, eras_per_year);
+			assert_eq!(
+				estimate.annual_stakers_reward,
+				expected_stakers_cut * eras_per_year as Balance
+			);
+
+			// fees are averaged over the recent payout history
+			TransactionFeePotHistory::<Test>::put(VecDeque::from(vec![100, 200, 300]));
+			let estimate = Rewards::estimate_reward(10_000);
+			let expected_stakers_cut = RewardParts::new(base_inflation, 200, DevelopmentFundTake::get()).stakers_cut;
+			assert_eq!(estimate.inflation_rate, Rewards::inflation_rate());
+			assert_eq!(estimate.era_inflation, base_inflation);
+			assert_eq!(estimate.era_transaction_fees, 200);
+			assert_eq!(estimate.era_stakers_reward, expected_stakers_cut);
+			assert_eq!(estimate.total_staked, 10_000);
+			assert_eq!(
+				estimate.annual_stakers_reward,
+				expected_stakers_cut * eras_per_year as Balance
+			);
+			// rewards are valued 1:1 in the staking asset
+			assert_eq!(
+				estimate.annual_return,
+				FixedU128::saturating_from_rational(expected_stakers_cut * eras_per_year as Balance, 10_000_u64)
+			);
+
+			// nothing staked, no return
+			assert!(Rewards::estimate_reward(0).annual_return.is_zero());
+		});
+	}
+
+	#[test]
+	fn validator_era_points_reads_current_era() {
+		ExtBuilder::default().build().execute_with(|| {
+			Rewards::reward_by_ids(vec![(1, 20), (2, 5), (1, 10)]);
+			assert_eq!(Rewards::validator_era_points(&1), 30);
+			assert_eq!(Rewards::validator_era_points(&2), 5);
+			assert_eq!(Rewards::validator_era_points(&3), 0);
+		});
+	}
+
 	#[test]
 	fn calculate_npos_payouts() {
 		ExtBuilder::default().build().execute_with(|| {
@@ -1407,6 +1579,67 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn process_reward_payout_compounds_rewards() {
+		ExtBuilder::default().build().execute_with(|| {
+			let (validator_stash, validator_stake) = (13, 1_000);
+			let nominator_stakes = [(1_u64, 1_000_u64), (2, 2_000)];
+			let commission = Perbill::from_rational(5_u32, 100);
+
+			let exposures = MockCommissionStakeInfo::new(
+				(validator_stash, validator_stake),
+				nominator_stakes.to_vec(),
+				commission,
+			)
+			.exposures;
+
+			Rewards::set_compound(&1, Some(Perbill::from_percent(1)));
+			// the mock compounder fails when no slippage is accepted
+			Rewards::set_compound(&2, Some(Perbill::zero()));
+
+			let payout = 1_033_221;
+			// compounding is weighed whether it succeeds or not
+			assert_eq!(
+				Rewards::process_reward_payout(&validator_stash, commission, &exposures, payout),
+				2 * MockRewardCompounder::compound_weight()
+			);
+
+			let payouts = Rewards::calculate_npos_payouts(&validator_stash, commission, &exposures, payout);
+			let payout_of = |stash: AccountId| payouts.iter().find(|(payee, _)| *payee == stash).unwrap().1;
+			let has_event = |event: RawEvent<Balance, AccountId>| {
+				System::events()
+					.iter()
+					.any(|e| e.event == Event::Rewards(event.clone()))
+			};
+
+			// reward converted to stake
+			assert_eq!(<Test as Config>::CurrencyToReward::free_balance(&1), 0);
+			assert_eq!(
+				crml_generic_asset::StakingAssetCurrency::<Test>::free_balance(&1),
+				payout_of(1)
+			);
+			assert!(has_event(RawEvent::RewardCompounded(1, payout_of(1), payout_of(1))));
+
+			// compounding failed, reward left in the stash
+			assert_eq!(<Test as Config>::CurrencyToReward::free_balance(&2), payout_of(2));
+			assert!(crml_generic_asset::StakingAssetCurrency::<Test>::free_balance(&2).is_zero());
+			assert!(has_event(RawEvent::RewardCompoundFailed(2, payout_of(2))));
+
+			// not compounding
+			assert_eq!(
+				<Test as Config>::CurrencyToReward::free_balance(&validator_stash),
+				payout_of(validator_stash)
+			);
+			assert!(!has_event(RawEvent::RewardCompoundFailed(
+				validator_stash,
+				payout_of(validator_stash)
+			)));
+
+			Rewards::remove_payee(&1);
+			assert_eq!(Rewards::compound_slippage(&1), None);
+		})
+	}
+
 	#[test]
 	fn on_end_era() {
 		ExtBuilder::default().build().execute_with(|| {
@@ -17,9 +17,9 @@
 
 use crate::{EraIndex, Exposure};
 use codec::{Decode, Encode, HasCompact};
-use frame_support::weights::Weight;
+use frame_support::{dispatch::DispatchError, weights::Weight};
 use scale_info::TypeInfo;
-use sp_runtime::{traits::AtLeast32BitUnsigned, Perbill};
+use sp_runtime::{traits::AtLeast32BitUnsigned, FixedU128, Perbill};
 use sp_std::collections::btree_map::BTreeMap;
 
 /// Something that can run payouts
@@ -73,6 +73,28 @@ impl<Balance: Copy + HasCompact + AtLeast32BitUnsigned> RewardParts<Balance> {
 	}
 }
 
+/// An estimate of staking returns, extrapolated from the current era's inflation and recent transaction fees
+#[derive(Debug, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct RewardEstimate<Balance> {
+	/// The annual base inflation rate
+	pub inflation_rate: FixedU128,
+	/// Inflation paid out per staking era
+	pub era_inflation: Balance,
+	/// Transaction fees paid out per staking era, averaged over recent eras
+	pub era_transaction_fees: Balance,
+	/// Estimated payout to stakers per staking era, after the treasury cut
+	pub era_stakers_reward: Balance,
+	/// The number of staking eras in a year (fiscal era)
+	pub eras_per_year: u32,
+	/// Estimated payout to stakers over a year
+	pub annual_stakers_reward: Balance,
+	/// The total stake the estimate is spread over
+	pub total_staked: Balance,
+	/// Estimated annual reward per unit of stake i.e. the annualised rate of return
+	/// Rewards are valued in the staking asset at the current exchange rate, zero if they can't be valued
+	pub annual_return: FixedU128,
+}
+
 /// Something which can perform reward calculation
 pub trait RewardCalculation {
 	/// The system account ID type
@@ -89,6 +111,10 @@ pub trait RewardCalculation {
 		stash: &Self::AccountId,
 		validator_commission_stake_map: &[(Self::AccountId, Perbill, Exposure<Self::AccountId, Self::Balance>)],
 	) -> Self::Balance;
+	/// Return the reward points earned by `validator` so far in the current era
+	fn validator_era_points(validator: &Self::AccountId) -> RewardPoint;
+	/// Estimate the annual staking returns given `total_staked` backing the validator set
+	fn estimate_reward(total_staked: Self::Balance) -> RewardEstimate<Self::Balance>;
 }
 
 pub trait HandlePayee {
@@ -98,12 +124,37 @@ pub trait HandlePayee {
 	/// (Re-)set the payment target for a stash account.
 	/// If payee is not different from stash, do no operations.
 	fn set_payee(stash: &Self::AccountId, payee: &Self::AccountId);
+	/// Set whether rewards for a stash account are compounded into its stake, accepting up to `max_slippage`
+	/// when converting them. `None` pays rewards to the payee instead.
+	fn set_compound(stash: &Self::AccountId, max_slippage: Option<Perbill>);
 	/// Remove the corresponding stash-payee from the look up. Do no operations if stash not found.
 	fn remove_payee(stash: &Self::AccountId);
 	/// Return the reward destination for the given stash account.
 	fn payee(stash: &Self::AccountId) -> Self::AccountId;
 }
 
+/// Something which can convert a reward into stake
+pub trait CompoundReward {
+	/// The system account ID type
+	type AccountId;
+	/// The balance type, shared by the reward and staking currencies
+	type Balance;
+
+	/// Sell `amount` of reward currency held by `stash` for the staking asset and bond the proceeds.
+	/// Fails if the sale would return less than `max_slippage` below the spot value of `amount`.
+	/// Returns the amount bonded.
+	fn compound(
+		stash: &Self::AccountId,
+		amount: Self::Balance,
+		max_slippage: Perbill,
+	) -> Result<Self::Balance, DispatchError>;
+	/// The max. weight of `compound`
+	fn compound_weight() -> Weight;
+	/// The value of `amount` of reward currency in the staking asset at the current exchange rate
+	/// Returns `None` if there's no exchange rate
+	fn staking_value(amount: Self::Balance) -> Option<Self::Balance>;
+}
+
 /// Counter for the number of "reward" points earned by a given validator.
 pub type RewardPoint = u32;
 
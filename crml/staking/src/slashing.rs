@@ -305,6 +305,7 @@ pub(crate) fn compute_slash<T: Config>(params: SlashParams<T>) -> Option<Unappli
 		others: nominators_slashed,
 		reporters: Vec::new(),
 		payout: reward_payout,
+		slash_era,
 	})
 }
 
@@ -561,6 +562,7 @@ pub(crate) fn clear_stash_metadata<T: Config>(stash: &T::AccountId) -> DispatchR
 fn do_slash<T: Config>(
 	stash: &T::AccountId,
 	value: BalanceOf<T>,
+	slash_era: EraIndex,
 	reward_payout: &mut BalanceOf<T>,
 	slashed_imbalance: &mut NegativeImbalanceOf<T>,
 ) {
@@ -574,7 +576,9 @@ fn do_slash<T: Config>(
 		None => return, // nothing to do.
 	};
 
-	let value = ledger.slash(value, T::Currency::minimum_balance());
+	// funds unbonded in or after the slashed era were at stake
+	let slashable_chunks_start = slash_era.saturating_add(T::BondingDuration::get());
+	let (value, slashed_unlocking) = ledger.slash(value, T::Currency::minimum_balance(), slashable_chunks_start);
 
 	if !value.is_zero() {
 		let (imbalance, missing) = T::Currency::slash(stash, value);
@@ -586,6 +590,7 @@ fn do_slash<T: Config>(
 		}
 
 		<Module<T>>::update_ledger(&controller, &ledger);
+		T::OnStakerSlash::on_slash(stash, ledger.active, &slashed_unlocking);
 
 		// trigger the event
 		<Module<T>>::deposit_event(super::RawEvent::Slash(stash.clone(), value));
@@ -600,12 +605,19 @@ pub(crate) fn apply_slash<T: Config>(unapplied_slash: UnappliedSlash<T::AccountI
 	do_slash::<T>(
 		&unapplied_slash.validator,
 		unapplied_slash.own,
+		unapplied_slash.slash_era,
 		&mut reward_payout,
 		&mut slashed_imbalance,
 	);
 
 	for &(ref nominator, nominator_slash) in &unapplied_slash.others {
-		do_slash::<T>(&nominator, nominator_slash, &mut reward_payout, &mut slashed_imbalance);
+		do_slash::<T>(
+			&nominator,
+			nominator_slash,
+			unapplied_slash.slash_era,
+			&mut reward_payout,
+			&mut slashed_imbalance,
+		);
 	}
 
 	pay_reporters::<T>(reward_payout, slashed_imbalance, &unapplied_slash.reporters);
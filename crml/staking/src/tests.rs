@@ -890,6 +890,304 @@ fn too_many_unbond_calls_should_not_work() {
 	})
 }
 
+#[test]
+fn staking_interface_unbond_merges_chunks() {
+	ExtBuilder::default().build_and_execute(|| {
+		let _ = Balances::make_free_balance_be(&100, 1_000);
+		assert_ok!(<Staking as StakingInterface>::bond(&100, 1_000, &101));
+		assert_eq!(Staking::bonded(&100), Some(100));
+
+		// chunks unlocking in the same era are merged
+		for _ in 0..MAX_UNLOCKING_CHUNKS + 1 {
+			assert_ok!(<Staking as StakingInterface>::unbond(&100, 10));
+		}
+		mock::start_active_era(1);
+		assert_ok!(<Staking as StakingInterface>::unbond(&100, 10));
+		assert_eq!(
+			<Staking as StakingInterface>::unlocking(&100),
+			vec![(3, 10 * (MAX_UNLOCKING_CHUNKS as Balance + 1)), (4, 10)]
+		);
+		assert_eq!(<Staking as StakingInterface>::active_stake(&100), 1_000 - 340);
+
+		mock::start_active_era(3);
+		assert_ok!(<Staking as StakingInterface>::withdraw_unbonded(&100));
+		assert_eq!(<Staking as StakingInterface>::unlocking(&100), vec![(4, 10)]);
+		assert_eq!(Staking::ledger(&100).unwrap().total, 670);
+	})
+}
+
+#[test]
+fn account_staking_info_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(Staking::unbond(Origin::signed(100), 100));
+
+		// the nominator can be looked up by stash or controller
+		let info = Staking::account_staking_info(&101).unwrap();
+		assert_eq!(Staking::account_staking_info(&100), Some(info.clone()));
+		assert_eq!(info.stash, 101);
+		assert_eq!(info.controller, 100);
+		assert_eq!(info.payee, 101);
+		assert_eq!(info.total, 500);
+		assert_eq!(info.active, 400);
+		assert_eq!(info.unlocking, vec![(3, 100)]);
+		assert_eq!(info.validator_commission, None);
+		assert_eq!(info.nominations, vec![11, 21]);
+		assert_eq_uvec!(info.exposures, vec![(11, 125), (21, 375)]);
+
+		// validators report their commission and own exposure
+		let info = Staking::account_staking_info(&11).unwrap();
+		assert_eq!(info.controller, 10);
+		assert_eq!(info.validator_commission, Some(Perbill::zero()));
+		assert!(info.nominations.is_empty());
+		assert_eq!(info.exposures, vec![(11, 1000)]);
+
+		// unbonded accounts have no staking info
+		assert_eq!(Staking::account_staking_info(&1), None);
+	})
+}
+
+#[test]
+fn validators_info_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(Staking::validate(
+			Origin::signed(30),
+			ValidatorPrefs {
+				commission: Perbill::from_percent(5)
+			}
+		));
+		// 31 validates from genesis at 0%, the increase is pending
+		assert_eq!(
+			Staking::pending_commission(31).map(|(_, commission)| commission),
+			Some(Perbill::from_percent(5))
+		);
+
+		let mut validators = Staking::validators_info();
+		validators.sort_by_key(|v| v.stash);
+		assert_eq!(
+			validators,
+			vec![
+				ValidatorInfo {
+					stash: 11,
+					commission: Perbill::zero(),
+					elected: true,
+					total_stake: 1125,
+					own_stake: 1000,
+					nominator_count: 1,
+					era_points: 0,
+				},
+				ValidatorInfo {
+					stash: 21,
+					commission: Perbill::zero(),
+					elected: true,
+					total_stake: 1375,
+					own_stake: 1000,
+					nominator_count: 1,
+					era_points: 0,
+				},
+				// 31 is a candidate but not elected, its pending commission isn't effective yet
+				ValidatorInfo {
+					stash: 31,
+					commission: Perbill::zero(),
+					elected: false,
+					total_stake: 0,
+					own_stake: 0,
+					nominator_count: 0,
+					era_points: 0,
+				},
+			]
+		);
+	})
+}
+
+fn staking_events() -> Vec<RawEvent<Balance, AccountId>> {
+	System::events()
+		.into_iter()
+		.filter_map(|r| match r.event {
+			mock::Event::Staking(inner) => Some(inner),
+			_ => None,
+		})
+		.collect()
+}
+
+#[test]
+fn commission_increase_is_delayed() {
+	ExtBuilder::default().build_and_execute(|| {
+		let commission = Perbill::from_percent(10);
+		assert_ok!(Staking::validate(Origin::signed(10), ValidatorPrefs { commission }));
+
+		// takes effect `CommissionChangeDelay` eras after the next era
+		let effective_era = 1 + CommissionChangeDelay::get();
+		assert_eq!(Staking::pending_commission(11), Some((effective_era, commission)));
+		assert_eq!(Staking::validators(11).commission, Perbill::zero());
+		assert!(staking_events().contains(&RawEvent::CommissionChangeScheduled(11, commission, effective_era)));
+
+		for era in 1..effective_era {
+			mock::start_active_era(era);
+			assert_eq!(Staking::eras_validator_prefs(era, 11).commission, Perbill::zero());
+		}
+		mock::start_active_era(effective_era);
+		assert_eq!(Staking::eras_validator_prefs(effective_era, 11).commission, commission);
+		assert_eq!(Staking::validators(11).commission, commission);
+		assert_eq!(Staking::pending_commission(11), None);
+		assert!(staking_events().contains(&RawEvent::CommissionChanged(11, commission)));
+	})
+}
+
+#[test]
+fn commission_decrease_is_immediate() {
+	ExtBuilder::default().build_and_execute(|| {
+		let high = Perbill::from_percent(20);
+		<Staking as crate::Store>::Validators::insert(11, ValidatorPrefs { commission: high });
+
+		// a pending increase is cancelled by a decrease
+		assert_ok!(Staking::validate(
+			Origin::signed(10),
+			ValidatorPrefs {
+				commission: Perbill::from_percent(30)
+			}
+		));
+		assert!(Staking::pending_commission(11).is_some());
+
+		let low = Perbill::from_percent(5);
+		assert_ok!(Staking::validate(
+			Origin::signed(10),
+			ValidatorPrefs { commission: low }
+		));
+		assert_eq!(Staking::validators(11).commission, low);
+		assert_eq!(Staking::pending_commission(11), None);
+		assert!(staking_events().contains(&RawEvent::CommissionChanged(11, low)));
+
+		mock::start_active_era(1);
+		assert_eq!(Staking::eras_validator_prefs(1, 11).commission, low);
+
+		// new validators set their commission immediately
+		assert_ok!(Staking::validate(
+			Origin::signed(40),
+			ValidatorPrefs {
+				commission: Perbill::from_percent(50)
+			}
+		));
+		assert_eq!(Staking::validators(41).commission, Perbill::from_percent(50));
+		assert_eq!(Staking::pending_commission(41), None);
+	})
+}
+
+#[test]
+fn commission_limits_are_enforced() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_noop!(
+			Staking::set_commission_limits(Origin::signed(10), Perbill::one(), Perbill::one()),
+			BadOrigin
+		);
+		assert_ok!(Staking::set_commission_limits(
+			Origin::root(),
+			Perbill::from_percent(20),
+			Perbill::from_percent(5)
+		));
+		assert_eq!(Staking::max_commission(), Perbill::from_percent(20));
+		assert_eq!(Staking::max_commission_increase(), Perbill::from_percent(5));
+		assert!(staking_events().contains(&RawEvent::SetCommissionLimits(
+			Perbill::from_percent(20),
+			Perbill::from_percent(5)
+		)));
+
+		// above the maximum commission
+		assert_noop!(
+			Staking::validate(
+				Origin::signed(40),
+				ValidatorPrefs {
+					commission: Perbill::from_percent(21)
+				}
+			),
+			Error::<Test>::CommissionTooHigh
+		);
+
+		// the increase may be up to 5% per era of delay
+		assert_noop!(
+			Staking::validate(
+				Origin::signed(10),
+				ValidatorPrefs {
+					commission: Perbill::from_percent(11)
+				}
+			),
+			Error::<Test>::CommissionIncreaseTooLarge
+		);
+		assert_ok!(Staking::validate(
+			Origin::signed(10),
+			ValidatorPrefs {
+				commission: Perbill::from_percent(10)
+			}
+		));
+
+		// pending changes are capped by a lowered maximum
+		assert_ok!(Staking::set_commission_limits(
+			Origin::root(),
+			Perbill::from_percent(8),
+			Perbill::from_percent(5)
+		));
+		mock::start_active_era(1 + CommissionChangeDelay::get());
+		assert_eq!(Staking::validators(11).commission, Perbill::from_percent(8));
+	})
+}
+
+#[test]
+fn chilled_validator_cannot_skip_commission_delay() {
+	ExtBuilder::default().build_and_execute(|| {
+		mock::start_active_era(1);
+		assert_ok!(Staking::chill(Origin::signed(10)));
+
+		// the commission in the current era is used as the baseline
+		let commission = Perbill::from_percent(10);
+		assert_ok!(Staking::validate(Origin::signed(10), ValidatorPrefs { commission }));
+		assert_eq!(Staking::validators(11).commission, Perbill::zero());
+		assert_eq!(
+			Staking::pending_commission(11),
+			Some((2 + CommissionChangeDelay::get(), commission))
+		);
+
+		// chilling drops a pending change
+		assert_ok!(Staking::chill(Origin::signed(10)));
+		assert_eq!(Staking::pending_commission(11), None);
+	})
+}
+
+#[test]
+fn validator_chilled_for_an_era_cannot_skip_commission_delay() {
+	ExtBuilder::default().validator_pool(true).build_and_execute(|| {
+		let commission = Perbill::from_percent(5);
+		<Staking as crate::Store>::Validators::insert(11, ValidatorPrefs { commission });
+		mock::start_active_era(1);
+		assert_eq!(Staking::eras_validator_prefs(1, 11).commission, commission);
+
+		// sit out era 2 entirely
+		assert_ok!(Staking::chill(Origin::signed(10)));
+		assert_eq!(Staking::last_commission(11), Some(commission));
+		mock::start_active_era(2);
+		assert!(!ErasValidatorPrefs::<Test>::contains_key(2, 11));
+
+		// the increase is delayed from the commission nominators last saw
+		assert_ok!(Staking::validate(
+			Origin::signed(10),
+			ValidatorPrefs {
+				commission: Perbill::one()
+			}
+		));
+		assert_eq!(Staking::validators(11).commission, commission);
+		assert_eq!(
+			Staking::pending_commission(11),
+			Some((3 + CommissionChangeDelay::get(), Perbill::one()))
+		);
+		assert_eq!(Staking::last_commission(11), None);
+		mock::start_active_era(3);
+		assert_eq!(Staking::eras_validator_prefs(3, 11).commission, commission);
+
+		// switching to nominating keeps the commission too
+		assert_ok!(Staking::nominate(Origin::signed(10), vec![21]));
+		assert_eq!(Staking::last_commission(11), Some(commission));
+		assert_eq!(Staking::pending_commission(11), None);
+	})
+}
+
 #[test]
 fn rebond_works() {
 	// * Should test
@@ -1530,6 +1828,46 @@ fn unbonded_balance_is_not_slashable() {
 	})
 }
 
+#[test]
+fn unbonding_funds_at_stake_are_slashed_pro_rata() {
+	ExtBuilder::default().build_and_execute(|| {
+		mock::start_active_era(1);
+		// unlocks in era 4, not at stake from era 2
+		assert_ok!(Staking::unbond(Origin::signed(10), 100));
+		mock::start_active_era(2);
+		// unlocks in era 5, at stake in era 2
+		assert_ok!(Staking::unbond(Origin::signed(10), 200));
+		ErasStakers::<Test>::insert(
+			2,
+			11,
+			Exposure {
+				total: 900,
+				own: 900,
+				others: vec![],
+			},
+		);
+
+		on_offence_now(
+			&[OffenceDetails {
+				offender: (11, Staking::eras_stakers(active_era(), 11)),
+				reporters: vec![],
+			}],
+			&[Perbill::from_percent(10)],
+		);
+
+		// the 90 slashed is shared by the active and era 5 funds
+		assert_eq!(
+			Staking::ledger(&10).unwrap(),
+			StakingLedger {
+				stash: 11,
+				total: 910,
+				active: 630,
+				unlocking: vec![UnlockChunk { value: 100, era: 4 }, UnlockChunk { value: 180, era: 5 }],
+			}
+		);
+	})
+}
+
 #[test]
 fn era_is_always_same_length() {
 	// This ensures that the sessions is always of the same length if there is no forcing no
@@ -18,7 +18,7 @@
 
 //! # Common crml types and traits
 
-use codec::Encode;
+use codec::{Decode, Encode};
 // Note: in the following traits the terms:
 // - 'token' / 'asset' / 'currency' and
 // - 'balance' / 'value' / 'amount'
@@ -28,11 +28,12 @@ use frame_support::{
 	traits::{ExistenceRequirement, Imbalance, SignedImbalance, WithdrawReasons},
 };
 pub use primitive_types::{H160, H256, U256};
+use scale_info::TypeInfo;
 use sp_runtime::{
 	traits::{AtLeast32BitUnsigned, Dispatchable, MaybeSerializeDeserialize, Saturating},
-	DispatchError, DispatchResult,
+	DispatchError, DispatchResult, RuntimeDebug,
 };
-use sp_std::{fmt::Debug, prelude::*, result};
+use sp_std::{collections::btree_map::BTreeMap, fmt::Debug, prelude::*, result};
 
 /// Tracks the status of sessions in an era
 pub trait FinalSessionTracker {
@@ -62,6 +63,32 @@ impl EthAbiCodec for u64 {
 	}
 }
 
+/// Domain separator prefixing ERC20 withdraw messages, `keccak256("ERC20_WITHDRAW")`
+/// Signatures of a withdraw message can't be replayed as another kind of message e.g. an ERC721 withdrawal
+pub const ERC20_WITHDRAW_TAG: [u8; 32] = [
+	0xd5, 0x9d, 0xb2, 0x09, 0x6a, 0x35, 0xab, 0x3e, 0x1e, 0x8b, 0x3b, 0x87, 0x0b, 0x17, 0xae, 0xd4, 0xb8, 0x98, 0x42,
+	0x10, 0xd4, 0x5e, 0xcd, 0xec, 0x4d, 0xa1, 0x4e, 0x81, 0x8d, 0xc4, 0xb6, 0x1b,
+];
+
+/// Domain separator prefixing ERC721 withdraw messages, `keccak256("ERC721_WITHDRAW")`
+pub const ERC721_WITHDRAW_TAG: [u8; 32] = [
+	0x25, 0x1b, 0x0b, 0xe0, 0x77, 0x10, 0xad, 0x95, 0xd5, 0xcf, 0xd3, 0x22, 0xc0, 0x72, 0x59, 0x60, 0xd7, 0x7e, 0xc1,
+	0x5e, 0x61, 0x29, 0xed, 0xdb, 0x26, 0xca, 0x7c, 0x53, 0xd7, 0xa6, 0x74, 0x17,
+];
+
+/// Proof that an Ethereum transaction and its receipt are included in a block
+#[derive(Clone, Default, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct EthReceiptProof {
+	/// The RLP encoded block header
+	pub header: Vec<u8>,
+	/// Index of the transaction in the block
+	pub tx_index: u64,
+	/// Merkle-Patricia proof of the transaction in the header's transactions trie
+	pub tx_proof: Vec<Vec<u8>>,
+	/// Merkle-Patricia proof of the transaction receipt in the header's receipts trie
+	pub receipt_proof: Vec<Vec<u8>>,
+}
+
 /// Reward validators for notarizations
 pub trait NotarizationRewardHandler {
 	type AccountId;
@@ -88,9 +115,39 @@ pub trait EventClaimVerifier {
 		tx_hash: &H256,
 		event_data: &[u8],
 	) -> Result<u64, DispatchError>;
+	/// Submit an event claim with a proof of the event's transaction receipt
+	/// The tx hash is derived from the proof, notaries need only agree on the proven block header
+	/// Returns a unique claim Id on success
+	fn submit_event_claim_with_proof(
+		contract_address: &H160,
+		event_signature: &H256,
+		event_data: &[u8],
+		proof: &EthReceiptProof,
+	) -> Result<u64, DispatchError>;
 	/// Generate proof of the given message
+	/// `batchable` messages may be signed in a batch if enabled, their contract must accept a Merkle proof of the
+	/// message e.g. `BensonBridge.withdrawBatched`
 	/// Returns a unique proof Id on success
-	fn generate_event_proof<M: EthAbiCodec>(message: &M) -> Result<u64, DispatchError>;
+	fn generate_event_proof<M: EthAbiCodec>(message: &M, batchable: bool) -> Result<u64, DispatchError>;
+}
+
+/// Identifies a handler of messages from Ethereum contracts e.g. a `PalletId`
+pub type MessageHandlerId = [u8; 8];
+
+/// Something that sends messages to Ethereum contracts
+pub trait EthMessageSender {
+	type AccountId;
+	/// Send `payload` to the `destination` contract on Ethereum, the message fee is paid by `sender`
+	/// Returns a unique message Id on success
+	fn send_message(sender: &Self::AccountId, destination: &H160, payload: &[u8]) -> Result<u64, DispatchError>;
+}
+
+/// Something that handles messages from Ethereum contracts
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+pub trait EthMessageHandler {
+	/// Handle a verified message `payload` from the `source` contract on Ethereum
+	/// Implementations should ignore messages for a `handler` other than their own
+	fn on_message(handler: &MessageHandlerId, message_id: u64, source: &H160, payload: &[u8]);
 }
 
 /// Something which provides an ID with authority from chain storage
@@ -123,6 +180,13 @@ pub trait RegistrationInfo {
 	fn registered_identity_count(who: &Self::AccountId) -> u32;
 }
 
+/// Provides the active governance council
+pub trait CouncilProvider {
+	type AccountId;
+	/// Return the ordered set of active council members
+	fn council() -> Vec<Self::AccountId>;
+}
+
 /// An abstraction over the accounting behaviour of a fungible, multi-currency system
 /// Currencies in the system are identifiable by a unique `CurrencyId`
 pub trait MultiCurrency {
@@ -283,3 +347,43 @@ pub trait StakingAmount {
 	/// Gets the total amount staked by all accounts
 	fn total_staked() -> Self::Balance;
 }
+
+/// Staking operations on behalf of a stash account managed by another pallet e.g. nomination pools
+/// Stashes are bonded as their own controller
+pub trait StakingInterface {
+	type AccountId;
+	type Balance;
+	/// The minimum amount a stash may have bonded
+	fn minimum_bond() -> Self::Balance;
+	/// The current era index
+	fn current_era() -> u32;
+	/// Number of eras unbonded funds remain at stake
+	fn bonding_duration() -> u32;
+	/// The amount of `stash` at stake in forthcoming eras
+	fn active_stake(stash: &Self::AccountId) -> Self::Balance;
+	/// The funds of `stash` being unbonded as (era unlocked at, amount)
+	fn unlocking(stash: &Self::AccountId) -> Vec<(u32, Self::Balance)>;
+	/// Bond `value` of `stash`'s free balance with rewards paid to `payee`
+	fn bond(stash: &Self::AccountId, value: Self::Balance, payee: &Self::AccountId) -> DispatchResult;
+	/// Bond up to `value` more of `stash`'s free balance
+	fn bond_extra(stash: &Self::AccountId, value: Self::Balance) -> DispatchResult;
+	/// Schedule `value` of `stash`'s active stake to be unlocked after the bonding duration
+	fn unbond(stash: &Self::AccountId, value: Self::Balance) -> DispatchResult;
+	/// Free any unlocked funds of `stash`
+	fn withdraw_unbonded(stash: &Self::AccountId) -> DispatchResult;
+	/// Nominate `targets` with the stake of `stash`
+	fn nominate(stash: &Self::AccountId, targets: Vec<Self::AccountId>) -> DispatchResult;
+	/// Stop `stash` nominating
+	fn chill(stash: &Self::AccountId) -> DispatchResult;
+}
+
+/// Notified when staking slashes a stash
+/// e.g. so nomination pools can slash their members' bonded and unbonding funds
+pub trait OnStakerSlash<AccountId, Balance> {
+	/// `stash` was slashed leaving `slashed_active` at stake and `slashed_unlocking` unlocking by era
+	fn on_slash(stash: &AccountId, slashed_active: Balance, slashed_unlocking: &BTreeMap<u32, Balance>);
+}
+
+impl<AccountId, Balance> OnStakerSlash<AccountId, Balance> for () {
+	fn on_slash(_: &AccountId, _: Balance, _: &BTreeMap<u32, Balance>) {}
+}
@@ -0,0 +1,522 @@
+// Copyright (C) 2020-2021 Parity Technologies (UK) Ltd. & Annie Lai Investments Ltd
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Ethereum calldata for submitting Ethy event proofs to the Benson bridge contracts
+
+use benson_primitives::eth::{crypto::AuthoritySignature, EventId, ValidatorSetId, VersionedEventProof};
+use crml_support::ERC20_WITHDRAW_TAG;
+use serde::{Deserialize, Serialize};
+use sp_core::{Bytes, H256};
+
+/// `BensonBridge.withdraw`, submitted by the withdrawal beneficiary with the event id as nonce
+pub const WITHDRAW_SIGNATURE: &[u8] = b"withdraw(address,uint256,uint256,uint8[],bytes32[],bytes32[])";
+/// `BensonBridge.withdrawBatched`, a withdrawal proven by a Merkle branch of a signed batch
+pub const WITHDRAW_BATCHED_SIGNATURE: &[u8] =
+	b"withdrawBatched(address,uint256,address,uint256,bytes32[],uint8[],bytes32[],bytes32[])";
+/// `setValidators` of the Benson bridge contracts
+pub const SET_VALIDATORS_SIGNATURE: &[u8] = b"setValidators(address[],uint256,uint256,uint8[],bytes32[],bytes32[])";
+
+/// Number of 32-byte words in a withdraw message (tag, token, amount, beneficiary, validator set id, event id)
+const WITHDRAW_MESSAGE_WORDS: usize = 6;
+
+/// The kind of message proven by an event proof
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum ProofKind {
+	/// An ERC20 withdrawal
+	Withdraw,
+	/// A validator set change
+	SetValidators,
+}
+
+/// The message an event proof was requested for
+#[derive(Debug, PartialEq, Clone)]
+pub enum ProvenMessage {
+	/// An opaque signing request message `abi.encode(param0,.., paramN, validator_set_id, event_id)`
+	Opaque(Vec<u8>),
+	/// A validator set change to the validators with these Ethereum addresses
+	SetValidators {
+		/// The Ethereum addresses of the next validators
+		validators: Vec<[u8; 20]>,
+		/// The next validator set id
+		validator_set_id: ValidatorSetId,
+	},
+}
+
+/// An event proof prepared for submission to Ethereum
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EventProofCalldata {
+	/// The proven event
+	pub event_id: EventId,
+	/// The validator set which signed the proof
+	pub validator_set_id: ValidatorSetId,
+	/// Signature recovery ids + 27, ordered by validator. `0` where a validator didn't sign
+	pub v: Vec<u8>,
+	/// Signature `r` values ordered by validator. Zero where a validator didn't sign
+	pub r: Vec<H256>,
+	/// Signature `s` values ordered by validator. Zero where a validator didn't sign
+	pub s: Vec<H256>,
+	/// Merkle branch of the message in its signed batch. Empty if the message was signed individually
+	pub branch: Vec<H256>,
+	/// The kind of proof, `None` if unknown
+	pub kind: Option<ProofKind>,
+	/// ABI encoded calldata to submit the proof, `None` if the kind of proof is unknown
+	pub calldata: Option<Bytes>,
+}
+
+impl EventProofCalldata {
+	/// Prepare `proof` of `message` for submission to Ethereum
+	pub fn new(proof: &VersionedEventProof, message: Option<&ProvenMessage>) -> Self {
+		let (event_id, validator_set_id, signatures, branch) = match proof {
+			VersionedEventProof::V1(proof) => (proof.event_id, proof.validator_set_id, &proof.signatures, None),
+			VersionedEventProof::V2(proof) => (
+				proof.event_id,
+				proof.validator_set_id,
+				&proof.signatures,
+				Some(proof.branch.as_slice()),
+			),
+		};
+		let (v, r, s) = split_signatures(signatures);
+		let kind_and_calldata = match (message, branch) {
+			(Some(ProvenMessage::Opaque(message)), None) => {
+				withdraw_calldata(message, event_id, &v, &r, &s).map(|calldata| (ProofKind::Withdraw, calldata))
+			}
+			(Some(ProvenMessage::Opaque(message)), Some(branch)) => {
+				withdraw_batched_calldata(message, event_id, branch, &v, &r, &s)
+					.map(|calldata| (ProofKind::Withdraw, calldata))
+			}
+			(
+				Some(ProvenMessage::SetValidators {
+					validators,
+					validator_set_id,
+				}),
+				None,
+			) => Some((
+				ProofKind::SetValidators,
+				set_validators_calldata(validators, *validator_set_id, event_id, &v, &r, &s),
+			)),
+			// validator set changes are never batched
+			_ => None,
+		};
+		let (kind, calldata) = match kind_and_calldata {
+			Some((kind, calldata)) => (Some(kind), Some(calldata.into())),
+			None => (None, None),
+		};
+
+		Self {
+			event_id,
+			validator_set_id,
+			v,
+			r: r.into_iter().map(H256::from).collect(),
+			s: s.into_iter().map(H256::from).collect(),
+			branch: branch.unwrap_or_default().iter().copied().map(H256::from).collect(),
+			kind,
+			calldata,
+		}
+	}
+}
+
+/// Split validator ordered `signatures` into the `v`, `r`, `s` arrays expected by the bridge contracts
+/// Omitted (default) signatures are left zeroed
+pub fn split_signatures(signatures: &[AuthoritySignature]) -> (Vec<u8>, Vec<[u8; 32]>, Vec<[u8; 32]>) {
+	let empty_sig = AuthoritySignature::default();
+	let mut v = Vec::with_capacity(signatures.len());
+	let mut r = Vec::with_capacity(signatures.len());
+	let mut s = Vec::with_capacity(signatures.len());
+	for signature in signatures {
+		if *signature == empty_sig {
+			v.push(0);
+			r.push([0_u8; 32]);
+			s.push([0_u8; 32]);
+			continue;
+		}
+		// 65 bytes: r (32) ‖ s (32) ‖ recovery id (1)
+		let signature: &[u8] = signature.as_ref();
+		let mut r_ = [0_u8; 32];
+		let mut s_ = [0_u8; 32];
+		r_.copy_from_slice(&signature[..32]);
+		s_.copy_from_slice(&signature[32..64]);
+		v.push(signature[64] + 27);
+		r.push(r_);
+		s.push(s_);
+	}
+
+	(v, r, s)
+}
+
+/// Return the (token, amount, beneficiary, event id) words if `message` is an ERC20 withdraw message for `event_id`
+/// Messages are classified by their tag, other messages of the same shape e.g. ERC721 withdrawals aren't matched
+/// i.e. `abi.encode(bytes32 tag, address token, uint256 amount, address beneficiary, uint256 validator_set_id, uint256 event_id)`
+fn withdraw_params(message: &[u8], event_id: EventId) -> Option<[Token; 4]> {
+	if message.len() != WITHDRAW_MESSAGE_WORDS * 32 || message[..32] != ERC20_WITHDRAW_TAG {
+		return None;
+	}
+	let words: Vec<[u8; 32]> = message.chunks(32).map(word).collect();
+	let is_address = |word: &[u8; 32]| word[..12].iter().all(|b| *b == 0);
+	if !is_address(&words[1]) || !is_address(&words[3]) || words[5] != uint(event_id) {
+		return None;
+	}
+
+	Some([
+		Token::Word(words[1]),
+		Token::Word(words[2]),
+		Token::Word(words[3]),
+		Token::Word(words[5]),
+	])
+}
+
+/// Return `withdraw` calldata if `message` is a withdraw message for `event_id`
+/// The beneficiary isn't a parameter, the contract pays the sender
+fn withdraw_calldata(message: &[u8], event_id: EventId, v: &[u8], r: &[[u8; 32]], s: &[[u8; 32]]) -> Option<Vec<u8>> {
+	let [token, amount, _beneficiary, event_id] = withdraw_params(message, event_id)?;
+	let mut tokens = vec![token, amount, event_id];
+	tokens.extend(signature_tokens(v, r, s));
+
+	Some(encode_call(WITHDRAW_SIGNATURE, &tokens))
+}
+
+/// Return `withdrawBatched` calldata if `message` is a withdraw message for `event_id` proven by `branch`
+fn withdraw_batched_calldata(
+	message: &[u8],
+	event_id: EventId,
+	branch: &[[u8; 32]],
+	v: &[u8],
+	r: &[[u8; 32]],
+	s: &[[u8; 32]],
+) -> Option<Vec<u8>> {
+	let mut tokens = withdraw_params(message, event_id)?.to_vec();
+	tokens.push(Token::Array(branch.to_vec()));
+	tokens.extend(signature_tokens(v, r, s));
+
+	Some(encode_call(WITHDRAW_BATCHED_SIGNATURE, &tokens))
+}
+
+/// Return the `v`, `r`, `s` signature array tokens
+fn signature_tokens(v: &[u8], r: &[[u8; 32]], s: &[[u8; 32]]) -> [Token; 3] {
+	[
+		Token::Array(v.iter().map(|v| uint(*v as u64)).collect()),
+		Token::Array(r.to_vec()),
+		Token::Array(s.to_vec()),
+	]
+}
+
+/// Return `setValidators` calldata for a validator set change to `validators`, proven by event `event_id`
+/// The contract verifies the signed message: `abi.encode(validators, validator_set_id, active_validator_set_id, event_id)`
+fn set_validators_calldata(
+	validators: &[[u8; 20]],
+	validator_set_id: ValidatorSetId,
+	event_id: EventId,
+	v: &[u8],
+	r: &[[u8; 32]],
+	s: &[[u8; 32]],
+) -> Vec<u8> {
+	let mut tokens = vec![
+		Token::Array(validators.iter().map(address).collect()),
+		Token::Word(uint(validator_set_id)),
+		Token::Word(uint(event_id)),
+	];
+	tokens.extend(signature_tokens(v, r, s));
+
+	encode_call(SET_VALIDATORS_SIGNATURE, &tokens)
+}
+
+/// An ABI value, either a static 32-byte word or a dynamic array of words
+#[derive(Clone)]
+enum Token {
+	Word([u8; 32]),
+	Array(Vec<[u8; 32]>),
+}
+
+/// ABI encode a call to the function with `signature` e.g. `transfer(address,uint256)` with `tokens`
+/// https://docs.soliditylang.org/en/develop/abi-spec.html#function-selector-and-argument-encoding
+fn encode_call(signature: &[u8], tokens: &[Token]) -> Vec<u8> {
+	let mut head = sp_core::keccak_256(signature)[..4].to_vec();
+	let mut tail = Vec::<u8>::new();
+	let head_size = tokens.len() * 32;
+	for token in tokens {
+		match token {
+			Token::Word(word) => head.extend_from_slice(word),
+			Token::Array(words) => {
+				// offset of the array data from the start of the arguments
+				head.extend_from_slice(&uint((head_size + tail.len()) as u64));
+				tail.extend_from_slice(&uint(words.len() as u64));
+				for word in words {
+					tail.extend_from_slice(word);
+				}
+			}
+		}
+	}
+	head.extend(tail);
+
+	head
+}
+
+/// Copy a 32 byte chunk into a word
+fn word(chunk: &[u8]) -> [u8; 32] {
+	let mut word = [0_u8; 32];
+	word.copy_from_slice(chunk);
+	word
+}
+
+/// ABI encode an unsigned integer
+fn uint(value: u64) -> [u8; 32] {
+	let mut word = [0_u8; 32];
+	word[24..].copy_from_slice(&value.to_be_bytes());
+	word
+}
+
+/// ABI encode an address
+fn address(address: &[u8; 20]) -> [u8; 32] {
+	let mut word = [0_u8; 32];
+	word[12..].copy_from_slice(address);
+	word
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use benson_primitives::eth::{BatchEventProof, EventProof};
+	use crml_support::ERC721_WITHDRAW_TAG;
+	use sp_core::{ecdsa, Pair};
+
+	fn signatures() -> Vec<AuthoritySignature> {
+		let alice = ecdsa::Pair::from_string("//Alice", None).unwrap();
+		let digest = sp_core::keccak_256(b"hello world");
+		vec![AuthoritySignature::default(), alice.sign_prehashed(&digest).into()]
+	}
+
+	fn event_proof(event_id: EventId) -> VersionedEventProof {
+		VersionedEventProof::V1(EventProof {
+			digest: [0_u8; 32],
+			event_id,
+			validator_set_id: 3,
+			signatures: signatures(),
+			block: [0_u8; 32],
+			tag: None,
+		})
+	}
+
+	fn batch_event_proof(event_id: EventId, branch: Vec<[u8; 32]>) -> VersionedEventProof {
+		VersionedEventProof::V2(BatchEventProof {
+			root: [0_u8; 32],
+			event_id,
+			batch_id: 10,
+			validator_set_id: 3,
+			signatures: signatures(),
+			leaf: [0_u8; 32],
+			branch,
+			block: [0_u8; 32],
+			tag: None,
+		})
+	}
+
+	#[test]
+	fn function_selectors() {
+		assert_eq!(hex::encode(&sp_core::keccak_256(WITHDRAW_SIGNATURE)[..4]), "3f387b66");
+		assert_eq!(
+			hex::encode(&sp_core::keccak_256(WITHDRAW_BATCHED_SIGNATURE)[..4]),
+			"525a09ab"
+		);
+		assert_eq!(
+			hex::encode(&sp_core::keccak_256(SET_VALIDATORS_SIGNATURE)[..4]),
+			"7f32e7e1"
+		);
+	}
+
+	#[test]
+	fn split_signatures_aligns_with_validators() {
+		let signatures = signatures();
+		let (v, r, s) = split_signatures(&signatures);
+		let signature: &[u8] = signatures[1].as_ref();
+
+		assert_eq!(v, vec![0, signature[64] + 27]);
+		assert_eq!(r, vec![[0_u8; 32], word(&signature[..32])]);
+		assert_eq!(s, vec![[0_u8; 32], word(&signature[32..64])]);
+	}
+
+	#[test]
+	fn encode_call_matches_solidity() {
+		// `abi.encodeWithSignature("setValidators(address[],uint256,uint256,uint8[],bytes32[],bytes32[])", [0x11..], 5, 9, [27], [0x22..], [0x33..])`
+		let calldata = set_validators_calldata(&[[0x11_u8; 20]], 5, 9, &[27], &[[0x22_u8; 32]], &[[0x33_u8; 32]]);
+		assert_eq!(
+			hex::encode(calldata),
+			[
+				"7f32e7e1",
+				"00000000000000000000000000000000000000000000000000000000000000c0",
+				"0000000000000000000000000000000000000000000000000000000000000005",
+				"0000000000000000000000000000000000000000000000000000000000000009",
+				"0000000000000000000000000000000000000000000000000000000000000100",
+				"0000000000000000000000000000000000000000000000000000000000000140",
+				"0000000000000000000000000000000000000000000000000000000000000180",
+				"0000000000000000000000000000000000000000000000000000000000000001",
+				"0000000000000000000000001111111111111111111111111111111111111111",
+				"0000000000000000000000000000000000000000000000000000000000000001",
+				"000000000000000000000000000000000000000000000000000000000000001b",
+				"0000000000000000000000000000000000000000000000000000000000000001",
+				"2222222222222222222222222222222222222222222222222222222222222222",
+				"0000000000000000000000000000000000000000000000000000000000000001",
+				"3333333333333333333333333333333333333333333333333333333333333333",
+			]
+			.concat()
+		);
+	}
+
+	#[test]
+	fn withdraw_calldata_matches_solidity() {
+		let event_id = 7;
+		// `abi.encode(keccak256("ERC20_WITHDRAW"), 0x44.., 1000, 0x55.., 3, 7)`
+		let message = [
+			ERC20_WITHDRAW_TAG,
+			address(&[0x44_u8; 20]),
+			uint(1_000),
+			address(&[0x55_u8; 20]),
+			uint(3),
+			uint(event_id),
+		]
+		.concat();
+		let proof = event_proof(event_id);
+		let result = EventProofCalldata::new(&proof, Some(&ProvenMessage::Opaque(message)));
+		assert_eq!(result.kind, Some(ProofKind::Withdraw));
+		assert!(result.branch.is_empty());
+
+		let signatures = signatures();
+		let signature: &[u8] = signatures[1].as_ref();
+		// `abi.encodeWithSignature("withdraw(address,uint256,uint256,uint8[],bytes32[],bytes32[])", ..)`
+		let expected = [
+			"3f387b66",
+			"0000000000000000000000004444444444444444444444444444444444444444",
+			"00000000000000000000000000000000000000000000000000000000000003e8",
+			"0000000000000000000000000000000000000000000000000000000000000007",
+			"00000000000000000000000000000000000000000000000000000000000000c0",
+			"0000000000000000000000000000000000000000000000000000000000000120",
+			"0000000000000000000000000000000000000000000000000000000000000180",
+			// v
+			"0000000000000000000000000000000000000000000000000000000000000002",
+			"0000000000000000000000000000000000000000000000000000000000000000",
+			&hex::encode(uint(signature[64] as u64 + 27)),
+			// r
+			"0000000000000000000000000000000000000000000000000000000000000002",
+			"0000000000000000000000000000000000000000000000000000000000000000",
+			&hex::encode(&signature[..32]),
+			// s
+			"0000000000000000000000000000000000000000000000000000000000000002",
+			"0000000000000000000000000000000000000000000000000000000000000000",
+			&hex::encode(&signature[32..64]),
+		]
+		.concat();
+		assert_eq!(result.calldata.map(|c| hex::encode(c.0)), Some(expected));
+	}
+
+	#[test]
+	fn withdraw_batched_calldata_matches_solidity() {
+		let event_id = 7;
+		// `abi.encode(keccak256("ERC20_WITHDRAW"), 0x44.., 1000, 0x55.., 3, 7)`
+		let message = [
+			ERC20_WITHDRAW_TAG,
+			address(&[0x44_u8; 20]),
+			uint(1_000),
+			address(&[0x55_u8; 20]),
+			uint(3),
+			uint(event_id),
+		]
+		.concat();
+		let proof = batch_event_proof(event_id, vec![[0x66_u8; 32]]);
+		let result = EventProofCalldata::new(&proof, Some(&ProvenMessage::Opaque(message)));
+		assert_eq!(result.kind, Some(ProofKind::Withdraw));
+		assert_eq!(result.branch, vec![H256::repeat_byte(0x66)]);
+
+		let signatures = signatures();
+		let signature: &[u8] = signatures[1].as_ref();
+		// `abi.encodeWithSignature("withdrawBatched(address,uint256,address,uint256,bytes32[],uint8[],bytes32[],bytes32[])", ..)`
+		let expected = [
+			"525a09ab",
+			"0000000000000000000000004444444444444444444444444444444444444444",
+			"00000000000000000000000000000000000000000000000000000000000003e8",
+			"0000000000000000000000005555555555555555555555555555555555555555",
+			"0000000000000000000000000000000000000000000000000000000000000007",
+			"0000000000000000000000000000000000000000000000000000000000000100",
+			"0000000000000000000000000000000000000000000000000000000000000140",
+			"00000000000000000000000000000000000000000000000000000000000001a0",
+			"0000000000000000000000000000000000000000000000000000000000000200",
+			// branch
+			"0000000000000000000000000000000000000000000000000000000000000001",
+			"6666666666666666666666666666666666666666666666666666666666666666",
+			// v
+			"0000000000000000000000000000000000000000000000000000000000000002",
+			"0000000000000000000000000000000000000000000000000000000000000000",
+			&hex::encode(uint(signature[64] as u64 + 27)),
+			// r
+			"0000000000000000000000000000000000000000000000000000000000000002",
+			"0000000000000000000000000000000000000000000000000000000000000000",
+			&hex::encode(&signature[..32]),
+			// s
+			"0000000000000000000000000000000000000000000000000000000000000002",
+			"0000000000000000000000000000000000000000000000000000000000000000",
+			&hex::encode(&signature[32..64]),
+		]
+		.concat();
+		assert_eq!(result.calldata.map(|c| hex::encode(c.0)), Some(expected));
+
+		// validator set changes are never batched
+		let result = EventProofCalldata::new(
+			&proof,
+			Some(&ProvenMessage::SetValidators {
+				validators: vec![[0x11_u8; 20]],
+				validator_set_id: 4,
+			}),
+		);
+		assert_eq!(result.calldata, None);
+	}
+
+	#[test]
+	fn unknown_messages_have_no_calldata() {
+		let proof = event_proof(7);
+		// message for another event
+		let message = [
+			ERC20_WITHDRAW_TAG,
+			address(&[0x44_u8; 20]),
+			uint(1_000),
+			address(&[0x55_u8; 20]),
+			uint(3),
+			uint(8),
+		]
+		.concat();
+		let result = EventProofCalldata::new(&proof, Some(&ProvenMessage::Opaque(message)));
+		assert_eq!(result.kind, None);
+		assert_eq!(result.calldata, None);
+
+		// an ERC721 withdrawal has the same shape but isn't an ERC20 withdrawal
+		let message = [
+			ERC721_WITHDRAW_TAG,
+			address(&[0x44_u8; 20]),
+			uint(1_000),
+			address(&[0x55_u8; 20]),
+			uint(3),
+			uint(7),
+		]
+		.concat();
+		let result = EventProofCalldata::new(&proof, Some(&ProvenMessage::Opaque(message)));
+		assert_eq!(result.kind, None);
+		assert_eq!(result.calldata, None);
+
+		// v,r,s are always available
+		let result = EventProofCalldata::new(&proof, None);
+		assert_eq!(result.calldata, None);
+		assert_eq!(result.v.len(), 2);
+		assert_eq!(result.validator_set_id, 3);
+	}
+}
@@ -18,18 +18,27 @@
 
 #![warn(missing_docs)]
 
-use benson_primitives::eth::{EventId, ETHY_ENGINE_ID};
-use ethy_gadget::notification::EthyEventProofStream;
+use benson_primitives::eth::{crypto::AuthorityId, ConsensusLog, EventId, VersionedEventProof, ETHY_ENGINE_ID};
+use codec::Decode;
+use ethy_gadget::{notification::EthyEventProofStream, EthyEcdsaToEthereum};
 use futures::{FutureExt, SinkExt, StreamExt};
 use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
 use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
 use log::warn;
 use sc_client_api::backend::AuxStore;
-use std::sync::Arc;
-
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+	generic::{BlockId, OpaqueDigestItemId},
+	traits::{Block as BlockT, Convert, Header},
+};
+use std::{marker::PhantomData, sync::Arc};
+
+pub mod calldata;
 mod notification;
 
+use calldata::{EventProofCalldata, ProvenMessage};
+
 /// Provides RPC methods for interacting with Ethy.
 #[allow(clippy::needless_return)]
 #[rpc]
@@ -52,16 +61,22 @@ pub trait EthyApi<Notification> {
 	/// Query a proof for a known event Id. Returns `null` if missing
 	#[rpc(name = "ethy_getEventProof")]
 	fn get_event_proof(&self, event_id: EventId) -> Result<Option<Notification>>;
+
+	/// Query a proof for a known event Id prepared for submission to Ethereum.
+	/// Returns validator ordered v/r/s signature arrays and calldata for known proof types. `null` if missing
+	#[rpc(name = "ethy_getEventProofCalldata")]
+	fn get_event_proof_calldata(&self, event_id: EventId) -> Result<Option<EventProofCalldata>>;
 }
 
 /// Implements the EthyApi RPC trait for interacting with Ethy.
-pub struct EthyRpcHandler<BE> {
+pub struct EthyRpcHandler<BE, B> {
 	event_proof_stream: EthyEventProofStream,
 	manager: SubscriptionManager,
 	backend: Arc<BE>,
+	_phantom: PhantomData<B>,
 }
 
-impl<BE> EthyRpcHandler<BE> {
+impl<BE, B> EthyRpcHandler<BE, B> {
 	/// Creates a new EthyRpcHandler instance.
 	pub fn new<E>(event_proof_stream: EthyEventProofStream, executor: E, backend: Arc<BE>) -> Self
 	where
@@ -72,13 +87,65 @@ impl<BE> EthyRpcHandler<BE> {
 			event_proof_stream,
 			manager,
 			backend,
+			_phantom: PhantomData,
 		}
 	}
 }
 
-impl<BE> EthyApi<notification::EventProofResponse> for EthyRpcHandler<BE>
+impl<BE, B> EthyRpcHandler<BE, B>
 where
-	BE: Send + Sync + 'static + AuxStore,
+	BE: AuxStore + HeaderBackend<B>,
+	B: BlockT,
+{
+	/// Return the stored proof for `event_id`, if any
+	fn event_proof(&self, event_id: EventId) -> Option<VersionedEventProof> {
+		let encoded_proof = self
+			.backend
+			.get_aux([&ETHY_ENGINE_ID[..], &event_id.to_be_bytes()[..]].concat().as_ref())
+			.ok()
+			.flatten()?;
+		VersionedEventProof::decode(&mut &encoded_proof[..]).ok()
+	}
+
+	/// Return the message `proof` was requested for from the digest of the requesting block
+	fn proven_message(&self, proof: &VersionedEventProof) -> Option<ProvenMessage> {
+		let (proof_event_id, block) = match proof {
+			VersionedEventProof::V1(proof) => (proof.event_id, proof.block),
+			VersionedEventProof::V2(proof) => (proof.event_id, proof.block),
+		};
+		let block_hash = B::Hash::decode(&mut &block[..]).ok()?;
+		let header = self.backend.header(BlockId::hash(block_hash)).ok().flatten()?;
+		header.digest().logs().iter().find_map(|log| {
+			match log.try_to::<ConsensusLog<AuthorityId>>(OpaqueDigestItemId::Consensus(&ETHY_ENGINE_ID)) {
+				Some(ConsensusLog::OpaqueSigningRequest((message, event_id))) if event_id == proof_event_id => {
+					Some(ProvenMessage::Opaque(message))
+				}
+				Some(ConsensusLog::BatchSigningRequest((messages, _batch_id))) => messages
+					.into_iter()
+					.find(|(event_id, _)| *event_id == proof_event_id)
+					.map(|(_, message)| ProvenMessage::Opaque(message)),
+				Some(ConsensusLog::PendingAuthoritiesChange((validator_set, event_id)))
+					if event_id == proof_event_id =>
+				{
+					Some(ProvenMessage::SetValidators {
+						validators: validator_set
+							.validators
+							.into_iter()
+							.map(EthyEcdsaToEthereum::convert)
+							.collect(),
+						validator_set_id: validator_set.id,
+					})
+				}
+				_ => None,
+			}
+		})
+	}
+}
+
+impl<BE, B> EthyApi<notification::EventProofResponse> for EthyRpcHandler<BE, B>
+where
+	BE: Send + Sync + 'static + AuxStore + HeaderBackend<B>,
+	B: BlockT,
 {
 	type Metadata = sc_rpc::Metadata;
 
@@ -118,4 +185,11 @@ where
 		}
 		Ok(None)
 	}
+
+	fn get_event_proof_calldata(&self, event_id: EventId) -> jsonrpc_core::Result<Option<EventProofCalldata>> {
+		Ok(self.event_proof(event_id).map(|proof| {
+			let message = self.proven_message(&proof);
+			EventProofCalldata::new(&proof, message.as_ref())
+		}))
+	}
 }
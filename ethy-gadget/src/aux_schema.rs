@@ -0,0 +1,176 @@
+// Copyright (C) 2020-2021 Parity Technologies (UK) Ltd. & Annie Lai Investments Ltd
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Schema for ETHY worker state persisted in the aux DB
+//!
+//! Event proofs are stored separately under `ETHY_ENGINE_ID ++ event_id`
+//! Validator sets are stored by id under `VALIDATOR_SET_KEY ++ validator_set_id`
+
+use codec::{Decode, Encode};
+use log::warn;
+
+use sc_client_api::backend::AuxStore;
+use sp_blockchain::Result as ClientResult;
+use sp_runtime::traits::{Block, NumberFor};
+
+use crate::witness_record::WitnessRecordSnapshot;
+use benson_primitives::eth::{crypto::AuthorityId as Public, ValidatorSet, ValidatorSetId};
+
+/// Aux DB key of the last finalized block handled by the worker
+const BEST_BLOCK_KEY: &[u8] = b"ethy_best_block";
+/// Aux DB key of the in-flight witnesses
+const WITNESS_RECORD_KEY: &[u8] = b"ethy_witness_record";
+/// Aux DB key prefix of validator sets active while the worker ran
+const VALIDATOR_SET_KEY: &[u8] = b"ethy_validator_set";
+
+fn validator_set_key(validator_set_id: ValidatorSetId) -> Vec<u8> {
+	[VALIDATOR_SET_KEY, &validator_set_id.encode()[..]].concat()
+}
+
+fn load_decode<BE: AuxStore, T: Decode>(backend: &BE, key: &[u8]) -> Option<T> {
+	match backend.get_aux(key) {
+		Ok(Some(encoded)) => T::decode(&mut &encoded[..])
+			.map_err(|err| warn!(target: "ethy", "💎 failed decoding aux entry: {:?}, {:?}", key, err))
+			.ok(),
+		Ok(None) => None,
+		Err(err) => {
+			warn!(target: "ethy", "💎 failed loading aux entry: {:?}, {:?}", key, err);
+			None
+		}
+	}
+}
+
+/// Load the last finalized block handled by the worker, if any
+pub(crate) fn load_best_block<B: Block, BE: AuxStore>(backend: &BE) -> Option<NumberFor<B>> {
+	load_decode(backend, BEST_BLOCK_KEY)
+}
+
+/// Load the in-flight witnesses, if any
+pub(crate) fn load_witness_record<BE: AuxStore>(backend: &BE) -> Option<WitnessRecordSnapshot> {
+	load_decode(backend, WITNESS_RECORD_KEY)
+}
+
+/// Load the validator set with `validator_set_id`, if it was active while the worker ran
+pub(crate) fn load_validator_set<BE: AuxStore>(
+	backend: &BE,
+	validator_set_id: ValidatorSetId,
+) -> Option<ValidatorSet<Public>> {
+	load_decode(backend, &validator_set_key(validator_set_id))
+}
+
+/// Write an active validator set, proofs requested while it was active are verified against it later
+/// The runtime state of the blocks it was active at may have been pruned by then
+pub(crate) fn write_validator_set<BE: AuxStore>(
+	backend: &BE,
+	validator_set: &ValidatorSet<Public>,
+) -> ClientResult<()> {
+	backend.insert_aux(
+		&[(
+			validator_set_key(validator_set.id).as_slice(),
+			validator_set.encode().as_slice(),
+		)],
+		&[],
+	)
+}
+
+/// Write the worker state i.e. the last finalized block handled and in-flight witnesses
+pub(crate) fn write_worker_state<B: Block, BE: AuxStore>(
+	backend: &BE,
+	best_block: NumberFor<B>,
+	witness_record: &WitnessRecordSnapshot,
+) -> ClientResult<()> {
+	backend.insert_aux(
+		&[
+			(BEST_BLOCK_KEY, best_block.encode().as_slice()),
+			(WITNESS_RECORD_KEY, witness_record.encode().as_slice()),
+		],
+		&[],
+	)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use parking_lot::RwLock;
+	use sp_runtime::testing::{Block as RawBlock, ExtrinsicWrapper};
+	use std::collections::HashMap;
+
+	type Block = RawBlock<ExtrinsicWrapper<u64>>;
+
+	#[derive(Default)]
+	struct MockAuxStore(RwLock<HashMap<Vec<u8>, Vec<u8>>>);
+
+	impl AuxStore for MockAuxStore {
+		fn insert_aux<
+			'a,
+			'b: 'a,
+			'c: 'a,
+			I: IntoIterator<Item = &'a (&'c [u8], &'c [u8])>,
+			D: IntoIterator<Item = &'a &'b [u8]>,
+		>(
+			&self,
+			insert: I,
+			delete: D,
+		) -> ClientResult<()> {
+			let mut store = self.0.write();
+			for (k, v) in insert {
+				store.insert(k.to_vec(), v.to_vec());
+			}
+			for k in delete {
+				store.remove(*k);
+			}
+			Ok(())
+		}
+
+		fn get_aux(&self, key: &[u8]) -> ClientResult<Option<Vec<u8>>> {
+			Ok(self.0.read().get(key).cloned())
+		}
+	}
+
+	#[test]
+	fn worker_state_roundtrip() {
+		let backend = MockAuxStore::default();
+		assert_eq!(load_best_block::<Block, _>(&backend), None);
+		assert_eq!(load_witness_record(&backend), None);
+
+		let snapshot = WitnessRecordSnapshot {
+			validator_set_id: 5,
+			..Default::default()
+		};
+		assert!(write_worker_state::<Block, _>(&backend, 123, &snapshot).is_ok());
+
+		assert_eq!(load_best_block::<Block, _>(&backend), Some(123));
+		assert_eq!(load_witness_record(&backend), Some(snapshot));
+	}
+
+	#[test]
+	fn validator_set_roundtrip() {
+		use sp_core::{ecdsa, Pair};
+
+		let backend = MockAuxStore::default();
+		let validator_set = ValidatorSet {
+			validators: ["//Alice", "//Bob"]
+				.iter()
+				.map(|s| Public::from(ecdsa::Pair::from_string(s, None).unwrap().public()))
+				.collect(),
+			id: 5,
+		};
+		assert!(write_validator_set(&backend, &validator_set).is_ok());
+
+		assert_eq!(load_validator_set(&backend, 5), Some(validator_set));
+		assert_eq!(load_validator_set(&backend, 4), None);
+	}
+}
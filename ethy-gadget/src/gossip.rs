@@ -22,12 +22,12 @@ use std::{
 	time::{Duration, Instant},
 };
 
-use sc_network::PeerId;
+use sc_network::{ObservedRole, PeerId};
 use sc_network_gossip::{MessageIntent, ValidationResult, Validator, ValidatorContext};
 
 use sp_runtime::traits::{Block, Hash, Header};
 
-use benson_primitives::eth::{crypto::AuthorityId as Public, EventId, Witness};
+use benson_primitives::eth::{crypto::AuthorityId as Public, EventId, ValidatorSetId, Witness};
 
 use crate::keystore::EthyKeystore;
 
@@ -55,13 +55,16 @@ where
 	B: Block,
 {
 	topic: B::Hash,
-	known_votes: RwLock<BTreeMap<EventId, Vec<Public>>>,
+	/// Witnessed (authority, digest) pairs by (event, validator set), sorted by authority
+	known_votes: RwLock<BTreeMap<(EventId, ValidatorSetId), Vec<(Public, [u8; 32])>>>,
 	/// Pruned list of recently completed events
 	complete_events: RwLock<VecDeque<EventId>>,
 	/// Public (ECDSA session) keys of active ethy validators
 	active_validators: RwLock<Vec<Public>>,
 	/// Scheduled time for re-broadcasting event witnesses
 	next_rebroadcast: Mutex<Instant>,
+	/// Connected (non-light) peers, these may serve proof requests
+	peers: RwLock<Vec<PeerId>>,
 }
 
 impl<B> GossipValidator<B>
@@ -75,13 +78,14 @@ where
 			active_validators: RwLock::new(active_validators),
 			complete_events: RwLock::new(Default::default()),
 			next_rebroadcast: Mutex::new(Instant::now() + REBROADCAST_AFTER),
+			peers: RwLock::new(Vec::new()),
 		}
 	}
 
 	/// Make a vote for an event as complete
 	pub fn mark_complete(&self, event_id: EventId) {
 		let mut known_votes = self.known_votes.write();
-		known_votes.remove(&event_id);
+		known_votes.retain(|(known_event_id, _), _| *known_event_id != event_id);
 		let mut complete_events = self.complete_events.write();
 		if complete_events.len() > MAX_COMPLETE_EVENT_CACHE {
 			complete_events.pop_front();
@@ -96,6 +100,23 @@ where
 		}
 	}
 
+	/// Note `votes` (authority, digest) have been witnessed for `event_id` by `validator_set_id`
+	/// e.g. when restoring witnesses after a restart
+	pub fn note_known_votes(
+		&self,
+		event_id: EventId,
+		validator_set_id: ValidatorSetId,
+		mut votes: Vec<(Public, [u8; 32])>,
+	) {
+		votes.sort_by(|a, b| a.0.cmp(&b.0));
+		self.known_votes.write().insert((event_id, validator_set_id), votes);
+	}
+
+	/// Return the connected (non-light) peers
+	pub fn peers(&self) -> Vec<PeerId> {
+		self.peers.read().clone()
+	}
+
 	pub fn set_active_validators(&self, new_active_validators: Vec<Public>) {
 		let mut active_validators = self.active_validators.write();
 		let _old = std::mem::replace(&mut *active_validators, new_active_validators);
@@ -107,6 +128,16 @@ impl<B> Validator<B> for GossipValidator<B>
 where
 	B: Block,
 {
+	fn new_peer(&self, _context: &mut dyn ValidatorContext<B>, who: &PeerId, role: ObservedRole) {
+		if !role.is_light() {
+			self.peers.write().push(*who);
+		}
+	}
+
+	fn peer_disconnected(&self, _context: &mut dyn ValidatorContext<B>, who: &PeerId) {
+		self.peers.write().retain(|peer| peer != who);
+	}
+
 	fn validate(
 		&self,
 		_context: &mut dyn ValidatorContext<B>,
@@ -123,9 +154,16 @@ where
 		{
 			trace!(target: "ethy", "💎 witness from: {:?}, validator set: {:?}, event: {:?}", authority_id, validator_set_id, event_id);
 
+			let vote_key = (event_id, validator_set_id);
 			let mut known_votes = self.known_votes.write();
-			let maybe_known = known_votes.get(&event_id).map(|v| v.binary_search(&authority_id));
-			if maybe_known.is_some() && maybe_known.unwrap().is_ok() {
+			let maybe_known = known_votes
+				.get(&vote_key)
+				.map(|v| v.binary_search_by(|(known_authority, _)| known_authority.cmp(&authority_id)));
+			let known_digest = match maybe_known {
+				Some(Ok(index)) => known_votes.get(&vote_key).map(|v| v[index].1),
+				_ => None,
+			};
+			if known_digest == Some(digest) {
 				trace!(target: "ethy", "💎 witness from: {:?}, event: {:?} is already known", &authority_id, event_id);
 				return ValidationResult::Discard;
 			}
@@ -135,6 +173,17 @@ where
 				return ValidationResult::Discard;
 			}
 
+			if known_digest.is_some() {
+				// the authority has witnessed a different digest for this event and validator set
+				// pass it to the worker as evidence of equivocation but don't propagate it
+				if EthyKeystore::verify_prehashed(&authority_id, &signature, &digest) {
+					warn!(target: "ethy", "💎 conflicting witness from: {:?}, event: {:?}", &authority_id, event_id);
+					return ValidationResult::ProcessAndDiscard(self.topic);
+				}
+				warn!(target: "ethy", "💎 bad signature: {:?}, event: {:?}", authority_id, event_id);
+				return ValidationResult::Discard;
+			}
+
 			if EthyKeystore::verify_prehashed(&authority_id, &signature, &digest) {
 				// Make the vote as seen
 				trace!(target: "ethy", "💎 verify prehashed OK, waiting lock: {:?}, event: {:?}", &authority_id, event_id);
@@ -143,13 +192,13 @@ where
 						// we've seen this nonce and need to add the new vote
 						// insert_index is guaranteed to be `Err` as it has not been recorded yet
 						let index = insert_index.err().unwrap();
-						if let Some(v) = known_votes.get_mut(&event_id) {
-							v.insert(index, authority_id.clone())
+						if let Some(v) = known_votes.get_mut(&vote_key) {
+							v.insert(index, (authority_id.clone(), digest))
 						}
 					}
 					None => {
 						// we haven't seen this nonce yet
-						known_votes.insert(event_id, vec![authority_id.clone()]);
+						known_votes.insert(vote_key, vec![(authority_id.clone(), digest)]);
 					}
 				}
 
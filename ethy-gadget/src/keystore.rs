@@ -52,12 +52,10 @@ impl EthyKeystore {
 		None
 	}
 
-	/// Sign `message` with the `public` key.
+	/// Sign the 32 byte `digest` with the `public` key, as is.
 	///
-	/// Note that `message` usually will be pre-hashed before being singed.
-	///
-	/// Return the message signature or an error in case of failure.
-	pub fn sign(&self, public: &Public, message: &[u8]) -> Result<Signature, error::Error> {
+	/// Return the digest signature or an error in case of failure.
+	pub fn sign_prehashed(&self, public: &Public, digest: &[u8; 32]) -> Result<Signature, error::Error> {
 		let store = if let Some(store) = self.0.clone() {
 			store
 		} else {
@@ -65,11 +63,7 @@ impl EthyKeystore {
 		};
 
 		let public = public.as_ref();
-		let msg = keccak_256(message);
-
-		// We want to sign the keccak hash of the message
-		// current plug `ecdsa::sign_with` will use blake2 by default
-		let sig = SyncCryptoStore::ecdsa_sign_prehashed(&*store, ETH_BRIDGE_KEY_TYPE, public, &msg)
+		let sig = SyncCryptoStore::ecdsa_sign_prehashed(&*store, ETH_BRIDGE_KEY_TYPE, public, digest)
 			.map_err(|e| error::Error::Keystore(e.to_string()))?
 			.ok_or_else(|| error::Error::Signature("ecdsa_sign_prehashed() failed".to_string()))?;
 
@@ -158,11 +152,11 @@ mod tests {
 		let ethy_store: EthyKeystore = Some(store.clone()).into();
 
 		let sig = ethy_store
-			.sign(
+			.sign_prehashed(
 				&pair.public().into(),
-				&hex!(
+				&keccak_256(&hex!(
 					"0000000000000000000000000000007B0000000000000000000000000000000000000000000000000000000000000000"
-				),
+				)),
 			)
 			.unwrap();
 
@@ -210,10 +204,9 @@ mod tests {
 
 		let ethy_store: EthyKeystore = Some(store.clone()).into();
 
-		let msg = b"are you involved or commited?";
-		let sig1 = ethy_store.sign(&pair.public().into(), msg).unwrap();
-
 		let msg = keccak_256(b"are you involved or commited?");
+		let sig1 = ethy_store.sign_prehashed(&pair.public().into(), &msg).unwrap();
+
 		let sig2 = SyncCryptoStore::ecdsa_sign_prehashed(&*store, ETH_BRIDGE_KEY_TYPE, &pair.public(), &msg)
 			.unwrap()
 			.unwrap();
@@ -234,8 +227,8 @@ mod tests {
 
 		let store: EthyKeystore = Some(store).into();
 
-		let msg = b"are you involved or commited?";
-		let sig = store.sign(&alice.public(), msg).err().unwrap();
+		let msg = keccak_256(b"are you involved or commited?");
+		let sig = store.sign_prehashed(&alice.public(), &msg).err().unwrap();
 		let err = Error::Signature("ecdsa_sign_prehashed() failed".to_string());
 		assert_eq!(sig, err);
 	}
@@ -245,9 +238,9 @@ mod tests {
 		let store: EthyKeystore = None.into();
 
 		let alice = crypto::AuthorityPair::from_string("//Alice", None).unwrap();
-		let msg = b"are you involved or commited";
+		let msg = keccak_256(b"are you involved or commited");
 
-		let sig = store.sign(&alice.public(), msg).err().unwrap();
+		let sig = store.sign_prehashed(&alice.public(), &msg).err().unwrap();
 		let err = Error::Keystore("no Keystore".to_string());
 		assert_eq!(sig, err);
 	}
@@ -266,7 +259,7 @@ mod tests {
 
 		// `msg` and `sig` match
 		let msg = b"are you involved or commited?";
-		let sig = store.sign(&pair.public(), msg).unwrap();
+		let sig = store.sign_prehashed(&pair.public(), &keccak_256(msg)).unwrap();
 		assert!(EthyKeystore::verify(&pair.public(), &sig, msg));
 
 		// `msg and `sig` don't match
@@ -24,6 +24,8 @@
 //!
 //! The current implementation simply assembles signatures from individual validators.
 //!
+//! Nodes missing proofs (e.g. after a restart or a fresh sync) request them from peers, see [`proof_sync`].
+//!
 
 use std::sync::Arc;
 
@@ -40,6 +42,7 @@ use sp_runtime::traits::Block;
 
 use benson_primitives::eth::EthyApi;
 
+mod aux_schema;
 mod error;
 mod gossip;
 mod keystore;
@@ -48,6 +51,10 @@ mod witness_record;
 mod worker;
 
 pub mod notification;
+pub mod proof_sync;
+
+pub use keystore::EthyEcdsaToEthereum;
+pub use proof_sync::{EthyProofRequestHandler, ProofRequestNetwork};
 
 /// The p2p protocol name for Eth bridge messages
 pub const ETHY_PROTOCOL_NAME: &'static str = "/benson/ethy/1";
@@ -97,7 +104,7 @@ where
 	BE: Backend<B>,
 	C: Client<B, BE>,
 	C::Api: EthyApi<B>,
-	N: GossipNetwork<B> + Clone + Send + 'static,
+	N: GossipNetwork<B> + ProofRequestNetwork + Clone + Send + 'static,
 {
 	/// ETHY client
 	pub client: Arc<C>,
@@ -105,7 +112,7 @@ where
 	pub backend: Arc<BE>,
 	/// Local key store
 	pub key_store: Option<SyncCryptoStorePtr>,
-	/// Gossip network
+	/// Gossip network, also used to request missing proofs from peers
 	pub network: N,
 	/// ETHY signed witness sender
 	pub event_proof_sender: notification::EthyEventProofSender,
@@ -123,7 +130,7 @@ where
 	BE: Backend<B>,
 	C: Client<B, BE>,
 	C::Api: EthyApi<B>,
-	N: GossipNetwork<B> + Clone + Send + 'static,
+	N: GossipNetwork<B> + ProofRequestNetwork + Clone + Send + 'static,
 {
 	let EthyParams {
 		client,
@@ -136,6 +143,7 @@ where
 	} = ethy_params;
 
 	let gossip_validator = Arc::new(gossip::GossipValidator::new(Default::default()));
+	let proof_request_network = Box::new(network.clone());
 	let gossip_engine = GossipEngine::new(network, ETHY_PROTOCOL_NAME, gossip_validator.clone(), None);
 
 	let metrics = prometheus_registry
@@ -159,6 +167,7 @@ where
 		event_proof_sender,
 		gossip_engine,
 		gossip_validator,
+		proof_request_network,
 		metrics,
 	};
 
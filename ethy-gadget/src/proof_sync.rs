@@ -0,0 +1,288 @@
+// Copyright (C) 2020-2021 Parity Technologies (UK) Ltd. & Annie Lai Investments Ltd
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Ethy proof catch-up
+//!
+//! Proofs are only assembled by nodes online to collect witnesses for an event.
+//! Nodes missing a proof for an event (e.g. after a restart or a fresh sync) request it from their Ethy peers
+//! over a libp2p request-response protocol.
+
+use std::{marker::PhantomData, sync::Arc, time::Duration};
+
+use codec::{Decode, Encode};
+use futures::{
+	channel::{mpsc, oneshot},
+	StreamExt,
+};
+use log::{debug, trace};
+
+use sc_client_api::Backend;
+use sc_network::{
+	config::{IncomingRequest, OutgoingResponse, RequestResponseConfig},
+	ExHashT, IfDisconnected, NetworkService, PeerId, ReputationChange, RequestFailure,
+};
+use sp_runtime::traits::Block;
+
+use benson_primitives::eth::{
+	crypto::{AuthorityId as Public, AuthoritySignature as Signature},
+	EventId, EventProof, ValidatorSet, VersionedEventProof, ETHY_ENGINE_ID,
+};
+
+use crate::{keystore::EthyKeystore, worker::PROOF_THRESHOLD};
+
+/// The p2p request-response protocol name for Ethy proof requests
+pub const ETHY_PROOF_REQUEST_PROTOCOL_NAME: &'static str = "/benson/ethy/proofs/1";
+
+/// Max. number of proofs that may be requested at once
+pub(crate) const MAX_PROOFS_PER_REQUEST: usize = 32;
+
+/// Max. number of inbound proof requests queued for the handler
+const MAX_PENDING_PROOF_REQUESTS: usize = 32;
+
+/// Reputation change for a peer sending a malformed proof request
+const COST_BAD_REQUEST: ReputationChange = ReputationChange::new(-(1 << 12), "ethy: bad proof request");
+
+/// A request for the proofs of `event_ids`
+/// The response is the SCALE encoded `Vec<VersionedEventProof>` of all requested proofs known to the peer
+#[derive(Encode, Decode, Debug, PartialEq)]
+pub(crate) struct EventProofRequest {
+	pub event_ids: Vec<EventId>,
+}
+
+/// Return the aux DB key of the proof for `event_id` (engine_id + proof_id)
+pub(crate) fn event_proof_key(event_id: EventId) -> Vec<u8> {
+	[&ETHY_ENGINE_ID[..], &event_id.to_be_bytes()[..]].concat()
+}
+
+/// Verify `proof` has a threshold of valid signatures from `validator_set` over `digest`
+pub(crate) fn verify_event_proof(proof: &EventProof, digest: &[u8; 32], validator_set: &ValidatorSet<Public>) -> bool {
+	if proof.digest != *digest
+		|| proof.validator_set_id != validator_set.id
+		|| proof.signatures.len() != validator_set.validators.len()
+	{
+		return false;
+	}
+
+	let empty_sig = Signature::default();
+	let mut signature_count = 0_usize;
+	for (signature, validator) in proof.signatures.iter().zip(validator_set.validators.iter()) {
+		if *signature == empty_sig {
+			continue;
+		}
+		if !EthyKeystore::verify_prehashed(validator, signature, digest) {
+			return false;
+		}
+		signature_count += 1;
+	}
+
+	let threshold = validator_set.validators.len() as f32 * PROOF_THRESHOLD;
+	signature_count > 0 && signature_count >= threshold as usize
+}
+
+/// A network able to send Ethy proof requests to peers
+pub trait ProofRequestNetwork: Send + Sync {
+	/// Send the encoded proof `request` to `peer`, the receiver resolves with its response
+	fn request_proofs(&self, peer: PeerId, request: Vec<u8>) -> oneshot::Receiver<Result<Vec<u8>, RequestFailure>>;
+}
+
+impl<B: Block, H: ExHashT> ProofRequestNetwork for Arc<NetworkService<B, H>> {
+	fn request_proofs(&self, peer: PeerId, request: Vec<u8>) -> oneshot::Receiver<Result<Vec<u8>, RequestFailure>> {
+		let (tx, rx) = oneshot::channel();
+		self.start_request(
+			peer,
+			ETHY_PROOF_REQUEST_PROTOCOL_NAME,
+			request,
+			tx,
+			IfDisconnected::ImmediateError,
+		);
+		rx
+	}
+}
+
+/// Serves Ethy proof requests from peers with proofs in the local aux DB
+pub struct EthyProofRequestHandler<B, BE> {
+	backend: Arc<BE>,
+	request_receiver: mpsc::Receiver<IncomingRequest>,
+	_phantom: PhantomData<B>,
+}
+
+impl<B, BE> EthyProofRequestHandler<B, BE>
+where
+	B: Block,
+	BE: Backend<B>,
+{
+	/// Create a new handler and the protocol config to add to
+	/// [`sc_network::config::NetworkConfiguration::request_response_protocols`].
+	pub fn new(backend: Arc<BE>) -> (Self, RequestResponseConfig) {
+		let (tx, request_receiver) = mpsc::channel(MAX_PENDING_PROOF_REQUESTS);
+		let config = RequestResponseConfig {
+			name: ETHY_PROOF_REQUEST_PROTOCOL_NAME.into(),
+			max_request_size: 1024,
+			max_response_size: 16 * 1024 * 1024,
+			request_timeout: Duration::from_secs(15),
+			inbound_queue: Some(tx),
+		};
+
+		(
+			Self {
+				backend,
+				request_receiver,
+				_phantom: PhantomData,
+			},
+			config,
+		)
+	}
+
+	/// Return the encoded proofs for a proof request `payload`
+	fn handle_request(&self, mut payload: &[u8]) -> Result<Vec<u8>, ()> {
+		let EventProofRequest { event_ids } = EventProofRequest::decode(&mut payload).map_err(|_| ())?;
+		if event_ids.len() > MAX_PROOFS_PER_REQUEST {
+			return Err(());
+		}
+
+		let proofs: Vec<VersionedEventProof> = event_ids
+			.into_iter()
+			.filter_map(|event_id| {
+				Backend::get_aux(self.backend.as_ref(), event_proof_key(event_id).as_ref())
+					.ok()
+					.flatten()
+					.and_then(|encoded_proof| VersionedEventProof::decode(&mut &encoded_proof[..]).ok())
+			})
+			.collect();
+
+		Ok(proofs.encode())
+	}
+
+	/// Run the handler, serving requests until the network shuts down
+	pub async fn run(mut self) {
+		while let Some(request) = self.request_receiver.next().await {
+			let IncomingRequest {
+				peer,
+				payload,
+				pending_response,
+			} = request;
+
+			let result = self.handle_request(&payload);
+			let reputation_changes = if result.is_err() {
+				debug!(target: "ethy", "💎 bad proof request from: {:?}", peer);
+				vec![COST_BAD_REQUEST]
+			} else {
+				trace!(target: "ethy", "💎 served proof request from: {:?}", peer);
+				Vec::new()
+			};
+
+			let _ = pending_response.send(OutgoingResponse {
+				result,
+				reputation_changes,
+				sent_feedback: None,
+			});
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use sp_core::{ecdsa, keccak_256, Pair};
+
+	fn signed_proof(signers: &[ecdsa::Pair], validator_count: usize, digest: [u8; 32]) -> EventProof {
+		let mut signatures = vec![Signature::default(); validator_count];
+		for (idx, signer) in signers.iter().enumerate() {
+			signatures[idx] = signer.sign_prehashed(&digest).into();
+		}
+		EventProof {
+			digest,
+			event_id: 1,
+			validator_set_id: 5,
+			signatures,
+			block: [1_u8; 32],
+			tag: None,
+		}
+	}
+
+	fn validator_set(pairs: &[ecdsa::Pair]) -> ValidatorSet<Public> {
+		ValidatorSet {
+			validators: pairs.iter().map(|p| Public::from(p.public())).collect(),
+			id: 5,
+		}
+	}
+
+	#[test]
+	fn verify_event_proof_works() {
+		let pairs: Vec<ecdsa::Pair> = ["//Alice", "//Bob", "//Charlie"]
+			.iter()
+			.map(|s| ecdsa::Pair::from_string(s, None).unwrap())
+			.collect();
+		let validator_set = validator_set(&pairs);
+		let digest = keccak_256(b"hello world");
+
+		let proof = signed_proof(&pairs[..2], 3, digest);
+		assert!(verify_event_proof(&proof, &digest, &validator_set));
+
+		// wrong digest
+		assert!(!verify_event_proof(&proof, &keccak_256(b"hello"), &validator_set));
+
+		// wrong validator set
+		let mut other_set = validator_set.clone();
+		other_set.id = 6;
+		assert!(!verify_event_proof(&proof, &digest, &other_set));
+	}
+
+	#[test]
+	fn verify_event_proof_requires_threshold() {
+		let pairs: Vec<ecdsa::Pair> = ["//Alice", "//Bob", "//Charlie"]
+			.iter()
+			.map(|s| ecdsa::Pair::from_string(s, None).unwrap())
+			.collect();
+		let validator_set = validator_set(&pairs);
+		let digest = keccak_256(b"hello world");
+
+		// 1/3 signatures
+		let proof = signed_proof(&pairs[..1], 3, digest);
+		assert!(!verify_event_proof(&proof, &digest, &validator_set));
+
+		// no signatures
+		let proof = signed_proof(&[], 3, digest);
+		assert!(!verify_event_proof(&proof, &digest, &validator_set));
+	}
+
+	#[test]
+	fn verify_event_proof_rejects_foreign_signatures() {
+		let pairs: Vec<ecdsa::Pair> = ["//Alice", "//Bob", "//Charlie"]
+			.iter()
+			.map(|s| ecdsa::Pair::from_string(s, None).unwrap())
+			.collect();
+		let validator_set = validator_set(&pairs);
+		let digest = keccak_256(b"hello world");
+
+		// signatures ordered differently to the validator set
+		let proof = signed_proof(&[pairs[1].clone(), pairs[0].clone()], 3, digest);
+		assert!(!verify_event_proof(&proof, &digest, &validator_set));
+
+		// signature from a non-validator
+		let mallory = ecdsa::Pair::from_string("//Mallory", None).unwrap();
+		let proof = signed_proof(&[pairs[0].clone(), mallory], 3, digest);
+		assert!(!verify_event_proof(&proof, &digest, &validator_set));
+	}
+
+	#[test]
+	fn event_proof_key_matches_worker() {
+		assert_eq!(
+			event_proof_key(1_234),
+			[&ETHY_ENGINE_ID[..], &1_234_u64.to_be_bytes()[..]].concat(),
+		);
+	}
+}
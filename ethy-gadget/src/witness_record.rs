@@ -16,11 +16,26 @@
 
 use benson_primitives::eth::{
 	crypto::{AuthorityId, AuthoritySignature as Signature},
-	EventId, Witness,
+	EventId, ValidatorSetId, Witness,
 };
+use codec::{Decode, Encode};
 use log::{error, trace};
 use std::collections::HashMap;
 
+/// An encodable snapshot of the witnesses in a `WitnessRecord`
+/// Used to persist in-flight witnesses across restarts
+#[derive(Encode, Decode, Clone, Debug, Default, PartialEq)]
+pub struct WitnessRecordSnapshot {
+	/// The validator set id the witness indexes refer to
+	pub validator_set_id: ValidatorSetId,
+	/// event_id -> digest -> [](authority index, signature)
+	record: Vec<(EventId, Vec<([u8; 32], Vec<(u32, Signature)>)>)>,
+	/// event_id -> (block, tag)
+	event_meta: Vec<(EventId, ([u8; 32], Option<Vec<u8>>))>,
+	/// event_id -> authorities that have witnessed it
+	pub has_voted: Vec<(EventId, Vec<AuthorityId>)>,
+}
+
 /// Tracks live witnesses
 ///
 /// Stores witnesses per message event_id and digest
@@ -71,6 +86,110 @@ impl WitnessRecord {
 	pub fn note_event_metadata(&mut self, event_id: EventId, block: [u8; 32], tag: Option<Vec<u8>>) {
 		self.event_meta.entry(event_id).or_insert((block, tag));
 	}
+	/// Has `authority_id` witnessed the event identified by `event_id`
+	pub fn has_voted(&self, event_id: EventId, authority_id: &AuthorityId) -> bool {
+		self.has_voted
+			.get(&event_id)
+			.map(|votes| votes.binary_search(authority_id).is_ok())
+			.unwrap_or_default()
+	}
+	/// Return the (authority, digest) of all witnesses noted for `event_id`
+	pub fn votes(&self, event_id: EventId) -> Vec<(AuthorityId, [u8; 32])> {
+		self.record
+			.get(&event_id)
+			.map(|digests| {
+				digests
+					.iter()
+					.flat_map(|(digest, signatures)| {
+						signatures.iter().filter_map(move |(authority_index, _)| {
+							self.validators.get(*authority_index).map(|v| (v.clone(), *digest))
+						})
+					})
+					.collect()
+			})
+			.unwrap_or_default()
+	}
+	/// Return a noted witness (digest, signature) from the same authority for the same event as `witness` but
+	/// over a different digest i.e. evidence of equivocation
+	pub fn conflicting_witness(&self, witness: &Witness) -> Option<([u8; 32], Signature)> {
+		let authority_index = self.validators.iter().position(|v| v == &witness.authority_id)?;
+		self.record
+			.get(&witness.event_id)?
+			.iter()
+			.find_map(|(digest, signatures)| {
+				if *digest == witness.digest {
+					return None;
+				}
+				signatures
+					.iter()
+					.find(|(index, _)| *index == authority_index)
+					.map(|(_, signature)| (*digest, signature.clone()))
+			})
+	}
+	/// Return a snapshot of the in-flight witnesses, `validator_set_id` is the set made the witnesses
+	pub fn snapshot(&self, validator_set_id: ValidatorSetId) -> WitnessRecordSnapshot {
+		let mut record: Vec<(EventId, Vec<([u8; 32], Vec<(u32, Signature)>)>)> = self
+			.record
+			.iter()
+			.map(|(event_id, digests)| {
+				let mut digests: Vec<([u8; 32], Vec<(u32, Signature)>)> = digests
+					.iter()
+					.map(|(digest, signatures)| {
+						let signatures = signatures
+							.iter()
+							.map(|(authority_index, signature)| (*authority_index as u32, signature.clone()))
+							.collect();
+						(*digest, signatures)
+					})
+					.collect();
+				digests.sort_by_key(|(digest, _)| *digest);
+				(*event_id, digests)
+			})
+			.collect();
+		record.sort_by_key(|(event_id, _)| *event_id);
+
+		let mut event_meta: Vec<(EventId, ([u8; 32], Option<Vec<u8>>))> = self
+			.event_meta
+			.iter()
+			.map(|(event_id, meta)| (*event_id, meta.clone()))
+			.collect();
+		event_meta.sort_by_key(|(event_id, _)| *event_id);
+
+		// only votes for in-flight events are useful
+		let mut has_voted: Vec<(EventId, Vec<AuthorityId>)> = self
+			.has_voted
+			.iter()
+			.filter(|(event_id, _)| self.record.contains_key(event_id))
+			.map(|(event_id, votes)| (*event_id, votes.clone()))
+			.collect();
+		has_voted.sort_by_key(|(event_id, _)| *event_id);
+
+		WitnessRecordSnapshot {
+			validator_set_id,
+			record,
+			event_meta,
+			has_voted,
+		}
+	}
+	/// Restore in-flight witnesses from `snapshot`
+	/// The validators of `snapshot.validator_set_id` must be set
+	pub fn restore(&mut self, snapshot: WitnessRecordSnapshot) {
+		for (event_id, digests) in snapshot.record {
+			let digests = digests
+				.into_iter()
+				.map(|(digest, signatures)| {
+					let signatures = signatures
+						.into_iter()
+						.map(|(authority_index, signature)| (authority_index as usize, signature))
+						.collect();
+					(digest, signatures)
+				})
+				.collect();
+			self.record.insert(event_id, digests);
+		}
+		self.event_meta.extend(snapshot.event_meta);
+		self.has_voted.extend(snapshot.has_voted);
+	}
 	/// Note a witness if we haven't seen it before
 	/// Returns true if the witness was noted, i.e previously unseen
 	pub fn note(&mut self, witness: &Witness) -> bool {
@@ -151,3 +270,113 @@ impl WitnessRecord {
 		return true;
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use sp_core::{ecdsa, keccak_256, Pair};
+
+	fn witness(pair: &ecdsa::Pair, event_id: EventId, digest: [u8; 32]) -> Witness {
+		Witness {
+			digest,
+			event_id,
+			validator_set_id: 1,
+			authority_id: pair.public().into(),
+			signature: pair.sign_prehashed(&digest).into(),
+		}
+	}
+
+	#[test]
+	fn snapshot_restores_witnesses() {
+		let alice = ecdsa::Pair::from_string("//Alice", None).unwrap();
+		let bob = ecdsa::Pair::from_string("//Bob", None).unwrap();
+		let validators: Vec<AuthorityId> = vec![alice.public().into(), bob.public().into()];
+		let digest = keccak_256(b"hello world");
+
+		let mut witness_record = WitnessRecord::default();
+		witness_record.set_validators(validators.clone());
+		witness_record.note_event_metadata(1, [1_u8; 32], Some(b"tag".to_vec()));
+		assert!(witness_record.note(&witness(&alice, 1, digest)));
+		assert!(witness_record.note(&witness(&bob, 2, digest)));
+
+		let snapshot = witness_record.snapshot(1);
+		assert_eq!(
+			WitnessRecordSnapshot::decode(&mut &snapshot.encode()[..]).unwrap(),
+			snapshot
+		);
+
+		let mut restored = WitnessRecord::default();
+		restored.set_validators(validators);
+		restored.restore(snapshot);
+
+		assert!(restored.has_voted(1, &alice.public().into()));
+		assert!(!restored.has_voted(1, &bob.public().into()));
+		assert!(restored.has_voted(2, &bob.public().into()));
+		assert_eq!(restored.event_metadata(1), Some(&([1_u8; 32], Some(b"tag".to_vec()))));
+		assert_eq!(
+			restored.signatures_for(1, &digest),
+			witness_record.signatures_for(1, &digest)
+		);
+		assert!(restored.has_consensus(1, &digest, 1));
+		// witnesses are not noted twice
+		assert!(!restored.note(&witness(&alice, 1, digest)));
+	}
+
+	#[test]
+	fn conflicting_witness_works() {
+		let alice = ecdsa::Pair::from_string("//Alice", None).unwrap();
+		let bob = ecdsa::Pair::from_string("//Bob", None).unwrap();
+		let digest = keccak_256(b"hello world");
+		let other_digest = keccak_256(b"hello");
+
+		let mut witness_record = WitnessRecord::default();
+		witness_record.set_validators(vec![alice.public().into(), bob.public().into()]);
+		let first = witness(&alice, 1, digest);
+		assert!(witness_record.note(&first));
+		assert!(witness_record.note(&witness(&bob, 1, other_digest)));
+
+		// alice signs a different digest for the same event
+		let second = witness(&alice, 1, other_digest);
+		assert_eq!(
+			witness_record.conflicting_witness(&second),
+			Some((digest, first.signature.clone()))
+		);
+		assert!(!witness_record.note(&second));
+
+		// same digest or other events don't conflict
+		assert_eq!(witness_record.conflicting_witness(&first), None);
+		assert_eq!(
+			witness_record.conflicting_witness(&witness(&alice, 2, other_digest)),
+			None
+		);
+		assert_eq!(
+			witness_record.conflicting_witness(&witness(&bob, 1, other_digest)),
+			None
+		);
+
+		let mut votes = witness_record.votes(1);
+		votes.sort();
+		let mut expected = vec![(alice.public().into(), digest), (bob.public().into(), other_digest)];
+		expected.sort();
+		assert_eq!(votes, expected);
+	}
+
+	#[test]
+	fn snapshot_omits_cleared_events() {
+		let alice = ecdsa::Pair::from_string("//Alice", None).unwrap();
+		let digest = keccak_256(b"hello world");
+
+		let mut witness_record = WitnessRecord::default();
+		witness_record.set_validators(vec![alice.public().into()]);
+		assert!(witness_record.note(&witness(&alice, 1, digest)));
+		witness_record.clear(1);
+
+		assert_eq!(
+			witness_record.snapshot(1),
+			WitnessRecordSnapshot {
+				validator_set_id: 1,
+				..Default::default()
+			}
+		);
+	}
+}
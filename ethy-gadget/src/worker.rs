@@ -14,39 +14,85 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use std::{convert::TryInto, sync::Arc};
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	convert::TryInto,
+	sync::Arc,
+};
 
 use codec::{Codec, Decode, Encode};
-use futures::{future, FutureExt, StreamExt};
+use futures::{
+	future::{self, BoxFuture},
+	stream::FuturesUnordered,
+	FutureExt, StreamExt,
+};
 use log::{debug, error, info, trace, warn};
 use parking_lot::Mutex;
 
 use sc_client_api::{Backend, FinalityNotification, FinalityNotifications};
+use sc_network::{PeerId, ReputationChange, RequestFailure};
 use sc_network_gossip::GossipEngine;
 
 use sp_api::BlockId;
 use sp_runtime::{
 	generic::OpaqueDigestItemId,
-	traits::{Block, Header, NumberFor},
+	traits::{Block, Header, NumberFor, One, Saturating},
 };
 
 use crate::{
+	aux_schema,
 	gossip::{topic, GossipValidator},
 	keystore::{EthyEcdsaToEthereum, EthyKeystore},
 	metric_inc, metric_set,
 	metrics::Metrics,
 	notification,
-	witness_record::WitnessRecord,
+	proof_sync::{event_proof_key, verify_event_proof, EventProofRequest, ProofRequestNetwork, MAX_PROOFS_PER_REQUEST},
+	witness_record::{WitnessRecord, WitnessRecordSnapshot},
 	Client,
 };
 use benson_primitives::eth::{
-	crypto::AuthorityId as Public, ConsensusLog, EthyApi, EventId, EventProof, ValidatorSet, ValidatorSetId,
-	VersionedEventProof, Witness, ETHY_ENGINE_ID, GENESIS_AUTHORITY_SET_ID,
+	crypto::{AuthorityId as Public, AuthoritySignature as Signature},
+	BatchEventProof, ConsensusLog, EquivocationProof, EthyApi, EventId, EventProof, Message, ValidatorSet,
+	ValidatorSetId, VersionedEventProof, Witness, ETHY_ENGINE_ID, GENESIS_AUTHORITY_SET_ID,
 };
+use benson_primitives::merkle::{merkle_branch, merkle_root};
 use crml_support::EthAbiCodec;
 
 /// % signature to generate a proof
-const PROOF_THRESHOLD: f32 = 0.6;
+pub(crate) const PROOF_THRESHOLD: f32 = 0.6;
+/// Finalized blocks to wait for a proof to be assembled locally before requesting it from peers
+const PROOF_REQUEST_DELAY: u32 = 10;
+/// Max. attempts to request a missing proof from peers before giving up
+const MAX_PROOF_REQUEST_ATTEMPTS: u32 = 5;
+/// Max. number of finalized blocks to handle on each finality notification
+/// A longer backlog is paged through, oldest first, over the following notifications
+const MAX_CATCH_UP_BLOCKS: u32 = 4_096;
+/// Metadata tag of batch proofs
+const BATCH_TAG: &[u8] = b"sys:batch";
+/// Reputation change for a peer responding with an invalid proof
+const COST_INVALID_PROOF: ReputationChange = ReputationChange::new(-(1 << 16), "ethy: invalid proof");
+
+/// An event proof response from a peer (peer, requested event ids, response)
+type ProofResponse = (PeerId, Vec<EventId>, Result<Vec<u8>, RequestFailure>);
+
+/// An event without a local proof
+#[derive(Clone)]
+struct MissingProof<B: Block> {
+	/// keccak hash of the signing request message, or the Merkle root of a batch
+	digest: [u8; 32],
+	/// The signing request message, if the digest is its hash
+	message: Option<Message>,
+	/// Hash of the block requesting the proof
+	block_hash: B::Hash,
+	/// Number of the block requesting the proof
+	block_number: NumberFor<B>,
+	/// Metadata tag of the proof
+	tag: Option<Vec<u8>>,
+	/// Id of the validator set active at the requesting block, the proof must be signed by it
+	validator_set_id: ValidatorSetId,
+	/// Number of times the proof has been requested from peers
+	attempts: u32,
+}
 
 pub(crate) struct WorkerParams<B, BE, C>
 where
@@ -58,6 +104,7 @@ where
 	pub event_proof_sender: notification::EthyEventProofSender,
 	pub gossip_engine: GossipEngine<B>,
 	pub gossip_validator: Arc<GossipValidator<B>>,
+	pub proof_request_network: Box<dyn ProofRequestNetwork>,
 	pub metrics: Option<Metrics>,
 }
 
@@ -74,14 +121,25 @@ where
 	event_proof_sender: notification::EthyEventProofSender,
 	gossip_engine: Arc<Mutex<GossipEngine<B>>>,
 	gossip_validator: Arc<GossipValidator<B>>,
+	proof_request_network: Box<dyn ProofRequestNetwork>,
 	metrics: Option<Metrics>,
 	finality_notifications: FinalityNotifications<B>,
 	/// Tracks on-going witnesses
 	witness_record: WitnessRecord,
+	/// Witnesses persisted before a restart, restored once their validator set is active
+	pending_witness_record: Option<WitnessRecordSnapshot>,
 	/// Best block we received a GRANDPA notification for
 	best_grandpa_block: NumberFor<B>,
 	/// Current validator set
 	validator_set: ValidatorSet<Public>,
+	/// Events without a local proof, to be requested from peers
+	missing_proofs: BTreeMap<EventId, MissingProof<B>>,
+	/// In-flight proof request to a peer
+	proof_requests: FuturesUnordered<BoxFuture<'static, ProofResponse>>,
+	/// Index of the next peer to request proofs from
+	next_proof_peer: usize,
+	/// Equivocations (event, authority) reported by this node
+	reported_equivocations: BTreeSet<(EventId, Public)>,
 }
 
 impl<B, C, BE> EthyWorker<B, C, BE>
@@ -105,9 +163,17 @@ where
 			event_proof_sender,
 			gossip_engine,
 			gossip_validator,
+			proof_request_network,
 			metrics,
 		} = worker_params;
 
+		// resume from the last finalized block handled before a restart
+		let finalized_number = client.info().finalized_number;
+		let best_grandpa_block = aux_schema::load_best_block::<B, _>(backend.as_ref())
+			.map(|best_block| std::cmp::min(best_block, finalized_number))
+			.unwrap_or(finalized_number);
+		let pending_witness_record = aux_schema::load_witness_record(backend.as_ref());
+
 		EthyWorker {
 			client: client.clone(),
 			backend,
@@ -115,14 +181,20 @@ where
 			event_proof_sender,
 			gossip_engine: Arc::new(Mutex::new(gossip_engine)),
 			gossip_validator,
+			proof_request_network,
 			metrics,
 			finality_notifications: client.finality_notification_stream(),
-			best_grandpa_block: client.info().finalized_number,
+			best_grandpa_block,
 			validator_set: ValidatorSet {
 				id: 0,
 				validators: Default::default(),
 			},
 			witness_record: Default::default(),
+			pending_witness_record,
+			missing_proofs: Default::default(),
+			proof_requests: FuturesUnordered::new(),
+			next_proof_peer: 0,
+			reported_equivocations: Default::default(),
 		}
 	}
 }
@@ -159,7 +231,99 @@ where
 	fn handle_finality_notification(&mut self, notification: FinalityNotification<B>) {
 		trace!(target: "ethy", "💎 finality notification for block #{:?}", &notification.header.number());
 
-		if let Some(active) = self.validator_set(&notification.header) {
+		// handle finalized blocks we haven't seen e.g. after a restart or during a sync
+		for header in self.unhandled_finalized_headers(&notification.header) {
+			self.handle_finalized_header(&header);
+		}
+
+		self.request_missing_proofs();
+		self.persist_state();
+	}
+
+	/// Report `witness` and a `conflicting` (digest, signature) from the same authority as an equivocation
+	/// The report is submitted as an unsigned extrinsic for the runtime to verify and slash the authority
+	fn report_equivocation(&mut self, witness: &Witness, conflicting: ([u8; 32], Signature)) {
+		if witness.validator_set_id != self.validator_set.id
+			|| !self
+				.reported_equivocations
+				.insert((witness.event_id, witness.authority_id.clone()))
+		{
+			return;
+		}
+
+		let (digest, signature) = conflicting;
+		let first = Witness {
+			digest,
+			signature,
+			..witness.clone()
+		};
+		// the proof must include both signed messages, only those of signing requests noted locally are known
+		let (first_message, second_message) = match (
+			self.signed_message(&first.digest),
+			self.signed_message(&witness.digest),
+		) {
+			(Some(first_message), Some(second_message)) => (first_message, second_message),
+			_ => {
+				debug!(target: "ethy", "💎 unknown message, can't report equivocation by: {:?}, event: {:?}", witness.authority_id, witness.event_id);
+				return;
+			}
+		};
+		let equivocation_proof = match EquivocationProof::from_witnesses(&first, first_message, witness, second_message)
+		{
+			Some(proof) => proof,
+			None => return,
+		};
+		warn!(target: "ethy", "💎 reporting equivocation by: {:?}, event: {:?}", witness.authority_id, witness.event_id);
+
+		let at = BlockId::hash(self.client.info().best_hash);
+		match self
+			.client
+			.runtime_api()
+			.submit_report_equivocation_unsigned_extrinsic(&at, equivocation_proof)
+		{
+			Ok(Some(())) => (),
+			Ok(None) => warn!(target: "ethy", "💎 failed to submit equivocation report, event: {:?}", witness.event_id),
+			Err(err) => error!(target: "ethy", "💎 equivocation report runtime error: {:?}", err),
+		}
+	}
+
+	/// Return the message of a signing request noted locally with `digest`, if known
+	fn signed_message(&self, digest: &[u8; 32]) -> Option<Message> {
+		self.missing_proofs
+			.values()
+			.find(|missing| missing.digest == *digest)
+			.and_then(|missing| missing.message.clone())
+	}
+
+	/// Return the finalized headers after `self.best_grandpa_block` up to and including `header`, oldest first
+	/// At most `MAX_CATCH_UP_BLOCKS` headers are returned, continuing from the last handled block on the next call
+	fn unhandled_finalized_headers(&self, header: &B::Header) -> Vec<B::Header> {
+		let mut headers = Vec::new();
+		let mut number = self.best_grandpa_block;
+
+		while number < *header.number() && headers.len() < MAX_CATCH_UP_BLOCKS as usize {
+			number = number.saturating_add(One::one());
+			if number == *header.number() {
+				headers.push(header.clone());
+				break;
+			}
+			match self.client.header(BlockId::Number(number)) {
+				Ok(Some(finalized)) => headers.push(finalized),
+				_ => {
+					// don't stall on unknown blocks
+					warn!(target: "ethy", "💎 finalized block unknown: {:?}, skipping to: {:?}", number, header.number());
+					headers.push(header.clone());
+					break;
+				}
+			}
+		}
+
+		headers
+	}
+
+	/// Handle proof requests in the finalized block `header`
+	fn handle_finalized_header(&mut self, header: &B::Header) {
+		if let Some(active) = self.validator_set(header) {
 			// Authority set change or genesis set id triggers new voting rounds
 			// this block has a different validator set id to the one we know about OR
 			// it's the first block
@@ -169,9 +333,13 @@ where
 				debug!(target: "ethy", "💎 new active validator set: {:?}", active);
 				debug!(target: "ethy", "💎 old validator set: {:?}", self.validator_set);
 				metric_set!(self, ethy_validator_set_id, active.id);
+				if aux_schema::write_validator_set(self.backend.as_ref(), &active).is_err() {
+					warn!(target: "ethy", "💎 failed to store validator set: {:?}", active.id);
+				}
 				self.gossip_validator.set_active_validators(active.validators.clone());
 				self.witness_record.set_validators(active.validators.clone());
 				self.validator_set = active;
+				self.restore_witness_record();
 			}
 		}
 
@@ -179,69 +347,53 @@ where
 			trace!(target: "ethy", "💎 Local authority id: {:?}", id);
 			id
 		} else {
-			trace!(target: "ethy", "💎 No authority id - can't vote for events in: {:?}", notification.header.hash());
+			trace!(target: "ethy", "💎 No authority id - can't vote for events in: {:?}", header.hash());
 			for ProofRequest {
-				message: _,
+				digest,
+				message,
 				event_id,
 				tag,
 				block,
-			} in extract_proof_requests::<B>(&notification.header, self.validator_set.id).into_iter()
+			} in extract_proof_requests::<B>(header, self.validator_set.id).into_iter()
 			{
 				trace!(target: "ethy", "💎 noting event metadata: {:?}", event_id);
 				// it's possible this event already has a proof stored due to differences in block
 				// propagation times.
-				// update the proof block hash and tag
-				let proof_key = [&ETHY_ENGINE_ID[..], &event_id.to_be_bytes()[..]].concat();
-
-				if let Ok(Some(encoded_proof)) = Backend::get_aux(self.backend.as_ref(), proof_key.as_ref()) {
-					if let Ok(VersionedEventProof::V1 { 0: mut proof }) =
-						VersionedEventProof::decode(&mut &encoded_proof[..])
-					{
-						proof.block = block;
-						proof.tag = tag;
-
-						if Backend::insert_aux(
-							self.backend.as_ref(),
-							&[
-								// DB key is (engine_id + proof_id)
-								(
-									[&ETHY_ENGINE_ID[..], &event_id.to_be_bytes()[..]].concat().as_ref(),
-									VersionedEventProof::V1(proof).encode().as_ref(),
-								),
-							],
-							&[],
-						)
-						.is_err()
-						{
-							// this is a warning for now, because until the round lifecycle is improved, we will
-							// conclude certain rounds multiple times.
-							error!(target: "ethy", "💎 failed to store proof: {:?}", event_id);
-						}
-					} else {
-						error!(target: "ethy", "💎 failed decoding event proof v1: {:?}", event_id);
-					}
-				} else {
+				if !self.update_proof_metadata(event_id, block, tag.clone()) {
 					// no proof is known for this event yet
-					self.witness_record.note_event_metadata(event_id, block, tag);
+					self.witness_record.note_event_metadata(event_id, block, tag.clone());
+					self.note_missing_proof(event_id, digest, message, header, tag);
 				}
 			}
 
 			// full node can't vote, we're done
+			self.best_grandpa_block = *header.number();
 			return;
 		};
 
-		// Search from (self.best_grandpa_block - notification.block) to find all signing requests
-		// Sign and broadcast a witness
+		// Sign and broadcast a witness for all signing requests
 		for ProofRequest {
+			digest,
 			message,
 			event_id,
 			tag,
 			block,
-		} in extract_proof_requests::<B>(&notification.header, self.validator_set.id).into_iter()
+		} in extract_proof_requests::<B>(header, self.validator_set.id).into_iter()
 		{
-			debug!(target: "ethy", "💎 got event proof request. event id: {:?}, message: {:?}", event_id, hex::encode(&message));
-			// `message = abi.encode(param0, param1,.., paramN, nonce)`
-			let signature = match self.key_store.sign(&authority_id, message.as_ref()) {
+			debug!(target: "ethy", "💎 got event proof request. event id: {:?}, digest: {:?}", event_id, hex::encode(&digest));
+			// the request may have been handled before a restart or assembled from gossip before this block
+			// was finalized locally
+			if self.update_proof_metadata(event_id, block, tag.clone()) {
+				trace!(target: "ethy", "💎 proof already known for event: {:?}", event_id);
+				continue;
+			}
+			if self.witness_record.has_voted(event_id, &authority_id) {
+				trace!(target: "ethy", "💎 already witnessed event: {:?}", event_id);
+				continue;
+			}
+
+			// `digest = keccak(abi.encode(param0, param1,.., paramN, nonce))` or the Merkle root of a batch of messages
+			let signature = match self.key_store.sign_prehashed(&authority_id, &digest) {
 				Ok(sig) => sig,
 				Err(err) => {
 					error!(target: "ethy", "💎 error signing witness: {:?}", err);
@@ -250,7 +402,7 @@ where
 			};
 			debug!(target: "ethy", "💎 signed event id: {:?}, validator set: {:?},\nsignature: {:?}", event_id, self.validator_set.id, hex::encode(&signature));
 			let witness = Witness {
-				digest: sp_core::keccak_256(message.as_ref()),
+				digest,
 				validator_set_id: self.validator_set.id,
 				event_id,
 				authority_id: authority_id.clone(),
@@ -262,7 +414,9 @@ where
 			debug!(target: "ethy", "💎 Sent witness: {:?}", witness);
 
 			// process the witness
-			self.witness_record.note_event_metadata(event_id, block, tag);
+			// the proof is requested from peers if it isn't assembled locally in time
+			self.witness_record.note_event_metadata(event_id, block, tag.clone());
+			self.note_missing_proof(event_id, digest, message, header, tag);
 			self.handle_witness(witness.clone());
 
 			// broadcast the witness
@@ -272,7 +426,316 @@ where
 			debug!(target: "ethy", "💎 gossiped witness for event: {:?}", witness.event_id);
 		}
 
-		self.best_grandpa_block = *notification.header.number();
+		self.best_grandpa_block = *header.number();
+	}
+
+	/// Update the block hash and tag of a stored proof for `event_id`
+	/// Returns whether a proof is known for the event
+	fn update_proof_metadata(&mut self, event_id: EventId, block: [u8; 32], tag: Option<Vec<u8>>) -> bool {
+		let proof_key = event_proof_key(event_id);
+		let encoded_proof = match Backend::get_aux(self.backend.as_ref(), proof_key.as_ref()) {
+			Ok(Some(encoded_proof)) => encoded_proof,
+			_ => return false,
+		};
+
+		match VersionedEventProof::decode(&mut &encoded_proof[..]) {
+			Ok(VersionedEventProof::V1(mut proof)) => {
+				if proof.block == block && proof.tag == tag {
+					return true;
+				}
+				proof.block = block;
+				proof.tag = tag;
+
+				if Backend::insert_aux(
+					self.backend.as_ref(),
+					&[
+						// DB key is (engine_id + proof_id)
+						(
+							proof_key.as_ref(),
+							VersionedEventProof::V1(proof.clone()).encode().as_ref(),
+						),
+					],
+					&[],
+				)
+				.is_err()
+				{
+					// this is a warning for now, because until the round lifecycle is improved, we will
+					// conclude certain rounds multiple times.
+					error!(target: "ethy", "💎 failed to store proof: {:?}", event_id);
+				}
+				self.store_batch_proofs(&proof);
+			}
+			// batched message proofs are stored with their metadata
+			Ok(VersionedEventProof::V2(_)) => (),
+			Err(_) => error!(target: "ethy", "💎 failed decoding event proof: {:?}", event_id),
+		}
+
+		true
+	}
+
+	/// Return the messages of the batch proven by `proof` from its requesting block, if known
+	fn batch_messages(&self, proof: &EventProof) -> Option<Vec<(EventId, Vec<u8>)>> {
+		let block_hash = B::Hash::decode(&mut &proof.block[..]).ok()?;
+		let header = self.client.header(BlockId::hash(block_hash)).ok().flatten()?;
+		header.digest().logs().iter().find_map(|log| {
+			match log.try_to::<ConsensusLog<Public>>(OpaqueDigestItemId::Consensus(&ETHY_ENGINE_ID)) {
+				Some(ConsensusLog::BatchSigningRequest((messages, batch_id))) if batch_id == proof.event_id => {
+					Some(messages)
+				}
+				_ => None,
+			}
+		})
+	}
+
+	/// Store and notify proofs for each message in the batch proven by `proof`
+	/// Each message is proven by the batch signatures and its Merkle branch in the batch
+	fn store_batch_proofs(&self, proof: &EventProof) {
+		if proof.tag.as_deref() != Some(BATCH_TAG) {
+			return;
+		}
+		let messages = match self.batch_messages(proof) {
+			Some(messages) => messages,
+			None => {
+				trace!(target: "ethy", "💎 batch messages unknown for proof: {:?}", proof.event_id);
+				return;
+			}
+		};
+		let leaves = batch_leaves(&messages);
+		if merkle_root(&leaves) != proof.digest {
+			error!(target: "ethy", "💎 batch root mismatch for proof: {:?}", proof.event_id);
+			return;
+		}
+
+		for (index, (event_id, _message)) in messages.iter().enumerate() {
+			let versioned_event_proof = VersionedEventProof::V2(BatchEventProof {
+				root: proof.digest,
+				event_id: *event_id,
+				batch_id: proof.event_id,
+				validator_set_id: proof.validator_set_id,
+				signatures: proof.signatures.clone(),
+				leaf: leaves[index],
+				branch: merkle_branch(&leaves, index).unwrap_or_default(),
+				block: proof.block,
+				tag: None,
+			});
+			if Backend::insert_aux(
+				self.backend.as_ref(),
+				&[(
+					event_proof_key(*event_id).as_ref(),
+					versioned_event_proof.encode().as_ref(),
+				)],
+				&[],
+			)
+			.is_err()
+			{
+				warn!(target: "ethy", "💎 failed to store batched proof: {:?}", event_id);
+				continue;
+			}
+			self.event_proof_sender.notify(versioned_event_proof);
+		}
+		debug!(target: "ethy", "💎 stored {:?} batched proofs for batch: {:?}", messages.len(), proof.event_id);
+	}
+
+	/// Restore in-flight witnesses persisted before a restart, once their validator set is active
+	fn restore_witness_record(&mut self) {
+		let snapshot = match self.pending_witness_record.take() {
+			Some(snapshot) => snapshot,
+			None => return,
+		};
+
+		if snapshot.validator_set_id == self.validator_set.id {
+			debug!(target: "ethy", "💎 restoring witnesses for events: {:?}", snapshot.has_voted.iter().map(|(event_id, _)| event_id).collect::<Vec<_>>());
+			let event_ids: Vec<EventId> = snapshot.has_voted.iter().map(|(event_id, _)| *event_id).collect();
+			self.witness_record.restore(snapshot);
+			for event_id in event_ids {
+				self.gossip_validator.note_known_votes(
+					event_id,
+					self.validator_set.id,
+					self.witness_record.votes(event_id),
+				);
+			}
+		} else if snapshot.validator_set_id > self.validator_set.id {
+			// still catching up to the validator set of the witnesses
+			self.pending_witness_record = Some(snapshot);
+		} else {
+			debug!(target: "ethy", "💎 discarding witnesses of old validator set: {:?}", snapshot.validator_set_id);
+		}
+	}
+
+	/// Persist the last finalized block handled and in-flight witnesses
+	/// Called once per finality notification, which includes all witnesses signed locally
+	/// Peer witnesses received since the last notification are gossiped again if lost on restart
+	fn persist_state(&self) {
+		let snapshot = match self.pending_witness_record {
+			// not restored yet, keep it
+			Some(ref pending) => pending.clone(),
+			None => self.witness_record.snapshot(self.validator_set.id),
+		};
+		if aux_schema::write_worker_state::<B, _>(self.backend.as_ref(), self.best_grandpa_block, &snapshot).is_err() {
+			warn!(target: "ethy", "💎 failed to persist worker state at block: {:?}", self.best_grandpa_block);
+		}
+	}
+
+	/// Note `event_id` requested in `header` has no local proof yet
+	fn note_missing_proof(
+		&mut self,
+		event_id: EventId,
+		digest: [u8; 32],
+		message: Option<Message>,
+		header: &B::Header,
+		tag: Option<Vec<u8>>,
+	) {
+		self.missing_proofs.entry(event_id).or_insert_with(|| MissingProof {
+			digest,
+			message,
+			block_hash: header.hash(),
+			block_number: *header.number(),
+			tag,
+			validator_set_id: self.validator_set.id,
+			attempts: 0,
+		});
+	}
+
+	/// Request overdue missing proofs from a peer
+	/// Only one request is in-flight at a time
+	fn request_missing_proofs(&mut self) {
+		if !self.proof_requests.is_empty() {
+			return;
+		}
+
+		let best_grandpa_block = self.best_grandpa_block;
+		let event_ids: Vec<EventId> = self
+			.missing_proofs
+			.iter()
+			.filter(|(_, missing)| {
+				missing.block_number.saturating_add(PROOF_REQUEST_DELAY.into()) <= best_grandpa_block
+			})
+			.map(|(event_id, _)| *event_id)
+			.take(MAX_PROOFS_PER_REQUEST)
+			.collect();
+		if event_ids.is_empty() {
+			return;
+		}
+
+		let peers = self.gossip_validator.peers();
+		if peers.is_empty() {
+			trace!(target: "ethy", "💎 no peers to request missing proofs: {:?}", event_ids);
+			return;
+		}
+		let peer = peers[self.next_proof_peer % peers.len()];
+		self.next_proof_peer = self.next_proof_peer.wrapping_add(1);
+
+		debug!(target: "ethy", "💎 requesting proofs: {:?} from: {:?}", event_ids, peer);
+		let request = EventProofRequest {
+			event_ids: event_ids.clone(),
+		};
+		let response = self.proof_request_network.request_proofs(peer, request.encode());
+		self.proof_requests.push(
+			async move {
+				let response = response.await.unwrap_or(Err(RequestFailure::Obsolete));
+				(peer, event_ids, response)
+			}
+			.boxed(),
+		);
+	}
+
+	/// Verify and store proofs returned by `peer` for the requested `event_ids`
+	fn handle_proof_response(
+		&mut self,
+		peer: PeerId,
+		event_ids: Vec<EventId>,
+		response: Result<Vec<u8>, RequestFailure>,
+	) {
+		let proofs = match response {
+			Ok(response) => match Vec::<VersionedEventProof>::decode(&mut &response[..]) {
+				Ok(proofs) => proofs,
+				Err(err) => {
+					debug!(target: "ethy", "💎 failed decoding proof response from: {:?}, {:?}", peer, err);
+					self.gossip_engine.lock().report(peer, COST_INVALID_PROOF);
+					Default::default()
+				}
+			},
+			Err(err) => {
+				debug!(target: "ethy", "💎 proof request to: {:?} failed: {:?}", peer, err);
+				Default::default()
+			}
+		};
+
+		for versioned_proof in proofs.into_iter() {
+			// only batch or single message proofs are requested
+			let mut proof = match versioned_proof {
+				VersionedEventProof::V1(proof) => proof,
+				VersionedEventProof::V2(proof) => {
+					debug!(target: "ethy", "💎 unrequested batched proof: {:?} from: {:?}", proof.event_id, peer);
+					continue;
+				}
+			};
+			let missing = match self.missing_proofs.get(&proof.event_id) {
+				Some(missing) if event_ids.contains(&proof.event_id) => missing.clone(),
+				_ => {
+					debug!(target: "ethy", "💎 unrequested proof: {:?} from: {:?}", proof.event_id, peer);
+					continue;
+				}
+			};
+
+			// verify against the validator set at the requesting block
+			// the runtime state may have been pruned, sets active while the worker ran are kept in aux
+			let validator_set = if missing.validator_set_id == self.validator_set.id {
+				Some(self.validator_set.clone())
+			} else {
+				aux_schema::load_validator_set(self.backend.as_ref(), missing.validator_set_id)
+			};
+			let validator_set = match validator_set {
+				Some(validator_set) => validator_set,
+				None => {
+					// the proof can't be verified, it's not the peer's fault
+					debug!(target: "ethy", "💎 unknown validator set: {:?} for proof: {:?}", missing.validator_set_id, proof.event_id);
+					continue;
+				}
+			};
+			if !verify_event_proof(&proof, &missing.digest, &validator_set) {
+				warn!(target: "ethy", "💎 invalid proof: {:?} from: {:?}", proof.event_id, peer);
+				self.gossip_engine.lock().report(peer, COST_INVALID_PROOF);
+				continue;
+			}
+
+			// use local event metadata
+			proof.block = missing.block_hash.as_ref().try_into().unwrap_or_default();
+			proof.tag = missing.tag;
+			let versioned_event_proof = VersionedEventProof::V1(proof.clone());
+
+			if Backend::insert_aux(
+				self.backend.as_ref(),
+				&[(
+					event_proof_key(proof.event_id).as_ref(),
+					versioned_event_proof.encode().as_ref(),
+				)],
+				&[],
+			)
+			.is_err()
+			{
+				warn!(target: "ethy", "💎 failed to store proof: {:?}", proof);
+				continue;
+			}
+			info!(target: "ethy", "💎 fetched proof for event: {:?} from: {:?}", proof.event_id, peer);
+
+			self.event_proof_sender.notify(versioned_event_proof);
+			self.store_batch_proofs(&proof);
+			self.missing_proofs.remove(&proof.event_id);
+			self.witness_record.clear(proof.event_id);
+			self.gossip_validator.mark_complete(proof.event_id);
+		}
+
+		// retry proofs the peer didn't have, with the next peer
+		for event_id in event_ids {
+			if let Some(missing) = self.missing_proofs.get_mut(&event_id) {
+				missing.attempts += 1;
+				if missing.attempts >= MAX_PROOF_REQUEST_ATTEMPTS {
+					warn!(target: "ethy", "💎 giving up requesting proof: {:?}", event_id);
+					self.missing_proofs.remove(&event_id);
+				}
+			}
+		}
 	}
 
 	/// Note an individual witness for a message
@@ -285,6 +748,11 @@ where
 		// As long as we have threshold of signatures the proof is valid.
 		info!(target: "ethy", "💎 got witness: {:?}", witness);
 
+		if let Some(conflicting) = self.witness_record.conflicting_witness(&witness) {
+			self.report_equivocation(&witness, conflicting);
+			return;
+		}
+
 		// only share if it's the first time witnessing the event
 		let first_observation = self.witness_record.note(&witness);
 		if !first_observation {
@@ -325,9 +793,7 @@ where
 				&[
 					// DB key is (engine_id + proof_id)
 					(
-						[&ETHY_ENGINE_ID[..], &event_proof.event_id.to_be_bytes()[..]]
-							.concat()
-							.as_ref(),
+						event_proof_key(event_proof.event_id).as_ref(),
 						versioned_event_proof.encode().as_ref(),
 					),
 				],
@@ -341,7 +807,10 @@ where
 			}
 			// Notify an subscribers that we've got a witness for a new message e.g. open RPC subscriptions
 			self.event_proof_sender.notify(versioned_event_proof);
+			// Prove the individual messages of a batch
+			self.store_batch_proofs(&event_proof);
 			// Remove from memory
+			self.missing_proofs.remove(&witness.event_id);
 			self.witness_record.clear(witness.event_id);
 			self.gossip_validator.mark_complete(witness.event_id);
 		} else {
@@ -377,6 +846,10 @@ where
 						return;
 					}
 				},
+				(peer, event_ids, response) = self.proof_requests.select_next_some() => {
+					self.handle_proof_response(peer, event_ids, response);
+					self.request_missing_proofs();
+				},
 				_ = gossip_engine.fuse() => {
 					error!(target: "ethy", "💎 Gossip engine has terminated.");
 					return;
@@ -387,8 +860,10 @@ where
 }
 
 pub struct ProofRequest {
-	/// raw message for signing
-	message: Vec<u8>,
+	/// digest for signing, the keccak hash of the message or Merkle root of a batch of messages
+	digest: [u8; 32],
+	/// The message of a signing request, proving equivocations on it requires the message
+	message: Option<Message>,
 	/// nonce/event Id of this request
 	event_id: EventId,
 	/// metadata tag about the proof
@@ -411,11 +886,19 @@ where
 			let res: Option<ProofRequest> =
 				match log.try_to::<ConsensusLog<Public>>(OpaqueDigestItemId::Consensus(&ETHY_ENGINE_ID)) {
 					Some(ConsensusLog::OpaqueSigningRequest((message, event_id))) => Some(ProofRequest {
-						message,
+						digest: sp_core::keccak_256(message.as_ref()),
+						message: Some(message),
 						event_id,
 						tag: None,
 						block: block_hash,
 					}),
+					Some(ConsensusLog::BatchSigningRequest((messages, batch_id))) => Some(ProofRequest {
+						digest: merkle_root(&batch_leaves(&messages)),
+						message: None,
+						event_id: batch_id,
+						tag: Some(BATCH_TAG.to_vec()),
+						block: block_hash,
+					}),
 					// Note: we also handle this in `find_authorities_change` to update the validator set
 					// here we want to convert it into an 'OpaqueSigningRequest` to create a proof of the validator set change
 					// we must do this before the validators officially change next session (~10 minutes)
@@ -423,7 +906,9 @@ where
 						let message =
 							abi_encode_validator_set_change(&next_validator_set, active_validator_set_id, event_id);
 						Some(ProofRequest {
-							message,
+							digest: sp_core::keccak_256(message.as_ref()),
+							// doesn't commit to the event like signing requests, equivocations aren't provable
+							message: None,
 							event_id,
 							tag: Some(b"sys:authority-change".to_vec()),
 							block: block_hash,
@@ -436,6 +921,14 @@ where
 		.collect()
 }
 
+/// Return the Merkle leaves of a batch of messages: `keccak(message)`
+fn batch_leaves(messages: &[(EventId, Vec<u8>)]) -> Vec<[u8; 32]> {
+	messages
+		.iter()
+		.map(|(_, message)| sp_core::keccak_256(message.as_ref()))
+		.collect()
+}
+
 /// Scan the `header` digest log for a ETHY validator set change. Return either the new
 /// validator set or `None` in case no validator set change has been signaled.
 fn find_authorities_change<B, Id>(header: &B::Header) -> Option<ValidatorSet<Id>>
@@ -538,4 +1031,58 @@ mod test {
 			"000000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000002560000000000000000000000000000000000000000000000000000000000000257000000000000000000000000000000000000000000000000000000000012d687000000000000000000000000000000000000000000000000000000000000000200000000000000000000000058dad74c38e9c4738bf3471f6aac6124f862faf500000000000000000000000058dad74c38e9c4738bf3471f6aac6124f862faf5"
 		);
 	}
+
+	#[test]
+	fn encode_validator_set_change_matches_bridge_contracts() {
+		// `crml/erc20-peg/test/setValidators.ts` verifies signatures of this message with the bridge contracts
+		let abi_encoded = abi_encode_validator_set_change(
+			&ValidatorSet::<Public> {
+				validators: vec![Public::from_slice(
+					// hardhat's first development account
+					&hex::decode(b"038318535b54105d4a7aae60c08fc45f9687181b4fdfc625bd1a753fa7397fed75").unwrap(),
+				)],
+				id: 2,
+			},
+			1,
+			7,
+		);
+		assert_eq!(
+			hex::encode(abi_encoded),
+			[
+				"0000000000000000000000000000000000000000000000000000000000000080",
+				"0000000000000000000000000000000000000000000000000000000000000002",
+				"0000000000000000000000000000000000000000000000000000000000000001",
+				"0000000000000000000000000000000000000000000000000000000000000007",
+				"0000000000000000000000000000000000000000000000000000000000000001",
+				"000000000000000000000000f39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+			]
+			.concat()
+		);
+	}
+
+	#[test]
+	fn extract_batch_proof_request() {
+		use sp_runtime::{
+			testing::{Block as TestBlock, ExtrinsicWrapper, Header as TestHeader},
+			DigestItem,
+		};
+
+		let messages = vec![(3_u64, b"hello".to_vec()), (4_u64, b"world".to_vec())];
+		let mut header = TestHeader::new_from_number(1);
+		header.digest_mut().push(DigestItem::Consensus(
+			ETHY_ENGINE_ID,
+			ConsensusLog::<Public>::BatchSigningRequest((messages.clone(), 5)).encode(),
+		));
+
+		let requests = extract_proof_requests::<TestBlock<ExtrinsicWrapper<u64>>>(&header, 1);
+		assert_eq!(requests.len(), 1);
+		let request = &requests[0];
+		assert_eq!(request.event_id, 5);
+		assert_eq!(request.tag, Some(BATCH_TAG.to_vec()));
+		assert_eq!(
+			request.digest,
+			merkle_root(&[sp_core::keccak_256(b"hello"), sp_core::keccak_256(b"world")])
+		);
+		assert_eq!(batch_leaves(&messages)[1], sp_core::keccak_256(b"world"));
+	}
 }
@@ -0,0 +1,142 @@
+/* Copyright 2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+//! Benson node client for Ethy event proofs
+
+use std::net::TcpStream;
+
+use benson_primitives::eth::{EventId, ValidatorSetId, VersionedEventProof};
+use codec::Decode;
+use ethereum_types::H256;
+use log::{debug, trace};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sp_core::Bytes;
+use tungstenite::{Message, WebSocket};
+
+use crate::{rpc, Error};
+
+/// The kind of message proven by an event proof (`ethy_getEventProofCalldata`)
+#[derive(Deserialize, Debug, PartialEq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum ProofKind {
+	/// An ERC20 withdrawal
+	Withdraw,
+	/// A validator set change
+	SetValidators,
+}
+
+/// An event proof prepared for submission to Ethereum (`ethy_getEventProofCalldata`)
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EventProofCalldata {
+	/// The proven event
+	pub event_id: EventId,
+	/// The validator set which signed the proof
+	pub validator_set_id: ValidatorSetId,
+	/// Merkle branch of the message in its signed batch. Empty if the message was signed individually
+	#[serde(default)]
+	pub branch: Vec<H256>,
+	/// The kind of proof, `None` if unknown
+	pub kind: Option<ProofKind>,
+	/// ABI encoded calldata to submit the proof, `None` if the kind of proof is unknown
+	pub calldata: Option<Bytes>,
+}
+
+/// A source of Ethy event proofs
+pub trait ProofSource {
+	/// Return the proof for `event_id` prepared for submission to Ethereum, `None` if unknown
+	fn event_proof_calldata(&self, event_id: EventId) -> Result<Option<EventProofCalldata>, Error>;
+}
+
+/// A Benson node JSON-RPC HTTP endpoint
+pub struct BensonHttp {
+	uri: String,
+}
+
+impl BensonHttp {
+	pub fn new(uri: &str) -> Self {
+		Self { uri: uri.into() }
+	}
+}
+
+impl ProofSource for BensonHttp {
+	fn event_proof_calldata(&self, event_id: EventId) -> Result<Option<EventProofCalldata>, Error> {
+		rpc::call(&self.uri, "ethy_getEventProofCalldata", json!([event_id]))
+	}
+}
+
+/// An `ethy_subscribeEventProofs` subscription
+pub struct EventProofSubscription {
+	socket: WebSocket<TcpStream>,
+}
+
+impl EventProofSubscription {
+	/// Subscribe to event proofs from the node websocket endpoint `uri` e.g. `ws://127.0.0.1:9944`
+	pub fn connect(uri: &str) -> Result<Self, Error> {
+		let url = url::Url::parse(uri).map_err(|err| Error::Config(format!("invalid benson ws URI: {}", err)))?;
+		let address = url
+			.socket_addrs(|| Some(9944))
+			.map_err(|err| Error::Transport(err.to_string()))?;
+		let stream = TcpStream::connect(address.as_slice()).map_err(|err| Error::Transport(err.to_string()))?;
+		let (mut socket, _) = tungstenite::client(uri, stream).map_err(|err| Error::Transport(err.to_string()))?;
+
+		let request = json!({
+			"jsonrpc": "2.0",
+			"id": 1,
+			"method": "ethy_subscribeEventProofs",
+			"params": [],
+		});
+		socket
+			.write_message(Message::Text(request.to_string()))
+			.map_err(|err| Error::Transport(err.to_string()))?;
+		debug!(target: "ethy-relayer", "subscribed to event proofs at: {}", uri);
+
+		Ok(Self { socket })
+	}
+
+	/// Wait for the next event proof, returns its event id
+	pub fn next_event_id(&mut self) -> Result<EventId, Error> {
+		loop {
+			let message = self
+				.socket
+				.read_message()
+				.map_err(|err| Error::Transport(err.to_string()))?;
+			let text = match message {
+				Message::Text(text) => text,
+				Message::Close(_) => return Err(Error::Transport("subscription closed".into())),
+				_ => continue,
+			};
+			let notification: Value = serde_json::from_str(&text).map_err(|err| Error::Response(err.to_string()))?;
+			if let Some(error) = notification.get("error") {
+				return Err(Error::Rpc(format!("ethy_subscribeEventProofs: {}", error)));
+			}
+			// notifications carry the SCALE encoded proof, skip the subscription id response
+			let encoded_proof = match notification.pointer("/params/result") {
+				Some(result) => {
+					serde_json::from_value::<Bytes>(result.clone()).map_err(|err| Error::Response(err.to_string()))?
+				}
+				None => {
+					trace!(target: "ethy-relayer", "ignoring message: {}", text);
+					continue;
+				}
+			};
+			let proof = VersionedEventProof::decode(&mut &encoded_proof[..])
+				.map_err(|err| Error::Response(format!("invalid event proof: {}", err)))?;
+
+			return Ok(proof.event_id());
+		}
+	}
+}
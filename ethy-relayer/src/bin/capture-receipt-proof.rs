@@ -0,0 +1,517 @@
+/* Copyright 2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+//! Capture an Ethereum receipt proof fixture for crml-eth-bridge tests
+//!
+//! Fetches the block of a tx from an Ethereum archive node, rebuilds its transactions and receipts tries and prints
+//! the header, tx, receipt and proofs as Rust consts in the style of `crml/eth-bridge/src/fixtures.rs`.
+//! Nothing is printed unless the encoded header hashes to the block hash and the rebuilt tries have the header's roots.
+//!
+//! The node must support `eth_getRawTransactionByHash` (e.g. geth, erigon, reth).
+
+use std::collections::HashMap;
+
+use ethereum_types::U256;
+use rlp::{Rlp, RlpStream};
+use serde_json::{json, Value};
+use sp_core::keccak_256;
+use structopt::StructOpt;
+
+/// Header fields, in RLP order
+const HEADER_FIELDS: [&str; 15] = [
+	"parentHash",
+	"sha3Uncles",
+	"miner",
+	"stateRoot",
+	"transactionsRoot",
+	"receiptsRoot",
+	"logsBloom",
+	"difficulty",
+	"number",
+	"gasLimit",
+	"gasUsed",
+	"timestamp",
+	"extraData",
+	"mixHash",
+	"nonce",
+];
+/// Header fields added by later forks, in RLP order
+const OPTIONAL_HEADER_FIELDS: [&str; 6] = [
+	"baseFeePerGas",
+	"withdrawalsRoot",
+	"blobGasUsed",
+	"excessBlobGas",
+	"parentBeaconBlockRoot",
+	"requestsHash",
+];
+/// Header fields encoded as integers
+const INT_FIELDS: [&str; 8] = [
+	"difficulty",
+	"number",
+	"gasLimit",
+	"gasUsed",
+	"timestamp",
+	"baseFeePerGas",
+	"blobGasUsed",
+	"excessBlobGas",
+];
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+	name = "capture-receipt-proof",
+	about = "Prints an Ethereum tx receipt proof as crml-eth-bridge test fixtures"
+)]
+struct Opts {
+	/// Ethereum archive node JSON-RPC endpoint
+	eth_http: String,
+	/// Hash of the mined tx to capture
+	tx_hash: String,
+}
+
+/// A captured tx and the proofs of it & its receipt
+struct Fixture {
+	number: u64,
+	hash: String,
+	tx_hash: String,
+	index: usize,
+	header: Vec<u8>,
+	tx: Vec<u8>,
+	receipt: Vec<u8>,
+	tx_proof: Vec<Vec<u8>>,
+	receipt_proof: Vec<Vec<u8>>,
+}
+
+/// Call `method` with `params` at the JSON-RPC endpoint `uri`
+fn rpc(uri: &str, method: &str, params: Value) -> Result<Value, String> {
+	let request = json!({
+		"jsonrpc": "2.0",
+		"id": 1,
+		"method": method,
+		"params": params,
+	});
+	let response: Value = ureq::post(uri)
+		.send_json(request)
+		.map_err(|err| format!("{}: {}", method, err))?
+		.into_json()
+		.map_err(|err| format!("{}: {}", method, err))?;
+	if let Some(error) = response.get("error") {
+		return Err(format!("{}: {}", method, error));
+	}
+	Ok(response.get("result").cloned().unwrap_or(Value::Null))
+}
+
+/// Decode a `0x` prefixed hex string field of `value`
+fn bytes(value: &Value, field: &str) -> Result<Vec<u8>, String> {
+	let hex_string = value[field]
+		.as_str()
+		.ok_or_else(|| format!("missing field: {}", field))?;
+	hex::decode(hex_string.trim_start_matches("0x")).map_err(|_| format!("invalid hex field: {}", field))
+}
+
+/// Decode a `0x` prefixed hex quantity field of `value`
+fn int(value: &Value, field: &str) -> Result<U256, String> {
+	let hex_string = value[field]
+		.as_str()
+		.ok_or_else(|| format!("missing field: {}", field))?;
+	U256::from_str_radix(hex_string.trim_start_matches("0x"), 16).map_err(|_| format!("invalid int field: {}", field))
+}
+
+/// RLP encode the header of `block` as returned by `eth_getBlockByHash`
+fn encode_header(block: &Value) -> Result<Vec<u8>, String> {
+	let fields: Vec<&str> = HEADER_FIELDS
+		.iter()
+		.chain(OPTIONAL_HEADER_FIELDS.iter().filter(|field| !block[**field].is_null()))
+		.copied()
+		.collect();
+	let mut stream = RlpStream::new_list(fields.len());
+	for field in fields {
+		if INT_FIELDS.contains(&field) {
+			stream.append(&int(block, field)?);
+		} else {
+			stream.append(&bytes(block, field)?);
+		}
+	}
+	Ok(stream.out().to_vec())
+}
+
+/// Consensus encode `receipt` as returned by `eth_getTransactionReceipt`
+fn encode_receipt(receipt: &Value) -> Result<Vec<u8>, String> {
+	let logs = receipt["logs"].as_array().ok_or("missing field: logs")?;
+	let mut stream = RlpStream::new_list(4);
+	// pre-byzantium receipts commit to a state root instead of a status
+	if receipt["root"].is_string() {
+		stream.append(&bytes(receipt, "root")?);
+	} else {
+		stream.append(&int(receipt, "status")?);
+	}
+	stream.append(&int(receipt, "cumulativeGasUsed")?);
+	stream.append(&bytes(receipt, "logsBloom")?);
+	stream.begin_list(logs.len());
+	for log in logs {
+		let topics = log["topics"].as_array().ok_or("missing field: topics")?;
+		stream.begin_list(3);
+		stream.append(&bytes(log, "address")?);
+		stream.begin_list(topics.len());
+		for topic in topics {
+			let topic = topic.as_str().ok_or("invalid topic")?;
+			stream.append(&hex::decode(topic.trim_start_matches("0x")).map_err(|_| "invalid topic")?);
+		}
+		stream.append(&bytes(log, "data")?);
+	}
+
+	let tx_type = if receipt["type"].is_string() {
+		int(receipt, "type")?.low_u32() as u8
+	} else {
+		0
+	};
+	let payload = stream.out().to_vec();
+	Ok(if tx_type == 0 {
+		payload
+	} else {
+		[&[tx_type][..], &payload].concat()
+	})
+}
+
+fn nibbles(key: &[u8]) -> Vec<u8> {
+	key.iter().flat_map(|byte| vec![byte >> 4, byte & 0x0f]).collect()
+}
+
+/// Hex prefix encode a nibble path
+fn hex_prefix(nibbles: &[u8], is_leaf: bool) -> Vec<u8> {
+	let flag = if is_leaf { 2 } else { 0 } + (nibbles.len() % 2) as u8;
+	let mut prefixed = if nibbles.len() % 2 == 1 {
+		vec![flag]
+	} else {
+		vec![flag, 0]
+	};
+	prefixed.extend_from_slice(nibbles);
+	prefixed.chunks(2).map(|pair| pair[0] * 16 + pair[1]).collect()
+}
+
+/// An in-memory Merkle-Patricia trie built from all of its (key, value) pairs at once
+struct Trie {
+	/// The encoded root node
+	root_node: Vec<u8>,
+	/// Encoded nodes referenced by hash
+	nodes: HashMap<[u8; 32], Vec<u8>>,
+}
+
+impl Trie {
+	fn new(mut items: Vec<(Vec<u8>, Vec<u8>)>) -> Self {
+		let mut trie = Self {
+			root_node: rlp::NULL_RLP.to_vec(),
+			nodes: Default::default(),
+		};
+		items.sort();
+		let items: Vec<(Vec<u8>, Vec<u8>)> = items.into_iter().map(|(key, value)| (nibbles(&key), value)).collect();
+		if !items.is_empty() {
+			trie.root_node = trie.build(&items, 0);
+		}
+		trie
+	}
+
+	fn root(&self) -> [u8; 32] {
+		keccak_256(&self.root_node)
+	}
+
+	/// Append a reference to the encoded child `node`, inlined if shorter than a hash
+	fn append_reference(&mut self, stream: &mut RlpStream, node: Vec<u8>) {
+		if node.len() < 32 {
+			stream.append_raw(&node, 1);
+		} else {
+			let node_hash = keccak_256(&node);
+			stream.append(&node_hash.to_vec());
+			self.nodes.insert(node_hash, node);
+		}
+	}
+
+	/// Encode the node of sorted `items` (nibble keys) below `depth`
+	fn build(&mut self, items: &[(Vec<u8>, Vec<u8>)], depth: usize) -> Vec<u8> {
+		let mut stream = RlpStream::new();
+		if let [(key, value)] = items {
+			stream.begin_list(2);
+			stream.append(&hex_prefix(&key[depth..], true));
+			stream.append(value);
+			return stream.out().to_vec();
+		}
+
+		let first = &items[0].0;
+		let mut shared = 0;
+		while items
+			.iter()
+			.all(|(key, _)| key.len() > depth + shared && key[depth + shared] == first[depth + shared])
+		{
+			shared += 1;
+		}
+		if shared > 0 {
+			let child = self.build(items, depth + shared);
+			stream.begin_list(2);
+			stream.append(&hex_prefix(&first[depth..depth + shared], false));
+			self.append_reference(&mut stream, child);
+			return stream.out().to_vec();
+		}
+
+		stream.begin_list(17);
+		for nibble in 0..16_u8 {
+			let children: Vec<(Vec<u8>, Vec<u8>)> = items
+				.iter()
+				.filter(|(key, _)| key.len() > depth && key[depth] == nibble)
+				.cloned()
+				.collect();
+			if children.is_empty() {
+				stream.append_empty_data();
+			} else {
+				let child = self.build(&children, depth + 1);
+				self.append_reference(&mut stream, child);
+			}
+		}
+		match items.iter().find(|(key, _)| key.len() == depth) {
+			Some((_, value)) => stream.append(value),
+			None => stream.append_empty_data(),
+		};
+		stream.out().to_vec()
+	}
+
+	/// The hashed nodes on the path to `key`, root first
+	fn proof(&self, key: &[u8]) -> Vec<Vec<u8>> {
+		let mut path = nibbles(key);
+		let mut proof = vec![self.root_node.clone()];
+		let mut node = self.root_node.clone();
+		loop {
+			let rlp = Rlp::new(&node);
+			let child = if rlp.item_count().unwrap_or_default() == 17 {
+				if path.is_empty() {
+					return proof;
+				}
+				rlp.at(path.remove(0) as usize).expect("branch has 17 items")
+			} else {
+				let prefix = nibbles(rlp.val_at::<Vec<u8>>(0).expect("node path is bytes").as_slice());
+				if prefix[0] >= 2 {
+					return proof;
+				}
+				let prefix_len = if prefix[0] % 2 == 0 {
+					prefix.len() - 2
+				} else {
+					prefix.len() - 1
+				};
+				path.drain(..prefix_len);
+				rlp.at(1).expect("extension has a child")
+			};
+			node = if child.is_list() {
+				child.as_raw().to_vec()
+			} else {
+				let child_hash = child.data().expect("child is bytes");
+				if child_hash.is_empty() {
+					return proof;
+				}
+				let mut node_hash = [0_u8; 32];
+				node_hash.copy_from_slice(child_hash);
+				let child = self.nodes[&node_hash].clone();
+				proof.push(child.clone());
+				child
+			};
+		}
+	}
+}
+
+/// Fetch `tx_hash` and its block from `uri`, the captured header and tries are checked against the node's hashes
+fn capture(uri: &str, tx_hash: &str) -> Result<Fixture, String> {
+	let tx = rpc(uri, "eth_getTransactionByHash", json!([tx_hash]))?;
+	if tx.is_null() || tx["blockHash"].is_null() {
+		return Err(format!("{} is not mined", tx_hash));
+	}
+	let block = rpc(uri, "eth_getBlockByHash", json!([tx["blockHash"], false]))?;
+
+	let header = encode_header(&block)?;
+	if keccak_256(&header)[..] != bytes(&block, "hash")?[..] {
+		return Err("encoded header doesn't match the block hash, unsupported header fields?".into());
+	}
+
+	let tx_hashes = block["transactions"].as_array().ok_or("missing field: transactions")?;
+	let mut raw_txs = vec![];
+	let mut receipts = vec![];
+	for hash in tx_hashes {
+		let raw_tx = rpc(uri, "eth_getRawTransactionByHash", json!([hash]))?;
+		raw_txs.push(
+			hex::decode(raw_tx.as_str().unwrap_or_default().trim_start_matches("0x")).map_err(|_| "invalid raw tx")?,
+		);
+		receipts.push(encode_receipt(&rpc(uri, "eth_getTransactionReceipt", json!([hash]))?)?);
+	}
+	let keys: Vec<Vec<u8>> = (0..raw_txs.len() as u64)
+		.map(|index| rlp::encode(&index).to_vec())
+		.collect();
+	let tx_trie = Trie::new(keys.iter().cloned().zip(raw_txs.iter().cloned()).collect());
+	let receipt_trie = Trie::new(keys.iter().cloned().zip(receipts.iter().cloned()).collect());
+	if tx_trie.root()[..] != bytes(&block, "transactionsRoot")?[..] {
+		return Err("rebuilt transactions root doesn't match the header".into());
+	}
+	if receipt_trie.root()[..] != bytes(&block, "receiptsRoot")?[..] {
+		return Err("rebuilt receipts root doesn't match the header".into());
+	}
+
+	let index = int(&tx, "transactionIndex")?.as_usize();
+	Ok(Fixture {
+		number: int(&block, "number")?.as_u64(),
+		hash: block["hash"].as_str().unwrap_or_default().to_string(),
+		tx_hash: tx_hash.to_string(),
+		index,
+		header,
+		tx: raw_txs[index].clone(),
+		receipt: receipts[index].clone(),
+		tx_proof: tx_trie.proof(&keys[index]),
+		receipt_proof: receipt_trie.proof(&keys[index]),
+	})
+}
+
+fn bytes_const(doc: &str, name: &str, value: &[u8]) -> String {
+	format!(
+		"/// {}\npub const {}: [u8; {}] = hex!(\"{}\");\n",
+		doc,
+		name,
+		value.len(),
+		hex::encode(value)
+	)
+}
+
+fn proof_const(doc: &str, name: &str, proof: &[Vec<u8>]) -> String {
+	let nodes: String = proof
+		.iter()
+		.map(|node| format!("\t&hex!(\"{}\"),\n", hex::encode(node)))
+		.collect();
+	format!(
+		"/// {}\npub const {}: [&[u8]; {}] = [\n{}];\n",
+		doc,
+		name,
+		proof.len(),
+		nodes
+	)
+}
+
+/// Format `fixture` as Rust consts
+fn to_rust(fixture: &Fixture) -> String {
+	let Fixture { number, index, .. } = fixture;
+	let prefix = format!("MAINNET_BLOCK_{}", number);
+	let source = format!("captured from mainnet block {} ({})", number, fixture.hash);
+	[
+		bytes_const(
+			&format!("Header {}", source),
+			&format!("{}_HEADER", prefix),
+			&fixture.header,
+		),
+		bytes_const(
+			&format!("Tx {} {}", fixture.tx_hash, source),
+			&format!("{}_TX_{}", prefix, index),
+			&fixture.tx,
+		),
+		bytes_const(
+			&format!("Receipt of tx {} {}", index, source),
+			&format!("{}_RECEIPT_{}", prefix, index),
+			&fixture.receipt,
+		),
+		proof_const(
+			&format!("Proof of tx {} in the transactions trie {}", index, source),
+			&format!("{}_TX_PROOF_{}", prefix, index),
+			&fixture.tx_proof,
+		),
+		proof_const(
+			&format!("Proof of receipt {} in the receipts trie {}", index, source),
+			&format!("{}_RECEIPT_PROOF_{}", prefix, index),
+			&fixture.receipt_proof,
+		),
+	]
+	.join("\n")
+}
+
+fn main() {
+	let opts = Opts::from_args();
+	match capture(&opts.eth_http, &opts.tx_hash) {
+		Ok(fixture) => println!("{}", to_rust(&fixture)),
+		Err(err) => {
+			eprintln!("capture failed: {}", err);
+			std::process::exit(1);
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn puppy_trie() -> Trie {
+		Trie::new(
+			[
+				("do", "verb"),
+				("dog", "puppy"),
+				("doge", "coin"),
+				("horse", "stallion"),
+			]
+			.iter()
+			.map(|(key, value)| (key.as_bytes().to_vec(), value.as_bytes().to_vec()))
+			.collect(),
+		)
+	}
+
+	#[test]
+	fn empty_trie_root() {
+		assert_eq!(
+			Trie::new(vec![]).root(),
+			hash("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421")
+		);
+	}
+
+	/// The ethereum/tests `trietest` puppy trie, see `PUPPY_TRIE_*` in crml/eth-bridge/src/fixtures.rs
+	#[test]
+	fn puppy_trie_root_and_proofs() {
+		let trie = puppy_trie();
+		assert_eq!(
+			trie.root(),
+			hash("5991bb8c6514148a29db676a14ac506cd2cd5775ace63c30a4fe457715e9ac84")
+		);
+		assert_eq!(
+			trie.proof(b"horse"),
+			vec![
+				hex::decode("e216a0bd3ee507e6c67cfefca98f84be47c1bbc009315fabc4405db4ba32190374572a").unwrap(),
+				hex::decode("f84080808080a094a9f95bd89698e4da1812e0518053813b4d5b87caaf6b3c6fa57e9e50c0ff68808080cf85206f727365887374616c6c696f6e8080808080808080").unwrap(),
+			]
+		);
+		assert_eq!(
+			trie.proof(b"doge"),
+			vec![
+				hex::decode("e216a0bd3ee507e6c67cfefca98f84be47c1bbc009315fabc4405db4ba32190374572a").unwrap(),
+				hex::decode("f84080808080a094a9f95bd89698e4da1812e0518053813b4d5b87caaf6b3c6fa57e9e50c0ff68808080cf85206f727365887374616c6c696f6e8080808080808080").unwrap(),
+				hex::decode("e482006fa0d43b87fdcd4217013ccc92d04662e12d36e4cc25dc690077cd821a1956fc3e36").unwrap(),
+				hex::decode("f3808080808080de17dc808080808080c63584636f696e8080808080808080808570757070798080808080808080808476657262").unwrap(),
+			]
+		);
+	}
+
+	/// The only tx of mainnet block 46147, see `MAINNET_TX_*_46147` in crml/eth-bridge/src/fixtures.rs
+	#[test]
+	fn mainnet_transactions_root() {
+		let tx = hex::decode("f86780862d79883d2000825208945df9b87991262f6ba471f09758cde1c0fc1de734827a69801ca088ff6cf0fefd94db46111149ae4bfc179e9b94721fffd821d38d16464b3f71d0a045e0aff800961cfce805daef7016b9b675c137a6a41a548f7b60a3484c06a33a").unwrap();
+		let key = rlp::encode(&0_u64).to_vec();
+		let trie = Trie::new(vec![(key, tx)]);
+		assert_eq!(
+			trie.root(),
+			hash("4513310fcb9f6f616972a3b948dc5d547f280849a87ebb5af0191f98b87be598")
+		);
+	}
+
+	fn hash(hex_string: &str) -> [u8; 32] {
+		let mut value = [0_u8; 32];
+		value.copy_from_slice(&hex::decode(hex_string).unwrap());
+		value
+	}
+}
@@ -0,0 +1,171 @@
+/* Copyright 2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+//! The relayer's local DB of handled event proofs
+//!
+//! Stored as a JSON file, rewritten atomically (write + rename) on each update.
+
+use std::{
+	collections::BTreeMap,
+	fs,
+	path::{Path, PathBuf},
+};
+
+use benson_primitives::eth::EventId;
+use ethereum_types::H256;
+use serde::{Deserialize, Serialize};
+
+use crate::Error;
+
+/// The outcome of relaying an event proof
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum RelayStatus {
+	/// The proof was submitted in a successful transaction
+	Relayed { tx_hash: H256 },
+	/// The proof isn't relayed by this relayer e.g. its kind isn't relayed or it was already relayed
+	Skipped { reason: String },
+	/// The proof can't be relayed e.g. the gas estimate exceeds the limit
+	Failed { reason: String },
+	/// Relaying failed for now e.g. the signing validator set isn't active yet or the RPC is unavailable
+	/// Retried on the next catch-up
+	Pending { reason: String },
+}
+
+impl RelayStatus {
+	/// Whether the proof is done with, `Pending` proofs are retried
+	pub fn is_final(&self) -> bool {
+		!matches!(self, RelayStatus::Pending { .. })
+	}
+}
+
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct RelayState {
+	/// The lowest event id not yet scanned on catch-up
+	next_event_id: EventId,
+	/// Event proofs handled by the relayer
+	handled: BTreeMap<EventId, RelayStatus>,
+}
+
+/// Tracks which event proofs were relayed
+pub struct RelayDb {
+	path: PathBuf,
+	state: RelayState,
+}
+
+impl RelayDb {
+	/// Open the DB at `path`, creating it if it doesn't exist
+	/// Catch-up starts from `from_event_id` for a new DB
+	pub fn open(path: &Path, from_event_id: EventId) -> Result<Self, Error> {
+		let state = match fs::read(path) {
+			Ok(encoded) => serde_json::from_slice(&encoded).map_err(|err| Error::Db(err.to_string()))?,
+			Err(err) if err.kind() == std::io::ErrorKind::NotFound => RelayState {
+				next_event_id: from_event_id,
+				..Default::default()
+			},
+			Err(err) => return Err(Error::Db(err.to_string())),
+		};
+		let db = Self {
+			path: path.to_path_buf(),
+			state,
+		};
+		db.flush()?;
+
+		Ok(db)
+	}
+
+	/// The lowest event id not yet scanned on catch-up
+	pub fn next_event_id(&self) -> EventId {
+		self.state.next_event_id
+	}
+
+	/// Set the lowest event id not yet scanned on catch-up
+	pub fn set_next_event_id(&mut self, event_id: EventId) -> Result<(), Error> {
+		self.state.next_event_id = event_id;
+		self.flush()
+	}
+
+	/// Return the status of an event proof, `None` if it hasn't been handled
+	pub fn status(&self, event_id: EventId) -> Option<&RelayStatus> {
+		self.state.handled.get(&event_id)
+	}
+
+	/// Return the pending proofs the catch-up cursor has passed
+	pub fn pending(&self) -> Vec<EventId> {
+		self.state
+			.handled
+			.range(..self.state.next_event_id)
+			.filter(|(_, status)| !status.is_final())
+			.map(|(event_id, _)| *event_id)
+			.collect()
+	}
+
+	/// Record the status of an event proof
+	pub fn set_status(&mut self, event_id: EventId, status: RelayStatus) -> Result<(), Error> {
+		self.state.handled.insert(event_id, status);
+		self.flush()
+	}
+
+	/// Write the state to disk
+	fn flush(&self) -> Result<(), Error> {
+		let encoded = serde_json::to_vec_pretty(&self.state).map_err(|err| Error::Db(err.to_string()))?;
+		let tmp_path = self.path.with_extension("tmp");
+		fs::write(&tmp_path, encoded)
+			.and_then(|_| fs::rename(&tmp_path, &self.path))
+			.map_err(|err| Error::Db(err.to_string()))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn state_persists() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("relayer.json");
+
+		let mut db = RelayDb::open(&path, 5).unwrap();
+		assert_eq!(db.next_event_id(), 5);
+		assert_eq!(db.status(5), None);
+
+		let relayed = RelayStatus::Relayed {
+			tx_hash: H256::repeat_byte(1),
+		};
+		db.set_status(5, relayed.clone()).unwrap();
+		db.set_next_event_id(6).unwrap();
+
+		// `from_event_id` only applies to a new DB
+		let db = RelayDb::open(&path, 0).unwrap();
+		assert_eq!(db.next_event_id(), 6);
+		assert_eq!(db.status(5), Some(&relayed));
+	}
+
+	#[test]
+	fn pending_proofs_behind_the_cursor() {
+		let dir = tempfile::tempdir().unwrap();
+		let mut db = RelayDb::open(&dir.path().join("relayer.json"), 0).unwrap();
+		let pending = RelayStatus::Pending {
+			reason: "connection refused".into(),
+		};
+		db.set_status(1, pending.clone()).unwrap();
+		db.set_status(2, RelayStatus::Skipped { reason: "".into() }).unwrap();
+		db.set_status(4, pending).unwrap();
+		db.set_next_event_id(3).unwrap();
+
+		assert_eq!(db.pending(), vec![1]);
+	}
+}
@@ -0,0 +1,106 @@
+/* Copyright 2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+//! Ethereum JSON-RPC client
+
+use ethereum_types::{H160, H256, U256, U64};
+use serde::Deserialize;
+use serde_json::json;
+use sp_core::Bytes;
+
+use crate::{rpc, transaction::Transaction, Error};
+
+/// The Ethereum JSON-RPC methods used by the relayer
+pub trait EthRpc {
+	/// The chain id for EIP-155 signatures
+	fn chain_id(&self) -> Result<u64, Error>;
+	/// The number of transactions mined from `address` i.e. its next nonce
+	fn transaction_count(&self, address: H160) -> Result<U256, Error>;
+	/// The current gas price
+	fn gas_price(&self) -> Result<U256, Error>;
+	/// Execute a read only call of contract `to` with `data` at the latest block, returns the ABI encoded output
+	fn call(&self, to: H160, data: &[u8]) -> Result<Vec<u8>, Error>;
+	/// Estimate the gas used by `tx` sent from `from`
+	/// Fails with `Error::Rpc` if the call would revert
+	fn estimate_gas(&self, from: H160, tx: &Transaction) -> Result<U256, Error>;
+	/// Submit a signed raw transaction, returns the tx hash
+	fn send_raw_transaction(&self, raw_tx: &[u8]) -> Result<H256, Error>;
+	/// Whether the tx succeeded, `None` if it hasn't been mined
+	fn transaction_status(&self, tx_hash: H256) -> Result<Option<bool>, Error>;
+}
+
+/// An Ethereum JSON-RPC HTTP endpoint e.g. geth, anvil or hardhat
+pub struct EthHttp {
+	uri: String,
+}
+
+impl EthHttp {
+	pub fn new(uri: &str) -> Self {
+		Self { uri: uri.into() }
+	}
+}
+
+#[derive(Deserialize)]
+struct Receipt {
+	/// `0x1` success, `0x0` reverted
+	status: Option<U64>,
+}
+
+impl EthRpc for EthHttp {
+	fn chain_id(&self) -> Result<u64, Error> {
+		rpc::call::<U64>(&self.uri, "eth_chainId", json!([])).map(|id| id.as_u64())
+	}
+	fn transaction_count(&self, address: H160) -> Result<U256, Error> {
+		rpc::call(&self.uri, "eth_getTransactionCount", json!([address, "latest"]))
+	}
+	fn gas_price(&self) -> Result<U256, Error> {
+		rpc::call(&self.uri, "eth_gasPrice", json!([]))
+	}
+	fn call(&self, to: H160, data: &[u8]) -> Result<Vec<u8>, Error> {
+		rpc::call::<Bytes>(
+			&self.uri,
+			"eth_call",
+			json!([{
+				"to": to,
+				"data": format!("0x{}", hex::encode(data)),
+			}, "latest"]),
+		)
+		.map(|output| output.0)
+	}
+	fn estimate_gas(&self, from: H160, tx: &Transaction) -> Result<U256, Error> {
+		rpc::call(
+			&self.uri,
+			"eth_estimateGas",
+			json!([{
+				"from": from,
+				"to": tx.to,
+				"gasPrice": tx.gas_price,
+				"value": tx.value,
+				"data": format!("0x{}", hex::encode(&tx.data)),
+			}]),
+		)
+	}
+	fn send_raw_transaction(&self, raw_tx: &[u8]) -> Result<H256, Error> {
+		rpc::call(
+			&self.uri,
+			"eth_sendRawTransaction",
+			json!([format!("0x{}", hex::encode(raw_tx))]),
+		)
+	}
+	fn transaction_status(&self, tx_hash: H256) -> Result<Option<bool>, Error> {
+		let receipt: Option<Receipt> = rpc::call(&self.uri, "eth_getTransactionReceipt", json!([tx_hash]))?;
+		Ok(receipt.map(|receipt| receipt.status == Some(U64::one())))
+	}
+}
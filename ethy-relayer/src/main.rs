@@ -0,0 +1,190 @@
+/* Copyright 2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+//! Ethy relayer
+//!
+//! Subscribes to Ethy event proofs from a Benson node and submits them to the Ethereum bridge contract.
+
+use std::{path::PathBuf, str::FromStr, thread, time::Duration};
+
+use ethereum_types::{H160, H256, U256};
+use log::{error, info, warn};
+use structopt::StructOpt;
+
+mod benson;
+mod db;
+mod eth;
+mod relayer;
+mod rpc;
+mod transaction;
+
+use benson::{BensonHttp, EventProofSubscription, ProofKind};
+use db::RelayDb;
+use eth::EthHttp;
+use relayer::{RelayConfig, Relayer};
+use transaction::Signer;
+
+/// Env var holding the relayer's hex encoded Ethereum private key, if `--eth-key-file` isn't given
+const ETH_KEY_ENV: &str = "ETHY_RELAYER_ETH_KEY";
+/// Delay before reconnecting to the Benson node
+const RECONNECT_DELAY: Duration = Duration::from_secs(6);
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("invalid config: {0}")]
+	Config(String),
+	#[error("relay DB: {0}")]
+	Db(String),
+	#[error("rpc transport: {0}")]
+	Transport(String),
+	#[error("rpc error: {0}")]
+	Rpc(String),
+	#[error("unexpected rpc response: {0}")]
+	Response(String),
+	#[error("tx: {0:?} not mined in time")]
+	Timeout(H256),
+}
+
+/// Parse a hex Ethereum address, optionally `0x` prefixed
+fn parse_address(address: &str) -> Result<H160, String> {
+	H160::from_str(address.trim_start_matches("0x")).map_err(|_| "invalid Ethereum address".into())
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+	name = "ethy-relayer",
+	about = "Relays Ethy event proofs to the Ethereum bridge contract"
+)]
+struct Opts {
+	/// Benson node websocket RPC endpoint, for the event proof subscription
+	#[structopt(long = "benson-ws", default_value = "ws://127.0.0.1:9944")]
+	benson_ws: String,
+	/// Benson node HTTP RPC endpoint, for querying event proofs
+	#[structopt(long = "benson-http", default_value = "http://127.0.0.1:9933")]
+	benson_http: String,
+	/// Ethereum JSON-RPC endpoint transactions are submitted to
+	#[structopt(long = "eth-http", default_value = "http://127.0.0.1:8545")]
+	eth_http: String,
+	/// Address of the bridge contract proofs are relayed to, run one relayer per contract
+	#[structopt(long = "bridge-contract", parse(try_from_str = parse_address))]
+	bridge_contract: H160,
+	/// File holding the relayer's hex encoded Ethereum private key. Defaults to the `ETHY_RELAYER_ETH_KEY` env var
+	/// The account should be dedicated to the relayer, unconfirmed transactions are replaced by nonce
+	#[structopt(long = "eth-key-file", parse(from_os_str))]
+	eth_key_file: Option<PathBuf>,
+	/// Path of the relay DB
+	#[structopt(long = "db", default_value = "ethy-relayer.json", parse(from_os_str))]
+	db: PathBuf,
+	/// Event id to start relaying from with a new relay DB
+	#[structopt(long = "from-event-id", default_value = "0")]
+	from_event_id: u64,
+	/// Also relay batched ERC20 withdrawals (validator set changes are always relayed)
+	#[structopt(long = "relay-withdrawals")]
+	relay_withdrawals: bool,
+	/// Value (wei) sent with relayed withdrawals to cover the bridge withdrawal fee
+	#[structopt(long = "withdrawal-fee", default_value = "100000000000000")]
+	withdrawal_fee: u128,
+	/// Max. gas limit of a relay transaction
+	#[structopt(long = "max-gas-limit", default_value = "8000000")]
+	max_gas_limit: u64,
+	/// Max. gas price (gwei) of a relay transaction
+	#[structopt(long = "max-gas-price", default_value = "500")]
+	max_gas_price: u64,
+	/// Max. number of times to retry a failed submission
+	#[structopt(long = "max-retries", default_value = "5")]
+	max_retries: u32,
+	/// Delay (seconds) before the first retry, doubled on each retry
+	#[structopt(long = "retry-delay", default_value = "6")]
+	retry_delay: u64,
+	/// Time (seconds) to wait for a submitted transaction to be mined before retrying
+	#[structopt(long = "confirmation-timeout", default_value = "300")]
+	confirmation_timeout: u64,
+}
+
+/// Read the relayer's Ethereum key
+fn signer(opts: &Opts) -> Result<Signer, Error> {
+	let private_key = match opts.eth_key_file {
+		Some(ref path) => {
+			std::fs::read_to_string(path).map_err(|err| Error::Config(format!("eth key file: {}", err)))?
+		}
+		None => std::env::var(ETH_KEY_ENV)
+			.map_err(|_| Error::Config(format!("pass --eth-key-file or set {}", ETH_KEY_ENV)))?,
+	};
+	Signer::from_hex(&private_key)
+}
+
+fn run(opts: Opts) -> Result<(), Error> {
+	let mut relay_kinds = vec![ProofKind::SetValidators];
+	if opts.relay_withdrawals {
+		relay_kinds.push(ProofKind::Withdraw);
+	}
+	let config = RelayConfig {
+		bridge_contract: opts.bridge_contract,
+		relay_kinds,
+		withdrawal_fee: opts.withdrawal_fee.into(),
+		max_gas_limit: opts.max_gas_limit.into(),
+		max_gas_price: U256::from(opts.max_gas_price) * 1_000_000_000_u64,
+		max_retries: opts.max_retries,
+		retry_delay: Duration::from_secs(opts.retry_delay),
+		confirmation_timeout: Duration::from_secs(opts.confirmation_timeout),
+		poll_interval: Duration::from_secs(2),
+	};
+	let db = RelayDb::open(&opts.db, opts.from_event_id)?;
+	let mut relayer = Relayer::new(
+		BensonHttp::new(&opts.benson_http),
+		EthHttp::new(&opts.eth_http),
+		signer(&opts)?,
+		db,
+		config,
+	)?;
+
+	loop {
+		// relay proofs generated while disconnected
+		if let Err(err) = relayer.catch_up() {
+			error!(target: "ethy-relayer", "catch-up failed: {}", err);
+			thread::sleep(RECONNECT_DELAY);
+			continue;
+		}
+
+		match EventProofSubscription::connect(&opts.benson_ws) {
+			Ok(mut subscription) => loop {
+				match subscription.next_event_id() {
+					Ok(event_id) => {
+						if let Err(err) = relayer.handle_event_proof(event_id) {
+							error!(target: "ethy-relayer", "failed handling proof: {:?}: {}", event_id, err);
+						}
+					}
+					Err(err) => {
+						warn!(target: "ethy-relayer", "event proof subscription dropped: {}", err);
+						break;
+					}
+				}
+			},
+			Err(err) => warn!(target: "ethy-relayer", "failed subscribing to event proofs: {}", err),
+		}
+
+		info!(target: "ethy-relayer", "reconnecting in {:?}", RECONNECT_DELAY);
+		thread::sleep(RECONNECT_DELAY);
+	}
+}
+
+fn main() {
+	env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("ethy-relayer=info")).init();
+
+	if let Err(err) = run(Opts::from_args()) {
+		error!(target: "ethy-relayer", "{}", err);
+		std::process::exit(1);
+	}
+}
@@ -0,0 +1,731 @@
+/* Copyright 2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+//! Submits Ethy event proofs to the Ethereum bridge contract
+
+use std::{
+	thread,
+	time::{Duration, Instant},
+};
+
+use benson_primitives::eth::EventId;
+use ethereum_types::{H160, H256, U256};
+use log::{debug, info, warn};
+
+use crate::{
+	benson::{EventProofCalldata, ProofKind, ProofSource},
+	db::{RelayDb, RelayStatus},
+	eth::EthRpc,
+	transaction::{Signer, Transaction},
+	Error,
+};
+
+/// `validatorSetId()` of the bridge contract
+const VALIDATOR_SET_ID_SELECTOR: [u8; 4] = [0x50, 0x97, 0x4e, 0x31];
+/// Revert reason of bridge contract calls for proofs which were already relayed
+/// e.g. "validator set id replayed", "nonce replayed"
+const REPLAYED_REVERT: &str = "replayed";
+
+/// Relayer settings
+#[derive(Debug, Clone)]
+pub struct RelayConfig {
+	/// The bridge contract proofs are submitted to
+	pub bridge_contract: H160,
+	/// The kinds of proof to relay
+	pub relay_kinds: Vec<ProofKind>,
+	/// Value (wei) sent with withdrawals to cover the bridge withdrawal fee
+	pub withdrawal_fee: U256,
+	/// Max. gas limit of a relay transaction
+	pub max_gas_limit: U256,
+	/// Max. gas price (wei) of a relay transaction
+	pub max_gas_price: U256,
+	/// Max. number of times to retry a failed submission
+	pub max_retries: u32,
+	/// Delay before the first retry, doubled on each retry
+	pub retry_delay: Duration,
+	/// Time to wait for a submitted transaction to be mined before retrying
+	pub confirmation_timeout: Duration,
+	/// Interval to poll for transaction receipts
+	pub poll_interval: Duration,
+}
+
+/// Relays event proofs from a Benson node to an Ethereum bridge contract
+pub struct Relayer<P, E> {
+	proofs: P,
+	eth: E,
+	signer: Signer,
+	chain_id: u64,
+	db: RelayDb,
+	config: RelayConfig,
+}
+
+impl<P: ProofSource, E: EthRpc> Relayer<P, E> {
+	pub fn new(proofs: P, eth: E, signer: Signer, db: RelayDb, config: RelayConfig) -> Result<Self, Error> {
+		let chain_id = eth.chain_id()?;
+		info!(target: "ethy-relayer", "relaying from: {:?} to: {:?} on chain: {}", signer.address, config.bridge_contract, chain_id);
+
+		Ok(Self {
+			proofs,
+			eth,
+			signer,
+			chain_id,
+			db,
+			config,
+		})
+	}
+
+	/// Retry pending proofs, then relay known proofs from the DB catch-up cursor up to the first unknown proof
+	/// Validator set changes are relayed strictly in order, catch-up stops at a pending validator set change as
+	/// later changes (and proofs) are signed by the validator set it activates
+	pub fn catch_up(&mut self) -> Result<(), Error> {
+		for event_id in self.db.pending() {
+			if let Some(proof) = self.proofs.event_proof_calldata(event_id)? {
+				self.relay(proof)?;
+			}
+		}
+
+		loop {
+			let event_id = self.db.next_event_id();
+			match self.proofs.event_proof_calldata(event_id)? {
+				Some(proof) => {
+					let kind = proof.kind;
+					let status = self.relay(proof)?;
+					if kind == Some(ProofKind::SetValidators) && !status.is_final() {
+						return Ok(());
+					}
+					self.db.set_next_event_id(event_id.saturating_add(1))?;
+				}
+				None => return Ok(()),
+			}
+		}
+	}
+
+	/// Handle a newly generated proof for `event_id`
+	/// Proofs may be generated out of order, earlier proofs are relayed by `catch_up` once known
+	pub fn handle_event_proof(&mut self, event_id: EventId) -> Result<(), Error> {
+		self.catch_up()?;
+		if event_id < self.db.next_event_id() || self.db.status(event_id).map_or(false, RelayStatus::is_final) {
+			return Ok(());
+		}
+		match self.proofs.event_proof_calldata(event_id)? {
+			// relayed in order by `catch_up`
+			Some(proof) if proof.kind == Some(ProofKind::SetValidators) => {
+				debug!(target: "ethy-relayer", "validator set change: {:?} waits on earlier proofs", event_id);
+				Ok(())
+			}
+			Some(proof) => self.relay(proof).map(|_| ()),
+			None => {
+				warn!(target: "ethy-relayer", "notified proof: {:?} is unknown", event_id);
+				Ok(())
+			}
+		}
+	}
+
+	/// Relay `proof` and record the outcome, proofs are only handled once unless pending
+	fn relay(&mut self, proof: EventProofCalldata) -> Result<RelayStatus, Error> {
+		let event_id = proof.event_id;
+		if let Some(status) = self.db.status(event_id).filter(|status| status.is_final()) {
+			return Ok(status.clone());
+		}
+
+		let status = match (proof.kind, proof.calldata) {
+			// `withdraw` pays `msg.sender`, only `withdrawBatched` may be submitted on behalf of the beneficiary
+			(Some(ProofKind::Withdraw), _) if proof.branch.is_empty() => RelayStatus::Skipped {
+				reason: "unbatched withdrawals are submitted by their beneficiary".into(),
+			},
+			(Some(kind), Some(calldata)) if self.config.relay_kinds.contains(&kind) => {
+				// proofs are only valid while the signing validator set is active on the bridge contract
+				match self.active_validator_set_id() {
+					Ok(active) if active < proof.validator_set_id.into() => RelayStatus::Pending {
+						reason: format!(
+							"validator set: {:?} not yet active, active: {}",
+							proof.validator_set_id, active
+						),
+					},
+					Ok(active) if active > proof.validator_set_id.into() => RelayStatus::Skipped {
+						reason: format!(
+							"signed by validator set: {:?}, active: {}",
+							proof.validator_set_id, active
+						),
+					},
+					Ok(_) => {
+						info!(target: "ethy-relayer", "relaying {:?} proof: {:?}, validator set: {:?}", kind, event_id, proof.validator_set_id);
+						self.submit(kind, calldata.0)
+					}
+					Err(err) => RelayStatus::Pending {
+						reason: format!("validator set query failed: {}", err),
+					},
+				}
+			}
+			(Some(kind), _) => RelayStatus::Skipped {
+				reason: format!("{:?} proofs are not relayed", kind),
+			},
+			(None, _) => RelayStatus::Skipped {
+				reason: "unknown proof kind".into(),
+			},
+		};
+		match status {
+			RelayStatus::Relayed { .. } => info!(target: "ethy-relayer", "relayed proof: {:?}, {:?}", event_id, status),
+			RelayStatus::Skipped { .. } => {
+				debug!(target: "ethy-relayer", "skipped proof: {:?}, {:?}", event_id, status)
+			}
+			RelayStatus::Failed { .. } => {
+				warn!(target: "ethy-relayer", "failed relaying proof: {:?}, {:?}", event_id, status)
+			}
+			RelayStatus::Pending { .. } => {
+				warn!(target: "ethy-relayer", "proof: {:?} pending, retrying on catch-up, {:?}", event_id, status)
+			}
+		}
+
+		self.db.set_status(event_id, status.clone())?;
+		Ok(status)
+	}
+
+	/// The id of the validator set active on the bridge contract (`validatorSetId()`)
+	fn active_validator_set_id(&self) -> Result<U256, Error> {
+		let output = self.eth.call(self.config.bridge_contract, &VALIDATOR_SET_ID_SELECTOR)?;
+		if output.len() != 32 {
+			return Err(Error::Response(format!("validatorSetId: {}", hex::encode(output))));
+		}
+		Ok(U256::from_big_endian(&output))
+	}
+
+	/// Submit `calldata` to the bridge contract, retrying failed or unconfirmed transactions
+	/// The proof is left pending if all retries fail
+	fn submit(&self, kind: ProofKind, calldata: Vec<u8>) -> RelayStatus {
+		let value = if kind == ProofKind::Withdraw {
+			self.config.withdrawal_fee
+		} else {
+			U256::zero()
+		};
+		let mut submitted = Vec::<H256>::new();
+		let mut last_error = String::new();
+
+		for attempt in 0..=self.config.max_retries {
+			if attempt > 0 {
+				thread::sleep(self.config.retry_delay * 2_u32.saturating_pow(attempt - 1));
+				// an earlier transaction may have been mined in the meantime
+				for tx_hash in submitted.iter() {
+					if let Ok(Some(true)) = self.eth.transaction_status(*tx_hash) {
+						return RelayStatus::Relayed { tx_hash: *tx_hash };
+					}
+				}
+			}
+
+			match self.try_submit(value, &calldata, attempt, &mut submitted) {
+				Ok(status) => return status,
+				Err(err) => {
+					warn!(target: "ethy-relayer", "relay attempt: {} failed: {}", attempt, err);
+					last_error = err.to_string();
+				}
+			}
+		}
+
+		RelayStatus::Pending { reason: last_error }
+	}
+
+	/// Sign and submit a transaction and wait for it to be mined
+	/// The gas price is raised 12.5% on each attempt so unconfirmed transactions are replaced
+	fn try_submit(
+		&self,
+		value: U256,
+		calldata: &[u8],
+		attempt: u32,
+		submitted: &mut Vec<H256>,
+	) -> Result<RelayStatus, Error> {
+		let gas_price = self.eth.gas_price()? * (8 + attempt) / 8;
+		let mut tx = Transaction {
+			nonce: self.eth.transaction_count(self.signer.address)?,
+			gas_price: gas_price.min(self.config.max_gas_price),
+			gas_limit: U256::zero(),
+			to: self.config.bridge_contract,
+			value,
+			data: calldata.to_vec(),
+		};
+
+		let gas = match self.eth.estimate_gas(self.signer.address, &tx) {
+			Ok(gas) => gas,
+			// the proof was already relayed e.g. by another relayer
+			Err(Error::Rpc(reason)) if reason.contains(REPLAYED_REVERT) => {
+				return Ok(RelayStatus::Skipped {
+					reason: format!("already relayed: {}", reason),
+				})
+			}
+			// e.g. withdrawals are paused, the call may succeed later
+			Err(Error::Rpc(reason)) => {
+				return Ok(RelayStatus::Pending {
+					reason: format!("call reverts: {}", reason),
+				})
+			}
+			Err(err) => return Err(err),
+		};
+		if gas > self.config.max_gas_limit {
+			return Ok(RelayStatus::Failed {
+				reason: format!("gas estimate: {} exceeds limit: {}", gas, self.config.max_gas_limit),
+			});
+		}
+		// allow for the estimate being off
+		tx.gas_limit = (gas * 6 / 5).min(self.config.max_gas_limit);
+
+		let tx_hash = self.eth.send_raw_transaction(&tx.sign(&self.signer, self.chain_id))?;
+		submitted.push(tx_hash);
+		info!(target: "ethy-relayer", "submitted tx: {:?}, nonce: {}, gas price: {}", tx_hash, tx.nonce, tx.gas_price);
+
+		let deadline = Instant::now() + self.config.confirmation_timeout;
+		loop {
+			match self.eth.transaction_status(tx_hash)? {
+				Some(true) => return Ok(RelayStatus::Relayed { tx_hash }),
+				// e.g. another relayer's transaction was mined first, checked again on catch-up
+				Some(false) => {
+					return Ok(RelayStatus::Pending {
+						reason: format!("tx: {:?} reverted", tx_hash),
+					})
+				}
+				None if Instant::now() >= deadline => return Err(Error::Timeout(tx_hash)),
+				None => thread::sleep(self.config.poll_interval),
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::eth::EthHttp;
+	use benson_primitives::eth::ValidatorSetId;
+	use sp_core::{keccak_256, Bytes};
+	use std::{cell::RefCell, collections::BTreeMap, convert::TryFrom};
+
+	#[derive(Default)]
+	struct MockProofs(BTreeMap<EventId, EventProofCalldata>);
+
+	impl ProofSource for MockProofs {
+		fn event_proof_calldata(&self, event_id: EventId) -> Result<Option<EventProofCalldata>, Error> {
+			Ok(self.0.get(&event_id).cloned())
+		}
+	}
+
+	#[derive(Default)]
+	struct MockEth {
+		/// Raw txs submitted
+		sent: RefCell<Vec<Vec<u8>>>,
+		/// Fail this many submissions before accepting
+		send_failures: RefCell<u32>,
+		/// The gas estimate, reverts if `None`
+		estimate: Option<U256>,
+		/// The revert reason of the gas estimate, "validator set id replayed" if `None`
+		revert: Option<&'static str>,
+		/// The validator set id active on the bridge contract
+		validator_set_id: RefCell<u64>,
+	}
+
+	impl EthRpc for MockEth {
+		fn chain_id(&self) -> Result<u64, Error> {
+			Ok(31_337)
+		}
+		fn transaction_count(&self, _address: H160) -> Result<U256, Error> {
+			Ok(self.sent.borrow().len().into())
+		}
+		fn gas_price(&self) -> Result<U256, Error> {
+			Ok(1_000_000_000_u64.into())
+		}
+		fn call(&self, _to: H160, data: &[u8]) -> Result<Vec<u8>, Error> {
+			assert_eq!(data, VALIDATOR_SET_ID_SELECTOR);
+			let mut output = [0_u8; 32];
+			U256::from(*self.validator_set_id.borrow()).to_big_endian(&mut output);
+			Ok(output.to_vec())
+		}
+		fn estimate_gas(&self, _from: H160, _tx: &Transaction) -> Result<U256, Error> {
+			self.estimate.ok_or_else(|| {
+				Error::Rpc(format!(
+					"execution reverted: {}",
+					self.revert.unwrap_or("validator set id replayed")
+				))
+			})
+		}
+		fn send_raw_transaction(&self, raw_tx: &[u8]) -> Result<H256, Error> {
+			let mut failures = self.send_failures.borrow_mut();
+			if *failures > 0 {
+				*failures -= 1;
+				return Err(Error::Transport("connection refused".into()));
+			}
+			self.sent.borrow_mut().push(raw_tx.to_vec());
+			Ok(H256::from(sp_core::keccak_256(raw_tx)))
+		}
+		fn transaction_status(&self, tx_hash: H256) -> Result<Option<bool>, Error> {
+			let mined = self
+				.sent
+				.borrow()
+				.iter()
+				.any(|raw_tx| H256::from(sp_core::keccak_256(raw_tx)) == tx_hash);
+			Ok(if mined { Some(true) } else { None })
+		}
+	}
+
+	fn proof(event_id: EventId, kind: Option<ProofKind>) -> EventProofCalldata {
+		EventProofCalldata {
+			event_id,
+			validator_set_id: 0,
+			branch: vec![],
+			kind,
+			calldata: kind.map(|_| Bytes(vec![1, 2, 3, 4])),
+		}
+	}
+
+	fn config() -> RelayConfig {
+		RelayConfig {
+			bridge_contract: H160::repeat_byte(1),
+			relay_kinds: vec![ProofKind::SetValidators],
+			withdrawal_fee: U256::zero(),
+			max_gas_limit: 8_000_000.into(),
+			max_gas_price: 500_000_000_000_u64.into(),
+			max_retries: 2,
+			retry_delay: Duration::from_millis(1),
+			confirmation_timeout: Duration::from_millis(10),
+			poll_interval: Duration::from_millis(1),
+		}
+	}
+
+	fn relayer(proofs: MockProofs, eth: MockEth) -> (Relayer<MockProofs, MockEth>, tempfile::TempDir) {
+		let dir = tempfile::tempdir().unwrap();
+		let db = RelayDb::open(&dir.path().join("relayer.json"), 0).unwrap();
+		// hardhat/anvil dev account #0
+		let signer = Signer::from_hex("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+		(Relayer::new(proofs, eth, signer, db, config()).unwrap(), dir)
+	}
+
+	#[test]
+	fn relays_validator_set_changes() {
+		let mut proofs = MockProofs::default();
+		proofs.0.insert(0, proof(0, Some(ProofKind::SetValidators)));
+		proofs.0.insert(1, proof(1, Some(ProofKind::Withdraw)));
+		proofs.0.insert(2, proof(2, None));
+		let eth = MockEth {
+			estimate: Some(100_000.into()),
+			..Default::default()
+		};
+		let (mut relayer, _dir) = relayer(proofs, eth);
+
+		relayer.catch_up().unwrap();
+		assert_eq!(relayer.db.next_event_id(), 3);
+		assert_eq!(relayer.eth.sent.borrow().len(), 1);
+		assert!(matches!(relayer.db.status(0), Some(RelayStatus::Relayed { .. })));
+		assert!(matches!(relayer.db.status(1), Some(RelayStatus::Skipped { .. })));
+		assert!(matches!(relayer.db.status(2), Some(RelayStatus::Skipped { .. })));
+
+		// proofs are relayed once
+		relayer.handle_event_proof(0).unwrap();
+		assert_eq!(relayer.eth.sent.borrow().len(), 1);
+	}
+
+	#[test]
+	fn relays_out_of_order_proofs() {
+		let mut proofs = MockProofs::default();
+		let mut withdrawal = proof(1, Some(ProofKind::Withdraw));
+		withdrawal.branch = vec![H256::repeat_byte(1)];
+		proofs.0.insert(1, withdrawal);
+		proofs.0.insert(2, proof(2, Some(ProofKind::SetValidators)));
+		let eth = MockEth {
+			estimate: Some(100_000.into()),
+			..Default::default()
+		};
+		let (mut relayer, _dir) = relayer(proofs, eth);
+		relayer.config.relay_kinds.push(ProofKind::Withdraw);
+
+		// proof 0 is pending, catch-up waits on it
+		relayer.handle_event_proof(1).unwrap();
+		assert_eq!(relayer.db.next_event_id(), 0);
+		assert!(matches!(relayer.db.status(1), Some(RelayStatus::Relayed { .. })));
+		assert_eq!(relayer.eth.sent.borrow().len(), 1);
+
+		// validator set changes are only relayed in order
+		relayer.handle_event_proof(2).unwrap();
+		assert_eq!(relayer.db.status(2), None);
+		relayer.proofs.0.insert(0, proof(0, None));
+		relayer.handle_event_proof(0).unwrap();
+		assert_eq!(relayer.db.next_event_id(), 3);
+		assert!(matches!(relayer.db.status(2), Some(RelayStatus::Relayed { .. })));
+		assert_eq!(relayer.eth.sent.borrow().len(), 2);
+	}
+
+	#[test]
+	fn relays_validator_set_changes_in_set_id_order() {
+		let set_change = |event_id: EventId, validator_set_id: ValidatorSetId| {
+			let mut proof = proof(event_id, Some(ProofKind::SetValidators));
+			proof.validator_set_id = validator_set_id;
+			proof
+		};
+		let mut proofs = MockProofs::default();
+		proofs.0.insert(0, set_change(0, 1));
+		proofs.0.insert(1, set_change(1, 2));
+		let eth = MockEth {
+			estimate: Some(100_000.into()),
+			..Default::default()
+		};
+		let (mut relayer, _dir) = relayer(proofs, eth);
+
+		// signing validator set 1 isn't active yet, later changes wait on it
+		relayer.catch_up().unwrap();
+		assert!(matches!(relayer.db.status(0), Some(RelayStatus::Pending { .. })));
+		assert_eq!(relayer.db.status(1), None);
+		assert_eq!(relayer.db.next_event_id(), 0);
+		assert!(relayer.eth.sent.borrow().is_empty());
+
+		// set 1 is activated e.g. by another relayer
+		*relayer.eth.validator_set_id.borrow_mut() = 1;
+		relayer.catch_up().unwrap();
+		assert!(matches!(relayer.db.status(0), Some(RelayStatus::Relayed { .. })));
+		assert!(matches!(relayer.db.status(1), Some(RelayStatus::Pending { .. })));
+		assert_eq!(relayer.db.next_event_id(), 1);
+
+		*relayer.eth.validator_set_id.borrow_mut() = 2;
+		relayer.catch_up().unwrap();
+		assert!(matches!(relayer.db.status(1), Some(RelayStatus::Relayed { .. })));
+		assert_eq!(relayer.db.next_event_id(), 2);
+		assert_eq!(relayer.eth.sent.borrow().len(), 2);
+
+		// signed by a retired validator set
+		relayer.proofs.0.insert(2, set_change(2, 1));
+		relayer.catch_up().unwrap();
+		assert!(matches!(relayer.db.status(2), Some(RelayStatus::Skipped { .. })));
+	}
+
+	#[test]
+	fn retries_reverting_withdrawals() {
+		let mut proofs = MockProofs::default();
+		let mut withdrawal = proof(0, Some(ProofKind::Withdraw));
+		withdrawal.branch = vec![H256::repeat_byte(1)];
+		proofs.0.insert(0, withdrawal);
+		proofs.0.insert(1, proof(1, None));
+		let eth = MockEth {
+			revert: Some("withdrawals paused"),
+			..Default::default()
+		};
+		let (mut relayer, _dir) = relayer(proofs, eth);
+		relayer.config.relay_kinds.push(ProofKind::Withdraw);
+
+		// a pending withdrawal doesn't hold up later proofs
+		relayer.catch_up().unwrap();
+		assert!(matches!(relayer.db.status(0), Some(RelayStatus::Pending { .. })));
+		assert_eq!(relayer.db.next_event_id(), 2);
+
+		relayer.eth.estimate = Some(100_000.into());
+		relayer.catch_up().unwrap();
+		assert!(matches!(relayer.db.status(0), Some(RelayStatus::Relayed { .. })));
+		assert_eq!(relayer.eth.sent.borrow().len(), 1);
+	}
+
+	#[test]
+	fn relays_batched_withdrawals_only() {
+		let mut proofs = MockProofs::default();
+		proofs.0.insert(0, proof(0, Some(ProofKind::Withdraw)));
+		let mut batched = proof(1, Some(ProofKind::Withdraw));
+		batched.branch = vec![H256::repeat_byte(1)];
+		proofs.0.insert(1, batched);
+		let eth = MockEth {
+			estimate: Some(100_000.into()),
+			..Default::default()
+		};
+		let (mut relayer, _dir) = relayer(proofs, eth);
+		relayer.config.relay_kinds.push(ProofKind::Withdraw);
+
+		relayer.catch_up().unwrap();
+		assert!(matches!(relayer.db.status(0), Some(RelayStatus::Skipped { .. })));
+		assert!(matches!(relayer.db.status(1), Some(RelayStatus::Relayed { .. })));
+		assert_eq!(relayer.eth.sent.borrow().len(), 1);
+	}
+
+	#[test]
+	fn skips_reverting_calls() {
+		let mut proofs = MockProofs::default();
+		proofs.0.insert(0, proof(0, Some(ProofKind::SetValidators)));
+		let (mut relayer, _dir) = relayer(proofs, MockEth::default());
+
+		relayer.catch_up().unwrap();
+		assert!(relayer.eth.sent.borrow().is_empty());
+		assert!(matches!(relayer.db.status(0), Some(RelayStatus::Skipped { .. })));
+	}
+
+	#[test]
+	fn retries_failed_submissions() {
+		let mut proofs = MockProofs::default();
+		proofs.0.insert(0, proof(0, Some(ProofKind::SetValidators)));
+		let eth = MockEth {
+			estimate: Some(100_000.into()),
+			send_failures: RefCell::new(2),
+			..Default::default()
+		};
+		let (mut relayer, _dir) = relayer(proofs, eth);
+
+		// 2 failures, succeeds on the last retry
+		relayer.handle_event_proof(0).unwrap();
+		assert!(matches!(relayer.db.status(0), Some(RelayStatus::Relayed { .. })));
+
+		// left pending after all retries
+		relayer.proofs.0.insert(1, proof(1, Some(ProofKind::SetValidators)));
+		*relayer.eth.send_failures.borrow_mut() = 3;
+		relayer.handle_event_proof(1).unwrap();
+		assert!(matches!(relayer.db.status(1), Some(RelayStatus::Pending { .. })));
+		assert_eq!(relayer.db.next_event_id(), 1);
+		assert_eq!(relayer.eth.sent.borrow().len(), 1);
+
+		// retried on the next catch-up
+		relayer.catch_up().unwrap();
+		assert!(matches!(relayer.db.status(1), Some(RelayStatus::Relayed { .. })));
+		assert_eq!(relayer.db.next_event_id(), 2);
+	}
+
+	#[test]
+	fn gas_limit_is_capped() {
+		let mut proofs = MockProofs::default();
+		proofs.0.insert(0, proof(0, Some(ProofKind::SetValidators)));
+		let eth = MockEth {
+			estimate: Some(9_000_000.into()),
+			..Default::default()
+		};
+		let (mut relayer, _dir) = relayer(proofs, eth);
+
+		relayer.catch_up().unwrap();
+		assert!(relayer.eth.sent.borrow().is_empty());
+		assert!(matches!(relayer.db.status(0), Some(RelayStatus::Failed { .. })));
+	}
+
+	/// ABI encode a `uint`
+	fn uint(value: u64) -> [u8; 32] {
+		let mut word = [0_u8; 32];
+		U256::from(value).to_big_endian(&mut word);
+		word
+	}
+
+	/// A validator set change to `validators` proven by event `event_id`, signed by validator set `next_set_id - 1`
+	/// `calldata` is `setValidators(address[],uint256,uint256,uint8[],bytes32[],bytes32[])`
+	fn signed_set_change(
+		event_id: EventId,
+		next_set_id: ValidatorSetId,
+		validators: &[H160],
+		signers: &[libsecp256k1::SecretKey],
+	) -> EventProofCalldata {
+		let address = |address: &H160| H256::from(*address).to_fixed_bytes();
+		// the message signed by ethy-gadget `abi.encode(address[] next, nextId, activeId, eventId)`
+		let message = [
+			vec![uint(0x80), uint(next_set_id), uint(next_set_id - 1), uint(event_id)],
+			vec![uint(validators.len() as u64)],
+			validators.iter().map(address).collect(),
+		]
+		.concat()
+		.concat();
+		let digest = libsecp256k1::Message::parse(&keccak_256(&message));
+		let (mut v, mut r, mut s) = (vec![], vec![], vec![]);
+		for signer in signers {
+			let (signature, recovery_id) = libsecp256k1::sign(&digest, signer);
+			let signature = signature.serialize();
+			v.push(uint(recovery_id.serialize() as u64 + 27));
+			r.push(<[u8; 32]>::try_from(&signature[..32]).unwrap());
+			s.push(<[u8; 32]>::try_from(&signature[32..]).unwrap());
+		}
+
+		let n = validators.len() as u64;
+		let m = signers.len() as u64;
+		let words = [
+			vec![
+				uint(0xc0),
+				uint(next_set_id),
+				uint(event_id),
+				uint(0xc0 + 32 * (1 + n)),
+				uint(0xc0 + 32 * (2 + n + m)),
+				uint(0xc0 + 32 * (3 + n + 2 * m)),
+				uint(n),
+			],
+			validators.iter().map(address).collect(),
+			vec![uint(m)],
+			v,
+			vec![uint(m)],
+			r,
+			vec![uint(m)],
+			s,
+		]
+		.concat();
+		let calldata = [&[0x7f, 0x32, 0xe7, 0xe1][..], &words.concat()[..]].concat();
+
+		EventProofCalldata {
+			event_id,
+			validator_set_id: next_set_id - 1,
+			branch: vec![],
+			kind: Some(ProofKind::SetValidators),
+			calldata: Some(Bytes(calldata)),
+		}
+	}
+
+	/// Relays validator set changes to a `BensonBridge` on a local dev chain
+	/// ```bash
+	/// anvil # or `npx hardhat node`
+	/// # deploys a fresh `BensonBridge` with account #0 as the only validator of set 0 (`initValidators`)
+	/// cd crml/erc20-peg && yarn deploy
+	/// ETHY_RELAYER_TEST_BRIDGE=<BensonBridge address> cargo test -p ethy-relayer -- --ignored
+	/// ```
+	#[test]
+	#[ignore]
+	fn relays_validator_set_changes_on_local_node() {
+		let bridge = std::env::var("ETHY_RELAYER_TEST_BRIDGE").expect("set ETHY_RELAYER_TEST_BRIDGE");
+		let eth_http = std::env::var("ETHY_RELAYER_TEST_ETH_HTTP").unwrap_or_else(|_| "http://127.0.0.1:8545".into());
+		let dev_key = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+		// dev account #0 relays and is the only validator, seeded by the deploy script
+		let signer = Signer::from_hex(dev_key).unwrap();
+		let validator = libsecp256k1::SecretKey::parse_slice(&hex::decode(dev_key).unwrap()).unwrap();
+		let validators = vec![signer.address];
+		let signers = [validator];
+
+		let mut proofs = MockProofs::default();
+		proofs.0.insert(0, signed_set_change(0, 1, &validators, &signers));
+		let set_change_3 = signed_set_change(2, 3, &validators, &signers);
+		proofs.0.insert(2, set_change_3.clone());
+
+		let dir = tempfile::tempdir().unwrap();
+		let db = RelayDb::open(&dir.path().join("relayer.json"), 0).unwrap();
+		let config = RelayConfig {
+			bridge_contract: crate::parse_address(&bridge).unwrap(),
+			confirmation_timeout: Duration::from_secs(30),
+			poll_interval: Duration::from_millis(100),
+			..config()
+		};
+		let mut relayer = Relayer::new(proofs, EthHttp::new(&eth_http), signer, db, config).unwrap();
+		assert_eq!(
+			relayer.active_validator_set_id().unwrap(),
+			U256::zero(),
+			"deploy a new bridge contract"
+		);
+
+		// the change to set 2 (event 1) is unknown, the change to set 3 waits on it
+		relayer.handle_event_proof(2).unwrap();
+		assert!(matches!(relayer.db.status(0), Some(RelayStatus::Relayed { .. })));
+		assert_eq!(relayer.active_validator_set_id().unwrap(), U256::from(1));
+		assert_eq!(relayer.db.status(2), None);
+		// validator set 2 isn't active yet
+		assert!(matches!(
+			relayer.relay(set_change_3).unwrap(),
+			RelayStatus::Pending { .. }
+		));
+
+		relayer
+			.proofs
+			.0
+			.insert(1, signed_set_change(1, 2, &validators, &signers));
+		relayer.catch_up().unwrap();
+		assert!(matches!(relayer.db.status(1), Some(RelayStatus::Relayed { .. })));
+		assert!(matches!(relayer.db.status(2), Some(RelayStatus::Relayed { .. })));
+		assert_eq!(relayer.db.next_event_id(), 3);
+		assert_eq!(relayer.active_validator_set_id().unwrap(), U256::from(3));
+	}
+}
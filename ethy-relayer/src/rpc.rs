@@ -0,0 +1,54 @@
+/* Copyright 2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+//! A minimal JSON-RPC over HTTP client
+
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+use crate::Error;
+
+/// HTTP request timeout
+const REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Call `method` with `params` at the JSON-RPC endpoint `uri`
+/// JSON-RPC error responses are returned as `Error::Rpc`, connection failures as `Error::Transport`
+pub fn call<T: DeserializeOwned>(uri: &str, method: &str, params: Value) -> Result<T, Error> {
+	let request = json!({
+		"jsonrpc": "2.0",
+		"id": 1,
+		"method": method,
+		"params": params,
+	});
+	let response: Value = match ureq::post(uri).timeout(REQUEST_TIMEOUT).send_json(request) {
+		Ok(response) => response.into_json().map_err(|err| Error::Response(err.to_string()))?,
+		// some clients respond to JSON-RPC errors with an HTTP error status
+		Err(ureq::Error::Status(_, response)) => response
+			.into_json()
+			.map_err(|err| Error::Transport(format!("{}: {}", method, err)))?,
+		Err(err) => return Err(Error::Transport(format!("{}: {}", method, err))),
+	};
+
+	if let Some(error) = response.get("error") {
+		let message = error
+			.get("message")
+			.and_then(Value::as_str)
+			.map(ToString::to_string)
+			.unwrap_or_else(|| error.to_string());
+		return Err(Error::Rpc(format!("{}: {}", method, message)));
+	}
+	let result = response.get("result").cloned().unwrap_or(Value::Null);
+	serde_json::from_value(result).map_err(|err| Error::Response(format!("{}: {}", method, err)))
+}
@@ -0,0 +1,141 @@
+/* Copyright 2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+//! Ethereum legacy transactions with EIP-155 replay protection
+//! https://eips.ethereum.org/EIPS/eip-155
+
+use ethereum_types::{H160, H256, U256};
+use rlp::RlpStream;
+use sp_core::keccak_256;
+
+use crate::Error;
+
+/// An unsigned legacy Ethereum transaction
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transaction {
+	pub nonce: U256,
+	pub gas_price: U256,
+	pub gas_limit: U256,
+	pub to: H160,
+	pub value: U256,
+	pub data: Vec<u8>,
+}
+
+impl Transaction {
+	/// Append the RLP encoded transaction fields to `stream`
+	fn rlp_append(&self, stream: &mut RlpStream) {
+		stream.append(&self.nonce);
+		stream.append(&self.gas_price);
+		stream.append(&self.gas_limit);
+		stream.append(&self.to);
+		stream.append(&self.value);
+		stream.append(&self.data);
+	}
+
+	/// Return the EIP-155 signing hash of the transaction for `chain_id`
+	/// `keccak256(rlp([nonce, gasPrice, gasLimit, to, value, data, chainId, 0, 0]))`
+	pub fn signing_hash(&self, chain_id: u64) -> H256 {
+		let mut stream = RlpStream::new_list(9);
+		self.rlp_append(&mut stream);
+		stream.append(&chain_id);
+		stream.append(&0_u8);
+		stream.append(&0_u8);
+		H256::from(keccak_256(stream.as_raw()))
+	}
+
+	/// Sign the transaction for `chain_id` with `signer`
+	/// Returns the raw signed transaction for `eth_sendRawTransaction`
+	pub fn sign(&self, signer: &Signer, chain_id: u64) -> Vec<u8> {
+		let message = libsecp256k1::Message::parse(self.signing_hash(chain_id).as_fixed_bytes());
+		let (signature, recovery_id) = libsecp256k1::sign(&message, &signer.secret);
+		let signature = signature.serialize();
+
+		let mut stream = RlpStream::new_list(9);
+		self.rlp_append(&mut stream);
+		stream.append(&(recovery_id.serialize() as u64 + chain_id * 2 + 35));
+		stream.append(&U256::from_big_endian(&signature[..32]));
+		stream.append(&U256::from_big_endian(&signature[32..]));
+		stream.out().to_vec()
+	}
+}
+
+/// Signs Ethereum transactions with a secp256k1 private key
+pub struct Signer {
+	secret: libsecp256k1::SecretKey,
+	/// The signer's Ethereum address
+	pub address: H160,
+}
+
+impl Signer {
+	/// Create a signer from a hex encoded private key, optionally `0x` prefixed
+	pub fn from_hex(private_key: &str) -> Result<Self, Error> {
+		let private_key = hex::decode(private_key.trim().trim_start_matches("0x"))
+			.map_err(|_| Error::Config("eth key is not valid hex".into()))?;
+		let secret = libsecp256k1::SecretKey::parse_slice(&private_key)
+			.map_err(|_| Error::Config("eth key is not a valid secp256k1 private key".into()))?;
+		// address is the last 20 bytes of `keccak256(uncompressed public key)` (without the 0x04 prefix)
+		let public = libsecp256k1::PublicKey::from_secret_key(&secret).serialize();
+		let address = H160::from_slice(&keccak_256(&public[1..])[12..]);
+
+		Ok(Self { secret, address })
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	/// The example transaction from EIP-155
+	fn eip155_transaction() -> Transaction {
+		Transaction {
+			nonce: 9.into(),
+			gas_price: 20_000_000_000_u64.into(),
+			gas_limit: 21_000.into(),
+			to: H160::repeat_byte(0x35),
+			value: 1_000_000_000_000_000_000_u64.into(),
+			data: vec![],
+		}
+	}
+
+	#[test]
+	fn signing_hash_matches_eip155() {
+		assert_eq!(
+			hex::encode(eip155_transaction().signing_hash(1)),
+			"daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53",
+		);
+	}
+
+	#[test]
+	fn sign_matches_eip155() {
+		let signer = Signer::from_hex(&"46".repeat(32)).unwrap();
+		assert_eq!(
+			hex::encode(eip155_transaction().sign(&signer, 1)),
+			[
+				"f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025",
+				"a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276",
+				"a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83",
+			]
+			.concat(),
+		);
+	}
+
+	#[test]
+	fn signer_address() {
+		// the well known hardhat/anvil dev account #0
+		let signer = Signer::from_hex("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+		assert_eq!(hex::encode(signer.address), "f39fd6e51aad88f6f4ce6ab8827279cfffb92266");
+		assert!(Signer::from_hex("0xnothex").is_err());
+	}
+}
@@ -113,6 +113,11 @@ pub enum ConsensusLog<AuthorityId: Encode + Decode> {
 	/// Signal an `AuthoritiesChange` is scheduled for next session
 	/// Generate a proof that the current validator set has witnessed the new authority set
 	PendingAuthoritiesChange((ValidatorSet<AuthorityId>, EventId)),
+	#[codec(index = 5)]
+	/// A request to sign the Merkle root of a batch of messages was logged
+	/// `(event_id, message)` of each batched message, and the event id of the batch
+	/// Validators sign the root only, each message is proven with its Merkle branch (see `crate::merkle`)
+	BatchSigningRequest((Vec<(EventId, Message)>, EventId)),
 }
 
 /// ETHY witness message.
@@ -164,14 +169,130 @@ impl EventProof {
 	}
 }
 
+/// Proof an Ethy authority signed conflicting witnesses i.e. different messages for the same event
+/// Only messages of `OpaqueSigningRequest`s are provable, as they commit to the validator set id and event id signed for
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, scale_info::TypeInfo)]
+pub struct EquivocationProof<AuthorityId> {
+	/// The equivocating authority
+	pub authority_id: AuthorityId,
+	/// The validator set the authority signed as a member of
+	pub validator_set_id: ValidatorSetId,
+	/// The equivocated event
+	pub event_id: EventId,
+	/// The first witness (signed message, signature)
+	pub first: (Message, sp_core::ecdsa::Signature),
+	/// The second witness (signed message, signature)
+	pub second: (Message, sp_core::ecdsa::Signature),
+}
+
+impl<AuthorityId> EquivocationProof<AuthorityId> {
+	/// Return whether the messages differ and are both signing requests for the proof's event and validator set
+	/// Signatures are not verified
+	pub fn is_conflicting(&self) -> bool {
+		self.first.0 != self.second.0
+			&& is_signing_request_for(&self.first.0, self.validator_set_id, self.event_id)
+			&& is_signing_request_for(&self.second.0, self.validator_set_id, self.event_id)
+	}
+}
+
+impl EquivocationProof<crypto::AuthorityId> {
+	/// Return an equivocation proof if `first` and `second` are conflicting witnesses from the same authority
+	/// over `first_message` and `second_message` respectively
+	/// Signatures are not verified
+	pub fn from_witnesses(
+		first: &Witness,
+		first_message: Message,
+		second: &Witness,
+		second_message: Message,
+	) -> Option<Self> {
+		if first.authority_id != second.authority_id
+			|| first.event_id != second.event_id
+			|| first.validator_set_id != second.validator_set_id
+			|| sp_io::hashing::keccak_256(&first_message) != first.digest
+			|| sp_io::hashing::keccak_256(&second_message) != second.digest
+		{
+			return None;
+		}
+
+		let proof = Self {
+			authority_id: first.authority_id.clone(),
+			validator_set_id: first.validator_set_id,
+			event_id: first.event_id,
+			first: (first_message, first.signature.clone().into()),
+			second: (second_message, second.signature.clone().into()),
+		};
+		if proof.is_conflicting() {
+			Some(proof)
+		} else {
+			None
+		}
+	}
+}
+
+/// Return whether `message` is a signing request for `event_id` by `validator_set_id`
+/// i.e. `abi.encodePacked(param0, param1,.., paramN, validator_set_id, event_id)`
+pub fn is_signing_request_for(message: &[u8], validator_set_id: ValidatorSetId, event_id: EventId) -> bool {
+	let abi_uint = |value: u64| {
+		let mut word = [0_u8; 32];
+		word[24..].copy_from_slice(&value.to_be_bytes());
+		word
+	};
+	message.len() >= 64
+		&& message[message.len() - 64..message.len() - 32] == abi_uint(validator_set_id)
+		&& message[message.len() - 32..] == abi_uint(event_id)
+}
+
+/// A proof of a message in a batch, signed by Merkle root
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct BatchEventProof {
+	/// The Merkle root of the batch, the digest signed by validators
+	pub root: [u8; 32],
+	/// The event id of the proven message
+	pub event_id: EventId,
+	/// The event id of the batch
+	pub batch_id: EventId,
+	/// The validators set Id that signed the batch
+	pub validator_set_id: ValidatorSetId,
+	/// Validators' signatures of `root`, ordered by validator
+	pub signatures: Vec<crypto::AuthoritySignature>,
+	/// The leaf of the proven message in the batch: `keccak(message)`
+	pub leaf: [u8; 32],
+	/// Merkle branch of `leaf` in the batch
+	pub branch: Vec<[u8; 32]>,
+	/// Block hash of the batch
+	pub block: [u8; 32],
+	/// Metadata tag for the event
+	pub tag: Option<Vec<u8>>,
+}
+
+impl BatchEventProof {
+	/// Return whether `branch` proves `leaf` is in the batch with `root`
+	pub fn is_valid_branch(&self) -> bool {
+		crate::merkle::process_branch(&self.leaf, &self.branch) == self.root
+	}
+}
+
 /// A [EventProof] with a version number. This variant will be appended
 /// to the block justifications for the block for which the signed witness
 /// has been generated.
 #[derive(Clone, Debug, PartialEq, Encode, Decode)]
 pub enum VersionedEventProof {
 	#[codec(index = 1)]
-	/// Current active version
+	/// A proof of a single message
 	V1(EventProof),
+	#[codec(index = 2)]
+	/// A proof of a message in a batch
+	V2(BatchEventProof),
+}
+
+impl VersionedEventProof {
+	/// Return the event id of the proven message
+	pub fn event_id(&self) -> EventId {
+		match self {
+			Self::V1(proof) => proof.event_id,
+			Self::V2(proof) => proof.event_id,
+		}
+	}
 }
 
 sp_api::decl_runtime_apis! {
@@ -180,5 +301,10 @@ sp_api::decl_runtime_apis! {
 	{
 		/// Return the current active ETHY validator set
 		fn validator_set() -> ValidatorSet<crypto::AuthorityId>;
+		/// Submit an unsigned extrinsic reporting an Ethy signing equivocation
+		/// Only useful in an offchain context
+		fn submit_report_equivocation_unsigned_extrinsic(
+			equivocation_proof: EquivocationProof<crypto::AuthorityId>,
+		) -> Option<()>;
 	}
 }
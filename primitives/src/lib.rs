@@ -20,5 +20,6 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub mod eth;
+pub mod merkle;
 pub mod traits;
 pub mod types;
@@ -0,0 +1,131 @@
+/* Copyright 2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+//! Keccak Merkle trees of Ethy messages for batched event proofs
+//!
+//! Leaves are `keccak256(message)`, pairs are hashed in sorted order `keccak256(min(a, b) ‖ max(a, b))`
+//! and a node without a sibling is promoted to the next level unchanged.
+//! This matches OpenZeppelin's `MerkleProof.processProof` so branches are verified on Ethereum without leaf indexes.
+
+use sp_io::hashing::keccak_256;
+use sp_std::prelude::*;
+
+/// A 32-byte tree node
+pub type Hash = [u8; 32];
+
+/// Hash a pair of nodes in sorted order
+fn hash_pair(a: &Hash, b: &Hash) -> Hash {
+	let (first, second) = if a <= b { (a, b) } else { (b, a) };
+	let mut buf = [0_u8; 64];
+	buf[..32].copy_from_slice(first);
+	buf[32..].copy_from_slice(second);
+	keccak_256(&buf)
+}
+
+/// Return the next level up the tree from `nodes`
+fn next_level(nodes: &[Hash]) -> Vec<Hash> {
+	nodes
+		.chunks(2)
+		.map(|pair| match pair {
+			[a, b] => hash_pair(a, b),
+			[a] => *a,
+			_ => unreachable!("chunks are 1 or 2 nodes"),
+		})
+		.collect()
+}
+
+/// Return the Merkle root of `leaves`, zero if there are none
+pub fn merkle_root(leaves: &[Hash]) -> Hash {
+	if leaves.is_empty() {
+		return Default::default();
+	}
+	let mut nodes = leaves.to_vec();
+	while nodes.len() > 1 {
+		nodes = next_level(&nodes);
+	}
+	nodes[0]
+}
+
+/// Return the Merkle branch (sibling nodes, leaf first) of the leaf at `index`, `None` if out of bounds
+pub fn merkle_branch(leaves: &[Hash], mut index: usize) -> Option<Vec<Hash>> {
+	if index >= leaves.len() {
+		return None;
+	}
+	let mut branch = Vec::new();
+	let mut nodes = leaves.to_vec();
+	while nodes.len() > 1 {
+		let sibling = index ^ 1;
+		if sibling < nodes.len() {
+			branch.push(nodes[sibling]);
+		}
+		nodes = next_level(&nodes);
+		index /= 2;
+	}
+	Some(branch)
+}
+
+/// Return the root computed from `leaf` and its Merkle `branch`
+pub fn process_branch(leaf: &Hash, branch: &[Hash]) -> Hash {
+	branch.iter().fold(*leaf, |node, sibling| hash_pair(&node, sibling))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use hex_literal::hex;
+
+	fn leaves(count: u8) -> Vec<Hash> {
+		(0..count).map(|i| keccak_256(&[i])).collect()
+	}
+
+	#[test]
+	fn root_of_single_leaf_is_the_leaf() {
+		let leaves = leaves(1);
+		assert_eq!(merkle_root(&leaves), leaves[0]);
+		assert_eq!(merkle_branch(&leaves, 0), Some(vec![]));
+		assert_eq!(merkle_root(&[]), [0_u8; 32]);
+	}
+
+	#[test]
+	fn root_matches_sorted_pair_hashing() {
+		// keccak256(abi.encodePacked(min(keccak256(0x00), keccak256(0x01)), max(..)))
+		assert_eq!(
+			merkle_root(&leaves(2)),
+			hex!("b2521d64679bc4720dabfbae7ce17947a5d373d987d3b0cc1e3042ba2054da4a"),
+		);
+	}
+
+	#[test]
+	fn branches_prove_leaves() {
+		for count in 1..=9_u8 {
+			let leaves = leaves(count);
+			let root = merkle_root(&leaves);
+			for (index, leaf) in leaves.iter().enumerate() {
+				let branch = merkle_branch(&leaves, index).expect("index in bounds");
+				assert_eq!(process_branch(leaf, &branch), root, "leaf {} of {}", index, count);
+			}
+			assert!(merkle_branch(&leaves, count as usize).is_none());
+		}
+	}
+
+	#[test]
+	fn branch_of_other_leaf_is_invalid() {
+		let leaves = leaves(4);
+		let root = merkle_root(&leaves);
+		let branch = merkle_branch(&leaves, 0).unwrap();
+		assert_ne!(process_branch(&leaves[1], &branch), root);
+		assert_ne!(process_branch(&keccak_256(b"forged"), &branch), root);
+	}
+}
@@ -33,8 +33,12 @@ pub mod config {
 	// for consistency expect
 	// 4 byte key for prefix and 8 byte key for subkeys
 
-	/// offchain storage config key for eth http URI
+	/// offchain storage config key for eth http URIs (comma separated)
 	pub const ETH_HTTP_URI: [u8; 8] = *b"ETH_HTTP";
+	/// offchain storage config key for the number of eth http URIs which must agree on a response
+	pub const ETH_HTTP_QUORUM: [u8; 15] = *b"ETH_HTTP_QUORUM";
+	/// offchain storage key prefix for eth http request failure counters (suffixed by URI)
+	pub const ETH_HTTP_FAILURES: [u8; 17] = *b"ETH_HTTP_FAILURES";
 }
 
 /// Money matters.
@@ -16,16 +16,24 @@
 
 //! Some configurable implementations as associated type for the substrate runtime.
 
-use crate::{BlockPayoutInterval, EpochDuration, Identity, Rewards, Runtime, SessionsPerEra, Staking, Treasury};
+use crate::{
+	BlockPayoutInterval, Call, Cennzx, EpochDuration, GenericAsset, Identity, Rewards, Runtime, SessionsPerEra,
+	Staking, Treasury,
+};
 use benson_primitives::types::{AccountId, Balance};
 use crml_generic_asset::{NegativeImbalance, StakingAssetCurrency};
-use crml_staking::{rewards::RunScheduledPayout, EraIndex};
+use crml_staking::{
+	rewards::{CompoundReward, RunScheduledPayout},
+	EraIndex,
+};
+use crml_support::MultiCurrency;
 use frame_support::{
+	dispatch::DispatchError,
 	traits::{Contains, ContainsLengthBound, Currency, Get, Imbalance, OnUnbalanced},
 	weights::{Weight, WeightToFeeCoefficient, WeightToFeeCoefficients, WeightToFeePolynomial},
 };
 use smallvec::smallvec;
-use sp_runtime::Perbill;
+use sp_runtime::{traits::Saturating, Perbill};
 use sp_std::{marker::PhantomData, prelude::*};
 
 /// Runs scheduled payouts for the rewards module.
@@ -97,9 +105,49 @@ impl<T: crml_staking::rewards::Config> RunScheduledPayout for ScheduledPayoutRun
 			payout_era,
 		);
 
-		Rewards::process_reward_payout(&validator_stash, commission, &exposures, amount);
+		let compound_weight = Rewards::process_reward_payout(&validator_stash, commission, &exposures, amount);
+
+		return T::WeightInfo::process_reward_payouts(exposures.others.len() as u32).saturating_add(compound_weight);
+	}
+}
+
+/// Compounds staking rewards by selling them for the staking asset via CENNZX and bonding the proceeds
+pub struct CennzxRewardCompounder;
+
+impl CompoundReward for CennzxRewardCompounder {
+	type AccountId = AccountId;
+	type Balance = Balance;
+
+	fn compound(
+		stash: &Self::AccountId,
+		amount: Self::Balance,
+		max_slippage: Perbill,
+	) -> Result<Balance, DispatchError> {
+		let reward_asset_id = <GenericAsset as MultiCurrency>::fee_currency();
+		let staking_asset_id = <GenericAsset as MultiCurrency>::staking_currency();
 
-		return T::WeightInfo::process_reward_payouts(exposures.others.len() as u32);
+		// the sale must return at least the reward's value at the current exchange rate, less `max_slippage`
+		let spot_value = Cennzx::get_spot_price(reward_asset_id, amount, staking_asset_id)?;
+		let minimum_buy = spot_value.saturating_sub(max_slippage * spot_value);
+		let staked = Cennzx::execute_sell(stash, stash, reward_asset_id, staking_asset_id, amount, minimum_buy)?;
+
+		Staking::do_bond_extra(stash, staked)
+	}
+
+	/// A CENNZX sale of the reward and `bond_extra`
+	fn compound_weight() -> Weight {
+		use crml_cennzx::WeightInfo as _;
+		use crml_staking::WeightInfo as _;
+
+		<Runtime as crml_cennzx::Config>::WeightInfo::sell_asset()
+			.saturating_add(<Runtime as crml_staking::Config>::WeightInfo::bond_extra())
+	}
+
+	/// The CENNZX spot price of `amount` reward currency in the staking asset
+	fn staking_value(amount: Self::Balance) -> Option<Balance> {
+		let reward_asset_id = <GenericAsset as MultiCurrency>::fee_currency();
+		let staking_asset_id = <GenericAsset as MultiCurrency>::staking_currency();
+		Cennzx::get_spot_price(reward_asset_id, amount, staking_asset_id).ok()
 	}
 }
 
@@ -146,6 +194,23 @@ impl OnUnbalanced<NegativeImbalance<Runtime>> for SlashFundsToTreasury {
 	}
 }
 
+/// Finds the calls dispatched by utility and scheduler calls so governance proposals follow their tracks
+pub struct GovernanceWrappedCalls;
+impl crml_governance::WrappedCalls<Call> for GovernanceWrappedCalls {
+	fn wrapped_calls(call: &Call) -> Option<Vec<Call>> {
+		match call {
+			Call::Utility(pallet_utility::Call::batch { calls })
+			| Call::Utility(pallet_utility::Call::batch_all { calls }) => Some(calls.clone()),
+			Call::Utility(pallet_utility::Call::as_derivative { call, .. })
+			| Call::Scheduler(pallet_scheduler::Call::schedule { call, .. })
+			| Call::Scheduler(pallet_scheduler::Call::schedule_named { call, .. })
+			| Call::Scheduler(pallet_scheduler::Call::schedule_after { call, .. })
+			| Call::Scheduler(pallet_scheduler::Call::schedule_named_after { call, .. }) => Some(vec![*call.clone()]),
+			_ => None,
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
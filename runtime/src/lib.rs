@@ -52,6 +52,7 @@ use static_assertions::const_assert;
 
 use crml_staking::rewards as crml_staking_rewards;
 pub use crml_staking::StakerStatus;
+use crml_staking::{AccountStakingInfo, RewardEstimate, ValidatorInfo};
 pub use frame_support::{
 	construct_runtime, debug,
 	dispatch::marker::PhantomData,
@@ -79,12 +80,14 @@ use benson_primitives::{
 };
 pub use crml_cennzx::{ExchangeAddressGenerator, FeeRate, PerMillion, PerThousand};
 use crml_cennzx_rpc_runtime_api::CennzxResult;
+use crml_eth_bridge::{EthHash, EventClaimId, EventClaimStatus, NotaryParticipation};
 pub use crml_generic_asset::{
 	impls::TransferDustImbalance, AllBalances, AssetInfo, Call as GenericAssetCall, SpendingAssetCurrency,
 	StakingAssetCurrency,
 };
-use crml_governance::{ProposalId, ProposalVoteInfo};
+use crml_governance::{ElectionInfo, ProposalId, ProposalInfo, ProposalVoteInfo};
 use crml_nft::{CollectionId, CollectionInfo, Listing, ListingId, SerialNumber, SeriesId, TokenId, TokenInfo};
+use crml_nomination_pools::{MemberInfo, PoolId, PoolInfo};
 use crml_transaction_payment::{FeeDetails, RuntimeDispatchInfo};
 pub use crml_transaction_payment::{Multiplier, TargetedFeeAdjustment};
 
@@ -94,7 +97,7 @@ use constants::{currency::*, time::*};
 
 // Implementations of some helper traits passed into runtime modules as associated types.
 pub mod impls;
-use impls::{DealWithFees, ScheduledPayoutRunner, SlashFundsToTreasury, WeightToCpayFee};
+use impls::{CennzxRewardCompounder, DealWithFees, ScheduledPayoutRunner, SlashFundsToTreasury, WeightToCpayFee};
 
 /// Deprecated host functions required for syncing blocks prior to 2.0 upgrade
 pub mod legacy_host_functions;
@@ -313,6 +316,8 @@ parameter_types! {
 	pub const BondingDuration: crml_staking::EraIndex = 28;
 	// 27 eras/days for a slash to be deferrable
 	pub const SlashDeferDuration: crml_staking::EraIndex = 27;
+	// 7 eras/days notice for validator commission increases
+	pub const CommissionChangeDelay: crml_staking::EraIndex = 7;
 	/// the highest n stakers that will receive rewards only
 	pub const MaxNominatorRewardedPerValidator: u32 = 128;
 	// Allow election solution computation during the entire last session (~10 minutes)
@@ -328,6 +333,7 @@ parameter_types! {
 impl crml_staking::Config for Runtime {
 	type BondingDuration = BondingDuration;
 	type Call = Call;
+	type CommissionChangeDelay = CommissionChangeDelay;
 	type Currency = StakingAssetCurrency<Self>;
 	type CurrencyToVote = U128CurrencyToVote;
 	type Event = Event;
@@ -337,6 +343,7 @@ impl crml_staking::Config for Runtime {
 	type MinSolutionScoreBump = MinSolutionScoreBump;
 	type NextNewSession = Session;
 	type OffchainSolutionWeightLimit = OffchainSolutionWeightLimit;
+	type OnStakerSlash = NominationPools;
 	type SessionInterface = Self;
 	type SessionsPerEra = SessionsPerEra;
 	type Slash = SlashFundsToTreasury; // send the slashed funds in CENNZ to the treasury.
@@ -441,10 +448,21 @@ impl pallet_sudo::Config for Runtime {
 parameter_types! {
 	/// Max. members of the council
 	pub const MaxCouncilSize: u16 = 255;
+	/// Deposit reserved by council candidates
+	pub const CandidacyBond: Balance = 100 * DOLLARS;
+	/// Council term length, an election is held at the end of each term
+	pub const CouncilTermDuration: BlockNumber = 28 * DAYS;
+	/// Runners-up kept after a council election
+	pub const DesiredRunnersUp: u32 = 7;
+	/// Max. candidates standing for a council election, besides the council and runners-up
+	pub const MaxCouncilCandidates: u32 = 32;
+	/// Max. accounts voting in council elections
+	pub const MaxCouncilVoters: u32 = 256;
 }
 impl crml_governance::Config for Runtime {
 	type Call = Call;
 	type Currency = SpendingAssetCurrency<Self>;
+	type StakingCurrency = StakingAssetCurrency<Self>;
 	type MaxCouncilSize = MaxCouncilSize;
 	type Scheduler = Scheduler;
 	type PalletsOrigin = OriginCaller;
@@ -452,6 +470,56 @@ impl crml_governance::Config for Runtime {
 	type WeightInfo = ();
 	type Registration = impls::RegistrationImplementation<Self>;
 	type StakingAmount = Staking;
+	type CandidacyBond = CandidacyBond;
+	type TermDuration = CouncilTermDuration;
+	type DesiredRunnersUp = DesiredRunnersUp;
+	type MaxCandidates = MaxCouncilCandidates;
+	type MaxVoters = MaxCouncilVoters;
+	type CurrencyToVote = U128CurrencyToVote;
+	type WrappedCalls = impls::GovernanceWrappedCalls;
+}
+
+parameter_types! {
+	pub const GrantsPalletId: PalletId = PalletId(*b"py/grant");
+	/// Delay between council approval of a grant milestone and its payout
+	pub const MilestonePayoutDelay: BlockNumber = 1 * DAYS;
+	/// Max. milestones per grant
+	pub const MaxMilestones: u32 = 32;
+}
+impl crml_grants::Config for Runtime {
+	type GrantsPalletId = GrantsPalletId;
+	type TreasuryPalletId = TreasuryPalletId;
+	type MultiCurrency = GenericAsset;
+	type Council = Governance;
+	type Scheduler = Scheduler;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type MilestonePayoutDelay = MilestonePayoutDelay;
+	type MaxMilestones = MaxMilestones;
+	type Event = Event;
+}
+
+parameter_types! {
+	pub const NominationPoolsPalletId: PalletId = PalletId(*b"py/nopls");
+	/// Min. amount to join a nomination pool
+	pub const MinJoinBond: Balance = 1 * DOLLARS;
+	/// Min. amount to create a nomination pool, the staking minimum bond also applies
+	pub const MinCreateBond: Balance = 1_000 * DOLLARS;
+	/// Max. nomination pools
+	pub const MaxPools: u32 = 512;
+	/// Max. members per nomination pool
+	pub const MaxMembersPerPool: u32 = 1_024;
+}
+impl crml_nomination_pools::Config for Runtime {
+	type PoolsPalletId = NominationPoolsPalletId;
+	type MultiCurrency = GenericAsset;
+	type Staking = Staking;
+	type MinJoinBond = MinJoinBond;
+	type MinCreateBond = MinCreateBond;
+	type MaxPools = MaxPools;
+	type MaxMembersPerPool = MaxMembersPerPool;
+	type Event = Event;
+	type WeightInfo = ();
 }
 
 impl pallet_utility::Config for Runtime {
@@ -587,6 +655,7 @@ impl crml_staking_rewards::Config for Runtime {
 	type FiscalEraLength = FiscalEraLength;
 	type HistoricalPayoutEras = HistoricalPayoutEras;
 	type ScheduledPayoutRunner = ScheduledPayoutRunner<Self>;
+	type RewardCompounder = CennzxRewardCompounder;
 	type TreasuryPalletId = TreasuryPalletId;
 	type WeightInfo = ();
 }
@@ -614,7 +683,7 @@ impl crml_eth_bridge::Config for Runtime {
 	/// Handle rewards for notaries
 	type RewardHandler = Rewards;
 	/// Tuple of modules subscribed to bridge events
-	type Subscribers = Erc20Peg;
+	type Subscribers = (Erc20Peg, NftPeg, EthMessaging);
 	/// The overarching dispatch call type.
 	type Call = Call;
 	/// The overarching event type.
@@ -623,6 +692,9 @@ impl crml_eth_bridge::Config for Runtime {
 	type UnixTime = Timestamp;
 	/// Reports final session status of an era
 	type FinalSessionTracker = Staking;
+	/// Reports notary misconduct offences for slashing
+	type ReportMisconduct = Offences;
+	type ReportEquivocation = Offences;
 }
 
 // transaction must have an event/log of the deposit
@@ -634,6 +706,8 @@ parameter_types! {
 	pub const DepositEventSignature: [u8; 32] = DEPOSIT_EVENT_SIGNATURE;
 	/// The ERC20 peg address
 	pub const PegPalletId: PalletId = PalletId(*b"erc20peg");
+	/// Withdrawals over an asset's delay threshold are held for 1 day
+	pub const Erc20WithdrawalDelay: BlockNumber = 1 * DAYS;
 }
 impl crml_erc20_peg::Config for Runtime {
 	/// Handles Ethereum events
@@ -643,6 +717,53 @@ impl crml_erc20_peg::Config for Runtime {
 	type MultiCurrency = GenericAsset;
 	/// PalletId/Account for this module
 	type PegPalletId = PegPalletId;
+	/// Delay of large withdrawals
+	type WithdrawalDelay = Erc20WithdrawalDelay;
+	/// The overarching event type.
+	type Event = Event;
+}
+
+// transaction must have an event/log of the deposit
+// i.e. keccack256("Erc721Deposit(address,address,uint256,bytes32)")
+const ERC721_DEPOSIT_EVENT_SIGNATURE: [u8; 32] =
+	hex_literal::hex!("8501906af158882859846196390fc225437705be1d0bb6772989face3f1ef267");
+parameter_types! {
+	/// The NFT bridge contract deposit event
+	pub const Erc721DepositEventSignature: [u8; 32] = ERC721_DEPOSIT_EVENT_SIGNATURE;
+	/// The NFT peg address
+	pub const NftPegPalletId: PalletId = PalletId(*b"nft-peg_");
+}
+impl crml_nft_peg::Config for Runtime {
+	/// Handles Ethereum events
+	type EthBridge = EthBridge;
+	type DepositEventSignature = Erc721DepositEventSignature;
+	/// PalletId/Account for this module
+	type PegPalletId = NftPegPalletId;
+	/// The overarching event type.
+	type Event = Event;
+}
+
+// transaction must have an event/log of the message
+// i.e. keccack256("MessageSent(address,bytes)")
+const MESSAGE_SENT_EVENT_SIGNATURE: [u8; 32] =
+	hex_literal::hex!("f22594d45c97446a57498873c6f8577dd7b9cda99a97b41b60339c38b8790fe1");
+parameter_types! {
+	/// The router contract message event
+	pub const MessageSentEventSignature: [u8; 32] = MESSAGE_SENT_EVENT_SIGNATURE;
+	/// The max. size of a message to or from Ethereum
+	pub const MaxMessagePayloadLength: u32 = 4_096;
+}
+impl crml_eth_messaging::Config for Runtime {
+	type MessageSentEventSignature = MessageSentEventSignature;
+	type MaxPayloadLength = MaxMessagePayloadLength;
+	/// Handles Ethereum events
+	type EthBridge = EthBridge;
+	/// Tuple of modules handling messages from Ethereum
+	type MessageHandlers = ();
+	/// Runtime currency system
+	type MultiCurrency = GenericAsset;
+	/// Message fees are paid to the treasury
+	type TreasuryPalletId = TreasuryPalletId;
 	/// The overarching event type.
 	type Event = Event;
 }
@@ -736,6 +857,10 @@ construct_runtime!(
 		EthBridge: crml_eth_bridge::{Pallet, Call, Storage, Event, ValidateUnsigned} = 32,
 		Erc20Peg: crml_erc20_peg::{Pallet, Call, Storage, Config, Event<T>} = 33,
 		EthWallet: crml_eth_wallet::{Pallet, Call, Event<T>, ValidateUnsigned} = 34,
+		Grants: crml_grants::{Pallet, Call, Storage, Event<T>} = 35,
+		NftPeg: crml_nft_peg::{Pallet, Call, Storage, Event<T>} = 36,
+		EthMessaging: crml_eth_messaging::{Pallet, Call, Storage, Event<T>} = 37,
+		NominationPools: crml_nomination_pools::{Pallet, Call, Storage, Event<T>} = 38,
 	}
 );
 
@@ -830,6 +955,9 @@ impl_runtime_apis! {
 		fn address_nonce(eth_address: &crml_support::H160) -> u32 {
 			EthWallet::address_nonce(eth_address)
 		}
+		fn typed_call(call: Vec<u8>, eth_address: &crml_support::H160) -> Option<crml_eth_wallet::eip712::TypedCall> {
+			EthWallet::typed_call_for(call, eth_address)
+		}
 	}
 
 	impl sp_authority_discovery::AuthorityDiscoveryApi<Block> for Runtime {
@@ -937,6 +1065,26 @@ impl_runtime_apis! {
 		fn validator_set() -> benson_primitives::eth::ValidatorSet<EthBridgeId> {
 			EthBridge::validator_set()
 		}
+		fn submit_report_equivocation_unsigned_extrinsic(
+			equivocation_proof: benson_primitives::eth::EquivocationProof<EthBridgeId>,
+		) -> Option<()> {
+			EthBridge::submit_report_equivocation_unsigned_extrinsic(equivocation_proof)
+		}
+	}
+
+	impl crml_eth_bridge_rpc_runtime_api::EthBridgeApi<Block, EthBridgeId> for Runtime {
+		fn event_claim_status(event_claim_id: EventClaimId) -> Option<EventClaimStatus<EthBridgeId>> {
+			EthBridge::event_claim_status(event_claim_id)
+		}
+		fn event_claim_ids_by_tx_hash(tx_hash: EthHash) -> Vec<EventClaimId> {
+			EthBridge::event_claim_ids_by_tx_hash(tx_hash)
+		}
+		fn is_tx_hash_processed(tx_hash: EthHash) -> bool {
+			EthBridge::is_tx_hash_processed(tx_hash)
+		}
+		fn notary_participation() -> (u32, Vec<(EthBridgeId, NotaryParticipation)>) {
+			EthBridge::session_notary_participation()
+		}
 	}
 
 	impl frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Index> for Runtime {
@@ -961,6 +1109,30 @@ impl_runtime_apis! {
 		fn proposal_votes() -> Vec<(ProposalId, ProposalVoteInfo)> {
 			Governance::get_proposal_votes()
 		}
+		fn election_info() -> ElectionInfo<AccountId> {
+			Governance::get_election_info()
+		}
+		fn proposal(proposal_id: ProposalId) -> Option<ProposalInfo<AccountId>> {
+			Governance::get_proposal_info(proposal_id)
+		}
+		fn proposals() -> Vec<ProposalInfo<AccountId>> {
+			Governance::get_proposals()
+		}
+	}
+
+	impl crml_nomination_pools_rpc_runtime_api::NominationPoolsApi<Block, AccountId> for Runtime {
+		fn pool(pool_id: PoolId) -> Option<PoolInfo<AccountId>> {
+			NominationPools::pool_info(pool_id)
+		}
+		fn pools() -> Vec<PoolInfo<AccountId>> {
+			NominationPools::pools()
+		}
+		fn member(who: AccountId) -> Option<MemberInfo> {
+			NominationPools::member_info(&who)
+		}
+		fn pending_rewards(who: AccountId) -> Balance {
+			NominationPools::pending_rewards(&who)
+		}
 	}
 
 	impl crml_transaction_payment_rpc_runtime_api::TransactionPaymentApi<
@@ -1053,6 +1225,18 @@ impl_runtime_apis! {
 		fn accrued_payout(stash: &AccountId) -> u64 {
 			Staking::accrued_payout(stash) as u64
 		}
+
+		fn account_info(who: AccountId) -> Option<AccountStakingInfo<AccountId, Balance>> {
+			Staking::account_staking_info(&who)
+		}
+
+		fn validators() -> Vec<ValidatorInfo<AccountId, Balance>> {
+			Staking::validators_info()
+		}
+
+		fn reward_estimate() -> RewardEstimate<Balance> {
+			Staking::reward_estimate()
+		}
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
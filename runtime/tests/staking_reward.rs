@@ -19,12 +19,16 @@ use benson_cli::chain_spec::AuthorityKeys;
 use benson_primitives::types::{AccountId, Balance, BlockNumber, DigestItem, Header};
 use benson_runtime::{
 	constants::{asset::*, currency::*, time::MILLISECS_PER_BLOCK},
-	Babe, Call, CheckedExtrinsic, EpochDuration, Executive, MaxNominatorRewardedPerValidator, Rewards, Runtime,
-	Session, SessionsPerEra, SlashDeferDuration, Staking, System, Timestamp, Treasury,
+	Babe, BondingDuration, Call, Cennzx, CheckedExtrinsic, EpochDuration, Event, Executive,
+	MaxNominatorRewardedPerValidator, NominationPools, Origin, Rewards, Runtime, Session, SessionsPerEra,
+	SlashDeferDuration, Staking, System, Timestamp, Treasury,
 };
 use codec::Encode;
-use crml_staking::{EraIndex, HandlePayee, RewardCalculation, StakingLedger};
+use crml_staking::{
+	rewards::RawEvent as RewardsEvent, EraIndex, HandlePayee, RewardCalculation, RewardDestination, StakingLedger,
+};
 use frame_support::{
+	assert_ok,
 	storage::StorageValue,
 	traits::{Currency, Get, OnFinalize, OnInitialize},
 	IterableStorageMap,
@@ -34,7 +38,7 @@ use sp_consensus_babe::{digests, AuthorityIndex, Slot, BABE_ENGINE_ID};
 use sp_core::{crypto::UncheckedFrom, H256};
 use sp_runtime::{
 	traits::{Header as HeaderT, Saturating, Zero},
-	Perbill,
+	FixedPointNumber, FixedU128, Perbill,
 };
 use sp_staking::{
 	offence::{DisableStrategy, Offence, OffenceDetails, OnOffenceHandler},
@@ -725,3 +729,269 @@ fn accrued_payout_nominators() {
 			assert_eq!(RewardCurrency::free_balance(&stash(9)), accrued_2); // stash(2) payee
 		});
 }
+
+#[test]
+fn compounded_rewards_are_bonded() {
+	let validators: Vec<AuthorityKeys> = make_authority_keys(2);
+	let initial_balance = 1_000 * DOLLARS;
+	ExtBuilder::default()
+		.initial_authorities(validators.as_slice())
+		.initial_balance(initial_balance)
+		.stash(initial_balance)
+		.build()
+		.execute_with(|| {
+			// setup CENNZ <> CPAY liquidity
+			let liquidity = 100 * DOLLARS;
+			assert_ok!(Cennzx::add_liquidity(
+				Origin::signed(alice()),
+				CENNZ_ASSET_ID,
+				liquidity,
+				liquidity,
+				liquidity,
+			));
+
+			let compounding_stash = Session::validators()[0].clone();
+			// the exchange fee alone exceeds a zero slippage limit, compounding fails
+			let failing_stash = Session::validators()[1].clone();
+			for (stash, max_slippage) in [
+				(&compounding_stash, Perbill::from_percent(5)),
+				(&failing_stash, Perbill::zero()),
+			]
+			.iter()
+			{
+				let controller = Staking::bonded(*stash).unwrap();
+				assert_ok!(Staking::set_payee(
+					Origin::signed(controller),
+					RewardDestination::Compound {
+						max_slippage: *max_slippage
+					},
+				));
+				assert_eq!(Rewards::payee(*stash), (*stash).clone());
+			}
+
+			let active_stake = |stash: &AccountId| Staking::ledger(Staking::bonded(stash).unwrap()).unwrap().active;
+			let compounding_stake = active_stake(&compounding_stash);
+			let compounding_reward_balance = RewardCurrency::free_balance(&compounding_stash);
+			let failing_stake = active_stake(&failing_stash);
+			let failing_reward_balance = RewardCurrency::free_balance(&failing_stash);
+
+			let reward = 1 * DOLLARS;
+			for stash in [&compounding_stash, &failing_stash].iter() {
+				// 100% commission, the validator takes the whole reward
+				Rewards::process_reward_payout(*stash, Perbill::one(), &Default::default(), reward);
+			}
+
+			let (compounded, staked) = System::events()
+				.iter()
+				.find_map(|r| match r.event {
+					Event::Rewards(RewardsEvent::RewardCompounded(ref who, amount, staked))
+						if who == &compounding_stash =>
+					{
+						Some((amount, staked))
+					}
+					_ => None,
+				})
+				.expect("reward compounded");
+			assert_eq!(compounded, reward);
+			assert!(!staked.is_zero());
+			assert_eq!(active_stake(&compounding_stash), compounding_stake + staked);
+			assert_eq!(
+				RewardCurrency::free_balance(&compounding_stash),
+				compounding_reward_balance
+			);
+
+			let failed = System::events()
+				.iter()
+				.find_map(|r| match r.event {
+					Event::Rewards(RewardsEvent::RewardCompoundFailed(ref who, amount)) if who == &failing_stash => {
+						Some(amount)
+					}
+					_ => None,
+				})
+				.expect("reward compounding failed");
+			assert_eq!(failed, reward);
+			assert_eq!(active_stake(&failing_stash), failing_stake);
+			assert_eq!(
+				RewardCurrency::free_balance(&failing_stash),
+				failing_reward_balance + reward
+			);
+		});
+}
+
+#[test]
+fn reward_estimate_values_rewards_at_the_cennzx_spot_price() {
+	let validators: Vec<AuthorityKeys> = make_authority_keys(2);
+	let initial_balance = 1_000 * DOLLARS;
+	ExtBuilder::default()
+		.initial_authorities(validators.as_slice())
+		.initial_balance(initial_balance)
+		.stash(initial_balance)
+		.build()
+		.execute_with(|| {
+			let total_staked = Staking::eras_total_stake(active_era());
+			crml_staking::rewards::TransactionFeePot::<Runtime>::put(10 * DOLLARS);
+			// no exchange, rewards can't be valued
+			assert!(Rewards::estimate_reward(total_staked).annual_return.is_zero());
+
+			// 1 CENNZ = 2 CPAY
+			assert_ok!(Cennzx::add_liquidity(
+				Origin::signed(alice()),
+				CENNZ_ASSET_ID,
+				100 * DOLLARS,
+				100 * DOLLARS,
+				200 * DOLLARS,
+			));
+
+			let estimate = Rewards::estimate_reward(total_staked);
+			assert!(!estimate.annual_stakers_reward.is_zero());
+			let annual_reward_value =
+				Cennzx::get_spot_price(CPAY_ASSET_ID, estimate.annual_stakers_reward, CENNZ_ASSET_ID).unwrap();
+			assert_eq!(annual_reward_value, estimate.annual_stakers_reward / 2);
+			assert_eq!(
+				estimate.annual_return,
+				FixedU128::saturating_from_rational(annual_reward_value, total_staked)
+			);
+
+			// nothing staked, no return
+			assert!(Rewards::estimate_reward(0).annual_return.is_zero());
+		});
+}
+
+#[test]
+fn nomination_pool_rewards_are_shared() {
+	let validators: Vec<AuthorityKeys> = make_authority_keys(2);
+	let initial_balance = 10_000 * DOLLARS;
+	ExtBuilder::default()
+		.initial_authorities(validators.as_slice())
+		.initial_balance(initial_balance)
+		.stash(initial_balance)
+		.build()
+		.execute_with(|| {
+			let pool_id = NominationPools::next_pool_id();
+			assert_ok!(NominationPools::create(
+				Origin::signed(alice()),
+				2_000 * DOLLARS,
+				alice(),
+				alice(),
+			));
+			assert_ok!(NominationPools::join(Origin::signed(bob()), pool_id, 1_000 * DOLLARS));
+
+			let bonded_account = NominationPools::bonded_account(pool_id);
+			let reward_account = NominationPools::reward_account(pool_id);
+			assert_ok!(NominationPools::nominate(
+				Origin::signed(alice()),
+				pool_id,
+				Session::validators()
+			));
+			let ledger = Staking::ledger(&bonded_account).expect("pool stash is its own controller");
+			assert_eq!(ledger.active, 3_000 * DOLLARS);
+			assert_eq!(
+				Staking::nominators(&bonded_account).unwrap().targets,
+				Session::validators()
+			);
+			assert_eq!(Rewards::payee(&bonded_account), reward_account);
+
+			// 100% commission, the pool stash takes the whole reward
+			let reward = 3 * DOLLARS;
+			Rewards::process_reward_payout(&bonded_account, Perbill::one(), &Default::default(), reward);
+			assert_eq!(RewardCurrency::free_balance(&reward_account), reward);
+			assert_eq!(NominationPools::pending_rewards(&alice()), 2 * DOLLARS);
+			assert_eq!(NominationPools::pending_rewards(&bob()), 1 * DOLLARS);
+
+			let bob_reward_balance = RewardCurrency::free_balance(&bob());
+			assert_ok!(NominationPools::claim_payout(Origin::signed(bob())));
+			assert_eq!(RewardCurrency::free_balance(&bob()), bob_reward_balance + 1 * DOLLARS);
+		});
+}
+
+#[test]
+fn nomination_pool_slashes_are_shared_by_unbonding_members() {
+	let validators: Vec<AuthorityKeys> = make_authority_keys(1);
+	let initial_balance = 10_000 * DOLLARS;
+	ExtBuilder::default()
+		.initial_authorities(validators.as_slice())
+		.initial_balance(initial_balance)
+		.stash(initial_balance)
+		.invulnerables_off()
+		.build()
+		.execute_with(|| {
+			let pool_id = NominationPools::next_pool_id();
+			assert_ok!(NominationPools::create(
+				Origin::signed(alice()),
+				2_000 * DOLLARS,
+				alice(),
+				alice(),
+			));
+			assert_ok!(NominationPools::join(Origin::signed(bob()), pool_id, 1_000 * DOLLARS));
+			assert_ok!(NominationPools::join(
+				Origin::signed(charlie()),
+				pool_id,
+				1_000 * DOLLARS
+			));
+			assert_ok!(NominationPools::nominate(
+				Origin::signed(alice()),
+				pool_id,
+				Session::validators()
+			));
+			// bob's funds are no longer at stake from era 1
+			assert_ok!(NominationPools::unbond(Origin::signed(bob()), 1_000 * DOLLARS));
+
+			start_active_era(1);
+			let bonded_account = NominationPools::bonded_account(pool_id);
+			let offender = &validators[0].0;
+			let exposure = Staking::eras_stakers(active_era(), &offender);
+			// the pool's 3,000 stake backs the validator
+			assert_eq!(exposure.total - exposure.own, 3_000 * DOLLARS);
+			// charlie's funds are at stake in era 1
+			assert_ok!(NominationPools::unbond(Origin::signed(charlie()), 1_000 * DOLLARS));
+
+			Staking::on_offence(
+				&[OffenceDetails {
+					offender: (offender.clone(), exposure),
+					reporters: vec![],
+				}],
+				&[Perbill::from_percent(10)],
+				Staking::eras_start_session_index(active_era()).expect("session index exists"),
+				DisableStrategy::Never,
+			);
+			// Fast-forward eras so that the slash is applied
+			start_active_era(1 + SlashDeferDuration::get());
+
+			// the pool's 300 slash is shared by the active stake and charlie's unbonding funds
+			let ledger = Staking::ledger(&bonded_account).expect("pool stash is its own controller");
+			assert_eq!(ledger.active, 1_800 * DOLLARS);
+			assert_eq!(ledger.total, 3_700 * DOLLARS);
+			assert_eq!(
+				NominationPools::member_info(&alice()).unwrap().active_stake,
+				1_800 * DOLLARS
+			);
+			assert_eq!(
+				NominationPools::member_info(&charlie()).unwrap().unbonding,
+				vec![(1 + BondingDuration::get(), 900 * DOLLARS)]
+			);
+			assert_eq!(
+				NominationPools::member_info(&bob()).unwrap().unbonding,
+				vec![(BondingDuration::get(), 1_000 * DOLLARS)]
+			);
+			let pool_events: Vec<_> = System::events()
+				.into_iter()
+				.filter_map(|r| match r.event {
+					Event::NominationPools(event) => Some(event),
+					_ => None,
+				})
+				.collect();
+			assert!(
+				pool_events.contains(&crml_nomination_pools::RawEvent::UnbondingPoolSlashed(
+					pool_id,
+					29,
+					900 * DOLLARS
+				))
+			);
+			assert!(pool_events.contains(&crml_nomination_pools::RawEvent::PoolSlashed(pool_id, 1_800 * DOLLARS)));
+
+			// bob's unlocked funds are withdrawn in full
+			let bob_balance = StakeCurrency::free_balance(&bob());
+			assert_ok!(NominationPools::withdraw_unbonded(Origin::signed(bob())));
+			assert_eq!(StakeCurrency::free_balance(&bob()), bob_balance + 1_000 * DOLLARS);
+		});
+}
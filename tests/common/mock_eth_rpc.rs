@@ -0,0 +1,160 @@
+/* Copyright 2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+//! An in-process mock Ethereum JSON-RPC server
+//!
+//! Serves scripted `eth_getTransactionReceipt` and `eth_getBlockByNumber` responses
+//! for the crml-eth-bridge offchain worker.
+
+use jsonrpc_core::{Error as RpcError, IoHandler, Params, Value};
+use jsonrpc_http_server::{Server, ServerBuilder};
+use serde_json::json;
+use sp_core::{H160, H256};
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex},
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Scripted Ethereum chain state
+#[derive(Default)]
+struct MockEthState {
+	/// Transaction receipts by tx hash
+	receipts: HashMap<H256, Value>,
+	/// Block timestamps (seconds) by block number, unscripted blocks are timestamped now
+	block_timestamps: HashMap<u64, u64>,
+	/// The latest block number
+	latest_block: u64,
+}
+
+impl MockEthState {
+	fn block(&self, number: u64) -> Value {
+		let timestamp = self.block_timestamps.get(&number).copied().unwrap_or_else(now);
+		json!({
+			"number": format!("{:#x}", number),
+			"hash": H256::from_low_u64_be(number),
+			"timestamp": format!("{:#x}", timestamp),
+		})
+	}
+}
+
+/// The current unix time (seconds)
+pub fn now() -> u64 {
+	SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+}
+
+/// A mock Ethereum JSON-RPC server, stopped on drop
+pub struct MockEthRpc {
+	state: Arc<Mutex<MockEthState>>,
+	server: Option<Server>,
+	/// The HTTP URI of the server
+	pub uri: String,
+}
+
+impl MockEthRpc {
+	/// Start a server on a free local port
+	pub fn start() -> Self {
+		let state = Arc::new(Mutex::new(MockEthState::default()));
+		let mut io = IoHandler::new();
+
+		let receipt_state = Arc::clone(&state);
+		io.add_sync_method("eth_getTransactionReceipt", move |params: Params| {
+			let (tx_hash,): (H256,) = params.parse()?;
+			Ok(receipt_state
+				.lock()
+				.unwrap()
+				.receipts
+				.get(&tx_hash)
+				.cloned()
+				.unwrap_or(Value::Null))
+		});
+
+		let block_state = Arc::clone(&state);
+		io.add_sync_method("eth_getBlockByNumber", move |params: Params| {
+			let (number, _full_txs): (String, bool) = params.parse()?;
+			let state = block_state.lock().unwrap();
+			let number = if number == "latest" {
+				state.latest_block
+			} else {
+				u64::from_str_radix(number.trim_start_matches("0x"), 16)
+					.map_err(|_| RpcError::invalid_params("expected a hex block number"))?
+			};
+			if number > state.latest_block {
+				return Ok(Value::Null);
+			}
+			Ok(state.block(number))
+		});
+
+		let server = ServerBuilder::new(io)
+			.start_http(&"127.0.0.1:0".parse().unwrap())
+			.expect("mock eth rpc server starts");
+		let uri = format!("http://{}", server.address());
+
+		Self {
+			state,
+			server: Some(server),
+			uri,
+		}
+	}
+
+	/// Set the latest block number
+	pub fn set_latest_block(&self, number: u64) {
+		self.state.lock().unwrap().latest_block = number;
+	}
+
+	/// Set the timestamp (seconds) of block `number`
+	pub fn set_block_timestamp(&self, number: u64, timestamp: u64) {
+		self.state.lock().unwrap().block_timestamps.insert(number, timestamp);
+	}
+
+	/// Script a successful tx `tx_hash` to `contract_address` included in `block_number`
+	/// which logged an event with `topic` and `data`
+	pub fn add_event(&self, tx_hash: H256, contract_address: H160, topic: H256, data: &[u8], block_number: u64) {
+		let block_hash = H256::from_low_u64_be(block_number);
+		let receipt = json!({
+			"blockHash": block_hash,
+			"blockNumber": format!("{:#x}", block_number),
+			"contractAddress": null,
+			"cumulativeGasUsed": "0x1685c",
+			"from": H160::repeat_byte(1),
+			"gasUsed": "0x1685c",
+			"logs": [{
+				"address": contract_address,
+				"topics": [topic],
+				"data": format!("0x{}", sp_core::hexdisplay::HexDisplay::from(&data.to_vec())),
+				"blockHash": block_hash,
+				"blockNumber": format!("{:#x}", block_number),
+				"transactionHash": tx_hash,
+				"transactionIndex": "0x0",
+				"logIndex": "0x0",
+				"removed": false,
+			}],
+			"logsBloom": format!("0x{}", "0".repeat(512)),
+			"status": "0x1",
+			"to": contract_address,
+			"transactionHash": tx_hash,
+			"transactionIndex": "0x0",
+		});
+		self.state.lock().unwrap().receipts.insert(tx_hash, receipt);
+	}
+}
+
+impl Drop for MockEthRpc {
+	fn drop(&mut self) {
+		if let Some(server) = self.server.take() {
+			server.close();
+		}
+	}
+}
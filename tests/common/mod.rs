@@ -0,0 +1,191 @@
+/* Copyright 2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+//! Helpers for end-to-end tests against a `benson --dev` node
+
+#![allow(dead_code)]
+
+pub mod mock_eth_rpc;
+
+use benson_primitives::types::{AccountId, Index};
+use benson_runtime::{Call, SignedExtra, UncheckedExtrinsic, VERSION};
+use codec::{Decode, Encode};
+use serde_json::{json, Value};
+use sp_core::{crypto::Ss58Codec, hashing::twox_128, hashing::twox_64, Bytes, H256};
+use sp_keyring::AccountKeyring;
+use sp_runtime::generic::Era;
+use std::{
+	net::TcpListener,
+	process::{Child, Command, Stdio},
+	thread,
+	time::{Duration, Instant},
+};
+
+/// Time to wait for the node to start or a condition to hold
+pub const TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Returns a free local port
+fn free_port() -> u16 {
+	TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port()
+}
+
+/// A `benson --dev` node process, killed on drop
+pub struct DevNode {
+	process: Child,
+	/// The node's HTTP RPC URI
+	pub rpc_uri: String,
+}
+
+impl DevNode {
+	/// Start a dev node with its eth-bridge offchain worker querying `eth_http`
+	/// Returns once the node's RPC is available
+	pub fn start(eth_http: &str) -> Self {
+		let rpc_port = free_port();
+		let process = Command::new(env!("CARGO_BIN_EXE_benson"))
+			.args(&[
+				"--dev",
+				"--tmp",
+				"--no-prometheus",
+				"--no-telemetry",
+				"--eth-http",
+				eth_http,
+			])
+			.args(&["--rpc-port", &rpc_port.to_string()])
+			.args(&["--ws-port", &free_port().to_string()])
+			.args(&["--port", &free_port().to_string()])
+			.stdout(Stdio::null())
+			.stderr(Stdio::null())
+			.spawn()
+			.expect("benson node starts");
+		let node = Self {
+			process,
+			rpc_uri: format!("http://127.0.0.1:{}", rpc_port),
+		};
+		wait_until("node rpc is available", || {
+			node.try_rpc("system_health", json!([])).is_some()
+		});
+
+		node
+	}
+
+	fn try_rpc(&self, method: &str, params: Value) -> Option<Value> {
+		let response: Value = ureq::post(&self.rpc_uri)
+			.send_json(json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params}))
+			.ok()?
+			.into_json()
+			.ok()?;
+		response.get("result").cloned()
+	}
+
+	/// Make a JSON-RPC request to the node
+	pub fn rpc(&self, method: &str, params: Value) -> Value {
+		self.try_rpc(method, params)
+			.unwrap_or_else(|| panic!("rpc {} succeeds", method))
+	}
+
+	/// Returns the raw value at `key` in the node's best block state
+	pub fn storage(&self, key: Vec<u8>) -> Option<Vec<u8>> {
+		let value = self.rpc("state_getStorage", json!([Bytes(key)]));
+		serde_json::from_value::<Option<Bytes>>(value)
+			.unwrap()
+			.map(|bytes| bytes.0)
+	}
+
+	/// Returns the decoded value at `key` in the node's best block state
+	pub fn storage_value<T: Decode>(&self, key: Vec<u8>) -> Option<T> {
+		self.storage(key)
+			.map(|value| T::decode(&mut &value[..]).expect("valid storage value"))
+	}
+
+	/// Sign `call` as `signer` and submit it to the node
+	pub fn submit(&self, signer: AccountKeyring, call: Call) -> H256 {
+		let account: AccountId = signer.to_account_id();
+		let nonce: Index = serde_json::from_value(self.rpc("system_accountNextIndex", json!([account.to_ss58check()])))
+			.expect("valid nonce");
+		let genesis_hash: H256 =
+			serde_json::from_value(self.rpc("chain_getBlockHash", json!([0]))).expect("valid hash");
+
+		let extra: SignedExtra = (
+			frame_system::CheckSpecVersion::new(),
+			frame_system::CheckTxVersion::new(),
+			frame_system::CheckGenesis::new(),
+			frame_system::CheckEra::from(Era::Immortal),
+			frame_system::CheckNonce::from(nonce),
+			frame_system::CheckWeight::new(),
+			crml_transaction_payment::ChargeTransactionPayment::from(0, None),
+		);
+		let payload = (
+			call,
+			extra.clone(),
+			VERSION.spec_version,
+			VERSION.transaction_version,
+			genesis_hash,
+			genesis_hash,
+		);
+		let signature = payload
+			.using_encoded(|b| {
+				if b.len() > 256 {
+					signer.sign(&sp_core::hashing::blake2_256(b))
+				} else {
+					signer.sign(b)
+				}
+			})
+			.into();
+		let extrinsic = UncheckedExtrinsic {
+			signature: Some((account, signature, extra)),
+			function: payload.0,
+		};
+
+		serde_json::from_value(self.rpc("author_submitExtrinsic", json!([Bytes(extrinsic.encode())])))
+			.expect("valid extrinsic hash")
+	}
+
+	/// Submit `call` as a sudo call by the dev sudo key (Alice)
+	pub fn submit_sudo(&self, call: Call) -> H256 {
+		self.submit(
+			AccountKeyring::Alice,
+			Call::Sudo(pallet_sudo::Call::sudo { call: Box::new(call) }),
+		)
+	}
+}
+
+impl Drop for DevNode {
+	fn drop(&mut self) {
+		let _ = self.process.kill();
+		let _ = self.process.wait();
+	}
+}
+
+/// Block until `condition` holds, panics after `TIMEOUT`
+pub fn wait_until(description: &str, mut condition: impl FnMut() -> bool) {
+	let start = Instant::now();
+	while !condition() {
+		if start.elapsed() > TIMEOUT {
+			panic!("timed out waiting until: {}", description);
+		}
+		thread::sleep(Duration::from_millis(500));
+	}
+}
+
+/// Storage key of a plain storage value
+pub fn storage_value_key(pallet: &str, item: &str) -> Vec<u8> {
+	[twox_128(pallet.as_bytes()), twox_128(item.as_bytes())].concat()
+}
+
+/// Storage key of a `twox_64_concat` storage map entry
+pub fn storage_map_key<K: Encode>(pallet: &str, item: &str, key: &K) -> Vec<u8> {
+	let key = key.encode();
+	[storage_value_key(pallet, item), twox_64(&key).to_vec(), key].concat()
+}
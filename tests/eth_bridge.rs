@@ -0,0 +1,208 @@
+/* Copyright 2021 Annie Lai Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://annie lai.ai/licenses/gplv3.txt
+*     https://annie lai.ai/licenses/lgplv3.txt
+*/
+
+//! End-to-end tests of the eth-bridge claim lifecycle
+//!
+//! Runs a `benson --dev` node whose notary (Alice) queries a mock Ethereum JSON-RPC server.
+//! Pruning of processed tx hashes happens hourly and is covered by the crml-eth-bridge
+//! unit test `prunes_expired_events` instead.
+
+mod common;
+
+use benson_runtime::{Call, DepositEventSignature};
+use common::{
+	mock_eth_rpc::{now, MockEthRpc},
+	storage_map_key, storage_value_key, wait_until, DevNode,
+};
+use crml_erc20_peg::Erc20DepositEvent;
+use crml_support::{EthAbiCodec, H160, H256, U256};
+use serde_json::json;
+use sp_core::Bytes;
+use sp_keyring::AccountKeyring;
+
+/// Address of the mock deposit contract
+const CONTRACT_ADDRESS: H160 = H160([0x44; 20]);
+/// The latest mock Ethereum block
+const LATEST_BLOCK: u64 = 100;
+
+/// Start a mock Ethereum chain and a dev node with deposits activated
+fn setup() -> (MockEthRpc, DevNode) {
+	let eth = MockEthRpc::start();
+	eth.set_latest_block(LATEST_BLOCK);
+	let node = DevNode::start(&eth.uri);
+
+	node.submit_sudo(Call::Erc20Peg(crml_erc20_peg::Call::set_contract_address {
+		eth_address: CONTRACT_ADDRESS,
+	}));
+	wait_until("contract address is set", || {
+		node.storage_value::<H160>(storage_value_key("Erc20Peg", "ContractAddress")) == Some(CONTRACT_ADDRESS)
+	});
+	node.submit_sudo(Call::Erc20Peg(crml_erc20_peg::Call::activate_deposits {
+		activate: true,
+	}));
+	wait_until("deposits are active", || {
+		node.storage_value::<bool>(storage_value_key("Erc20Peg", "DepositsActive")) == Some(true)
+	});
+
+	(eth, node)
+}
+
+/// A deposit of native Eth to Bob
+fn mock_deposit() -> Erc20DepositEvent {
+	Erc20DepositEvent {
+		token_address: H160::zero(),
+		amount: U256::from(1_000_000_u64),
+		beneficiary: H256::from(AccountKeyring::Bob.to_raw_public()),
+	}
+}
+
+/// Script `deposit` in tx `tx_hash` at `block_number` on the mock chain
+fn add_deposit(eth: &MockEthRpc, tx_hash: H256, deposit: &Erc20DepositEvent, block_number: u64) {
+	eth.add_event(
+		tx_hash,
+		CONTRACT_ADDRESS,
+		H256::from(DepositEventSignature::get()),
+		&EthAbiCodec::encode(deposit),
+		block_number,
+	);
+}
+
+/// Submit a deposit claim as Alice, returning the id of the event claim it creates
+fn submit_deposit_claim(node: &DevNode, tx_hash: H256, deposit: Erc20DepositEvent) -> u64 {
+	let event_claim_id = next_event_claim_id(node);
+	node.submit(
+		AccountKeyring::Alice,
+		Call::Erc20Peg(crml_erc20_peg::Call::deposit_claim {
+			tx_hash,
+			claim: deposit,
+		}),
+	);
+	wait_until("event claim is submitted", || {
+		next_event_claim_id(node) > event_claim_id
+	});
+
+	event_claim_id
+}
+
+fn next_event_claim_id(node: &DevNode) -> u64 {
+	node.storage_value(storage_value_key("EthBridge", "NextEventClaimId"))
+		.unwrap_or_default()
+}
+
+fn is_claim_pending(node: &DevNode, event_claim_id: u64) -> bool {
+	node.storage(storage_map_key("EthBridge", "EventClaims", &event_claim_id))
+		.is_some()
+}
+
+fn has_notarizations(node: &DevNode, event_claim_id: u64) -> bool {
+	let prefix = storage_map_key("EthBridge", "EventNotarizations", &event_claim_id);
+	node.rpc("state_getKeys", json!([Bytes(prefix)]))
+		.as_array()
+		.map_or(false, |keys| !keys.is_empty())
+}
+
+fn is_tx_processed(node: &DevNode, tx_hash: H256) -> bool {
+	node.storage(storage_map_key("EthBridge", "ProcessedTxHashes", &tx_hash))
+		.is_some()
+}
+
+#[test]
+fn valid_deposit_is_notarized() {
+	let (eth, node) = setup();
+	let tx_hash = H256::repeat_byte(1);
+	add_deposit(&eth, tx_hash, &mock_deposit(), LATEST_BLOCK - 10);
+
+	let event_claim_id = submit_deposit_claim(&node, tx_hash, mock_deposit());
+	wait_until("claim is processed", || !is_claim_pending(&node, event_claim_id));
+
+	assert!(is_tx_processed(&node, tx_hash));
+}
+
+#[test]
+fn deposit_is_notarized_once_confirmed() {
+	let (eth, node) = setup();
+	let tx_hash = H256::repeat_byte(2);
+	add_deposit(&eth, tx_hash, &mock_deposit(), LATEST_BLOCK);
+
+	// `NotEnoughConfirmations` is transient, the claim stays pending
+	let event_claim_id = submit_deposit_claim(&node, tx_hash, mock_deposit());
+	wait_until("claim is notarized", || has_notarizations(&node, event_claim_id));
+	assert!(is_claim_pending(&node, event_claim_id));
+
+	// the notary retries once the deposit is confirmed
+	eth.set_latest_block(LATEST_BLOCK + 10);
+	wait_until("claim is processed", || !is_claim_pending(&node, event_claim_id));
+
+	assert!(is_tx_processed(&node, tx_hash));
+}
+
+#[test]
+fn unconfirmed_deposit_expires() {
+	let (eth, node) = setup();
+	node.submit_sudo(Call::EthBridge(crml_eth_bridge::Call::set_claim_expiry { blocks: 5 }));
+	wait_until("claim expiry is set", || {
+		node.storage_value::<u32>(storage_value_key("EthBridge", "ClaimExpiryBlocks")) == Some(5)
+	});
+	let tx_hash = H256::repeat_byte(5);
+	add_deposit(&eth, tx_hash, &mock_deposit(), LATEST_BLOCK);
+
+	let event_claim_id = submit_deposit_claim(&node, tx_hash, mock_deposit());
+	wait_until("claim expires", || !is_claim_pending(&node, event_claim_id));
+
+	assert!(!is_tx_processed(&node, tx_hash));
+}
+
+#[test]
+fn deposit_past_deadline_is_rejected() {
+	let (eth, node) = setup();
+	let tx_hash = H256::repeat_byte(3);
+	let block_number = LATEST_BLOCK - 10;
+	add_deposit(&eth, tx_hash, &mock_deposit(), block_number);
+	let deadline: u64 = node
+		.storage_value(storage_value_key("EthBridge", "EventDeadlineSeconds"))
+		.unwrap_or(604_800);
+	eth.set_block_timestamp(block_number, now() - deadline - 60);
+
+	let event_claim_id = submit_deposit_claim(&node, tx_hash, mock_deposit());
+	wait_until("claim is processed", || !is_claim_pending(&node, event_claim_id));
+
+	assert!(!is_tx_processed(&node, tx_hash));
+}
+
+#[test]
+fn processed_deposit_cannot_be_claimed_again() {
+	let (eth, node) = setup();
+	let tx_hash = H256::repeat_byte(4);
+	add_deposit(&eth, tx_hash, &mock_deposit(), LATEST_BLOCK - 10);
+
+	let event_claim_id = submit_deposit_claim(&node, tx_hash, mock_deposit());
+	wait_until("claim is processed", || !is_claim_pending(&node, event_claim_id));
+	assert!(is_tx_processed(&node, tx_hash));
+
+	// the replayed claim fails so no new event claim is created
+	node.submit(
+		AccountKeyring::Alice,
+		Call::Erc20Peg(crml_erc20_peg::Call::deposit_claim {
+			tx_hash,
+			claim: mock_deposit(),
+		}),
+	);
+	wait_until("replayed claim is included", || {
+		node.rpc("author_pendingExtrinsics", json!([]))
+			.as_array()
+			.map_or(false, |pending| pending.is_empty())
+	});
+	assert_eq!(next_event_claim_id(&node), event_claim_id + 1);
+}